@@ -8,9 +8,16 @@ import streamlit as st
 import pandas as pd
 import json
 import os
+import urllib.error
+import urllib.request
 from datetime import datetime
 import time
 
+# Run history API served by the Rust backend (see [api] in config.toml)
+API_URL = os.environ.get('M3_API_URL', 'http://127.0.0.1:8787')
+# Backend profile (`back --profile <name>`) whose export to show
+PROFILE = os.environ.get('M3_PROFILE')
+
 # Page configuration
 st.set_page_config(
     page_title="M3 Movement - Product Scraper",
@@ -42,7 +49,10 @@ st.markdown("""
 
 def load_data():
     """Load scraper data from JSON file"""
-    data_file = os.path.join(os.path.dirname(__file__), 'scraper_data.json')
+    base_dir = os.path.dirname(__file__)
+    if PROFILE:
+        base_dir = os.path.join(base_dir, 'profiles', PROFILE)
+    data_file = os.path.join(base_dir, 'scraper_data.json')
     if os.path.exists(data_file):
         try:
             with open(data_file, 'r') as f:
@@ -51,11 +61,62 @@ def load_data():
             return None
     return None
 
+def api_get(path):
+    """GET a JSON document from the backend API, or None if it is unavailable"""
+    try:
+        with urllib.request.urlopen(f"{API_URL}{path}", timeout=5) as resp:
+            return json.load(resp)
+    except (urllib.error.URLError, json.JSONDecodeError, TimeoutError):
+        return None
+
+def load_run_summaries(page_size=100):
+    """All run summaries (no product arrays), oldest first"""
+    runs = []
+    offset = 0
+    while True:
+        page = api_get(f"/api/runs?limit={page_size}&offset={offset}")
+        if not page or not page.get('runs'):
+            break
+        runs.extend(page['runs'])
+        offset += len(page['runs'])
+        if offset >= page.get('total', 0):
+            break
+    return list(reversed(runs))
+
+def api_post(path, payload):
+    """POST a JSON body to the backend API; returns the decoded reply, or None on failure"""
+    request = urllib.request.Request(
+        f"{API_URL}{path}",
+        data=json.dumps(payload).encode(),
+        headers={'Content-Type': 'application/json'},
+        method='POST',
+    )
+    try:
+        with urllib.request.urlopen(request, timeout=5) as resp:
+            return json.load(resp)
+    except (urllib.error.URLError, json.JSONDecodeError, TimeoutError):
+        return None
+
+@st.cache_data(ttl=60)
+def load_run_products(run_id):
+    """Product arrays and opportunities for a single run"""
+    return api_get(f"/api/runs/{run_id}/products") or {}
+
+def amount(value):
+    """Numeric amount of an exported price: {"amount": "189.99", "currency": "USD"}, or a bare number from older runs"""
+    if isinstance(value, dict):
+        return float(value.get('amount', 0))
+    if isinstance(value, str):
+        return float(value)
+    return value
+
 def format_currency(value):
-    """Format a number as currency"""
+    """Format an exported price as currency"""
     if value is None:
         return "N/A"
-    return f"${value:,.2f}"
+    if isinstance(value, dict) and value.get('currency', 'USD') != 'USD':
+        return f"{amount(value):,.2f} {value['currency']}"
+    return f"${amount(value):,.2f}"
 
 def calculate_profit_margin(buy_price, sell_price):
     """Calculate profit margin percentage"""
@@ -84,7 +145,7 @@ def main():
     # Sidebar with run history
     st.sidebar.title("📊 Run History")
     
-    run_history = data.get('run_history', [])
+    run_history = load_run_summaries()
     
     if run_history:
         st.sidebar.success(f"📜 {len(run_history)} runs recorded")
@@ -100,7 +161,7 @@ def main():
             index=len(run_history) - 1  # Default to latest
         )
     else:
-        st.sidebar.info("No historical runs yet")
+        st.sidebar.info(f"No historical runs yet (history API: {API_URL})")
         selected_run_idx = None
     
     # Display metrics
@@ -116,9 +177,34 @@ def main():
         opportunities = data.get('arbitrage_opportunities', [])
         st.metric("💰 Arbitrage Opportunities", len(opportunities))
     
+    # Sold medians vs their baseline across devices: above 100 the market is heating up
+    heat = data.get('market_heat')
+    if heat:
+        readings = {'heating': '🔥 Heating up', 'cooling': '🧊 Cooling down', 'steady': '➖ Steady'}
+        st.metric(
+            f"🌡️ Market Heat ({len(heat.get('devices', []))} devices vs {heat.get('baseline_days')}-day baseline)",
+            f"{heat.get('index', 100):.1f}",
+            delta=f"{heat.get('index', 100) - 100:+.1f} · {readings.get(heat.get('direction'), '')}",
+        )
+    
     # Last updated
     last_updated = data.get('last_updated', 'Unknown')
     st.caption(f"Last updated: {last_updated}")
+    if data.get('partial'):
+        st.info(f"⏳ Run #{data.get('run_count')} is still scraping: listings are fresh for the sources done so far, opportunities are from the previous run.")
+    
+    # Ticker: what changed in the latest run, diffed by the backend
+    changes = api_get('/api/changes/latest')
+    if changes:
+        items = [f"🆕 {o.get('buy_product_name')} ({o.get('margin_percent', 0):.0f}%)"
+                 for o in changes.get('new_opportunities', [])[:5]]
+        items += [f"📉 {d.get('name')} {format_currency(d.get('old_price'))} → {format_currency(d.get('new_price'))}"
+                  for d in changes.get('price_drops', [])[:5]]
+        gone = len(changes.get('disappeared', []))
+        if gone:
+            items.append(f"👋 {gone} listings gone")
+        if items:
+            st.info("  •  ".join(items))
     
     # Tabs
     tab1, tab2, tab3, tab4, tab5 = st.tabs([
@@ -134,6 +220,100 @@ def main():
         st.header("💰 Arbitrage Opportunities")
         st.markdown("*Swappa products compared to eBay SOLD listing averages*")
         
+        # Listings added with `back track <url>`
+        for alert in data.get('tracking_alerts', []):
+            st.warning(f"🔔 [{alert.get('title') or alert.get('url')}]({alert.get('url')}) - {alert.get('message')}")
+        tracked = data.get('tracked_listings', [])
+        if tracked:
+            with st.expander(f"🔭 Tracked Listings ({len(tracked)})"):
+                st.dataframe(pd.DataFrame([{
+                    'Title': t.get('title') or 'N/A',
+                    'Source': t.get('source'),
+                    'Price': format_currency(t['price']) if t.get('price') is not None else 'N/A',
+                    'Status': t.get('status'),
+                    'URL': t.get('url'),
+                } for t in tracked]), hide_index=True, use_container_width=True,
+                    column_config={'URL': st.column_config.LinkColumn('URL')})
+        
+        # Auctions ending soon, counted down from their end time
+        auctions = data.get('auction_opportunities', [])
+        if auctions:
+            st.subheader(f"⏱️ Auctions Ending Soon ({len(auctions)})")
+            now = datetime.now()
+            for auction in auctions:
+                try:
+                    ends_at = datetime.strptime(auction.get('ends_at', ''), '%Y-%m-%d %H:%M:%S')
+                except ValueError:
+                    continue
+                minutes_left = int((ends_at - now).total_seconds() // 60)
+                if minutes_left < 0:
+                    continue
+                st.markdown(
+                    f"⏳ **{minutes_left} min left** ({auction.get('bid_count', 0)} bids) - "
+                    f"[{auction.get('buy_product_name', 'Unknown')}]({auction.get('buy_url', '#')}): "
+                    f"bid {format_currency(auction.get('buy_price'))} vs sold avg "
+                    f"{format_currency(auction.get('ebay_avg_sold_price'))}"
+                )
+        
+        # Listings seen before that came back cheaper
+        price_drops = data.get('price_drops', [])
+        if price_drops:
+            with st.expander(f"📉 Price Drops ({len(price_drops)})"):
+                st.dataframe(pd.DataFrame([{
+                    'Listing': d.get('name'),
+                    'Source': d.get('source'),
+                    'Was': format_currency(d.get('previous_price')),
+                    'Now': format_currency(d.get('price')),
+                    'Drop': f"-{format_currency(d.get('drop'))} ({d.get('drop_percent', 0):.1f}%)",
+                    'Opportunity': '💰' if d.get('opportunity') else '',
+                    'URL': d.get('url'),
+                } for d in price_drops]), hide_index=True, use_container_width=True,
+                    column_config={'URL': st.column_config.LinkColumn('URL')})
+        
+        # Margins too good to be true, kept apart from the real opportunities
+        needs_review = data.get('needs_review', [])
+        if needs_review:
+            with st.expander(f"⚠️ Needs Review ({len(needs_review)}) - margins too high to trust"):
+                st.caption("Usually a mismatched comp or a scam listing. Check before buying.")
+                for opp in needs_review:
+                    st.markdown(
+                        f"**{opp.get('margin_percent', 0) or 0:.0f}%** - "
+                        f"[{opp.get('buy_product_name', 'Unknown')}]({opp.get('buy_url', '#')}) "
+                        f"on {opp.get('buy_source', 'Unknown')}: {format_currency(opp.get('buy_price'))} vs sold avg "
+                        f"{format_currency(opp.get('ebay_avg_sold_price'))}"
+                    )
+        
+        # Suggested buys within the configured bankroll
+        portfolio = data.get('portfolio') or {}
+        if portfolio.get('picks'):
+            st.subheader(f"💼 Suggested Portfolio ({format_currency(portfolio.get('budget'))} budget)")
+            st.caption(f"{len(portfolio['picks'])} items, {format_currency(portfolio.get('total_cost'))} spent, "
+                       f"{format_currency(portfolio.get('expected_profit'))} expected profit")
+            st.dataframe(pd.DataFrame([{
+                'Item': p.get('buy_product_name'),
+                'Source': p.get('buy_source'),
+                'Buy': format_currency(p.get('buy_price')),
+                'Profit': format_currency(p.get('potential_profit')),
+                'URL': p.get('buy_url'),
+            } for p in portfolio['picks']]), hide_index=True, use_container_width=True,
+                column_config={'URL': st.column_config.LinkColumn('URL')})
+        
+        # Held inventory (`back inventory add`) valued against this run's comps
+        inventory = data.get('inventory', [])
+        if inventory:
+            pnl = sum(amount(i['unrealized_pnl']) for i in inventory if i.get('unrealized_pnl') is not None)
+            st.subheader(f"📦 Inventory ({len(inventory)} held)")
+            st.caption(f"{format_currency(pnl)} unrealized P&L after fees")
+            st.dataframe(pd.DataFrame([{
+                'Item': i.get('name'),
+                'Cost': format_currency(i.get('cost')),
+                'Market Value': format_currency(i['market_value']) if i.get('market_value') is not None else 'N/A',
+                'Unrealized P&L': format_currency(i['unrealized_pnl']) if i.get('unrealized_pnl') is not None else 'N/A',
+                'Comps': i.get('comp_count'),
+                'Days Held': i.get('days_held'),
+                'Stale': '🧊' if i.get('stale') else '',
+            } for i in inventory]), hide_index=True, use_container_width=True)
+        
         opportunities = data.get('arbitrage_opportunities', [])
         
         if opportunities:
@@ -156,14 +336,32 @@ def main():
                     col1, col2, col3 = st.columns(3)
                     
                     with col1:
-                        st.markdown("### 💰 Buy on Swappa")
-                        st.metric("Price", format_currency(opp.get('buy_price')))
-                        swappa_url = opp.get('buy_url', '#')
-                        st.markdown(f"[View on Swappa]({swappa_url})")
+                        buy_source = opp.get('buy_source', 'Swappa')
+                        st.markdown(f"### 💰 Buy on {buy_source}")
+                        # Cut since the run the listing was last seen in
+                        price_drop = opp.get('price_drop')
+                        st.metric(
+                            "Price",
+                            format_currency(opp.get('buy_price')),
+                            delta=f"-{amount(price_drop['drop']):.2f} since run {price_drop['previous_run_id']}" if price_drop else None,
+                        )
+                        buy_url = opp.get('buy_url', '#')
+                        st.markdown(f"[View on {buy_source}]({buy_url})")
+                        if opp.get('expires_at'):
+                            st.caption(f"⏳ Deal ends {opp['expires_at']}")
                     
                     with col2:
-                        st.markdown("### 📊 eBay Sold Average")
-                        st.metric("Average Sold Price", format_currency(opp.get('ebay_avg_sold_price')))
+                        comp_sources = ", ".join(opp.get('comp_sources', [])) or "eBay"
+                        st.markdown(f"### 📊 Sold Prices ({comp_sources})")
+                        # Older exports only have the average
+                        median = opp.get('ebay_median_sold_price') or opp.get('ebay_avg_sold_price')
+                        st.metric("Median Sold Price", format_currency(median))
+                        if opp.get('ebay_median_sold_price'):
+                            st.caption(
+                                f"P25-P75: {format_currency(opp.get('ebay_p25_sold_price'))} - "
+                                f"{format_currency(opp.get('ebay_p75_sold_price'))}, "
+                                f"average {format_currency(opp.get('ebay_avg_sold_price'))}"
+                            )
                         ebay_urls = opp.get('sample_ebay_urls', [])
                         if ebay_urls:
                             st.markdown(f"[View Sample Sale]({ebay_urls[0]})")
@@ -173,9 +371,61 @@ def main():
                     with col3:
                         st.markdown("### 💵 Potential Profit")
                         potential_profit = opp.get('potential_profit', 0)
-                        st.metric("Profit", format_currency(potential_profit))
+                        # Change since the listing's last run as an opportunity
+                        trend = opp.get('trend')
+                        st.metric(
+                            "Net Profit",
+                            format_currency(potential_profit),
+                            delta=f"{amount(trend['profit_delta']):+.2f} since run {trend['previous_run_id']}" if trend else None,
+                        )
+                        if opp.get('gross_profit'):
+                            st.caption(f"{format_currency(opp['gross_profit'])} before fees and shipping")
                         st.markdown(f"<span class='{profit_class}'>Margin: {profit_margin:.1f}%</span>", 
                                    unsafe_allow_html=True)
+                        floor = opp.get('trade_in_floor')
+                        if floor:
+                            st.caption(f"🛟 Worst case: trade in to [{floor['provider']}]({floor['url']}) for {format_currency(floor['price'])}")
+                    
+                    # Notes/tags added via `back note` / `back tag` or the API
+                    annotation = opp.get('annotation') or {}
+                    if annotation.get('tags'):
+                        st.markdown(" ".join(f"`{t}`" for t in annotation['tags']))
+                    for note in annotation.get('notes', []):
+                        st.caption(f"📝 {note.get('added_at', '')[:16].replace('T', ' ')} {note.get('text')}")
+                    
+                    # Every comp behind the average, so the match can be audited
+                    comps = opp.get('comps', [])
+                    if comps:
+                        condition = opp.get('buy_condition')
+                        st.markdown(f"**Sold comps ({len(comps)})**" + (f" - buy listing is {condition}" if condition else ""))
+                        comps_df = pd.DataFrame([{
+                            'Title': c.get('title'),
+                            'Price': format_currency(c.get('price')),
+                            'Sold': c.get('sold_date') or 'N/A',
+                            'Source': c.get('source'),
+                            'Similarity': f"{c.get('similarity', 0):.0f}",
+                            'Condition': c.get('condition') or 'N/A',
+                            'Storage': f"{c['storage_gb']}GB" if c.get('storage_gb') else 'N/A',
+                            'Carrier': c.get('carrier') or 'N/A',
+                            'Unadjusted': format_currency(c['unadjusted_price']) if c.get('unadjusted_price') else '',
+                            'URL': c.get('url'),
+                        } for c in comps])
+                        st.dataframe(comps_df, hide_index=True, use_container_width=True,
+                                     column_config={'URL': st.column_config.LinkColumn('URL')})
+                    
+                    rejected = opp.get('rejected_comps', [])
+                    if rejected:
+                        st.caption(
+                            f"🚫 {len(rejected)} outlier comps left out: "
+                            + ", ".join(f"[{format_currency(c.get('price'))}]({c.get('url')})" for c in rejected)
+                        )
+                    
+                    # Feedback for the matcher: these comps won't be matched to this listing again
+                    if st.button("🙅 Bad match", key=f"bad_match_{opp.get('buy_url')}"):
+                        if api_post('/api/feedback/bad-match', {'buy_url': opp.get('buy_url')}):
+                            st.success("Marked as a bad match; suppressed from the next run on.")
+                        else:
+                            st.error("Could not reach the API to record the bad match.")
         else:
             st.info("No arbitrage opportunities found yet. The scraper needs to find similar products on both Swappa and eBay SOLD listings.")
     
@@ -186,7 +436,7 @@ def main():
         
         # Get products with comparison from run history
         if run_history and selected_run_idx is not None:
-            selected_run = run_history[selected_run_idx]
+            selected_run = load_run_products(run_history[selected_run_idx]['run_id'])
             swappa_products = selected_run.get('swappa_products', [])
             newegg_products = selected_run.get('newegg_products', [])
             # Combine both sources
@@ -259,18 +509,18 @@ def main():
                     icon = "⚪"
                     margin_text = "N/A"
                 
-                with st.expander(f"{icon} {source_icon} [{source}] {product.get('name', 'Unknown')[:50]} - ${product.get('price_numeric', 0):.0f} → Margin: {margin_text}"):
+                with st.expander(f"{icon} {source_icon} [{source}] {product.get('name', 'Unknown')[:50]} - ${amount(product.get('price_numeric', 0)):.0f} → Margin: {margin_text}"):
                     col1, col2, col3 = st.columns(3)
                     
                     with col1:
                         st.markdown(f"**{source_icon} {source} Listing**")
-                        st.write(f"Price: ${product.get('price_numeric', 0):.2f}")
+                        st.write(f"Price: {format_currency(product.get('price_numeric'))}")
                         st.markdown(f"[View on {source}]({product.get('url', '#')})")
                     
                     with col2:
                         st.markdown("**eBay Sold Average**")
                         if ebay_avg:
-                            st.write(f"Avg: ${ebay_avg:.2f}")
+                            st.write(f"Avg: {format_currency(ebay_avg)}")
                             st.write(f"Based on {product.get('ebay_sold_count', 0)} sales")
                             st.write(f"Range: {product.get('ebay_price_range', 'N/A')}")
                         else:
@@ -279,7 +529,7 @@ def main():
                     with col3:
                         st.markdown("**Profit Analysis**")
                         if margin is not None:
-                            potential = product.get('potential_profit', 0)
+                            potential = amount(product.get('potential_profit')) or 0
                             color = "green" if potential > 0 else "red"
                             st.markdown(f"Potential: <span style='color:{color}'>${potential:.2f}</span>", unsafe_allow_html=True)
                             st.markdown(f"Margin: <span style='color:{color}'>{margin:.1f}%</span>", unsafe_allow_html=True)
@@ -293,12 +543,25 @@ def main():
                 st.markdown("### Current Swappa Products (without comparison)")
                 df = pd.DataFrame(swappa)
                 if not df.empty:
+                    df['price'] = df['price'].map(format_currency)
                     st.dataframe(df[['name', 'price', 'url']] if 'url' in df.columns else df, use_container_width=True)
     
     # Tab 3: Run History
     with tab3:
         st.header("📜 Run History")
         
+        # Per-model price trends from the compact price_history export
+        price_history = data.get('price_history', [])
+        if price_history:
+            rows = [{'timestamp': point['timestamp'], 'model': m['model'],
+                     'Swappa avg ask': amount(m.get('swappa_avg_ask')), 'eBay sold median': amount(m.get('ebay_sold_median'))}
+                    for point in price_history for m in point.get('models', [])]
+            history_df = pd.DataFrame(rows)
+            models = sorted(history_df['model'].unique())
+            model = st.selectbox("📈 Price trend for model:", models)
+            trend = history_df[history_df['model'] == model].set_index('timestamp')
+            st.line_chart(trend[['Swappa avg ask', 'eBay sold median']])
+        
         if run_history:
             for i, run in enumerate(reversed(run_history)):
                 run_idx = len(run_history) - 1 - i
@@ -312,20 +575,18 @@ def main():
                     with col2:
                         st.metric("eBay Sold", run.get('total_ebay_sold', 0))
                     with col3:
-                        opps = run.get('arbitrage_opportunities', [])
-                        st.metric("Opportunities", len(opps))
+                        st.metric("Opportunities", run.get('opportunity_count', 0))
                     
                     # Show best opportunity from this run
                     best = run.get('best_opportunity')
                     if best:
                         st.markdown("**Best Opportunity:**")
-                        st.write(f"🏆 {best.get('buy_product_name', 'Unknown')}: {best.get('margin_percent', 0):.1f}% margin (${best.get('potential_profit', 0):.2f} profit)")
+                        st.write(f"🏆 {best.get('buy_product_name', 'Unknown')}: {best.get('margin_percent', 0):.1f}% margin ({format_currency(best.get('potential_profit', 0))} profit)")
                     
                     # Products with comparison
-                    products = run.get('swappa_products', [])
-                    if products:
-                        profitable = len([p for p in products if (p.get('margin_percent') or 0) > 0])
-                        st.markdown(f"**Products analyzed:** {len(products)} ({profitable} profitable)")
+                    analyzed = run.get('swappa_analyzed', 0)
+                    if analyzed:
+                        st.markdown(f"**Products analyzed:** {analyzed} ({run.get('swappa_profitable', 0)} profitable)")
         else:
             st.info("No run history available yet. The scraper will record history as it runs.")
     
@@ -376,8 +637,8 @@ def main():
                 
                 with col3:
                     st.markdown("### Opportunities")
-                    opps1 = len(run1.get('arbitrage_opportunities', []))
-                    opps2 = len(run2.get('arbitrage_opportunities', []))
+                    opps1 = run1.get('opportunity_count', 0)
+                    opps2 = run2.get('opportunity_count', 0)
                     delta = opps2 - opps1
                     st.metric(f"Run {run1.get('run_id', run1_idx+1)}", opps1)
                     st.metric(f"Run {run2.get('run_id', run2_idx+1)}", opps2, delta=delta)
@@ -385,8 +646,10 @@ def main():
                 # Compare opportunities
                 st.markdown("### Arbitrage Comparison")
                 
-                run1_opps = {o.get('buy_product_name'): o for o in run1.get('arbitrage_opportunities', [])}
-                run2_opps = {o.get('buy_product_name'): o for o in run2.get('arbitrage_opportunities', [])}
+                run1_products = load_run_products(run1['run_id'])
+                run2_products = load_run_products(run2['run_id'])
+                run1_opps = {o.get('buy_product_name'): o for o in run1_products.get('arbitrage_opportunities', [])}
+                run2_opps = {o.get('buy_product_name'): o for o in run2_products.get('arbitrage_opportunities', [])}
                 
                 all_products = set(run1_opps.keys()) | set(run2_opps.keys())
                 
@@ -429,14 +692,7 @@ def main():
             
             # Average margins over time
             st.subheader("Average Profit Margins Over Time")
-            avg_margins = []
-            for run in run_history:
-                products = run.get('swappa_products', [])
-                if products:
-                    margins = [p.get('margin_percent', 0) or 0 for p in products if p.get('margin_percent') is not None]
-                    avg_margins.append(sum(margins) / len(margins) if margins else 0)
-                else:
-                    avg_margins.append(0)
+            avg_margins = [run.get('avg_swappa_margin') or 0 for run in run_history]
             
             margin_data = pd.DataFrame({
                 'Run': [run.get('run_id', i+1) for i, run in enumerate(run_history)],
@@ -446,7 +702,7 @@ def main():
             
             # Opportunity count over time
             st.subheader("Arbitrage Opportunities Over Time")
-            opp_counts = [len(run.get('arbitrage_opportunities', [])) for run in run_history]
+            opp_counts = [run.get('opportunity_count', 0) for run in run_history]
             opp_data = pd.DataFrame({
                 'Run': [run.get('run_id', i+1) for i, run in enumerate(run_history)],
                 'Opportunities': opp_counts
@@ -454,6 +710,32 @@ def main():
             st.bar_chart(opp_data.set_index('Run'))
         else:
             st.info("Need more runs to show analytics. Keep the scraper running!")
+        
+        # Sell-through of the buy sources from listings followed until they vanish
+        lifecycle = data.get('listing_lifecycle', {})
+        if lifecycle.get('sources'):
+            st.subheader("Sell-Through by Source")
+            st.dataframe(pd.DataFrame([{
+                'Source': s.get('source'),
+                'Active': s.get('active', 0),
+                'Sold': s.get('sold', 0),
+                'Removed': s.get('removed', 0),
+                'Sell-through': f"{s['sell_through_percent']:.0f}%" if s.get('sell_through_percent') is not None else 'N/A',
+                'Median days to sell': f"{s['median_days_to_sell']:.1f}" if s.get('median_days_to_sell') is not None else 'N/A',
+            } for s in lifecycle['sources']]), hide_index=True, use_container_width=True)
+        
+        # Requests per domain and time spent waiting on the rate limiter
+        crawl_audit = data.get('crawl_audit', [])
+        if crawl_audit:
+            with st.expander(f"🚦 Crawl Audit ({sum(d.get('requests', 0) for d in crawl_audit)} requests)"):
+                st.dataframe(pd.DataFrame([{
+                    'Domain': d.get('domain'),
+                    'Requests': d.get('requests', 0),
+                    'Waited': d.get('waited', 0),
+                    'Total wait (s)': round(d.get('wait_ms', 0) / 1000, 1),
+                    'Longest wait (s)': round(d.get('longest_wait_ms', 0) / 1000, 1),
+                    'Limit': f"{d.get('requests_per_minute')}/min, {d.get('min_delay_ms')}ms apart",
+                } for d in crawl_audit]), hide_index=True, use_container_width=True)
     
     # Auto-refresh option
     st.sidebar.markdown("---")
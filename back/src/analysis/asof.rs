@@ -0,0 +1,115 @@
+// Market state for one device at a past moment, rebuilt from the stored run
+// history: the best buy-side ask, the sold median and the opportunities that
+// were open, as the last run at or before that moment saw them. Served by
+// /api/asof; backtests and "what did we see yesterday at noon" debugging use
+// the same call.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::Serialize;
+
+use crate::attributes::ProductAttributes;
+use crate::money::Money;
+use crate::{ArbitrageOpportunity, storage};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Ask {
+    pub name: String,
+    pub source: String,
+    pub url: String,
+    pub price: Money,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketState {
+    pub device: String,
+    // The requested moment, local time
+    pub at: String,
+    // The run the state comes from: the last one at or before `at`
+    pub run_id: u32,
+    pub run_timestamp: String,
+    pub best_ask: Option<Ask>,
+    pub ask_count: usize,
+    pub sold_median: Option<Money>,
+    pub sold_count: usize,
+    // Opportunities of that run whose deal hadn't ended by `at`
+    pub open_opportunities: Vec<ArbitrageOpportunity>,
+}
+
+// RFC 3339 ("2024-06-01T12:00:00Z"), or a local "2024-06-01T12:00:00" /
+// "2024-06-01 12:00:00"
+pub fn parse_at(text: &str) -> Result<NaiveDateTime, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Ok(at.with_timezone(&Local).naive_local());
+    }
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(text, TIMESTAMP_FORMAT))
+        .map_err(|_| format!("Invalid time '{}': use e.g. 2024-06-01T12:00:00Z", text))
+}
+
+// `device` is a catalog model ("iphone 13", or "iphone-13" as in a URL),
+// optionally with storage ("iphone 13 128gb")
+fn matches_device(title: &str, device: &str) -> bool {
+    let attributes = ProductAttributes::parse(title);
+    [attributes.model.clone(), attributes.device_key()]
+        .iter()
+        .flatten()
+        .any(|key| key.eq_ignore_ascii_case(device))
+}
+
+pub fn market_at(device: &str, at: NaiveDateTime) -> Result<MarketState, String> {
+    let device = device.replace('-', " ").to_lowercase();
+    let at_text = at.format(TIMESTAMP_FORMAT).to_string();
+
+    // Timestamps are local "%Y-%m-%d %H:%M:%S", so they order as strings
+    let index = storage::load_run_index();
+    let oldest = index.iter().map(|entry| &entry.timestamp).min().ok_or("no stored runs yet")?;
+    if at_text < *oldest {
+        return Err(format!(
+            "{} is older than the oldest stored run ({}); [maintenance] run_history_days sets how far back runs are kept",
+            at_text, oldest
+        ));
+    }
+    let entry = index
+        .into_iter()
+        .filter(|entry| entry.timestamp <= at_text)
+        .max_by(|a, b| a.timestamp.cmp(&b.timestamp))
+        .ok_or(format!("no stored run at or before {}", at_text))?;
+    let run = storage::load_run(entry.run_id).ok_or(format!("run {} not found", entry.run_id))?;
+
+    let asks: Vec<Ask> = run
+        .swappa_products
+        .iter()
+        .chain(&run.newegg_products)
+        .chain(&run.mercari_products)
+        .chain(&run.storefront_products)
+        .filter(|p| p.price_numeric.is_positive() && matches_device(&p.name, &device))
+        .map(|p| Ask { name: p.name.clone(), source: p.source.clone(), url: p.url.clone(), price: p.price_numeric })
+        .collect();
+    let sold: Vec<Money> = run
+        .ebay_sold_products
+        .iter()
+        .chain(&run.mercari_sold_products)
+        .filter(|p| matches_device(&p.name, &device))
+        .filter_map(|p| p.amount())
+        .collect();
+    let open_opportunities = run
+        .arbitrage_opportunities
+        .into_iter()
+        .filter(|opp| matches_device(&opp.buy_product_name, &device))
+        .filter(|opp| opp.expires_at.as_ref().is_none_or(|expires_at| *expires_at >= at_text))
+        .collect();
+
+    Ok(MarketState {
+        device,
+        at: at_text,
+        run_id: run.run_id,
+        run_timestamp: run.timestamp,
+        ask_count: asks.len(),
+        best_ask: asks.into_iter().min_by_key(|ask| ask.price),
+        sold_median: Money::median(&sold),
+        sold_count: sold.len(),
+        open_opportunities,
+    })
+}
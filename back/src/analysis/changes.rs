@@ -0,0 +1,109 @@
+// What changed between two stored runs: opportunities that are new, buy-side
+// listings whose price dropped, and listings that are gone. Served by
+// /api/changes/latest so the dashboard ticker doesn't diff snapshots itself.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::money::Money;
+use crate::{ArbitrageOpportunity, ProductWithComparison, RunSnapshot};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceDrop {
+    pub name: String,
+    pub source: String,
+    pub url: String,
+    pub old_price: Money,
+    pub new_price: Money,
+    pub drop_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoneListing {
+    pub name: String,
+    pub source: String,
+    pub url: String,
+    pub last_price: Money,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunChanges {
+    pub run_id: u32,
+    pub timestamp: String,
+    // None when there is no earlier run to compare against
+    pub previous_run_id: Option<u32>,
+    pub new_opportunities: Vec<ArbitrageOpportunity>,
+    pub price_drops: Vec<PriceDrop>,
+    pub disappeared: Vec<GoneListing>,
+}
+
+// Buy-side listings of a run by URL (Swappa, Mercari, storefronts)
+fn buy_side(run: &RunSnapshot) -> HashMap<&str, &ProductWithComparison> {
+    run.swappa_products
+        .iter()
+        .chain(run.mercari_products.iter())
+        .chain(run.storefront_products.iter())
+        .filter(|p| p.price_numeric.is_positive())
+        .map(|p| (p.url.as_str(), p))
+        .collect()
+}
+
+pub fn diff_runs(previous: Option<&RunSnapshot>, current: &RunSnapshot) -> RunChanges {
+    let Some(previous) = previous else {
+        return RunChanges {
+            run_id: current.run_id,
+            timestamp: current.timestamp.clone(),
+            previous_run_id: None,
+            new_opportunities: current.arbitrage_opportunities.clone(),
+            price_drops: Vec::new(),
+            disappeared: Vec::new(),
+        };
+    };
+
+    let known: HashSet<&str> = previous.arbitrage_opportunities.iter().map(|o| o.buy_url.as_str()).collect();
+    let new_opportunities = current
+        .arbitrage_opportunities
+        .iter()
+        .filter(|o| !known.contains(o.buy_url.as_str()))
+        .cloned()
+        .collect();
+
+    let before = buy_side(previous);
+    let after = buy_side(current);
+
+    let mut price_drops: Vec<PriceDrop> = after
+        .iter()
+        .filter_map(|(url, now)| {
+            let then = before.get(url)?;
+            (now.price_numeric < then.price_numeric).then(|| PriceDrop {
+                name: now.name.clone(),
+                source: now.source.clone(),
+                url: url.to_string(),
+                old_price: then.price_numeric,
+                new_price: now.price_numeric,
+                drop_percent: (then.price_numeric - now.price_numeric).percent_of(then.price_numeric),
+            })
+        })
+        .collect();
+    price_drops.sort_by(|a, b| b.drop_percent.partial_cmp(&a.drop_percent).unwrap_or(std::cmp::Ordering::Equal));
+
+    let disappeared = before
+        .iter()
+        .filter(|(url, _)| !after.contains_key(*url))
+        .map(|(url, then)| GoneListing {
+            name: then.name.clone(),
+            source: then.source.clone(),
+            url: url.to_string(),
+            last_price: then.price_numeric,
+        })
+        .collect();
+
+    RunChanges {
+        run_id: current.run_id,
+        timestamp: current.timestamp.clone(),
+        previous_run_id: Some(previous.run_id),
+        new_opportunities,
+        price_drops,
+        disappeared,
+    }
+}
@@ -0,0 +1,95 @@
+// Sold-price differences by color within a device (e.g. the Natural Titanium
+// premium), used to adjust suggested list prices for the buy item's color
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::price_above_floor;
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::Config;
+use crate::money::Money;
+
+// Minimum sold samples before a color or device average is trusted
+const MIN_COLOR_SAMPLES: usize = 2;
+const MIN_DEVICE_SAMPLES: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColorPremium {
+    pub device: String,
+    pub color: String,
+    pub avg_sold: Money,
+    pub sample_count: usize,
+    pub device_avg_sold: Money,
+    // Color average relative to the device average, in percent
+    pub premium_percent: f64,
+}
+
+pub fn color_premiums(sold_comps: &[Product], config: &Config) -> Vec<ColorPremium> {
+    // device -> color (None = unknown) -> prices
+    let mut by_device: BTreeMap<String, BTreeMap<Option<String>, Vec<Money>>> = BTreeMap::new();
+
+    for sold in sold_comps {
+        let (Some(price), Some(device)) = (price_above_floor(sold, config), sold.attributes.device_key()) else {
+            continue;
+        };
+        by_device.entry(device).or_default().entry(sold.attributes.color.clone()).or_default().push(price);
+    }
+
+    let mut premiums = Vec::new();
+    for (device, by_color) in by_device {
+        let all_prices: Vec<Money> = by_color.values().flatten().copied().collect();
+        let colored: Vec<(String, Vec<Money>)> = by_color
+            .into_iter()
+            .filter_map(|(color, prices)| color.map(|c| (c, prices)))
+            .filter(|(_, prices)| prices.len() >= MIN_COLOR_SAMPLES)
+            .collect();
+        // A premium needs at least two colors to compare
+        if all_prices.len() < MIN_DEVICE_SAMPLES || colored.len() < 2 {
+            continue;
+        }
+
+        let Some(device_avg) = Money::mean(&all_prices) else {
+            continue;
+        };
+        for (color, prices) in colored {
+            let Some(avg) = Money::mean(&prices) else {
+                continue;
+            };
+            premiums.push(ColorPremium {
+                device: device.clone(),
+                color,
+                avg_sold: avg,
+                sample_count: prices.len(),
+                device_avg_sold: device_avg,
+                premium_percent: (avg - device_avg).percent_of(device_avg),
+            });
+        }
+    }
+
+    premiums
+}
+
+// Premium (in percent) for a buy item's device and color, if known
+pub fn premium_for(premiums: &[ColorPremium], attributes: &ProductAttributes) -> Option<f64> {
+    let device = attributes.device_key()?;
+    let color = attributes.color.as_ref()?;
+    premiums
+        .iter()
+        .find(|p| p.device == device && &p.color == color)
+        .map(|p| p.premium_percent)
+}
+
+pub fn display_color_premiums(premiums: &[ColorPremium]) {
+    if premiums.is_empty() {
+        return;
+    }
+
+    println!("\n🎨 COLOR PRICE DIFFERENCES (sold comps):");
+    let mut notable: Vec<_> = premiums.iter().filter(|p| p.premium_percent.abs() >= 3.0).collect();
+    notable.sort_by(|a, b| b.premium_percent.abs().partial_cmp(&a.premium_percent.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    for premium in notable.iter().take(10) {
+        println!("   {} {}: {:+.1}% ({} avg over {} sales vs {})",
+            premium.device, premium.color, premium.premium_percent, premium.avg_sold, premium.sample_count, premium.device_avg_sold);
+    }
+}
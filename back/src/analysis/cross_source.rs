@@ -0,0 +1,111 @@
+// Buy-side vs buy-side comparison: for each canonical device + condition,
+// rank the buy sources by landed cost and flag when one undercuts the rest
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::price_above_floor;
+use crate::Product;
+use crate::attributes::Condition;
+use crate::config::{Config, CrossSourceConfig};
+use crate::money::Money;
+
+// Cheapest offer from one source within a device group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceOffer {
+    pub source: String,
+    pub price: Money,
+    pub landed_cost: Money,
+    pub url: String,
+    pub listing_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceComparison {
+    pub device: String,
+    pub condition: String,
+    // Sorted by landed cost, cheapest first
+    pub offers: Vec<SourceOffer>,
+    // How much cheaper the best source is than the runner-up, in percent
+    pub undercut_percent: f64,
+    pub is_undercut: bool,
+}
+
+// Coarse condition bucket so grades from different sources line up
+pub fn condition_bucket(product: &Product) -> &'static str {
+    product.attributes.condition.map_or("Unknown", Condition::label)
+}
+
+fn landed_cost(price: Money, source: &str, config: &CrossSourceConfig) -> Money {
+    let shipping = Money::usd(config.shipping.get(source).copied().unwrap_or(0.0));
+    (price + shipping).scale(1.0 + config.tax_percent / 100.0)
+}
+
+pub fn compare_buy_sources(products: &[Product], config: &Config) -> Vec<SourceComparison> {
+    // (device, condition) -> source -> offer
+    let mut groups: BTreeMap<(String, String), BTreeMap<String, SourceOffer>> = BTreeMap::new();
+
+    for product in products {
+        let (Some(price), Some(device)) = (price_above_floor(product, config), product.attributes.device_key()) else {
+            continue;
+        };
+
+        let key = (device, condition_bucket(product).to_string());
+        let cost = landed_cost(price, &product.source, &config.cross_source);
+        let offer = groups
+            .entry(key)
+            .or_default()
+            .entry(product.source.clone())
+            .or_insert_with(|| SourceOffer {
+                source: product.source.clone(),
+                price,
+                landed_cost: cost,
+                url: product.url.clone(),
+                listing_count: 0,
+            });
+        offer.listing_count += 1;
+        if cost < offer.landed_cost {
+            offer.price = price;
+            offer.landed_cost = cost;
+            offer.url = product.url.clone();
+        }
+    }
+
+    let mut comparisons = Vec::new();
+    for ((device, condition), by_source) in groups {
+        // Only interesting when at least two sources carry the device
+        if by_source.len() < 2 {
+            continue;
+        }
+
+        let mut offers: Vec<SourceOffer> = by_source.into_values().collect();
+        offers.sort_by_key(|offer| offer.landed_cost);
+
+        let undercut_percent = (offers[1].landed_cost - offers[0].landed_cost).percent_of(offers[1].landed_cost);
+        comparisons.push(SourceComparison {
+            device,
+            condition,
+            offers,
+            undercut_percent,
+            is_undercut: undercut_percent > config.cross_source.undercut_percent,
+        });
+    }
+
+    comparisons.sort_by(|a, b| b.undercut_percent.partial_cmp(&a.undercut_percent).unwrap_or(std::cmp::Ordering::Equal));
+    comparisons
+}
+
+pub fn display_source_comparisons(comparisons: &[SourceComparison]) {
+    let undercuts: Vec<_> = comparisons.iter().filter(|c| c.is_undercut).collect();
+
+    println!("\n🔀 BUY-SOURCE COMPARISON ({} devices on 2+ sources, {} undercuts)", comparisons.len(), undercuts.len());
+
+    for comparison in undercuts.iter().take(10) {
+        let best = &comparison.offers[0];
+        println!("\n   {} ({}) - {} is {:.1}% cheaper", comparison.device, comparison.condition, best.source, comparison.undercut_percent);
+        for offer in &comparison.offers {
+            println!("      {:<12} {} landed ({} listed, {} listings)", offer.source, offer.landed_cost, offer.price, offer.listing_count);
+        }
+        println!("      🔗 {}", best.url);
+    }
+}
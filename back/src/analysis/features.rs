@@ -0,0 +1,193 @@
+// Flat feature table over stored run history for training external pricing
+// models (`back export --features`). One row per opportunity, taken from the
+// run it first surfaced in so the features are what was known at decision
+// time, labeled with its outcome (see tuning.rs). Column names and order are
+// fixed; a schema file describing them is written next to the CSV.
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::timing::parse_sold_at;
+use super::tuning::{self, Outcome};
+use crate::attributes::{self, ProductAttributes, Storage};
+use crate::money::Money;
+use crate::redact::redact_url;
+use crate::{ArbitrageOpportunity, storage};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+// Bump when a column is added, removed or changes meaning
+const SCHEMA_VERSION: u32 = 1;
+
+struct Column {
+    name: &'static str,
+    kind: &'static str,
+    description: &'static str,
+}
+
+const fn column(name: &'static str, kind: &'static str, description: &'static str) -> Column {
+    Column { name, kind, description }
+}
+
+const COLUMNS: [Column; 26] = [
+    column("run_id", "integer", "Run the opportunity first surfaced in"),
+    column("observed_at", "datetime", "Timestamp of that run, YYYY-MM-DD HH:MM:SS local time"),
+    column("buy_source", "string", "Platform of the buy listing"),
+    column("buy_url", "string", "Buy listing URL, or host#hash with --redact"),
+    column("device", "string", "Canonical model plus storage, e.g. \"iphone 14 pro 256gb\""),
+    column("model", "string", "Canonical model, e.g. \"iphone 14 pro\""),
+    column("storage_gb", "integer", "Storage capacity in GB"),
+    column("condition", "string", "new, excellent, good, fair or refurbished"),
+    column("carrier", "string", "unlocked, verizon, at&t or t-mobile"),
+    column("color", "string", "Color parsed from the title"),
+    column("buy_price", "float", "Buy price in USD, shipping included where known"),
+    column("comp_count", "integer", "Sold comps after outlier rejection"),
+    column("sold_mean", "float", "Mean sold price of the comps"),
+    column("sold_median", "float", "Median sold price of the comps"),
+    column("sold_p25", "float", "25th percentile sold price"),
+    column("sold_p75", "float", "75th percentile sold price"),
+    column("sold_min", "float", "Lowest comp sold price"),
+    column("sold_max", "float", "Highest comp sold price"),
+    column("comp_sources", "string", "Platforms the comps sold on, |-separated"),
+    column("comp_velocity_per_day", "float", "Dated comps per day between the oldest and newest sale"),
+    column("trade_in_floor", "float", "Best trade-in quote for the device"),
+    column("gross_profit", "float", "Sold price minus buy price"),
+    column("net_profit", "float", "Gross profit net of [fees]"),
+    column("margin_percent", "float", "Net profit as a percentage of buy price"),
+    column("outcome", "string", "vanished (gone within the window, presumably sold), lingered, or pending (still listed, younger than the window)"),
+    column("hours_to_vanish", "float", "Hours from first sighting to the first run without the listing, for vanished rows"),
+];
+
+fn float(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+// Exact, without a currency symbol: "1299.99"
+fn money(value: Money) -> String {
+    value.decimal()
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+// RFC 4180 quoting
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Sales per day across the span of the dated comps; None with fewer than two
+fn comp_velocity(opportunity: &ArbitrageOpportunity) -> Option<f64> {
+    let dates: Vec<NaiveDate> = opportunity
+        .comps
+        .iter()
+        .filter_map(|c| c.sold_date.as_deref().and_then(parse_sold_at))
+        .map(|(date, _)| date)
+        .collect();
+    if dates.len() < 2 {
+        return None;
+    }
+    let oldest = dates.iter().min()?;
+    let newest = dates.iter().max()?;
+    let days = (*newest - *oldest).num_days() + 1;
+    Some(dates.len() as f64 / days as f64)
+}
+
+fn row(
+    run_id: u32,
+    observed_at: NaiveDateTime,
+    opportunity: &ArbitrageOpportunity,
+    outcome: Outcome,
+    hours_to_vanish: Option<f64>,
+    redact: bool,
+) -> [String; COLUMNS.len()] {
+    let parsed = ProductAttributes::parse(&opportunity.buy_product_name);
+    let comp_prices = opportunity.comps.iter().map(|c| c.price);
+    let sold_min = comp_prices.clone().min();
+    let sold_max = comp_prices.max();
+    [
+        run_id.to_string(),
+        observed_at.format(TIMESTAMP_FORMAT).to_string(),
+        opportunity.buy_source.clone(),
+        if redact { redact_url(&opportunity.buy_url) } else { opportunity.buy_url.clone() },
+        parsed.device_key().unwrap_or_default(),
+        parsed.model.unwrap_or_default(),
+        optional(attributes::parse_storage(&opportunity.buy_product_name).map(Storage::gb)),
+        optional(opportunity.buy_condition.map(|c| c.label().to_lowercase())),
+        optional(attributes::parse_carrier(&opportunity.buy_product_name).map(|c| c.label().to_lowercase())),
+        opportunity.color.clone().unwrap_or_default(),
+        money(opportunity.buy_price),
+        opportunity.ebay_sold_count.to_string(),
+        money(opportunity.ebay_avg_sold_price),
+        money(opportunity.ebay_median_sold_price),
+        money(opportunity.ebay_p25_sold_price),
+        money(opportunity.ebay_p75_sold_price),
+        optional(sold_min.map(money)),
+        optional(sold_max.map(money)),
+        opportunity.comp_sources.join("|"),
+        optional(comp_velocity(opportunity).map(|v| format!("{:.3}", v))),
+        optional(opportunity.trade_in_floor.as_ref().map(|floor| money(floor.price))),
+        money(opportunity.gross_profit),
+        money(opportunity.potential_profit),
+        float(opportunity.margin_percent),
+        outcome.label().to_string(),
+        optional(hours_to_vanish.map(|h| format!("{:.1}", h))),
+    ]
+}
+
+// "features.csv" -> "features.schema.json"
+fn schema_path(output: &str) -> String {
+    Path::new(output).with_extension("schema.json").to_string_lossy().into_owned()
+}
+
+// Write the feature table to `output` and its schema next to it
+pub fn export_features(output: &str, window_hours: i64, redact: bool) -> Result<(), String> {
+    let window = Duration::hours(window_hours);
+    let history = tuning::listing_history();
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut lines = vec![COLUMNS.iter().map(|c| c.name).collect::<Vec<&str>>().join(",")];
+    for entry in storage::load_run_index() {
+        let Some(run) = storage::load_run(entry.run_id) else {
+            continue;
+        };
+        let Ok(observed_at) = NaiveDateTime::parse_from_str(&run.timestamp, TIMESTAMP_FORMAT) else {
+            continue;
+        };
+        for opportunity in &run.arbitrage_opportunities {
+            if !seen.insert(opportunity.buy_url.clone()) {
+                continue;
+            }
+            let outcome = tuning::outcome(&opportunity.buy_url, observed_at, &history, window);
+            let hours_to_vanish = tuning::gone_at(&opportunity.buy_url, observed_at, &history)
+                .filter(|_| outcome == Outcome::Vanished)
+                .map(|gone_at| (gone_at - observed_at).num_minutes() as f64 / 60.0);
+            let fields = row(run.run_id, observed_at, opportunity, outcome, hours_to_vanish, redact);
+            lines.push(fields.iter().map(|f| csv_field(f)).collect::<Vec<String>>().join(","));
+        }
+    }
+    if lines.len() == 1 {
+        return Err("No opportunities in stored run history to export".to_string());
+    }
+
+    std::fs::write(output, lines.join("\n") + "\n").map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    let schema = json!({
+        "schema_version": SCHEMA_VERSION,
+        "format": "csv",
+        "outcome_window_hours": window_hours,
+        "redacted": redact,
+        "columns": COLUMNS
+            .iter()
+            .map(|c| json!({ "name": c.name, "type": c.kind, "description": c.description }))
+            .collect::<Vec<_>>(),
+    });
+    let schema_file = schema_path(output);
+    storage::write_json(&schema_file, &schema)?;
+    println!("📁 {} opportunity rows written to {} (schema: {})", lines.len() - 1, output, schema_file);
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// Market heat: one number for whether the used-phone market is heating up or
+// cooling down. Each tracked device's eBay sold median this run is divided by
+// its median over the last [market_heat] baseline_days of price history, and
+// the ratios are averaged weighted by this run's sold comps, so a busy device
+// moves the index more than one with three sales. 100 is the baseline; 104
+// means devices are selling about 4% above their 30-day prices.
+
+use chrono::{NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use super::price_history::PriceHistoryPoint;
+use crate::config::MarketHeatConfig;
+use crate::money::Money;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeatDirection {
+    Heating,
+    Cooling,
+    Steady,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceHeat {
+    pub model: String,
+    pub sold_median: Money,
+    pub baseline_median: Money,
+    // This run's sold comps, the device's weight in the index
+    pub sales: usize,
+    pub baseline_runs: usize,
+    // Sold median as a percentage of the baseline
+    pub index: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketHeat {
+    pub run_id: u32,
+    pub index: f64,
+    pub direction: HeatDirection,
+    pub baseline_days: i64,
+    // Devices behind the index, most sales first
+    pub devices: Vec<DeviceHeat>,
+}
+
+impl MarketHeat {
+    // "🔥 104.2 heating up (6 devices vs 30-day baseline)"
+    pub fn label(&self) -> String {
+        let (icon, reading) = match self.direction {
+            HeatDirection::Heating => ("🔥", "heating up"),
+            HeatDirection::Cooling => ("🧊", "cooling down"),
+            HeatDirection::Steady => ("➖", "steady"),
+        };
+        format!("{} {:.1} {} ({} devices vs {}-day baseline)", icon, self.index, reading, self.devices.len(), self.baseline_days)
+    }
+}
+
+fn timestamp(point: &PriceHistoryPoint) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(&point.timestamp, TIMESTAMP_FORMAT).ok()
+}
+
+// The index for the newest point of `history` against the points before it;
+// None until some device has enough sales and baseline runs
+pub fn market_heat(history: &[PriceHistoryPoint], config: &MarketHeatConfig) -> Option<MarketHeat> {
+    let current = history.last()?;
+    let now = timestamp(current)?;
+    let window_start = now - TimeDelta::days(config.baseline_days);
+    let baseline: Vec<&PriceHistoryPoint> = history[..history.len() - 1]
+        .iter()
+        .filter(|point| timestamp(point).is_some_and(|t| t >= window_start && t <= now))
+        .collect();
+
+    let mut devices: Vec<DeviceHeat> = current
+        .models
+        .iter()
+        .filter(|stats| stats.ebay_sold_count >= config.min_sales.max(1))
+        .filter_map(|stats| {
+            let sold_median = stats.ebay_sold_median?;
+            let medians: Vec<Money> = baseline
+                .iter()
+                .filter_map(|point| point.models.iter().find(|m| m.model == stats.model)?.ebay_sold_median)
+                .collect();
+            if medians.len() < config.min_baseline_runs.max(1) {
+                return None;
+            }
+            let baseline_median = Money::median(&medians)?;
+            baseline_median.is_positive().then(|| DeviceHeat {
+                model: stats.model.clone(),
+                sold_median,
+                baseline_median,
+                sales: stats.ebay_sold_count,
+                baseline_runs: medians.len(),
+                index: sold_median.percent_of(baseline_median),
+            })
+        })
+        .collect();
+    if devices.is_empty() {
+        return None;
+    }
+    devices.sort_by(|a, b| b.sales.cmp(&a.sales).then_with(|| a.model.cmp(&b.model)));
+
+    let weight: usize = devices.iter().map(|d| d.sales).sum();
+    let index = devices.iter().map(|d| d.index * d.sales as f64).sum::<f64>() / weight as f64;
+    let direction = if index > 100.0 + config.steady_band {
+        HeatDirection::Heating
+    } else if index < 100.0 - config.steady_band {
+        HeatDirection::Cooling
+    } else {
+        HeatDirection::Steady
+    };
+    Some(MarketHeat { run_id: current.run_id, index, direction, baseline_days: config.baseline_days, devices })
+}
+
+pub fn display_market_heat(heat: Option<&MarketHeat>) {
+    let Some(heat) = heat else {
+        println!("Market heat: not enough sold history yet");
+        return;
+    };
+    println!("Market heat: {}", heat.label());
+    for device in heat.devices.iter().take(5) {
+        println!(
+            "   {:<24} {:>6.1}  {} vs {} ({} sold)",
+            device.model, device.index, device.sold_median, device.baseline_median, device.sales
+        );
+    }
+}
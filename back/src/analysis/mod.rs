@@ -0,0 +1,26 @@
+// Analyses computed from a run's scraped data, independent of the core
+// Swappa-vs-sold-comps arbitrage engine in main.rs
+
+pub mod asof;
+pub mod changes;
+pub mod color;
+pub mod cross_source;
+pub mod features;
+pub mod heat;
+pub mod portfolio;
+pub mod price_history;
+pub mod reference;
+pub mod timing;
+pub mod trend;
+pub mod tuning;
+
+use crate::Product;
+use crate::config::Config;
+use crate::money::Money;
+
+// A listing's price when it's above its model's price floor; cheaper ones are
+// accessories, parts and parse errors
+pub fn price_above_floor(product: &Product, config: &Config) -> Option<Money> {
+    let price = product.amount()?;
+    (price > Money::usd(config.thresholds_for(product.attributes.model.as_deref()).price_floor)).then_some(price)
+}
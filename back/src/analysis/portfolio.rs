@@ -0,0 +1,70 @@
+// Bankroll-aware buying: per-item spend limits and a suggested portfolio,
+// greedily picking the opportunities with the best profit per dollar until
+// the budget runs out.
+
+use serde::{Deserialize, Serialize};
+
+use crate::attributes::ProductAttributes;
+use crate::config::BankrollConfig;
+use crate::money::Money;
+use crate::ArbitrageOpportunity;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Portfolio {
+    pub budget: Money,
+    pub total_cost: Money,
+    pub expected_profit: Money,
+    pub picks: Vec<ArbitrageOpportunity>,
+}
+
+// Spend limit for a listing: the watchlist entry for its device if any, else the global cap
+pub fn max_price_for(name: &str, config: &BankrollConfig) -> Option<f64> {
+    let attributes = ProductAttributes::parse(name);
+    let watchlist = [attributes.device_key(), attributes.model.clone()]
+        .into_iter()
+        .flatten()
+        .find_map(|key| {
+            config.max_price_by_device.iter().find(|(device, _)| device.eq_ignore_ascii_case(&key)).map(|(_, max)| *max)
+        });
+    watchlist.or((config.max_item_price > 0.0).then_some(config.max_item_price))
+}
+
+pub fn within_limits(name: &str, buy_price: Money, config: &BankrollConfig) -> bool {
+    max_price_for(name, config).is_none_or(|max| buy_price <= Money::usd(max))
+}
+
+pub fn suggest_portfolio(opportunities: &[ArbitrageOpportunity], config: &BankrollConfig) -> Portfolio {
+    let mut portfolio = Portfolio { budget: Money::usd(config.budget), ..Default::default() };
+    if !portfolio.budget.is_positive() {
+        return portfolio;
+    }
+
+    let mut candidates: Vec<&ArbitrageOpportunity> = opportunities.iter().filter(|o| o.potential_profit.is_positive() && o.buy_price.is_positive()).collect();
+    candidates.sort_by(|a, b| b.potential_profit.cmp_ratio(b.buy_price, a.potential_profit, a.buy_price));
+
+    for opp in candidates {
+        if portfolio.total_cost + opp.buy_price <= portfolio.budget {
+            portfolio.total_cost += opp.buy_price;
+            portfolio.expected_profit += opp.potential_profit;
+            portfolio.picks.push(opp.clone());
+        }
+    }
+    portfolio
+}
+
+pub fn display_portfolio(portfolio: &Portfolio) {
+    if !portfolio.budget.is_positive() {
+        return;
+    }
+
+    println!(
+        "\n💼 SUGGESTED PORTFOLIO ({} budget): {} items, {} spent, {} expected profit",
+        portfolio.budget,
+        portfolio.picks.len(),
+        portfolio.total_cost,
+        portfolio.expected_profit
+    );
+    for opp in &portfolio.picks {
+        println!("   {:>9} → +{}  {} ({})", opp.buy_price, opp.potential_profit, opp.buy_product_name, opp.buy_source);
+    }
+}
@@ -0,0 +1,88 @@
+// Compact per-model price stats for every run, kept much longer than the raw
+// run snapshots so the dashboard can chart asks and sold prices over time.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use super::price_above_floor;
+use crate::config::Config;
+use crate::money::Money;
+use crate::{Product, storage};
+
+const PRICE_HISTORY_FILE: &str = "runs/price_history.json";
+// About two weeks of minute-by-minute runs on disk, the last day in the export
+const MAX_POINTS: usize = 20_000;
+const EXPORT_POINTS: usize = 1_440;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelStats {
+    pub model: String,
+    pub swappa_avg_ask: Option<Money>,
+    pub swappa_count: usize,
+    pub ebay_sold_median: Option<Money>,
+    pub ebay_sold_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceHistoryPoint {
+    pub run_id: u32,
+    pub timestamp: String,
+    pub models: Vec<ModelStats>,
+}
+
+fn prices_by_model(products: &[Product], config: &Config) -> BTreeMap<String, Vec<Money>> {
+    let mut by_model: BTreeMap<String, Vec<Money>> = BTreeMap::new();
+    for product in products {
+        let (Some(model), Some(price)) = (product.attributes.model.clone(), price_above_floor(product, config)) else {
+            continue;
+        };
+        by_model.entry(model).or_default().push(price);
+    }
+    by_model
+}
+
+pub fn model_stats(swappa: &[Product], ebay_sold: &[Product], config: &Config) -> Vec<ModelStats> {
+    let asks = prices_by_model(swappa, config);
+    let sold = prices_by_model(ebay_sold, config);
+    let mut models: Vec<&String> = asks.keys().chain(sold.keys()).collect();
+    models.sort();
+    models.dedup();
+
+    models
+        .into_iter()
+        .map(|model| {
+            let ask = asks.get(model);
+            let sold = sold.get(model);
+            ModelStats {
+                model: model.clone(),
+                swappa_avg_ask: ask.and_then(|prices| Money::mean(prices)),
+                swappa_count: ask.map_or(0, Vec::len),
+                ebay_sold_median: sold.and_then(|prices| Money::median(prices)),
+                ebay_sold_count: sold.map_or(0, Vec::len),
+            }
+        })
+        .collect()
+}
+
+pub fn load() -> Vec<PriceHistoryPoint> {
+    match fs::read_to_string(storage::profile_path(PRICE_HISTORY_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+// Append this run's point and return the most recent points for the export
+pub fn record(run_id: u32, timestamp: &str, models: Vec<ModelStats>) -> Vec<PriceHistoryPoint> {
+    let mut history = load();
+    history.retain(|point| point.run_id != run_id);
+    history.push(PriceHistoryPoint { run_id, timestamp: timestamp.to_string(), models });
+    if history.len() > MAX_POINTS {
+        history.drain(..history.len() - MAX_POINTS);
+    }
+    let _ = fs::create_dir_all(storage::profile_path(storage::RUNS_DIR));
+    if let Err(e) = storage::write_json(&storage::profile_path(PRICE_HISTORY_FILE), &history) {
+        eprintln!("❌ {}", e);
+    }
+    history.split_off(history.len().saturating_sub(EXPORT_POINTS))
+}
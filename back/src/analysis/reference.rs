@@ -0,0 +1,109 @@
+// First-party prices as a reference point (Decluttr's fixed refurb table,
+// Google Store Pixel prices): compare each model/storage/condition price to
+// the eBay sold average for that device
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::price_above_floor;
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::Config;
+use crate::money::Money;
+use crate::sources::decluttr::DecluttrPrice;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferencePrice {
+    // Where the reference price comes from; older exports only had Decluttr
+    #[serde(default = "default_source")]
+    pub source: String,
+    pub device: String,
+    pub condition: String,
+    pub reference_price: Money,
+    pub ebay_avg_sold: Option<Money>,
+    pub ebay_sold_count: usize,
+    // eBay sold average relative to the reference price, in percent
+    pub spread_percent: Option<f64>,
+}
+
+fn default_source() -> String {
+    "Decluttr".to_string()
+}
+
+// Cheapest reference price per (device, condition), against the eBay sold average
+fn compare_rows(source: &str, rows: Vec<(String, String, Money)>, ebay_sold: &[Product], config: &Config) -> Vec<ReferencePrice> {
+    let mut sold_by_device: BTreeMap<String, Vec<Money>> = BTreeMap::new();
+    for sold in ebay_sold {
+        if let (Some(price), Some(device)) = (price_above_floor(sold, config), sold.attributes.device_key()) {
+            sold_by_device.entry(device).or_default().push(price);
+        }
+    }
+
+    // Colors are priced alike, so keep the cheapest per device + condition
+    let mut cheapest: BTreeMap<(String, String), Money> = BTreeMap::new();
+    for (device, condition, row_price) in rows {
+        let price = cheapest.entry((device, condition)).or_insert(row_price);
+        *price = (*price).min(row_price);
+    }
+
+    cheapest
+        .into_iter()
+        .map(|((device, condition), reference_price)| {
+            let sold = sold_by_device.get(&device);
+            let ebay_avg_sold = sold.and_then(|prices| Money::mean(prices));
+            ReferencePrice {
+                source: source.to_string(),
+                ebay_sold_count: sold.map_or(0, |prices| prices.len()),
+                spread_percent: ebay_avg_sold.map(|avg| (avg - reference_price).percent_of(reference_price)),
+                device,
+                condition,
+                reference_price,
+                ebay_avg_sold,
+            }
+        })
+        .collect()
+}
+
+pub fn compare_to_sold(table: &[DecluttrPrice], ebay_sold: &[Product], config: &Config) -> Vec<ReferencePrice> {
+    let rows = table
+        .iter()
+        .filter_map(|row| {
+            let device = ProductAttributes::parse(&row.to_product().name).device_key()?;
+            Some((device, row.condition.clone(), row.price))
+        })
+        .collect();
+    compare_rows("Decluttr", rows, ebay_sold, config)
+}
+
+// Reference rows from a first-party store's products, graded "New" unless stated
+pub fn compare_products_to_sold(source: &str, products: &[Product], ebay_sold: &[Product], config: &Config) -> Vec<ReferencePrice> {
+    let rows = products
+        .iter()
+        .filter(|p| p.source == source)
+        .filter_map(|p| {
+            let device = p.attributes.device_key()?;
+            let price = p.amount()?;
+            Some((device, p.grade.clone().unwrap_or_else(|| "New".to_string()), price))
+        })
+        .collect();
+    compare_rows(source, rows, ebay_sold, config)
+}
+
+pub fn display_reference_prices(references: &[ReferencePrice]) {
+    let matched: Vec<_> = references.iter().filter(|r| r.ebay_avg_sold.is_some()).collect();
+
+    println!("\n📐 REFERENCE PRICES ({} rows, {} with eBay sold data)", references.len(), matched.len());
+
+    for reference in matched.iter().take(15) {
+        println!(
+            "   {:<28} {:<10} {} {} vs eBay sold {} ({:+.1}%, {} sales)",
+            reference.device,
+            reference.condition,
+            reference.source,
+            reference.reference_price,
+            reference.ebay_avg_sold.unwrap_or_default(),
+            reference.spread_percent.unwrap_or(0.0),
+            reference.ebay_sold_count
+        );
+    }
+}
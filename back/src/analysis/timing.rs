@@ -0,0 +1,148 @@
+// When do devices sell highest? Sold comps are grouped by the day (and hour,
+// when the platform shows one) the listing ended, with each price taken
+// relative to its device's average so cheap and expensive models mix fairly.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::price_above_floor;
+use crate::Product;
+use crate::config::Config;
+use crate::money::Money;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// Minimum sales in a bucket before it is reported
+const MIN_BUCKET_SAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeBucket {
+    pub label: String,
+    pub sample_count: usize,
+    // Average of (price / device average - 1), in percent
+    pub avg_premium_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceBestDay {
+    pub device: String,
+    pub weekday: String,
+    pub sample_count: usize,
+    pub avg_premium_percent: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimingReport {
+    pub dated_sales: usize,
+    pub by_weekday: Vec<TimeBucket>,
+    // Empty unless sold dates carry a time of day
+    pub by_hour: Vec<TimeBucket>,
+    pub best_day_by_device: Vec<DeviceBestDay>,
+}
+
+// eBay shows "Oct 3, 2026"; accept an optional trailing time as well
+pub fn parse_sold_at(sold_date: &str) -> Option<(NaiveDate, Option<u32>)> {
+    let sold_date = sold_date.trim();
+    for format in ["%b %d, %Y %H:%M", "%b %d, %Y %I:%M %p", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(at) = NaiveDateTime::parse_from_str(sold_date, format) {
+            return Some((at.date(), Some(at.hour())));
+        }
+    }
+    for format in ["%b %d, %Y", "%Y-%m-%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(sold_date, format) {
+            return Some((date, None));
+        }
+    }
+    None
+}
+
+fn buckets(groups: BTreeMap<usize, Vec<f64>>, label: impl Fn(usize) -> String) -> Vec<TimeBucket> {
+    groups
+        .into_iter()
+        .filter(|(_, premiums)| premiums.len() >= MIN_BUCKET_SAMPLES)
+        .map(|(key, premiums)| TimeBucket {
+            label: label(key),
+            sample_count: premiums.len(),
+            avg_premium_percent: premiums.iter().sum::<f64>() / premiums.len() as f64,
+        })
+        .collect()
+}
+
+pub fn timing_report(sold_comps: &[Product], config: &Config) -> TimingReport {
+    // device -> [(weekday, hour, price)]
+    let mut by_device: BTreeMap<String, Vec<(usize, Option<u32>, Money)>> = BTreeMap::new();
+
+    for sold in sold_comps {
+        let Some((date, hour)) = sold.sold_date.as_deref().and_then(parse_sold_at) else {
+            continue;
+        };
+        let (Some(price), Some(device)) = (price_above_floor(sold, config), sold.attributes.device_key()) else {
+            continue;
+        };
+        let weekday = date.weekday().num_days_from_monday() as usize;
+        by_device.entry(device).or_default().push((weekday, hour, price));
+    }
+
+    let mut report = TimingReport::default();
+    let mut weekday_groups: BTreeMap<usize, Vec<f64>> = BTreeMap::new();
+    let mut hour_groups: BTreeMap<usize, Vec<f64>> = BTreeMap::new();
+
+    for (device, sales) in by_device {
+        // A single sale is always exactly its own average
+        if sales.len() < 2 {
+            continue;
+        }
+        let prices: Vec<Money> = sales.iter().map(|(_, _, price)| *price).collect();
+        let Some(device_avg) = Money::mean(&prices) else {
+            continue;
+        };
+        let mut device_weekdays: BTreeMap<usize, Vec<f64>> = BTreeMap::new();
+
+        for (weekday, hour, price) in &sales {
+            let premium = (*price - device_avg).percent_of(device_avg);
+            weekday_groups.entry(*weekday).or_default().push(premium);
+            device_weekdays.entry(*weekday).or_default().push(premium);
+            if let Some(hour) = hour {
+                hour_groups.entry(*hour as usize).or_default().push(premium);
+            }
+        }
+        report.dated_sales += sales.len();
+
+        let best = buckets(device_weekdays, |d| WEEKDAYS[d].to_string())
+            .into_iter()
+            .max_by(|a, b| a.avg_premium_percent.partial_cmp(&b.avg_premium_percent).unwrap_or(std::cmp::Ordering::Equal));
+        if let Some(best) = best {
+            report.best_day_by_device.push(DeviceBestDay {
+                device,
+                weekday: best.label,
+                sample_count: best.sample_count,
+                avg_premium_percent: best.avg_premium_percent,
+            });
+        }
+    }
+
+    report.by_weekday = buckets(weekday_groups, |d| WEEKDAYS[d].to_string());
+    report.by_hour = buckets(hour_groups, |h| format!("{:02}:00", h));
+    report
+}
+
+pub fn display_timing_report(report: &TimingReport) {
+    if report.by_weekday.is_empty() {
+        return;
+    }
+
+    println!("\n🕒 SOLD PRICE BY END DAY ({} dated sales):", report.dated_sales);
+    for bucket in &report.by_weekday {
+        println!("   {}: {:+.1}% vs device average ({} sales)", bucket.label, bucket.avg_premium_percent, bucket.sample_count);
+    }
+    if !report.by_hour.is_empty() {
+        println!("   By end hour:");
+        for bucket in &report.by_hour {
+            println!("   {}: {:+.1}% ({} sales)", bucket.label, bucket.avg_premium_percent, bucket.sample_count);
+        }
+    }
+    for best in report.best_day_by_device.iter().take(10) {
+        println!("   📅 {} sells best on {} ({:+.1}%, {} sales)", best.device, best.weekday, best.avg_premium_percent, best.sample_count);
+    }
+}
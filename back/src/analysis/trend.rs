@@ -0,0 +1,86 @@
+// Opportunity trends between runs: each opportunity's net profit and margin
+// against the same buy listing in the most recent earlier run that had it as
+// an opportunity, so deals moving in your favor (price cut, comps rising)
+// stand out from ones drifting away.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::money::{Currency, Money};
+use crate::storage;
+
+// How many earlier runs to search for a listing's last values
+const LOOKBACK_RUNS: usize = 5;
+// Profit changes smaller than a dollar count as steady
+const STEADY_PROFIT_DELTA: Money = Money::from_minor(100, Currency::Usd);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Improving,
+    Worsening,
+    Steady,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trend {
+    pub direction: TrendDirection,
+    pub profit_delta: Money,
+    // Percentage points
+    pub margin_delta: f64,
+    pub previous_run_id: u32,
+}
+
+impl Trend {
+    pub fn arrow(&self) -> &'static str {
+        match self.direction {
+            TrendDirection::Improving => "↑",
+            TrendDirection::Worsening => "↓",
+            TrendDirection::Steady => "→",
+        }
+    }
+
+    // "↑ +$12.50 (+2.1 pts) since run 41"
+    pub fn label(&self) -> String {
+        format!(
+            "{} {:+} ({:+.1} pts) since run {}",
+            self.arrow(),
+            self.profit_delta,
+            self.margin_delta,
+            self.previous_run_id
+        )
+    }
+}
+
+// Buy URL -> (run id, net profit, margin) from the newest earlier run listing it
+pub struct PreviousValues(HashMap<String, (u32, Money, f64)>);
+
+// Read before the current run is stored, so the newest stored run is the previous one
+pub fn previous_values() -> PreviousValues {
+    let mut values = HashMap::new();
+    for entry in storage::load_run_index().iter().rev().take(LOOKBACK_RUNS) {
+        let Some(run) = storage::load_run(entry.run_id) else {
+            continue;
+        };
+        for opp in run.arbitrage_opportunities {
+            values.entry(opp.buy_url).or_insert((run.run_id, opp.potential_profit, opp.margin_percent));
+        }
+    }
+    PreviousValues(values)
+}
+
+impl PreviousValues {
+    // None for a listing that wasn't an opportunity in the lookback window
+    pub fn trend(&self, buy_url: &str, profit: Money, margin_percent: f64) -> Option<Trend> {
+        let (previous_run_id, previous_profit, previous_margin) = *self.0.get(buy_url)?;
+        let profit_delta = profit - previous_profit;
+        let direction = if profit_delta.abs() < STEADY_PROFIT_DELTA {
+            TrendDirection::Steady
+        } else if profit_delta.is_positive() {
+            TrendDirection::Improving
+        } else {
+            TrendDirection::Worsening
+        };
+        Some(Trend { direction, profit_delta, margin_delta: margin_percent - previous_margin, previous_run_id })
+    }
+}
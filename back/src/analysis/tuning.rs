@@ -0,0 +1,194 @@
+// Signal-to-noise report over stored run history (`back analysis report
+// --tuning`). An opportunity whose listing vanished within the window was
+// presumably sold, i.e. a real deal; one that lingered was probably priced
+// that way for a reason. Bucketing by margin and comp count shows which
+// thresholds surface deals that actually move.
+
+use chrono::{Duration, NaiveDateTime};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::storage;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+const MARGIN_BUCKETS: [(f64, &str); 5] = [(20.0, "10-20%"), (35.0, "20-35%"), (50.0, "35-50%"), (100.0, "50-100%"), (f64::INFINITY, "100%+")];
+const COMP_BUCKETS: [(usize, &str); 3] = [(4, "2-4 comps"), (9, "5-9 comps"), (usize::MAX, "10+ comps")];
+
+// What happened to a surfaced listing within the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Vanished,
+    Lingered,
+    // Still listed and younger than the window
+    Pending,
+}
+
+impl Outcome {
+    pub fn label(self) -> &'static str {
+        match self {
+            Outcome::Vanished => "vanished",
+            Outcome::Lingered => "lingered",
+            Outcome::Pending => "pending",
+        }
+    }
+}
+
+// Per stored run, oldest first: timestamp and every buy-side URL still listed
+pub fn listing_history() -> Vec<(NaiveDateTime, HashSet<String>)> {
+    let mut runs = Vec::new();
+    for entry in storage::load_run_index() {
+        let Some(run) = storage::load_run(entry.run_id) else {
+            continue;
+        };
+        let Ok(timestamp) = NaiveDateTime::parse_from_str(&run.timestamp, TIMESTAMP_FORMAT) else {
+            continue;
+        };
+        runs.push((timestamp, listed_urls(&run)));
+    }
+    runs.sort_by_key(|(timestamp, _)| *timestamp);
+    runs
+}
+
+fn listed_urls(run: &crate::RunSnapshot) -> HashSet<String> {
+    run.swappa_products
+        .iter()
+        .chain(run.newegg_products.iter())
+        .chain(run.mercari_products.iter())
+        .chain(run.storefront_products.iter())
+        .map(|p| p.url.clone())
+        .chain(run.arbitrage_opportunities.iter().map(|o| o.buy_url.clone()))
+        .collect()
+}
+
+// First run after the opportunity surfaced that no longer lists it
+pub fn gone_at(url: &str, first_seen: NaiveDateTime, runs: &[(NaiveDateTime, HashSet<String>)]) -> Option<NaiveDateTime> {
+    runs.iter()
+        .filter(|(timestamp, _)| *timestamp > first_seen)
+        .find(|(_, listed)| !listed.contains(url))
+        .map(|(timestamp, _)| *timestamp)
+}
+
+// Outcome of a listing first seen at `first_seen`, from the listing history
+pub fn outcome(url: &str, first_seen: NaiveDateTime, runs: &[(NaiveDateTime, HashSet<String>)], window: Duration) -> Outcome {
+    let gone_at = gone_at(url, first_seen, runs);
+    let newest = runs.last().map_or(first_seen, |(timestamp, _)| *timestamp);
+
+    match gone_at {
+        Some(gone_at) if gone_at - first_seen <= window => Outcome::Vanished,
+        Some(_) => Outcome::Lingered,
+        None if newest - first_seen > window => Outcome::Lingered,
+        None => Outcome::Pending,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Outcomes {
+    vanished: usize,
+    lingered: usize,
+    // Still listed and younger than the window
+    pending: usize,
+}
+
+impl Outcomes {
+    fn vanished_percent(&self) -> Option<f64> {
+        let decided = self.vanished + self.lingered;
+        (decided > 0).then(|| self.vanished as f64 / decided as f64 * 100.0)
+    }
+}
+
+// First sighting of an opportunity
+struct Surfaced {
+    first_seen: NaiveDateTime,
+    margin_percent: f64,
+    comp_count: usize,
+}
+
+// Bucket indexes into MARGIN_BUCKETS / COMP_BUCKETS
+fn margin_bucket(margin: f64) -> usize {
+    MARGIN_BUCKETS.iter().position(|(max, _)| margin < *max).unwrap_or(MARGIN_BUCKETS.len() - 1)
+}
+
+fn comp_bucket(count: usize) -> usize {
+    COMP_BUCKETS.iter().position(|(max, _)| count <= *max).unwrap_or(COMP_BUCKETS.len() - 1)
+}
+
+pub fn print_tuning_report(window_hours: i64) {
+    let window = Duration::hours(window_hours);
+
+    // Per run: timestamp and every buy-side URL still listed
+    let mut runs: Vec<(NaiveDateTime, HashSet<String>)> = Vec::new();
+    let mut surfaced: HashMap<String, Surfaced> = HashMap::new();
+    for entry in storage::load_run_index() {
+        let Some(run) = storage::load_run(entry.run_id) else {
+            continue;
+        };
+        let Ok(timestamp) = NaiveDateTime::parse_from_str(&run.timestamp, TIMESTAMP_FORMAT) else {
+            continue;
+        };
+        let listed = listed_urls(&run);
+        for opp in &run.arbitrage_opportunities {
+            surfaced.entry(opp.buy_url.clone()).or_insert(Surfaced {
+                first_seen: timestamp,
+                margin_percent: opp.margin_percent,
+                comp_count: opp.ebay_sold_count,
+            });
+        }
+        runs.push((timestamp, listed));
+    }
+    runs.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let (Some((oldest, _)), Some((newest, _))) = (runs.first(), runs.last()) else {
+        println!("No stored runs to analyze");
+        return;
+    };
+    if *newest - *oldest < window {
+        println!(
+            "⚠️  Stored runs only cover {}h, less than the {}h window; raise [maintenance] run_history_days to keep more",
+            (*newest - *oldest).num_hours(),
+            window_hours
+        );
+    }
+
+    // (margin bucket, comp bucket) -> outcomes
+    let mut table: BTreeMap<(usize, usize), Outcomes> = BTreeMap::new();
+    let mut totals = Outcomes::default();
+    for (url, opp) in &surfaced {
+        let outcomes = table.entry((margin_bucket(opp.margin_percent), comp_bucket(opp.comp_count))).or_default();
+        match outcome(url, opp.first_seen, &runs, window) {
+            Outcome::Vanished => {
+                outcomes.vanished += 1;
+                totals.vanished += 1;
+            }
+            Outcome::Lingered => {
+                outcomes.lingered += 1;
+                totals.lingered += 1;
+            }
+            Outcome::Pending => {
+                outcomes.pending += 1;
+                totals.pending += 1;
+            }
+        }
+    }
+
+    println!("🎯 TUNING REPORT: {} opportunities over {} runs ({}h window)", surfaced.len(), runs.len(), window_hours);
+    println!("   Vanished = no longer listed within {}h (presumably sold); lingered = still listed after it\n", window_hours);
+    println!("   {:<10} {:<11} {:>8} {:>8} {:>8} {:>10}", "Margin", "Comps", "Vanished", "Lingered", "Pending", "Vanished %");
+    for ((margin_index, comp_index), outcomes) in &table {
+        println!(
+            "   {:<10} {:<11} {:>8} {:>8} {:>8} {:>10}",
+            MARGIN_BUCKETS[*margin_index].1,
+            COMP_BUCKETS[*comp_index].1,
+            outcomes.vanished,
+            outcomes.lingered,
+            outcomes.pending,
+            outcomes.vanished_percent().map_or("-".to_string(), |p| format!("{:.0}%", p)),
+        );
+    }
+    println!(
+        "\n   Overall: {} vanished, {} lingered, {} pending ({} vanished)",
+        totals.vanished,
+        totals.lingered,
+        totals.pending,
+        totals.vanished_percent().map_or("-".to_string(), |p| format!("{:.0}%", p)),
+    );
+}
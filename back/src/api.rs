@@ -0,0 +1,194 @@
+// HTTP API over the stored run history, so the dashboard can page through
+// runs and load one run's products at a time, plus matcher feedback.
+//   GET  /api/runs?limit=5&offset=20   run summaries, newest first
+//   GET  /api/runs/{id}/products       product arrays of a single run
+//   GET  /api/changes/latest           delta of the newest run vs the one before
+//   GET  /api/kpis                     newest run's KPIs and market heat index
+//   GET  /api/asof?device=iphone-13&at=2024-06-01T12:00:00Z
+//                                      best ask, sold median and open opportunities at a past moment
+//   POST /api/feedback/bad-match       {"buy_url": ..., "reason": ...}
+//                                      404 unknown buy_url, 409 before the first export
+//   GET  /api/notes                    all notes/tags by listing URL
+//   POST /api/notes                    {"url": ..., "note": ..., "add_tags": [...], "remove_tags": [...]}
+
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::heat::MarketHeat;
+use crate::analysis::{asof, changes};
+use crate::feedback::{self, BadMatchError};
+use crate::notes::{self, AnnotationUpdate};
+use crate::storage::{self, RunIndexEntry};
+use crate::{ArbitrageOpportunity, Product, ProductWithComparison};
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct PageParams {
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct RunsPage {
+    total: usize,
+    limit: usize,
+    offset: usize,
+    runs: Vec<RunIndexEntry>,
+}
+
+#[derive(Serialize)]
+struct RunProducts {
+    run_id: u32,
+    timestamp: String,
+    swappa_products: Vec<ProductWithComparison>,
+    newegg_products: Vec<ProductWithComparison>,
+    mercari_products: Vec<ProductWithComparison>,
+    storefront_products: Vec<ProductWithComparison>,
+    ebay_sold_products: Vec<Product>,
+    mercari_sold_products: Vec<Product>,
+    arbitrage_opportunities: Vec<ArbitrageOpportunity>,
+}
+
+#[derive(Serialize)]
+struct Kpis {
+    run: RunIndexEntry,
+    // From the last full export; None until there's enough sold history
+    market_heat: Option<MarketHeat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsOfParams {
+    device: String,
+    // Defaults to now
+    at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BadMatchRequest {
+    buy_url: String,
+    reason: Option<String>,
+}
+
+fn not_found(message: String) -> Response {
+    (StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+async fn list_runs(Query(params): Query<PageParams>) -> Json<RunsPage> {
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    let offset = params.offset.unwrap_or(0);
+
+    let mut index = storage::load_run_index();
+    index.reverse();
+    let total = index.len();
+    let runs = index.into_iter().skip(offset).take(limit).collect();
+
+    Json(RunsPage { total, limit, offset, runs })
+}
+
+async fn run_products(Path(run_id): Path<u32>) -> Response {
+    let Some(run) = storage::load_run(run_id) else {
+        return not_found(format!("run {} not found", run_id));
+    };
+    Json(RunProducts {
+        run_id: run.run_id,
+        timestamp: run.timestamp,
+        swappa_products: run.swappa_products,
+        newegg_products: run.newegg_products,
+        mercari_products: run.mercari_products,
+        storefront_products: run.storefront_products,
+        ebay_sold_products: run.ebay_sold_products,
+        mercari_sold_products: run.mercari_sold_products,
+        arbitrage_opportunities: run.arbitrage_opportunities,
+    })
+    .into_response()
+}
+
+async fn latest_changes() -> Response {
+    let index = storage::load_run_index();
+    let Some(latest) = index.last() else {
+        return not_found("no runs recorded yet".to_string());
+    };
+    let Some(current) = storage::load_run(latest.run_id) else {
+        return not_found(format!("run {} not found", latest.run_id));
+    };
+    let previous = index.iter().rev().nth(1).and_then(|entry| storage::load_run(entry.run_id));
+    Json(changes::diff_runs(previous.as_ref(), &current)).into_response()
+}
+
+async fn kpis() -> Response {
+    let Some(run) = storage::load_run_index().pop() else {
+        return not_found("no runs recorded yet".to_string());
+    };
+    let market_heat = crate::load_frontend_data().and_then(|data| data.market_heat);
+    Json(Kpis { run, market_heat }).into_response()
+}
+
+async fn market_as_of(Query(params): Query<AsOfParams>) -> Response {
+    let at = match params.at.as_deref().map(asof::parse_at) {
+        Some(Ok(at)) => at,
+        Some(Err(e)) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))).into_response(),
+        None => chrono::Local::now().naive_local(),
+    };
+    match asof::market_at(&params.device, at) {
+        Ok(state) => Json(state).into_response(),
+        Err(e) => not_found(e),
+    }
+}
+
+async fn bad_match(Json(request): Json<BadMatchRequest>) -> Response {
+    match feedback::mark_bad_match(&request.buy_url, request.reason) {
+        Ok(bad_match) => (StatusCode::CREATED, Json(bad_match)).into_response(),
+        Err(e) => {
+            let status = match e {
+                BadMatchError::UnknownBuyUrl(_) => StatusCode::NOT_FOUND,
+                BadMatchError::NoScraperData => StatusCode::CONFLICT,
+                BadMatchError::Write(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        }
+    }
+}
+
+async fn list_notes() -> Response {
+    Json(notes::load_annotations()).into_response()
+}
+
+async fn update_notes(Json(update): Json<AnnotationUpdate>) -> Response {
+    match notes::annotate(update) {
+        Ok(annotation) => Json(annotation).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))).into_response(),
+    }
+}
+
+fn router() -> Router {
+    Router::new()
+        .route("/api/runs", get(list_runs))
+        .route("/api/runs/{id}/products", get(run_products))
+        .route("/api/changes/latest", get(latest_changes))
+        .route("/api/kpis", get(kpis))
+        .route("/api/asof", get(market_as_of))
+        .route("/api/feedback/bad-match", post(bad_match))
+        .route("/api/notes", get(list_notes).post(update_notes))
+}
+
+// Serve the API until the process exits. Bind failures are logged and the
+// scraper keeps running without it.
+pub async fn serve(bind: String) {
+    let listener = match tokio::net::TcpListener::bind(&bind).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("❌ Failed to start API on {}: {}", bind, e);
+            return;
+        }
+    };
+    println!("🌐 API listening on http://{}/api/runs", bind);
+    if let Err(e) = axum::serve(listener, router()).await {
+        eprintln!("❌ API server stopped: {}", e);
+    }
+}
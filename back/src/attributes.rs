@@ -0,0 +1,381 @@
+// Structured attributes of a listing. Every Product carries them: sources
+// with structured data (Swappa's JSON) set what they know, and whatever is
+// left is parsed from the title and grade as products are collected, so
+// matching and pricing read typed fields instead of re-parsing names.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{catalog, locale};
+
+// Marketing color names, multi-word names first so "Black Titanium" wins
+// over "Black". "Mint" is left out since Swappa uses it as a condition grade.
+pub const COLORS: &[&str] = &[
+    // Apple
+    "natural titanium", "blue titanium", "white titanium", "black titanium", "desert titanium",
+    "space black", "space gray", "deep purple", "sierra blue", "alpine green", "pacific blue",
+    "product red", "midnight", "starlight", "graphite", "ultramarine", "teal",
+    // Samsung
+    "titanium gray", "titanium black", "titanium violet", "titanium yellow",
+    "phantom black", "onyx black", "marble gray", "cobalt violet", "amber yellow", "lavender", "cream",
+    // Google
+    "obsidian", "porcelain", "hazel", "bay", "snow", "charcoal", "lemongrass",
+    // Generic
+    "black", "white", "silver", "gold", "blue", "green", "purple", "pink", "red", "yellow", "gray",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductAttributes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand: Option<Brand>,
+    // Catalog device id, e.g. "iphone 15 pro max", or a [categories] keyword
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    // Exported in GB under its old name, which stored runs and the frontend use
+    #[serde(default, rename = "storage_gb", skip_serializing_if = "Option::is_none")]
+    pub storage: Option<Storage>,
+    // Unlocked or the carrier it's locked to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<Carrier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<Condition>,
+    // Lowercase marketing color, one of COLORS when parsed from a title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl ProductAttributes {
+    // Everything a bare title tells (no grade, so condition only from its wording)
+    pub fn parse(name: &str) -> ProductAttributes {
+        let mut attributes = ProductAttributes::default();
+        attributes.fill(name, None);
+        attributes
+    }
+
+    // Parse the attributes still unset from a listing's title and grade
+    pub fn fill(&mut self, name: &str, grade: Option<&str>) {
+        let device = catalog::identify(name);
+        if self.model.is_none() {
+            self.model = device
+                .map(|device| device.id.clone())
+                .or_else(|| catalog::category_model(name).map(|(_, keyword)| keyword.to_string()));
+        }
+        if self.brand.is_none() {
+            self.brand = device.map(|device| device.brand).or_else(|| parse_brand(name));
+        }
+        if self.storage.is_none() {
+            self.storage = parse_storage(name);
+        }
+        if self.carrier.is_none() {
+            self.carrier = parse_carrier(name);
+        }
+        if self.condition.is_none() {
+            self.condition = parse_condition(name, grade);
+        }
+        if self.color.is_none() {
+            self.color = parse_color(name).map(String::from);
+        }
+    }
+
+    // Model plus storage, the grouping key for per-device price stats
+    pub fn device_key(&self) -> Option<String> {
+        let model = self.model.as_ref()?;
+        match self.storage {
+            Some(storage) => Some(format!("{} {}", model, storage.label())),
+            None => Some(model.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Brand {
+    Apple,
+    Samsung,
+    Google,
+    OnePlus,
+    Motorola,
+}
+
+impl Brand {
+    pub fn label(self) -> &'static str {
+        match self {
+            Brand::Apple => "Apple",
+            Brand::Samsung => "Samsung",
+            Brand::Google => "Google",
+            Brand::OnePlus => "OnePlus",
+            Brand::Motorola => "Motorola",
+        }
+    }
+}
+
+// Brand named in a title, or implied by a product line ("iPhone", "Galaxy")
+pub fn parse_brand(name: &str) -> Option<Brand> {
+    let text = locale::to_english(name);
+    let has = |words: &[&str]| text.split(|c: char| !c.is_alphanumeric()).any(|word| words.contains(&word));
+
+    if has(&["apple", "iphone", "ipad"]) {
+        Some(Brand::Apple)
+    } else if has(&["samsung", "galaxy"]) {
+        Some(Brand::Samsung)
+    } else if has(&["google", "pixel"]) {
+        Some(Brand::Google)
+    } else if has(&["oneplus"]) {
+        Some(Brand::OnePlus)
+    } else if has(&["motorola", "moto"]) {
+        Some(Brand::Motorola)
+    } else {
+        None
+    }
+}
+
+// Phone storage tiers; other sizes in titles are RAM ("12GB RAM").
+// Serialized as the size in GB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub enum Storage {
+    Gb32,
+    Gb64,
+    Gb128,
+    Gb256,
+    Gb512,
+    Tb1,
+    Tb2,
+}
+
+impl Storage {
+    const ALL: [Storage; 7] = [Storage::Gb32, Storage::Gb64, Storage::Gb128, Storage::Gb256, Storage::Gb512, Storage::Tb1, Storage::Tb2];
+
+    pub fn gb(self) -> u32 {
+        match self {
+            Storage::Gb32 => 32,
+            Storage::Gb64 => 64,
+            Storage::Gb128 => 128,
+            Storage::Gb256 => 256,
+            Storage::Gb512 => 512,
+            Storage::Tb1 => 1024,
+            Storage::Tb2 => 2048,
+        }
+    }
+
+    pub fn from_gb(gb: u32) -> Option<Storage> {
+        Storage::ALL.into_iter().find(|storage| storage.gb() == gb)
+    }
+
+    // "256gb", "1tb"
+    pub fn label(self) -> String {
+        storage_label(self.gb())
+    }
+}
+
+impl TryFrom<u32> for Storage {
+    type Error = String;
+
+    fn try_from(gb: u32) -> Result<Storage, String> {
+        Storage::from_gb(gb).ok_or_else(|| format!("{} GB is not a storage tier", gb))
+    }
+}
+
+impl From<Storage> for u32 {
+    fn from(storage: Storage) -> u32 {
+        storage.gb()
+    }
+}
+
+// First storage tier in the title: "256GB", "256 GB", "1TB"
+pub fn parse_storage(name: &str) -> Option<Storage> {
+    let text = locale::to_english(name);
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() || (i > 0 && bytes[i - 1].is_ascii_alphanumeric()) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let number: u32 = text[start..i].parse().unwrap_or(0);
+        let unit = text[i..].trim_start();
+        let multiplier = if unit.starts_with("gb") {
+            1
+        } else if unit.starts_with("tb") {
+            1024
+        } else {
+            continue;
+        };
+        let whole_word = !unit[2..].starts_with(|c: char| c.is_ascii_alphanumeric());
+        if whole_word && let Some(storage) = Storage::from_gb(number.saturating_mul(multiplier)) {
+            return Some(storage);
+        }
+    }
+    None
+}
+
+// Multi-unit listings: "lot of 5", "3x iPhone 12", "x3", "10 pcs"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lot {
+    Quantity(u32),
+    // "lot", "wholesale" or a "phones bundle" without a count
+    Unknown,
+}
+
+const LOT_WORDS: [&str; 4] = ["lot", "bundle", "set", "pack"];
+const UNIT_WORDS: [&str; 6] = ["pcs", "pc", "pieces", "units", "phones", "devices"];
+const PLURAL_DEVICE_WORDS: [&str; 5] = ["iphones", "smartphones", "handsets", "cellphones", "tablets"];
+
+pub fn parse_lot(name: &str) -> Option<Lot> {
+    let text = name.to_lowercase();
+    let tokens: Vec<&str> = text
+        .split(|c: char| c.is_whitespace() || "(),/-:;".contains(c))
+        .filter(|t| !t.is_empty())
+        .collect();
+    let count = |token: &str| token.parse::<u32>().ok().filter(|n| (2..=100).contains(n));
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1).copied().unwrap_or_default();
+        // "lot of 5", "bundle of 3"
+        if LOT_WORDS.contains(token)
+            && next == "of"
+            && let Some(n) = tokens.get(i + 2).and_then(|t| count(t))
+        {
+            return Some(Lot::Quantity(n));
+        }
+        // "3x" / "x3", but not a camera's "5x zoom"
+        let multiplier = token.strip_suffix('x').or_else(|| token.strip_prefix('x')).and_then(count);
+        if let Some(n) = multiplier
+            && !matches!(next, "zoom" | "optical" | "telephoto")
+        {
+            return Some(Lot::Quantity(n));
+        }
+        // "10 pcs", "10pcs"
+        if let Some(n) = count(token).filter(|_| UNIT_WORDS.contains(&next)) {
+            return Some(Lot::Quantity(n));
+        }
+        if let Some(n) = UNIT_WORDS.iter().find_map(|unit| token.strip_suffix(unit)).and_then(count) {
+            return Some(Lot::Quantity(n));
+        }
+    }
+    // "bundle" alone is usually one phone with extras ("iPhone 13 Bundle",
+    // "Bundle: iPhone 12 + AirPods"); it's a lot only next to a count or
+    // plural device word ("phones bundle", "bundle of iphones", "bundle 3")
+    let bundle = tokens.iter().enumerate().any(|(i, t)| {
+        let after = match tokens.get(i + 1).copied() {
+            Some("of") => tokens.get(i + 2).copied(),
+            next => next,
+        };
+        let neighbors = [i.checked_sub(1).and_then(|j| tokens.get(j).copied()), after];
+        matches!(*t, "bundle" | "bundles")
+            && neighbors.into_iter().flatten().any(|n| count(n).is_some() || UNIT_WORDS.contains(&n) || PLURAL_DEVICE_WORDS.contains(&n))
+    });
+    (bundle || tokens.iter().any(|t| matches!(*t, "lot" | "lots" | "wholesale"))).then_some(Lot::Unknown)
+}
+
+// "256gb", "1tb", as used in device keys and [storage] price_adjustments
+pub fn storage_label(gb: u32) -> String {
+    if gb >= 1024 && gb.is_multiple_of(1024) {
+        format!("{}tb", gb / 1024)
+    } else {
+        format!("{}gb", gb)
+    }
+}
+
+// Find a color name as whole words in the title
+pub fn parse_color(name: &str) -> Option<&'static str> {
+    let normalized: String = locale::to_english(name)
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let padded = format!(" {} ", normalized.split_whitespace().collect::<Vec<_>>().join(" "));
+
+    COLORS
+        .iter()
+        .find(|color| padded.contains(&format!(" {} ", color)))
+        .copied()
+}
+
+// Listing condition, normalized across Swappa grades, storefront grades and
+// eBay/Mercari titles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Condition {
+    New,
+    // Mint, pristine, flawless, like new, grade A
+    Excellent,
+    Good,
+    Fair,
+    // Refurbished/renewed without a cosmetic grade
+    Refurbished,
+}
+
+impl Condition {
+    pub fn label(self) -> &'static str {
+        match self {
+            Condition::New => "New",
+            Condition::Excellent => "Excellent",
+            Condition::Good => "Good",
+            Condition::Fair => "Fair",
+            Condition::Refurbished => "Refurbished",
+        }
+    }
+}
+
+// Carrier lock; locked phones sell for less and only to that carrier's customers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Carrier {
+    Unlocked,
+    Verizon,
+    #[serde(rename = "AT&T")]
+    Att,
+    #[serde(rename = "T-Mobile")]
+    TMobile,
+}
+
+impl Carrier {
+    pub fn label(self) -> &'static str {
+        match self {
+            Carrier::Unlocked => "Unlocked",
+            Carrier::Verizon => "Verizon",
+            Carrier::Att => "AT&T",
+            Carrier::TMobile => "T-Mobile",
+        }
+    }
+}
+
+// "Unlocked" wins over a carrier name, since unlocked listings often name the
+// carrier they came from ("Verizon, factory unlocked")
+pub fn parse_carrier(name: &str) -> Option<Carrier> {
+    let text = locale::to_english(name);
+    let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+
+    if has(&["unlocked"]) && !has(&["not unlocked", "carrier locked"]) {
+        Some(Carrier::Unlocked)
+    } else if has(&["verizon"]) {
+        Some(Carrier::Verizon)
+    } else if has(&["at&t", "at & t"]) || text.split(|c: char| !c.is_alphanumeric()).any(|w| w == "att") {
+        Some(Carrier::Att)
+    } else if has(&["t-mobile", "tmobile", "t mobile"]) {
+        Some(Carrier::TMobile)
+    } else {
+        None
+    }
+}
+
+// Condition from a storefront grade if there is one, else from the title.
+// A cosmetic grade wins over "refurbished", which only says who restored it.
+pub fn parse_condition(name: &str, grade: Option<&str>) -> Option<Condition> {
+    let text = locale::to_english(&format!("{} {}", grade.unwrap_or_default(), name));
+    let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+
+    if has(&["mint", "pristine", "flawless", "excellent", "like new", "grade a", "premium"]) {
+        Some(Condition::Excellent)
+    } else if has(&["good", "grade b"]) {
+        Some(Condition::Good)
+    } else if has(&["fair", "acceptable", "grade c"]) {
+        Some(Condition::Fair)
+    } else if has(&["refurbished", "renewed", "re-newed"]) {
+        Some(Condition::Refurbished)
+    } else if has(&["new"]) && !has(&["pre-owned"]) {
+        Some(Condition::New)
+    } else {
+        None
+    }
+}
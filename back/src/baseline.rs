@@ -0,0 +1,163 @@
+// Known-good extraction baselines per source/device. `back baseline save`
+// records the shape of a good scrape (how many products, how often each
+// field is filled, how many URLs are usable); every run is then compared
+// against it so a selector that silently breaks shows up as a regression
+// instead of as a quiet drop in opportunities.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::cli::BaselineSaveArgs;
+use crate::config::{BaselineConfig, Config};
+use crate::error_journal::{self, ErrorCategory};
+use crate::{Product, single_source, storage};
+
+const BASELINE_FILE: &str = "baselines.json";
+const ALL_DEVICES: &str = "*";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub source: String,
+    pub device: String,
+    pub saved_at: String,
+    pub products: usize,
+    // Share of products with each field present (price: parseable)
+    pub fill_rates: BTreeMap<String, f64>,
+    // Share of products whose URL is an absolute http(s) URL
+    pub valid_url_rate: f64,
+}
+
+fn rate(products: &[Product], present: impl Fn(&Product) -> bool) -> f64 {
+    if products.is_empty() {
+        return 0.0;
+    }
+    products.iter().filter(|p| present(p)).count() as f64 / products.len() as f64
+}
+
+fn valid_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://")) && url.split('/').nth(2).is_some_and(|host| host.contains('.'))
+}
+
+fn measure(source: &str, device: &str, products: &[Product]) -> Baseline {
+    let fill_rates = BTreeMap::from([
+        ("name".to_string(), rate(products, |p| !p.name.trim().is_empty())),
+        ("price".to_string(), rate(products, |p| p.price.is_some())),
+        ("sold_date".to_string(), rate(products, |p| p.sold_date.is_some())),
+        ("grade".to_string(), rate(products, |p| p.grade.is_some())),
+        ("warranty".to_string(), rate(products, |p| p.warranty.is_some())),
+    ]);
+    Baseline {
+        source: source.to_string(),
+        device: device.to_string(),
+        saved_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        products: products.len(),
+        fill_rates,
+        valid_url_rate: rate(products, |p| valid_url(&p.url)),
+    }
+}
+
+fn key(source: &str, device: &str) -> String {
+    format!("{}/{}", source, device)
+}
+
+fn load() -> BTreeMap<String, Baseline> {
+    match fs::read_to_string(storage::profile_path(BASELINE_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+// `baseline save`: scrape the source now and store its shape as the baseline,
+// one entry per Product.source it returned (storefront sources may span regions)
+pub async fn save(args: &BaselineSaveArgs, config: &Config, client: &reqwest::Client) -> Result<(), String> {
+    let products = single_source::scrape(&args.source, args.device.as_deref(), config, client).await?;
+    if products.is_empty() {
+        return Err(format!("{} returned no products; not saving an empty baseline", args.source));
+    }
+    let device = args.device.clone().unwrap_or_else(|| ALL_DEVICES.to_string());
+
+    let mut by_source: BTreeMap<&str, Vec<Product>> = BTreeMap::new();
+    for product in &products {
+        by_source.entry(product.source.as_str()).or_default().push(product.clone());
+    }
+    let mut baselines = load();
+    for (source, products) in by_source {
+        let baseline = measure(source, &device, &products);
+        print_baseline(&baseline);
+        baselines.insert(key(source, &device), baseline);
+    }
+    storage::write_json(&storage::profile_path(BASELINE_FILE), &baselines)
+}
+
+fn print_baseline(baseline: &Baseline) {
+    let rates: Vec<String> = baseline.fill_rates.iter().map(|(field, rate)| format!("{} {:.0}%", field, rate * 100.0)).collect();
+    println!(
+        "📐 {} [{}]: {} products, valid URLs {:.0}%, {}",
+        baseline.source, baseline.device, baseline.products, baseline.valid_url_rate * 100.0, rates.join(", ")
+    );
+}
+
+pub fn print_baselines() {
+    let baselines = load();
+    if baselines.is_empty() {
+        println!("No baselines saved yet. Save one with `back baseline save <source>`.");
+    }
+    for baseline in baselines.values() {
+        print_baseline(baseline);
+        println!("   saved {}", baseline.saved_at);
+    }
+}
+
+// What got worse compared to the baseline, if anything significant
+fn regressions(baseline: &Baseline, current: &Baseline, config: &BaselineConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    if (current.products as f64) < baseline.products as f64 * config.min_count_ratio {
+        problems.push(format!("{} products vs {} in baseline", current.products, baseline.products));
+    }
+    for (field, expected) in &baseline.fill_rates {
+        let actual = current.fill_rates.get(field).copied().unwrap_or(0.0);
+        if expected - actual > config.max_rate_drop {
+            problems.push(format!("{} filled {:.0}% vs {:.0}%", field, actual * 100.0, expected * 100.0));
+        }
+    }
+    if baseline.valid_url_rate - current.valid_url_rate > config.max_rate_drop {
+        problems.push(format!(
+            "valid URLs {:.0}% vs {:.0}%",
+            current.valid_url_rate * 100.0,
+            baseline.valid_url_rate * 100.0
+        ));
+    }
+    problems
+}
+
+// Compare this run's products against every saved baseline, journaling each
+// regression. Returns the number of source/device pairs that regressed.
+pub fn check<'a>(products: impl Iterator<Item = &'a Product>, config: &BaselineConfig) -> usize {
+    let baselines = load();
+    if baselines.is_empty() {
+        return 0;
+    }
+    let products: Vec<&Product> = products.collect();
+
+    let mut regressed = 0;
+    for baseline in baselines.values() {
+        let device = (baseline.device != ALL_DEVICES).then_some(baseline.device.as_str());
+        let current_products: Vec<Product> = products
+            .iter()
+            .filter(|p| p.source == baseline.source && single_source::device_matches(&p.name, device))
+            .map(|p| (*p).clone())
+            .collect();
+        let current = measure(&baseline.source, &baseline.device, &current_products);
+        let problems = regressions(baseline, &current, config);
+        if problems.is_empty() {
+            continue;
+        }
+        regressed += 1;
+        let message = format!("Extraction regressed from baseline [{}]: {}", baseline.device, problems.join("; "));
+        eprintln!("  ⚠️  {}: {}", baseline.source, message);
+        error_journal::record(ErrorCategory::Parse, &baseline.source, None, &message, None);
+    }
+    regressed
+}
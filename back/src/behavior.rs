@@ -0,0 +1,200 @@
+// Human-like page interaction for the Selenium scrapers. Fixed load waits
+// and evenly spaced scrolls every 600-800ms are an easy bot signature over a
+// long session, so each source's interaction is drawn from a behavior
+// profile instead: a load wait, wheel scrolls of random size with a dwell
+// after each (now and then a longer reading pause or a nudge back up), and
+// pointer movement between scrolls. Wheel and pointer events go through CDP,
+// so the page sees trusted input; a driver without CDP falls back to
+// window.scrollBy. Profiles are picked per source under [politeness.behavior].
+
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::page_driver::PageDriver;
+
+// Where the pointer may wander: inside the 1920x1200 window the scrapers open
+const POINTER_X: [u64; 2] = [120, 1800];
+const POINTER_Y: [u64; 2] = [160, 1050];
+// Intermediate points per pointer movement
+const POINTER_STEPS: [u64; 2] = [4, 12];
+
+// Ranges are [low, high], inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BehaviorProfile {
+    // Wait after a page opens before touching it
+    pub load_wait_ms: [u64; 2],
+    pub scroll_step_px: [u64; 2],
+    // Pause after each scroll step
+    pub dwell_ms: [u64; 2],
+    // Chance per step of a longer pause, as if reading a listing
+    pub read_chance: f64,
+    pub read_ms: [u64; 2],
+    // Chance per step of scrolling back up a little before going on
+    pub scroll_back_chance: f64,
+    // Pointer movements per scroll step
+    pub mouse_moves: [u64; 2],
+    // Pause between pages of the same source
+    pub between_pages_ms: [u64; 2],
+}
+
+const BRISK: BehaviorProfile = BehaviorProfile {
+    load_wait_ms: [2500, 4000],
+    scroll_step_px: [600, 1100],
+    dwell_ms: [300, 800],
+    read_chance: 0.05,
+    read_ms: [1200, 2500],
+    scroll_back_chance: 0.03,
+    mouse_moves: [0, 1],
+    between_pages_ms: [600, 1500],
+};
+
+const CASUAL: BehaviorProfile = BehaviorProfile {
+    load_wait_ms: [3500, 5500],
+    scroll_step_px: [350, 800],
+    dwell_ms: [450, 1400],
+    read_chance: 0.12,
+    read_ms: [1800, 4500],
+    scroll_back_chance: 0.08,
+    mouse_moves: [0, 2],
+    between_pages_ms: [900, 2500],
+};
+
+const CAUTIOUS: BehaviorProfile = BehaviorProfile {
+    load_wait_ms: [4500, 8000],
+    scroll_step_px: [250, 650],
+    dwell_ms: [700, 2200],
+    read_chance: 0.2,
+    read_ms: [2500, 7000],
+    scroll_back_chance: 0.12,
+    mouse_moves: [1, 3],
+    between_pages_ms: [2000, 6000],
+};
+
+// Fields a custom profile leaves out come from "casual"
+impl Default for BehaviorProfile {
+    fn default() -> Self {
+        CASUAL
+    }
+}
+
+impl BehaviorProfile {
+    // Profiles available by name without defining them in config.toml
+    pub fn builtin(name: &str) -> Option<BehaviorProfile> {
+        match name {
+            "brisk" => Some(BRISK),
+            "casual" => Some(CASUAL),
+            "cautious" => Some(CAUTIOUS),
+            _ => None,
+        }
+    }
+}
+
+// A profile in use by one source, with its own random stream and pointer position
+#[derive(Debug)]
+pub struct Behavior {
+    pub profile: BehaviorProfile,
+    // The politeness preset's delay factor, applied to the pause between pages
+    pace: f64,
+    state: AtomicU64,
+    pointer: Mutex<(f64, f64)>,
+}
+
+impl Behavior {
+    pub fn new(profile: BehaviorProfile, pace: f64) -> Behavior {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+        Behavior::seeded(profile, pace, nanos)
+    }
+
+    pub fn seeded(profile: BehaviorProfile, pace: f64, seed: u64) -> Behavior {
+        Behavior { profile, pace, state: AtomicU64::new(seed | 1), pointer: Mutex::new((960.0, 600.0)) }
+    }
+
+    // xorshift64*: not for secrets, just unpredictable enough to not repeat
+    fn next(&self) -> u64 {
+        let step = |mut x: u64| {
+            x ^= x >> 12;
+            x ^= x << 25;
+            x ^= x >> 27;
+            x
+        };
+        let previous = self.state.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| Some(step(x))).unwrap_or(1);
+        step(previous).wrapping_mul(0x2545f4914f6cdd1d)
+    }
+
+    pub fn between(&self, [low, high]: [u64; 2]) -> u64 {
+        let (low, high) = (low.min(high), low.max(high));
+        low + self.next() % (high - low).saturating_add(1)
+    }
+
+    fn chance(&self, probability: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    fn duration(&self, range_ms: [u64; 2]) -> Duration {
+        Duration::from_millis(self.between(range_ms))
+    }
+
+    pub async fn wait_for_load(&self, driver: &dyn PageDriver) {
+        driver.settle(self.duration(self.profile.load_wait_ms)).await;
+    }
+
+    pub async fn between_pages(&self, driver: &dyn PageDriver) {
+        driver.settle(self.duration(self.profile.between_pages_ms).mul_f64(self.pace.max(0.0))).await;
+    }
+
+    // Glide the pointer to a random spot along a slightly bowed path
+    async fn move_pointer(&self, driver: &dyn PageDriver) {
+        let (from_x, from_y) = *self.pointer.lock().unwrap();
+        let (to_x, to_y) = (self.between(POINTER_X) as f64, self.between(POINTER_Y) as f64);
+        let bow = self.between([0, 120]) as f64 - 60.0;
+        let steps = self.between(POINTER_STEPS);
+        for i in 1..=steps {
+            let t = i as f64 / steps as f64;
+            // Ease in and out, as a hand does
+            let eased = t * t * (3.0 - 2.0 * t);
+            let lift = bow * 4.0 * t * (1.0 - t);
+            let (x, y) = (from_x + (to_x - from_x) * eased, from_y + (to_y - from_y) * eased + lift);
+            if driver.move_mouse(x, y).await.is_err() {
+                return;
+            }
+            driver.settle(Duration::from_millis(self.between([8, 30]))).await;
+        }
+        *self.pointer.lock().unwrap() = (to_x, to_y);
+    }
+
+    async fn scroll_by(&self, driver: &dyn PageDriver, delta: i64) {
+        let (x, y) = *self.pointer.lock().unwrap();
+        if driver.wheel(x, y, delta as f64).await.is_err() {
+            let _ = driver.run_script(&format!("window.scrollBy(0, {})", delta)).await;
+        }
+    }
+
+    // Scroll down about `depth_px` so lazy content renders, the way a person skims
+    pub async fn scroll(&self, driver: &dyn PageDriver, depth_px: u64) {
+        let mut scrolled = 0;
+        while scrolled < depth_px {
+            for _ in 0..self.between(self.profile.mouse_moves) {
+                self.move_pointer(driver).await;
+            }
+            let step = self.between(self.profile.scroll_step_px).min(depth_px - scrolled).max(1);
+            self.scroll_by(driver, step as i64).await;
+            scrolled += step;
+            if self.chance(self.profile.scroll_back_chance) {
+                let back = self.between([step / 5, step / 2]);
+                self.scroll_by(driver, -(back as i64)).await;
+                driver.settle(self.duration(self.profile.dwell_ms)).await;
+                self.scroll_by(driver, back as i64).await;
+            }
+            let dwell = if self.chance(self.profile.read_chance) { self.profile.read_ms } else { self.profile.dwell_ms };
+            driver.settle(self.duration(dwell)).await;
+        }
+    }
+
+    pub async fn scroll_to_top(&self, driver: &dyn PageDriver) {
+        let _ = driver.run_script("window.scrollTo({ top: 0, behavior: 'smooth' })").await;
+        driver.settle(self.duration(self.profile.dwell_ms)).await;
+    }
+}
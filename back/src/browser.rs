@@ -0,0 +1,84 @@
+// Persistent Chrome profiles per Selenium source. Sites that fingerprint
+// returning browsers go easier on one whose cookies, storage and cache carry
+// over between runs, so each site's sources launch with a --user-data-dir
+// kept under [browser] profiles_dir ("ebay" for eBay, eBay BIN and eBay
+// auctions, which the supervisor never runs at once) instead of a fresh
+// temporary one every run. A source listed in [browser.profiles] uses its own
+// directory instead. A profile that got blocked is burned; with
+// reset_on_block it is wiped before the next launch.
+
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::Path;
+use thirtyfour::ChromeCapabilities;
+use thirtyfour::prelude::*;
+
+use crate::config::BrowserConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::storage;
+use crate::supervisor::site_of;
+
+// Written into the profile at each launch, so blocks can be dated against it
+const LAST_LAUNCH_FILE: &str = "m3_last_launch";
+
+fn last_launch(dir: &Path) -> Option<DateTime<Local>> {
+    let text = fs::read_to_string(dir.join(LAST_LAUNCH_FILE)).ok()?;
+    DateTime::parse_from_rfc3339(text.trim()).ok().map(|t| t.with_timezone(&Local))
+}
+
+// Whether the journal has a block on the site of `source` since `since`; its
+// sources share one session in the site's eyes
+pub fn blocked_since(source: &str, since: DateTime<Local>) -> bool {
+    let site = site_of(source);
+    error_journal::load_entries()
+        .iter()
+        .any(|e| e.category == ErrorCategory::Blocked && site_of(&e.source) == site && e.timestamp > since)
+}
+
+fn blocked_since_launch(dir: &Path, source: &str) -> bool {
+    last_launch(dir).is_some_and(|launched| blocked_since(source, launched))
+}
+
+// The source's profile directory, before --profile scoping
+fn profile_dir(config: &BrowserConfig, source: &str) -> Option<String> {
+    if let Some(dir) = config.profiles.get(&source.to_lowercase()) {
+        return Some(dir.clone());
+    }
+    let site: String = site_of(source).chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    (config.persist && !site.is_empty()).then(|| format!("{}/{}", config.profiles_dir.trim_end_matches('/'), site))
+}
+
+// Sources with the same key can share a browser session: they use one profile
+pub fn session_key(config: &BrowserConfig, source: &str) -> String {
+    profile_dir(config, source).unwrap_or_else(|| site_of(source))
+}
+
+// Point Chrome at the source's persistent profile, if it keeps one
+pub fn use_profile(caps: &mut ChromeCapabilities, config: &BrowserConfig, source: &str) {
+    let Some(dir) = profile_dir(config, source) else {
+        return;
+    };
+    let dir = storage::profile_path(&dir);
+    let path = Path::new(&dir);
+
+    if config.reset_on_block && path.exists() && blocked_since_launch(path, source) {
+        match fs::remove_dir_all(path) {
+            Ok(()) => println!("  🧹 {} was blocked last time - starting a fresh Chrome profile", source),
+            Err(e) => eprintln!("  ⚠️  Failed to reset Chrome profile {}: {}", dir, e),
+        }
+    }
+    match last_launch(path) {
+        Some(launched) => println!("  🍪 {} resumes its Chrome session from {}", source, launched.format("%Y-%m-%d %H:%M")),
+        None => println!("  🍪 {} starts a Chrome profile in {}", source, dir),
+    }
+    if let Err(e) = fs::create_dir_all(path) {
+        eprintln!("  ⚠️  Failed to create Chrome profile {}: {} - using a temporary one", dir, e);
+        return;
+    }
+    if let Err(e) = fs::write(path.join(LAST_LAUNCH_FILE), Local::now().to_rfc3339()) {
+        eprintln!("  ⚠️  Failed to write {}/{}: {}", dir, LAST_LAUNCH_FILE, e);
+    }
+    // ChromeDriver may run from another directory
+    let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    caps.add_arg(&format!("--user-data-dir={}", absolute.display())).ok();
+}
@@ -0,0 +1,135 @@
+// Network capture for endpoint discovery. Sites like Swappa and eBay load
+// listings from internal JSON APIs; when enabled, a script installed before
+// any page script wraps fetch/XHR and keeps JSON responses, which are saved
+// under captures/<source>/ after each page so the endpoints can be studied.
+// (thirtyfour can send CDP commands but not receive Network events, hence the
+// in-page hook rather than Network.responseReceived.)
+
+use chrono::Local;
+use serde_json::json;
+use std::fs;
+
+use crate::config::CaptureConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::page_driver::PageDriver;
+
+// __M3_MAX_BYTES__ is replaced with the configured body limit
+const HOOK_SCRIPT: &str = r#"
+(function () {
+    if (window.__m3Captured) return;
+    window.__m3Captured = [];
+    var maxBytes = __M3_MAX_BYTES__;
+
+    function keep(url, status, contentType, body) {
+        if (!contentType || contentType.indexOf('json') === -1) return;
+        if (!body || body.length > maxBytes) return;
+        window.__m3Captured.push({ url: String(url), status: status, body: body });
+    }
+
+    var originalFetch = window.fetch;
+    if (originalFetch) {
+        window.fetch = function () {
+            return originalFetch.apply(this, arguments).then(function (response) {
+                try {
+                    var copy = response.clone();
+                    copy.text().then(function (body) {
+                        keep(copy.url, copy.status, copy.headers.get('content-type'), body);
+                    }).catch(function () {});
+                } catch (e) {}
+                return response;
+            });
+        };
+    }
+
+    var originalOpen = XMLHttpRequest.prototype.open;
+    XMLHttpRequest.prototype.open = function (method, url) {
+        this.__m3Url = url;
+        this.addEventListener('load', function () {
+            try {
+                if (this.responseType === '' || this.responseType === 'text') {
+                    keep(this.responseURL || this.__m3Url, this.status, this.getResponseHeader('content-type'), this.responseText);
+                }
+            } catch (e) {}
+        });
+        return originalOpen.apply(this, arguments);
+    };
+})();
+"#;
+
+const DRAIN_SCRIPT: &str = r#"
+    var captured = window.__m3Captured || [];
+    window.__m3Captured = [];
+    return captured;
+"#;
+
+// Install the in-page hook for every page this driver loads from now on.
+// Also used without [capture] by scrapers that parse the captured JSON.
+pub async fn install_hook(driver: &dyn PageDriver, max_body_kb: usize) -> bool {
+    let script = HOOK_SCRIPT.replace("__M3_MAX_BYTES__", &(max_body_kb * 1024).to_string());
+    match driver.add_init_script(&script).await {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("  Warning: Failed to install network capture: {}", e);
+            error_journal::record(ErrorCategory::Driver, "Network capture", None, &e, None);
+            false
+        }
+    }
+}
+
+// Install the hook when [capture] is enabled
+pub async fn install(driver: &dyn PageDriver, config: &CaptureConfig) {
+    if config.enabled && install_hook(driver, config.max_body_kb).await {
+        println!("  📡 Network capture enabled, saving JSON responses to {}/", config.dir);
+    }
+}
+
+// Take the responses captured since the last drain: [{ url, status, body }]
+pub async fn drain(driver: &dyn PageDriver) -> Vec<serde_json::Value> {
+    match driver.run_script(DRAIN_SCRIPT).await {
+        Ok(result) => result.as_array().cloned().unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+// Drain and save in one step, for scrapers that don't use the payloads themselves
+pub async fn save(driver: &dyn PageDriver, config: &CaptureConfig, source: &str, page_url: &str) {
+    if config.enabled {
+        let responses = drain(driver).await;
+        write(config, source, page_url, &responses);
+    }
+}
+
+// Save drained responses, tagged with the page they came from
+pub fn write(config: &CaptureConfig, source: &str, page_url: &str, responses: &[serde_json::Value]) {
+    if !config.enabled || responses.is_empty() {
+        return;
+    }
+
+    let dir = format!("{}/{}", config.dir, source.to_lowercase().replace(' ', "_"));
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("  Warning: Failed to create {}: {}", dir, e);
+        return;
+    }
+
+    let stamp = Local::now().format("%Y%m%d_%H%M%S");
+    let mut saved = 0;
+    for (i, response) in responses.iter().enumerate() {
+        let body = response.get("body").and_then(|v| v.as_str()).unwrap_or("");
+        // Keep the payload as JSON when it parses, raw text otherwise
+        let payload = serde_json::from_str::<serde_json::Value>(body).unwrap_or_else(|_| json!(body));
+        let record = json!({
+            "captured_at": Local::now().to_rfc3339(),
+            "page_url": page_url,
+            "request_url": response.get("url"),
+            "status": response.get("status"),
+            "payload": payload,
+        });
+        let path = format!("{}/{}_{:03}.json", dir, stamp, i);
+        if let Ok(content) = serde_json::to_string_pretty(&record)
+            && fs::write(&path, content).is_ok()
+        {
+            saved += 1;
+        }
+    }
+    println!("    📡 Saved {} captured JSON responses to {}/", saved, dir);
+}
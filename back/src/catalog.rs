@@ -0,0 +1,210 @@
+// Canonical device catalog (devices.toml, bundled into the binary). A listing
+// title resolves to the catalog device whose id, alias or model number it
+// contains, preferring the longest match so "iPhone 15 Pro Max" is not taken
+// for the 15 Pro. The device id is the canonical model everywhere else:
+// ProductAttributes::model, comp grouping, [thresholds.models] keys.
+// Titles outside the catalog can still resolve through [categories] (GPUs,
+// consoles, ...), whose longest matching keyword or alias gives the model.
+// Laptops, GPUs and consoles ship as packs (category_packs.toml) that
+// [packs] enabled turns into categories.
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::attributes::{Brand, storage_label};
+use crate::config::CategoryConfig;
+use crate::locale;
+
+const CATALOG: &str = include_str!("../devices.toml");
+const PACKS: &str = include_str!("../category_packs.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Device {
+    // Lowercase canonical model, e.g. "iphone 15 pro max"
+    pub id: String,
+    pub brand: Brand,
+    // Display name, e.g. "iPhone 15 Pro Max"
+    pub model: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // Model numbers without region suffix, e.g. "A2849", "SM-S928"
+    #[serde(default)]
+    pub variants: Vec<String>,
+    pub storage_gb: Vec<u32>,
+    pub released: NaiveDate,
+    // Normalized id and aliases, then model numbers (matched as prefixes)
+    #[serde(skip)]
+    phrases: Vec<(String, bool)>,
+}
+
+#[derive(Deserialize)]
+struct CatalogFile {
+    devices: Vec<Device>,
+}
+
+static DEVICES: OnceLock<Vec<Device>> = OnceLock::new();
+
+// The bundled catalog; a malformed devices.toml is a build mistake, so it panics
+pub fn devices() -> &'static [Device] {
+    DEVICES.get_or_init(|| {
+        let mut file: CatalogFile = toml::from_str(CATALOG).unwrap_or_else(|e| panic!("devices.toml: {}", e));
+        for device in &mut file.devices {
+            let names = std::iter::once(&device.id).chain(&device.aliases).map(|name| (normalize(name), false));
+            let variants = device.variants.iter().map(|variant| (normalize(variant), true));
+            device.phrases = names.chain(variants).collect();
+        }
+        file.devices
+    })
+}
+
+// Part of a model name: letters, digits and the "+" of "S24+"
+fn is_word_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '+')
+}
+
+// Lowercased, locale-normalized, punctuation to single spaces:
+// "Apple iPhone-15 Pro (A2849)" -> "apple iphone 15 pro a2849"
+fn normalize(text: &str) -> String {
+    locale::to_english(text)
+        .split(|c: char| !is_word_char(Some(c)))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Whether `phrase` occurs in `text` on word boundaries; `prefix` lets the
+// phrase run on into a longer word ("sm s928" in "sm s928u1")
+fn contains_phrase(text: &str, phrase: &str, prefix: bool) -> bool {
+    text.match_indices(phrase).any(|(start, _)| {
+        let end = start + phrase.len();
+        !is_word_char(text[..start].chars().last()) && (prefix || !is_word_char(text[end..].chars().next()))
+    })
+}
+
+impl Device {
+    // Length of the longest id, alias or model number found in normalized `text`
+    fn match_len(&self, text: &str) -> Option<usize> {
+        self.phrases.iter().filter(|(phrase, prefix)| contains_phrase(text, phrase, *prefix)).map(|(phrase, _)| phrase.len()).max()
+    }
+
+    pub fn storage_label(&self) -> String {
+        self.storage_gb.iter().map(|gb| storage_label(*gb)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+// The catalog device a listing title names, if any
+pub fn identify(name: &str) -> Option<&'static Device> {
+    let text = normalize(name);
+    devices()
+        .iter()
+        .filter_map(|device| device.match_len(&text).map(|len| (len, device)))
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, device)| device)
+}
+
+// The catalog device with canonical model `id`
+pub fn device(id: &str) -> Option<&'static Device> {
+    devices().iter().find(|device| device.id == id)
+}
+
+static PACK_FILE: OnceLock<HashMap<String, CategoryConfig>> = OnceLock::new();
+
+// A built-in category pack by name
+pub fn pack(name: &str) -> Result<CategoryConfig, String> {
+    let packs = PACK_FILE.get_or_init(|| toml::from_str(PACKS).unwrap_or_else(|e| panic!("category_packs.toml: {}", e)));
+    packs.get(&name.to_lowercase()).cloned().ok_or_else(|| {
+        let mut known: Vec<&str> = packs.keys().map(String::as_str).collect();
+        known.sort();
+        format!("Unknown category pack '{}'. Built-in packs: {}", name, known.join(", "))
+    })
+}
+
+// A category from [categories]: its name and (keyword as written lowercase,
+// keyword or alias normalized) pairs
+struct Category {
+    name: String,
+    keywords: Vec<(String, String)>,
+}
+
+static CATEGORIES: OnceLock<Vec<Category>> = OnceLock::new();
+
+// Called by Config::load, since titles are resolved without a config at hand;
+// the first config loaded wins
+pub fn set_categories(categories: &HashMap<String, CategoryConfig>) {
+    let mut names: Vec<&String> = categories.keys().collect();
+    names.sort();
+    let categories = names
+        .into_iter()
+        .map(|name| Category {
+            name: name.clone(),
+            keywords: {
+                let category = &categories[name];
+                let keywords = category.keywords.iter().map(|k| (k.to_lowercase(), normalize(k)));
+                let aliases = category.aliases.iter().map(|(alias, k)| (k.to_lowercase(), normalize(alias)));
+                keywords.chain(aliases).collect()
+            },
+        })
+        .collect();
+    let _ = CATEGORIES.set(categories);
+}
+
+// (category, model keyword) for a title the catalog doesn't cover
+pub fn category_model(name: &str) -> Option<(&'static str, &'static str)> {
+    let text = normalize(name);
+    CATEGORIES
+        .get()?
+        .iter()
+        .flat_map(|category| category.keywords.iter().map(move |keyword| (category, keyword)))
+        .filter(|(_, (_, phrase))| !phrase.is_empty() && contains_phrase(&text, phrase, false))
+        .max_by_key(|(_, (_, phrase))| phrase.len())
+        .map(|(category, (keyword, _))| (category.name.as_str(), keyword.as_str()))
+}
+
+// Category a model keyword belongs to; None for catalog devices
+pub fn category_of(model: &str) -> Option<&'static str> {
+    CATEGORIES
+        .get()?
+        .iter()
+        .find(|category| category.keywords.iter().any(|(keyword, _)| keyword == model))
+        .map(|category| category.name.as_str())
+}
+
+// `back catalog`: every device, or what one title resolves to
+pub fn print_catalog(title: Option<&str>) {
+    let Some(title) = title else {
+        println!("📚 {} devices in the catalog\n", devices().len());
+        for device in devices() {
+            println!("   {:<20} {:<28} {}  {}", device.id, device.model, device.released, device.storage_label());
+        }
+        for category in CATEGORIES.get().into_iter().flatten() {
+            let mut keywords: Vec<&str> = category.keywords.iter().map(|(keyword, _)| keyword.as_str()).collect();
+            keywords.sort();
+            keywords.dedup();
+            println!("\n🗂️  Category {}: {}", category.name, keywords.join(", "));
+        }
+        return;
+    };
+    if identify(title).is_none()
+        && let Some((category, keyword)) = category_model(title)
+    {
+        println!("📦 {}", title);
+        println!("   Category: {}", category);
+        println!("   Model:    {}", keyword);
+        return;
+    }
+    match identify(title) {
+        Some(device) => {
+            println!("📱 {}", title);
+            println!("   Device:   {} ({})", device.id, device.model);
+            println!("   Brand:    {}", device.brand.label());
+            println!("   Released: {}", device.released);
+            println!("   Storage:  {}", device.storage_label());
+            if !device.variants.is_empty() {
+                println!("   Models:   {}", device.variants.join(", "));
+            }
+        }
+        None => println!("❓ No catalog device found in \"{}\"", title),
+    }
+}
@@ -0,0 +1,123 @@
+// Mid-run checkpoints. As each source finishes, the output of every source
+// scraped so far this run is saved to checkpoint.json, and their listings are
+// written into scraper_data.json over the last full export (marked partial),
+// so a run that dies during eBay still leaves the frontend fresh Swappa and
+// Newegg listings. A restart within [export] resume_minutes picks the
+// interrupted run back up: the sources in its checkpoint aren't scraped
+// again, and the analysis runs over their saved output plus the rest.
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+use crate::config::ExportConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::supervisor::{Role, SourceOutput, SourceReport};
+use crate::{ScraperData, storage};
+
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Checkpoint {
+    run_id: u32,
+    updated: String,
+    // Source name -> what it scraped this run
+    sources: HashMap<String, SourceOutput>,
+}
+
+fn load() -> Option<Checkpoint> {
+    let content = fs::read_to_string(storage::profile_path(CHECKPOINT_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// Saved outputs by source name when run `run_id` is the interrupted run of
+// the checkpoint and it was written recently enough to resume
+pub fn resumable(run_id: u32, config: &ExportConfig) -> HashMap<String, SourceOutput> {
+    let Some(checkpoint) = load().filter(|c| config.checkpoints && c.run_id == run_id) else {
+        return HashMap::new();
+    };
+    let fresh = NaiveDateTime::parse_from_str(&checkpoint.updated, "%Y-%m-%d %H:%M:%S")
+        .is_ok_and(|updated| Local::now().naive_local() - updated <= TimeDelta::minutes(config.resume_minutes));
+    if !fresh {
+        return HashMap::new();
+    }
+    println!("⏩ Resuming run #{} from its checkpoint ({} sources done)", run_id, checkpoint.sources.len());
+    checkpoint.sources
+}
+
+// Reports scraped this run, as opposed to reused or failed ones
+fn scraped(run_id: u32, reports: &[SourceReport]) -> impl Iterator<Item = &SourceReport> {
+    reports.iter().filter(move |r| r.scraped_in == Some(run_id) && r.failure.is_none())
+}
+
+// Save the run so far and write its listings into the export
+pub fn record(run_id: u32, reports: &[SourceReport], config: &ExportConfig) {
+    if !config.checkpoints {
+        return;
+    }
+    let checkpoint = Checkpoint {
+        run_id,
+        updated: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        sources: scraped(run_id, reports).map(|r| (r.name.clone(), r.output.clone())).collect(),
+    };
+    if let Err(e) = storage::write_json(&storage::profile_path(CHECKPOINT_FILE), &checkpoint) {
+        eprintln!("  ❌ {}", e);
+        error_journal::record(ErrorCategory::Storage, "Checkpoint", None, &e, None);
+        return;
+    }
+    save_partial_export(run_id, &checkpoint.updated, reports);
+}
+
+// The last full export with the listings scraped so far this run swapped in;
+// analysis sections stay from the last full run until this one finishes
+fn save_partial_export(run_id: u32, updated: &str, reports: &[SourceReport]) {
+    let mut data = crate::load_frontend_data().unwrap_or_default();
+    data.last_updated = updated.to_string();
+    data.run_count = run_id;
+    data.partial = true;
+
+    let mut storefronts_done = Vec::new();
+    let mut storefront_products = Vec::new();
+    for report in scraped(run_id, reports) {
+        let output = &report.output;
+        match report.role {
+            Role::Newegg => data.newegg_products = output.products.clone(),
+            Role::Swappa => data.swappa_products = output.products.clone(),
+            Role::Ebay => data.ebay_products = output.products.clone(),
+            Role::Mercari => {
+                data.mercari_products = output.products.clone();
+                data.mercari_sold_products = output.sold.clone();
+            }
+            Role::Storefront | Role::Decluttr => {
+                // Listings may carry a regional name ("Reebelo AU") rather than the source's
+                storefronts_done.push(report.name.as_str());
+                storefronts_done.extend(output.products.iter().map(|p| p.source.as_str()));
+                storefront_products.extend(output.products.iter().cloned());
+                if report.role == Role::Decluttr {
+                    data.decluttr_prices = output.decluttr_prices.clone();
+                }
+            }
+            Role::Auctions => {}
+        }
+    }
+    // Storefronts not scraped yet keep their listings from the last export
+    if !storefronts_done.is_empty() {
+        data.storefront_products.retain(|p| !storefronts_done.contains(&p.source.as_str()));
+        data.storefront_products.extend(storefront_products);
+    }
+    save(&data);
+}
+
+fn save(data: &ScraperData) {
+    let path = storage::profile_path(crate::FRONTEND_DATA_FILE);
+    if let Err(e) = storage::write_json(&path, data) {
+        eprintln!("  ❌ {}", e);
+        error_journal::record(ErrorCategory::Storage, "Export", Some(&path), &e, None);
+    }
+}
+
+// The run finished and its full export is written
+pub fn clear() {
+    let _ = fs::remove_file(storage::profile_path(CHECKPOINT_FILE));
+}
@@ -0,0 +1,323 @@
+// The ChromeDriver behind the Selenium sources. Instead of expecting one on
+// port 9515, the first browser launch of a process starts its own: the
+// binary from [chromedriver] path, else a chromedriver on PATH whose major
+// version matches the installed Chrome, else one downloaded from Chrome for
+// Testing for that Chrome build, checked against the checksum its storage
+// bucket sends and kept under [chromedriver] dir. It runs on
+// a free port, counts as started once its /status says ready, is restarted if
+// it dies, and is killed when the process exits (on Ctrl+C after the browser
+// sessions are quit, see shutdown.rs).
+// --chromedriver-url or [chromedriver] url connects to an external one
+// instead and starts nothing.
+
+use base64::Engine;
+use serde_json::Value;
+use std::env::consts::{ARCH, OS};
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::{Duration, Instant};
+use thirtyfour::ChromeCapabilities;
+use thirtyfour::prelude::*;
+use tokio::time::sleep;
+
+use crate::config::ChromeDriverConfig;
+use crate::shutdown;
+
+const BINARY: &str = if cfg!(windows) { "chromedriver.exe" } else { "chromedriver" };
+const DOWNLOADS_URL: &str = "https://googlechromelabs.github.io/chrome-for-testing/latest-patch-versions-per-build-with-downloads.json";
+// Binaries `--version` is asked of to find the installed Chrome
+const CHROME_BINARIES: [&str; 5] = [
+    "google-chrome",
+    "google-chrome-stable",
+    "chromium",
+    "chromium-browser",
+    "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+];
+
+static CONFIG: OnceLock<ChromeDriverConfig> = OnceLock::new();
+static EXTERNAL: OnceLock<String> = OnceLock::new();
+// URL of the ChromeDriver this process started; held while one is starting
+static ENDPOINT: tokio::sync::Mutex<Option<String>> = tokio::sync::Mutex::const_new(None);
+static CHILD: Mutex<Option<Child>> = Mutex::new(None);
+static TEARDOWN: Once = Once::new();
+
+// Called by Config::load; the first config loaded wins
+pub fn configure(config: &ChromeDriverConfig) {
+    let _ = CONFIG.set(config.clone());
+}
+
+// --chromedriver-url, over [chromedriver] url
+pub fn set_external(url: &str) {
+    let _ = EXTERNAL.set(url.to_string());
+}
+
+fn config() -> ChromeDriverConfig {
+    CONFIG.get().cloned().unwrap_or_default()
+}
+
+// Start a browser session with `caps`, starting ChromeDriver first if needed
+pub async fn connect(caps: ChromeCapabilities) -> Result<WebDriver, String> {
+    let url = endpoint().await?;
+    WebDriver::new(&url, caps).await.map_err(|e| format!("{} ({})", e, url))
+}
+
+async fn endpoint() -> Result<String, String> {
+    let config = config();
+    if let Some(url) = EXTERNAL.get().or(config.url.as_ref()) {
+        return Ok(url.clone());
+    }
+    let mut endpoint = ENDPOINT.lock().await;
+    if let Some(url) = endpoint.as_ref()
+        && running()
+    {
+        return Ok(url.clone());
+    }
+    if endpoint.is_some() {
+        eprintln!("  ⚠️  ChromeDriver exited - starting it again");
+    }
+    let url = start(&config).await?;
+    *endpoint = Some(url.clone());
+    Ok(url)
+}
+
+fn running() -> bool {
+    let mut child = CHILD.lock().unwrap_or_else(|e| e.into_inner());
+    child.as_mut().is_some_and(|child| matches!(child.try_wait(), Ok(None)))
+}
+
+// Kill the ChromeDriver this process started, if any
+pub fn stop() {
+    let mut child = CHILD.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(mut child) = child.take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+extern "C" fn stop_at_exit() {
+    stop();
+}
+
+// Ctrl+C would otherwise end the process without running exit handlers
+fn stop_on_exit() {
+    TEARDOWN.call_once(|| {
+        unsafe {
+            libc::atexit(stop_at_exit);
+        }
+        shutdown::install();
+    });
+}
+
+async fn start(config: &ChromeDriverConfig) -> Result<String, String> {
+    let binary = locate(config).await?;
+    let port = TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| format!("No free port for ChromeDriver: {}", e))?;
+    let child = Command::new(&binary)
+        .arg(format!("--port={}", port))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", binary.display(), e))?;
+    stop();
+    *CHILD.lock().unwrap_or_else(|e| e.into_inner()) = Some(child);
+    stop_on_exit();
+
+    let url = format!("http://127.0.0.1:{}", port);
+    if let Err(e) = wait_until_ready(&url, Duration::from_secs(config.startup_timeout_secs)).await {
+        stop();
+        return Err(e);
+    }
+    println!("  🚗 Started ChromeDriver {} on port {}", binary.display(), port);
+    Ok(url)
+}
+
+async fn wait_until_ready(url: &str, timeout: Duration) -> Result<(), String> {
+    let client = reqwest::Client::builder().no_proxy().timeout(Duration::from_secs(2)).build().map_err(|e| e.to_string())?;
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if !running() {
+            return Err("ChromeDriver exited while starting".to_string());
+        }
+        if let Ok(response) = client.get(format!("{}/status", url)).send().await
+            && let Ok(body) = response.text().await
+            && serde_json::from_str::<Value>(&body).is_ok_and(|status| status["value"]["ready"] == true)
+        {
+            return Ok(());
+        }
+        sleep(Duration::from_millis(250)).await;
+    }
+    Err(format!("ChromeDriver wasn't ready after {}s", timeout.as_secs()))
+}
+
+// "Google Chrome 120.0.6099.109" -> "120.0.6099.109"
+pub fn version_in(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .find(|word| word.contains('.') && word.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        .map(str::to_string)
+}
+
+async fn version_of(binary: &Path) -> Option<String> {
+    let output = tokio::process::Command::new(binary).arg("--version").stderr(Stdio::null()).output().await.ok()?;
+    version_in(&String::from_utf8_lossy(&output.stdout))
+}
+
+async fn chrome_version() -> Option<String> {
+    for binary in CHROME_BINARIES {
+        if let Some(version) = version_of(Path::new(binary)).await {
+            return Some(version);
+        }
+    }
+    None
+}
+
+pub fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or_default()
+}
+
+fn on_path() -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?).map(|dir| dir.join(BINARY)).find(|path| path.is_file())
+}
+
+// Chrome for Testing's name for this machine
+fn platform() -> Option<&'static str> {
+    match (OS, ARCH) {
+        ("linux", "x86_64") => Some("linux64"),
+        ("macos", "aarch64") => Some("mac-arm64"),
+        ("macos", "x86_64") => Some("mac-x64"),
+        ("windows", "x86_64") => Some("win64"),
+        ("windows", "x86") => Some("win32"),
+        _ => None,
+    }
+}
+
+async fn locate(config: &ChromeDriverConfig) -> Result<PathBuf, String> {
+    if let Some(path) = &config.path {
+        let path = PathBuf::from(path);
+        return if path.is_file() { Ok(path) } else { Err(format!("[chromedriver] path {} doesn't exist", path.display())) };
+    }
+    let chrome = chrome_version().await;
+    let found = on_path();
+    match (&found, &chrome) {
+        (Some(found), Some(chrome)) => match version_of(found).await {
+            Some(driver) if major(&driver) != major(chrome) => {
+                eprintln!("  ⚠️  {} is ChromeDriver {}, Chrome is {}", found.display(), driver, chrome);
+            }
+            _ => return Ok(found.clone()),
+        },
+        (Some(found), None) => return Ok(found.clone()),
+        _ => {}
+    }
+    // Only a mismatched one on PATH, or none
+    let Some(chrome) = chrome else {
+        return Err("Chrome isn't installed (or not where it's looked for) and there's no chromedriver on PATH".to_string());
+    };
+    let build = chrome.rsplit_once('.').map_or(chrome.as_str(), |(build, _)| build);
+    let platform = platform().ok_or_else(|| format!("No ChromeDriver downloads for {} {}", OS, ARCH))?;
+    let target = Path::new(&config.dir).join(format!("chromedriver-{}", build));
+    let binary = target.join(format!("chromedriver-{}", platform)).join(BINARY);
+    if binary.is_file() {
+        return Ok(binary);
+    }
+    let downloaded = if config.download {
+        download(build, platform, &target, &binary).await
+    } else {
+        Err(format!("No chromedriver for Chrome {}; install one, set [chromedriver] path or enable download", chrome))
+    };
+    match (downloaded, found) {
+        (Ok(binary), _) => Ok(binary),
+        (Err(e), Some(found)) => {
+            eprintln!("  ⚠️  {} - trying {} anyway", e, found.display());
+            Ok(found)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+async fn download(build: &str, platform: &str, target: &Path, binary: &Path) -> Result<PathBuf, String> {
+    println!("  ⬇️  Downloading ChromeDriver for Chrome {} ({})...", build, platform);
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(120)).build().map_err(|e| e.to_string())?;
+    let index = client
+        .get(DOWNLOADS_URL)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| format!("Failed to fetch the ChromeDriver downloads: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read the ChromeDriver downloads: {}", e))?;
+    let index: Value = serde_json::from_str(&index).map_err(|e| format!("Unexpected ChromeDriver downloads list: {}", e))?;
+    let url = index["builds"][build]["downloads"]["chromedriver"]
+        .as_array()
+        .and_then(|downloads| downloads.iter().find(|d| d["platform"] == platform))
+        .and_then(|download| download["url"].as_str())
+        .ok_or_else(|| format!("No ChromeDriver download for Chrome {} on {} (they start at Chrome 115)", build, platform))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    let expected = goog_crc32c(response.headers()).ok_or_else(|| format!("{} came without a checksum to verify it by", url))?;
+    let archive = response.bytes().await.map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if crc32c(&archive) != expected {
+        return Err(format!("The ChromeDriver download from {} doesn't match its checksum", url));
+    }
+
+    fs::create_dir_all(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let zip = target.join("chromedriver.zip");
+    fs::write(&zip, &archive).map_err(|e| format!("Failed to write {}: {}", zip.display(), e))?;
+    let extracted = extract(&zip, target).await;
+    let _ = fs::remove_file(&zip);
+    extracted?;
+    if !binary.is_file() {
+        return Err(format!("The ChromeDriver download had no {}", binary.display()));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(binary, fs::Permissions::from_mode(0o755)).map_err(|e| format!("Failed to make {} executable: {}", binary.display(), e))?;
+    }
+    println!("  ✓ ChromeDriver saved to {}", binary.display());
+    Ok(binary.to_path_buf())
+}
+
+// CRC-32C (Castagnoli) of `bytes`, the checksum Cloud Storage reports
+pub fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0x82f6_3b78 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+// The crc32c in Cloud Storage's x-goog-hash headers ("crc32c=<base64>,md5=...")
+fn goog_crc32c(headers: &reqwest::header::HeaderMap) -> Option<u32> {
+    headers
+        .get_all("x-goog-hash")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|hash| hash.trim().strip_prefix("crc32c="))
+        .and_then(|encoded| base64::engine::general_purpose::STANDARD.decode(encoded).ok())
+        .and_then(|bytes| Some(u32::from_be_bytes(bytes.try_into().ok()?)))
+}
+
+// unzip where it's installed, else tar (bsdtar on macOS and Windows reads zips)
+async fn extract(zip: &Path, target: &Path) -> Result<(), String> {
+    let unzip = tokio::process::Command::new("unzip").arg("-o").arg("-q").arg(zip).arg("-d").arg(target).status().await;
+    if unzip.as_ref().is_ok_and(|status| status.success()) {
+        return Ok(());
+    }
+    match tokio::process::Command::new("tar").arg("-xf").arg(zip).arg("-C").arg(target).status().await {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(format!("Failed to extract {} (needs unzip or tar)", zip.display())),
+    }
+}
@@ -0,0 +1,316 @@
+// Command-line interface. With no subcommand the scraper runs its loop.
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::error_journal::ErrorCategory;
+use crate::replay::ReplayMode;
+
+#[derive(Debug, Parser)]
+#[command(about = "Phone arbitrage scraper")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Override [thresholds] min_margin_percent for this run
+    #[arg(long)]
+    pub min_margin: Option<f64>,
+    /// Override [thresholds] min_profit for this run
+    #[arg(long)]
+    pub min_profit: Option<f64>,
+    /// Override [thresholds] min_similarity for this run
+    #[arg(long)]
+    pub min_similarity: Option<f64>,
+    /// Export each opportunity's match similarity and list every sold comp behind it ([export] verbose)
+    #[arg(long)]
+    pub verbose_export: bool,
+    /// Keep seen-state, run history and exports under profiles/<name>/
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+    /// Serve plain HTTP fetches from pages saved under replay/ (saving misses), or only saved pages with offline
+    #[arg(long, global = true, value_enum)]
+    pub replay: Option<ReplayMode>,
+    /// Connect to a ChromeDriver already running at this URL instead of starting one ([chromedriver] url)
+    #[arg(long, global = true)]
+    pub chromedriver_url: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Show entries from the error journal
+    Errors(ErrorsArgs),
+    /// Score two listing titles against each other and show each token's part in the similarity
+    Match(MatchArgs),
+    /// List the device catalog and [categories], or show which model a listing title resolves to
+    Catalog(CatalogArgs),
+    /// Mark an opportunity from the latest run as a bad match so its comps are never matched to it again
+    BadMatch(BadMatchArgs),
+    /// Add a note to a product or opportunity, by listing URL
+    Note(NoteArgs),
+    /// Add or remove tags on a product or opportunity, by listing URL
+    Tag(TagArgs),
+    /// List notes and tags
+    Notes(NotesArgs),
+    /// Record phones bought and sold; held ones are valued against fresh comps every run
+    Inventory(InventoryArgs),
+    /// Create a draft eBay listing for an inventory item through the Sell API
+    Draft(DraftArgs),
+    /// Track a specific listing every run and alert on price drops or sale; lists tracked listings without a URL
+    Track(TrackArgs),
+    /// Reports over the stored run history
+    Analysis(AnalysisArgs),
+    /// Save or list known-good extraction baselines that every run is checked against
+    Baseline(BaselineArgs),
+    /// Run a single source once and print the products it extracts
+    Scrape(ScrapeArgs),
+    /// Extract storefront pages with old and new selector profiles and diff the results
+    Canary(CanaryArgs),
+    /// Run the maintenance pass (run history compaction, journal and capture cleanup) now
+    Maintenance,
+    /// Write a copy of the latest export (or a stored run), optionally redacted for sharing
+    Export(ExportArgs),
+    /// Print the top opportunities to a PDF deal sheet with photos, comp summaries and QR codes to the buy listings
+    DealSheet(DealSheetArgs),
+    /// Re-check every buy listing in the latest export, mark sold or removed ones and print the ones still up
+    Verify,
+}
+
+#[derive(Debug, Args)]
+pub struct BaselineArgs {
+    #[command(subcommand)]
+    pub command: BaselineCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BaselineCommand {
+    /// Scrape a source now and save the result as its known-good baseline
+    Save(BaselineSaveArgs),
+    /// Show saved baselines
+    List,
+}
+
+#[derive(Debug, Args)]
+pub struct BaselineSaveArgs {
+    /// Same source names as `scrape`
+    pub source: String,
+    /// Baseline a single device's pages, e.g. iphone-14
+    #[arg(long)]
+    pub device: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ScrapeArgs {
+    /// swappa, ebay, mercari, newegg, or a storefront source name, e.g. backmarket
+    pub source: String,
+    /// Only this device's pages (or products), e.g. iphone-14
+    #[arg(long)]
+    pub device: Option<String>,
+    /// Print the products as JSON on stdout; progress output goes to stderr
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Drop seller names and notes and replace URLs with hashes
+    #[arg(long)]
+    pub redact: bool,
+    /// Export this stored run instead of the latest scraper_data.json
+    #[arg(long)]
+    pub run: Option<u32>,
+    /// Flat per-opportunity feature table (CSV) across all stored runs, plus a schema file, for training pricing models
+    #[arg(long, conflicts_with = "run")]
+    pub features: bool,
+    /// With --features: listings gone within this many hours count as vanished
+    #[arg(long, default_value_t = 24)]
+    pub window_hours: i64,
+    /// Defaults to scraper_data.export.json, or features.csv with --features
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DealSheetArgs {
+    /// Use this stored run instead of the latest scraper_data.json
+    #[arg(long)]
+    pub run: Option<u32>,
+    /// How many opportunities to include, most profitable first
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+    /// Write the HTML sheet without printing it to PDF (no ChromeDriver needed)
+    #[arg(long)]
+    pub html: bool,
+    /// Defaults to deal_sheet.pdf, or deal_sheet.html with --html
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct AnalysisArgs {
+    #[command(subcommand)]
+    pub command: AnalysisCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AnalysisCommand {
+    Report(ReportArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// How many surfaced opportunities vanished (presumably sold) vs lingered, by margin and comp count
+    #[arg(long)]
+    pub tuning: bool,
+    /// Listings gone within this many hours count as vanished
+    #[arg(long, default_value_t = 24)]
+    pub window_hours: i64,
+}
+
+#[derive(Debug, Args)]
+pub struct NoteArgs {
+    pub url: String,
+    /// e.g. "offered $520, waiting on seller"
+    pub text: String,
+}
+
+#[derive(Debug, Args)]
+pub struct TagArgs {
+    pub url: String,
+    /// e.g. negotiating, "seller slow"
+    #[arg(required = true)]
+    pub tags: Vec<String>,
+    /// Remove the tags instead of adding them
+    #[arg(long)]
+    pub remove: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct InventoryArgs {
+    #[command(subcommand)]
+    pub command: InventoryCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum InventoryCommand {
+    /// Record a phone you bought
+    Add(InventoryAddArgs),
+    /// Mark an inventory item as sold
+    Sold(InventorySoldArgs),
+    /// Show inventory items
+    List,
+}
+
+#[derive(Debug, Args)]
+pub struct InventoryAddArgs {
+    /// Listing it was bought from; the name is taken from the latest run if it's there
+    pub url: Option<String>,
+    /// What you paid, shipping and tax included
+    #[arg(long)]
+    pub cost: f64,
+    /// Title to match comps on, e.g. "iPhone 14 Pro 256GB Unlocked"
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct InventorySoldArgs {
+    /// Item number from `inventory list`
+    pub id: u32,
+    /// What it sold for
+    #[arg(long)]
+    pub price: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct DraftArgs {
+    /// Item number from `inventory list`
+    pub id: u32,
+    /// List price; defaults to the item's market value from the latest run
+    #[arg(long)]
+    pub price: Option<f64>,
+    /// Photo URL to add ahead of the stored listing photos; repeatable
+    #[arg(long = "image")]
+    pub images: Vec<String>,
+    /// Print the request bodies instead of calling eBay
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct MatchArgs {
+    /// Buy listing title
+    pub first: String,
+    /// Sold listing title
+    pub second: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CanaryArgs {
+    /// Selector profile to check, e.g. "gazelle"; default is every profile that differs
+    pub profile: Option<String>,
+    /// Selectors file with the old profiles; default is the built-in selectors
+    #[arg(long)]
+    pub old: Option<String>,
+    /// Selectors file with the new profiles
+    #[arg(long, default_value = "selectors.toml")]
+    pub new: String,
+}
+
+#[derive(Debug, Args)]
+pub struct CatalogArgs {
+    /// Listing title to resolve, e.g. "Apple iPhone 15 Pro Max A2849 256GB"
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct TrackArgs {
+    /// eBay or Swappa listing URL
+    pub url: Option<String>,
+    /// Stop tracking the listing
+    #[arg(long, requires = "url")]
+    pub remove: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct NotesArgs {
+    /// Only listings with this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct BadMatchArgs {
+    /// Buy URL of the opportunity
+    pub buy_url: String,
+    /// Why the match is wrong, e.g. "comps are for the Pro Max"
+    #[arg(long)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ErrorsArgs {
+    /// Only errors newer than this, e.g. 30m, 24h, 7d
+    #[arg(long, value_parser = parse_duration)]
+    pub since: Option<chrono::Duration>,
+    /// Only errors from sources containing this name, e.g. ebay
+    #[arg(long)]
+    pub source: Option<String>,
+    #[arg(long, value_enum)]
+    pub category: Option<ErrorCategory>,
+    /// Show at most this many of the newest matches
+    #[arg(long, default_value_t = 50)]
+    pub limit: usize,
+}
+
+// "90s", "30m", "24h", "7d"
+pub fn parse_duration(text: &str) -> Result<chrono::Duration, String> {
+    let text = text.trim();
+    let split = text.len().checked_sub(1).ok_or("empty duration")?;
+    let (number, unit) = text.split_at(split);
+    let value: i64 = number.parse().map_err(|_| format!("invalid duration '{}'", text))?;
+    match unit {
+        "s" => Ok(chrono::Duration::seconds(value)),
+        "m" => Ok(chrono::Duration::minutes(value)),
+        "h" => Ok(chrono::Duration::hours(value)),
+        "d" => Ok(chrono::Duration::days(value)),
+        _ => Err(format!("invalid duration '{}', expected a number followed by s, m, h or d", text)),
+    }
+}
@@ -0,0 +1,286 @@
+// Sold-comp providers. The sold prices behind every opportunity come from a
+// list of providers, each answering "what sold for this device within the
+// window": this run's eBay sold search and Mercari sold listings, eBay's
+// Marketplace Insights API, and the sold listings stored with earlier runs.
+// [comps.providers] order lists tiers asked in turn for each device on the
+// buy side; a tier's providers are merged, and the next tier is only asked
+// while the device has fewer than min_comps comps. The analysis sees one
+// pooled comp list and doesn't care which provider filled it.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeDelta};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::analysis::timing::parse_sold_at;
+use crate::attributes::ProductAttributes;
+use crate::config::{CompProviderKind, CompProvidersConfig};
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::{Currency, Money};
+use crate::{Product, retry, storage};
+
+const EBAY_API_SOURCE: &str = "eBay API";
+// Marketplace Insights returns at most 200 sales per call
+const EBAY_API_LIMIT: usize = 200;
+
+pub struct SoldComp {
+    pub listing: Product,
+    pub sold_on: Option<NaiveDate>,
+}
+
+#[async_trait]
+pub trait CompProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+    // Comps for a catalog device (ProductAttributes model) sold within `window`
+    async fn query(&self, device: &str, window: TimeDelta) -> Vec<SoldComp>;
+}
+
+fn within(sold_on: Option<NaiveDate>, window: TimeDelta) -> bool {
+    // Undated sales come from a search that only shows recent ones
+    sold_on.is_none_or(|date| date >= (Local::now() - window).date_naive())
+}
+
+// Sold listings held in memory, tagged with their device
+struct Listings(Vec<(Option<String>, SoldComp)>);
+
+impl Listings {
+    fn new(comps: impl IntoIterator<Item = SoldComp>) -> Listings {
+        Listings(comps.into_iter().map(|comp| (comp.listing.attributes.model.clone(), comp)).collect())
+    }
+
+    fn query(&self, device: &str, window: TimeDelta) -> Vec<SoldComp> {
+        self.0
+            .iter()
+            .filter(|(model, comp)| model.as_deref() == Some(device) && within(comp.sold_on, window))
+            .map(|(_, comp)| SoldComp { listing: comp.listing.clone(), sold_on: comp.sold_on })
+            .collect()
+    }
+}
+
+// Runs stored before listings carried typed attributes get them parsed here
+fn dated(listing: &Product) -> SoldComp {
+    let sold_on = listing.sold_date.as_deref().and_then(parse_sold_at).map(|(date, _)| date);
+    let mut listing = listing.clone();
+    listing.fill_attributes();
+    SoldComp { listing, sold_on }
+}
+
+// What a scraping source sold this run: eBay sold search or Mercari
+pub struct ScrapedComps {
+    name: &'static str,
+    listings: Listings,
+}
+
+impl ScrapedComps {
+    pub fn new(name: &'static str, sold: &[Product]) -> ScrapedComps {
+        ScrapedComps { name, listings: Listings::new(sold.iter().map(dated)) }
+    }
+}
+
+#[async_trait]
+impl CompProvider for ScrapedComps {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn query(&self, device: &str, window: TimeDelta) -> Vec<SoldComp> {
+        self.listings.query(device, window)
+    }
+}
+
+// Sold listings of the stored runs; undated ones count as sold on their run's day
+pub struct StoredComps {
+    listings: Listings,
+}
+
+impl StoredComps {
+    pub fn load() -> StoredComps {
+        let mut seen = HashSet::new();
+        let mut comps = Vec::new();
+        for entry in storage::load_run_index().iter().rev() {
+            let Some(run) = storage::load_run(entry.run_id) else {
+                continue;
+            };
+            let run_day = NaiveDateTime::parse_from_str(&run.timestamp, "%Y-%m-%d %H:%M:%S").ok().map(|at| at.date());
+            for listing in run.ebay_sold_products.iter().chain(&run.mercari_sold_products) {
+                // Newest run first, so a listing keeps its latest price
+                if seen.insert(listing.url.clone()) {
+                    let comp = dated(listing);
+                    comps.push(SoldComp { sold_on: comp.sold_on.or(run_day), ..comp });
+                }
+            }
+        }
+        StoredComps { listings: Listings::new(comps) }
+    }
+}
+
+#[async_trait]
+impl CompProvider for StoredComps {
+    fn name(&self) -> &'static str {
+        "cache"
+    }
+
+    async fn query(&self, device: &str, window: TimeDelta) -> Vec<SoldComp> {
+        self.listings.query(device, window)
+    }
+}
+
+// eBay Marketplace Insights item_sales search (a restricted API: the
+// application needs the buy.marketplace.insights scope)
+pub struct EbayApiComps {
+    client: reqwest::Client,
+    token: String,
+    api_base: String,
+    marketplace: String,
+}
+
+impl EbayApiComps {
+    fn sale(&self, sale: &Value) -> Option<SoldComp> {
+        if sale.pointer("/lastSoldPrice/currency").and_then(|v| v.as_str()) != Some("USD") {
+            return None;
+        }
+        let price = Money::parse_decimal(sale.pointer("/lastSoldPrice/value")?.as_str()?, Currency::Usd)?;
+        let sold_at = sale
+            .get("lastSoldDate")
+            .and_then(|v| v.as_str())
+            .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+            .map(|at| at.with_timezone(&Local));
+        let mut listing = Product {
+            name: sale.get("title")?.as_str()?.to_string(),
+            price: Some(price),
+            url: sale.get("itemWebUrl")?.as_str()?.split('?').next()?.to_string(),
+            source: "eBay".to_string(),
+            sold_date: sold_at.map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string()),
+            grade: None,
+            warranty: None,
+            expires_at: None,
+            region: None,
+            currency: None,
+            attributes: ProductAttributes::default(),
+            listed_at: None,
+            lot_quantity: None,
+        };
+        listing.fill_attributes();
+        Some(SoldComp { listing, sold_on: sold_at.map(|at| at.date_naive()) })
+    }
+}
+
+#[async_trait]
+impl CompProvider for EbayApiComps {
+    fn name(&self) -> &'static str {
+        "eBay API"
+    }
+
+    async fn query(&self, device: &str, window: TimeDelta) -> Vec<SoldComp> {
+        let endpoint = format!("{}/buy/marketplace_insights/v1_beta/item_sales/search", self.api_base);
+        let limit = EBAY_API_LIMIT.to_string();
+        let Ok(url) = reqwest::Url::parse_with_params(&endpoint, [("q", device), ("limit", limit.as_str())]) else {
+            return Vec::new();
+        };
+        let response = retry::send(url.as_str(), || {
+            self.client
+                .get(url.clone())
+                .bearer_auth(&self.token)
+                .header("X-EBAY-C-MARKETPLACE-ID", &self.marketplace)
+        })
+        .await;
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!("  ❌ eBay API request for {} failed: {}", device, e);
+                error_journal::record(ErrorCategory::Fetch, EBAY_API_SOURCE, Some(url.as_str()), &e, None);
+                return Vec::new();
+            }
+        };
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            eprintln!("  ❌ eBay API returned HTTP {} for {}", status, device);
+            error_journal::record(ErrorCategory::Http, EBAY_API_SOURCE, Some(url.as_str()), format!("HTTP {}", status), Some(&text));
+            return Vec::new();
+        }
+        let Ok(body) = serde_json::from_str::<Value>(&text) else {
+            error_journal::record(ErrorCategory::Parse, EBAY_API_SOURCE, Some(url.as_str()), "Response is not JSON", Some(&text));
+            return Vec::new();
+        };
+        body.get("itemSales")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|sale| self.sale(sale))
+            // The search is by keyword; keep sales of this exact device
+            .filter(|comp| comp.listing.attributes.model.as_deref() == Some(device))
+            .filter(|comp| within(comp.sold_on, window))
+            .collect()
+    }
+}
+
+// Providers per tier of [comps.providers] order
+pub fn tiers(config: &CompProvidersConfig, client: &reqwest::Client, ebay_sold: &[Product], mercari_sold: &[Product]) -> Vec<Vec<Box<dyn CompProvider>>> {
+    config
+        .order
+        .iter()
+        .map(|tier| {
+            tier.kinds()
+                .iter()
+                .filter_map(|kind| -> Option<Box<dyn CompProvider>> {
+                    match kind {
+                        CompProviderKind::EbayScrape => Some(Box::new(ScrapedComps::new("eBay", ebay_sold))),
+                        CompProviderKind::Mercari => Some(Box::new(ScrapedComps::new("Mercari", mercari_sold))),
+                        CompProviderKind::Cache => Some(Box::new(StoredComps::load())),
+                        CompProviderKind::EbayApi if config.ebay_api_token.is_empty() => {
+                            eprintln!("⚠️  [comps.providers] ebay_api needs ebay_api_token, skipping it");
+                            None
+                        }
+                        CompProviderKind::EbayApi => Some(Box::new(EbayApiComps {
+                            client: client.clone(),
+                            token: config.ebay_api_token.clone(),
+                            api_base: config.ebay_api_base.clone(),
+                            marketplace: config.ebay_api_marketplace.clone(),
+                        })),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// The comp pool for `devices`: each device's comps from the first tiers
+// that give it min_comps, each listing once
+pub async fn gather(tiers: &[Vec<Box<dyn CompProvider>>], devices: &BTreeSet<String>, config: &CompProvidersConfig) -> Vec<Product> {
+    let window = TimeDelta::days(config.window_days);
+    let mut seen = HashSet::new();
+    let mut pool = Vec::new();
+    let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+    let mut fell_back = 0;
+
+    for device in devices {
+        let mut found = 0;
+        for (i, tier) in tiers.iter().enumerate() {
+            if i > 0 {
+                if found >= config.min_comps {
+                    break;
+                }
+                if i == 1 {
+                    fell_back += 1;
+                }
+            }
+            for provider in tier {
+                for comp in provider.query(device, window).await {
+                    if comp.listing.url.is_empty() || seen.insert(comp.listing.url.clone()) {
+                        found += 1;
+                        *counts.entry(provider.name()).or_default() += 1;
+                        pool.push(comp.listing);
+                    }
+                }
+            }
+        }
+    }
+
+    let summary: Vec<String> = counts.iter().map(|(name, count)| format!("{} {}", name, count)).collect();
+    println!("\n🧮 Sold comps for {} devices: {}", devices.len(), if summary.is_empty() { "none".to_string() } else { summary.join(", ") });
+    if fell_back > 0 {
+        println!("   ↪️  {} devices had fewer than {} and used fallback providers", fell_back, config.min_comps);
+    }
+    pool
+}
@@ -0,0 +1,204 @@
+// Deal sheet: the top opportunities of the latest run (or a stored run) as a
+// printable PDF, one card per deal with a listing photo, the comp summary and
+// a QR code to the buy listing, for sourcing partners who won't open JSON.
+// The sheet is laid out as HTML and printed to PDF by headless Chrome through
+// ChromeDriver, the same browser the Selenium sources use; --html skips the
+// printing and keeps the HTML.
+
+use base64::Engine;
+use chrono::Local;
+use thirtyfour::common::print::{PrintMargins, PrintParameters};
+use thirtyfour::prelude::*;
+
+use crate::cli::DealSheetArgs;
+use crate::digest::html_escape;
+use crate::money::Money;
+use crate::qr::QrCode;
+use crate::{ArbitrageOpportunity, FRONTEND_DATA_FILE, ScraperData, chromedriver, storage};
+
+// Sold comps listed under each deal; the rest are summarized
+const LISTED_COMPS: usize = 3;
+
+const STYLE: &str = "
+@page { size: letter; margin: 1cm; }
+body { font-family: sans-serif; font-size: 10pt; color: #222; }
+h1 { font-size: 16pt; margin: 0 0 4px; }
+.meta { color: #666; margin-bottom: 12px; }
+.deal { display: flex; gap: 12px; border: 1px solid #ccc; border-radius: 6px; padding: 10px; margin-bottom: 10px; page-break-inside: avoid; }
+.photo { width: 110px; height: 110px; object-fit: contain; flex: none; }
+.nophoto { width: 110px; height: 110px; flex: none; background: #f2f2f2; }
+.body { flex: 1; min-width: 0; }
+.title { font-weight: bold; font-size: 11pt; }
+.numbers td { padding: 1px 10px 1px 0; }
+.profit { font-weight: bold; color: #1a7f37; }
+.comps { margin: 4px 0 0; padding-left: 16px; color: #444; }
+.url { font-size: 7pt; color: #666; word-break: break-all; }
+.qr { width: 110px; flex: none; text-align: center; }
+.qr svg { width: 110px; height: 110px; }
+";
+
+fn money(value: Money) -> String {
+    html_escape(&value.to_string())
+}
+
+fn load_opportunities(run: Option<u32>) -> Result<(String, Vec<ArbitrageOpportunity>), String> {
+    match run {
+        Some(run_id) => {
+            let run = storage::load_run(run_id).ok_or(format!("run {} not found", run_id))?;
+            Ok((format!("run {} - {}", run.run_id, run.timestamp), run.arbitrage_opportunities))
+        }
+        None => {
+            let path = storage::profile_path(FRONTEND_DATA_FILE);
+            let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            let data: ScraperData = serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+            Ok((format!("run {} - {}", data.run_count, data.last_updated), data.arbitrage_opportunities))
+        }
+    }
+}
+
+fn deal_card(opp: &ArbitrageOpportunity, photo: Option<&String>) -> String {
+    let mut html = String::from("<div class=\"deal\">\n");
+    match photo {
+        Some(src) => html.push_str(&format!("<img class=\"photo\" src=\"{}\">\n", html_escape(src))),
+        None => html.push_str("<div class=\"nophoto\"></div>\n"),
+    }
+
+    html.push_str("<div class=\"body\">\n");
+    html.push_str(&format!("<div class=\"title\">{}</div>\n", html_escape(&opp.buy_product_name)));
+    let mut facts = vec![html_escape(&opp.buy_source)];
+    if let Some(condition) = opp.buy_condition {
+        facts.push(condition.label().to_string());
+    }
+    if let Some(color) = &opp.color {
+        facts.push(html_escape(color));
+    }
+    if let Some(expires_at) = &opp.expires_at {
+        facts.push(format!("deal ends {}", html_escape(expires_at)));
+    }
+    html.push_str(&format!("<div class=\"meta\">{}</div>\n", facts.join(" · ")));
+
+    let resale = opp.suggested_list_price.unwrap_or(opp.ebay_median_sold_price);
+    html.push_str("<table class=\"numbers\">\n");
+    html.push_str(&format!(
+        "<tr><td>Buy</td><td>{}</td><td>Resale</td><td>{}</td><td>Net profit</td><td class=\"profit\">{} ({:.1}%)</td></tr>\n",
+        money(opp.buy_price),
+        money(resale),
+        money(opp.potential_profit),
+        opp.margin_percent
+    ));
+    html.push_str(&format!(
+        "<tr><td>Sold comps</td><td>{}</td><td>Median</td><td>{}</td><td>Middle half</td><td>{} - {}</td></tr>\n",
+        opp.ebay_sold_count,
+        money(opp.ebay_median_sold_price),
+        money(opp.ebay_p25_sold_price),
+        money(opp.ebay_p75_sold_price)
+    ));
+    html.push_str("</table>\n");
+
+    let mut summary = format!("Range {}", html_escape(&opp.ebay_price_range));
+    if !opp.comp_sources.is_empty() {
+        summary.push_str(&format!(" from {}", html_escape(&opp.comp_sources.join(", "))));
+    }
+    if let Some(floor) = &opp.trade_in_floor {
+        summary.push_str(&format!(" · trade-in floor {} ({})", money(floor.price), html_escape(&floor.provider)));
+    }
+    html.push_str(&format!("<div>{}</div>\n", summary));
+
+    if !opp.comps.is_empty() {
+        html.push_str("<ul class=\"comps\">\n");
+        for comp in opp.comps.iter().take(LISTED_COMPS) {
+            html.push_str(&format!(
+                "<li>{} - {}{}</li>\n",
+                money(comp.price),
+                html_escape(&comp.title),
+                comp.sold_date.as_ref().map(|date| format!(" (sold {})", html_escape(date))).unwrap_or_default()
+            ));
+        }
+        if opp.comps.len() > LISTED_COMPS {
+            html.push_str(&format!("<li>and {} more</li>\n", opp.comps.len() - LISTED_COMPS));
+        }
+        html.push_str("</ul>\n");
+    }
+    html.push_str(&format!("<div class=\"url\">{}</div>\n", html_escape(&opp.buy_url)));
+    html.push_str("</div>\n");
+
+    // Listing URLs are short enough in practice; the printed URL covers the rest
+    if let Some(code) = QrCode::encode(&opp.buy_url) {
+        html.push_str(&format!("<div class=\"qr\">{}<br>Scan to buy</div>\n", code.to_svg(4)));
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+fn build_html(title: &str, opportunities: &[ArbitrageOpportunity]) -> String {
+    let photos = storage::load_detail_images();
+    let mut html = String::new();
+    html.push_str(&format!("<html><head><meta charset=\"utf-8\"><style>{}</style></head><body>\n", STYLE));
+    html.push_str(&format!("<h1>📋 Deal sheet - {}</h1>\n", Local::now().format("%Y-%m-%d")));
+    html.push_str(&format!(
+        "<div class=\"meta\">Top {} opportunities from {}. Profit is net of fees against the sold median.</div>\n",
+        opportunities.len(),
+        html_escape(title)
+    ));
+    for opp in opportunities {
+        let photo = photos.get(&opp.buy_url).and_then(|images| images.first());
+        html.push_str(&deal_card(opp, photo));
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+// Print an HTML file to PDF through ChromeDriver
+async fn print_pdf(html_path: &str) -> Result<Vec<u8>, String> {
+    let mut caps = DesiredCapabilities::chrome();
+    caps.add_arg("--headless=new").ok();
+    caps.add_arg("--disable-gpu").ok();
+    caps.add_arg("--no-sandbox").ok();
+    caps.add_arg("--disable-dev-shm-usage").ok();
+    // ChromeDriver may run from another directory
+    let absolute = std::fs::canonicalize(html_path).map_err(|e| format!("Failed to resolve {}: {}", html_path, e))?;
+
+    let driver = chromedriver::connect(caps)
+        .await
+        .map_err(|e| format!("Failed to connect to ChromeDriver: {} (or pass --html)", e))?;
+    let result = async {
+        driver.goto(format!("file://{}", absolute.display())).await?;
+        let parameters = PrintParameters {
+            background: true,
+            margin: PrintMargins { top: 0.0, bottom: 0.0, left: 0.0, right: 0.0 },
+            ..Default::default()
+        };
+        driver.print_page_base64(parameters).await
+    }
+    .await;
+    driver.quit().await.ok();
+
+    let encoded = result.map_err(|e| format!("Failed to print {}: {}", html_path, e))?;
+    base64::engine::general_purpose::STANDARD.decode(encoded).map_err(|e| format!("ChromeDriver returned a bad PDF: {}", e))
+}
+
+pub async fn export(args: &DealSheetArgs) -> Result<(), String> {
+    let (title, mut opportunities) = load_opportunities(args.run)?;
+    if opportunities.is_empty() {
+        return Err(format!("no opportunities in {}", title));
+    }
+    opportunities.sort_by_key(|o| std::cmp::Reverse(o.potential_profit));
+    opportunities.truncate(args.top);
+
+    let html = build_html(&title, &opportunities);
+    let output = args.output.clone().unwrap_or_else(|| storage::profile_path(if args.html { "deal_sheet.html" } else { "deal_sheet.pdf" }));
+    if args.html {
+        std::fs::write(&output, html).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+        println!("📋 Deal sheet with {} opportunities written to {}", opportunities.len(), output);
+        return Ok(());
+    }
+
+    // Chrome prints from a file, so the HTML goes next to the PDF
+    let html_path = format!("{}.html", output.trim_end_matches(".pdf"));
+    std::fs::write(&html_path, html).map_err(|e| format!("Failed to write {}: {}", html_path, e))?;
+    let pdf = print_pdf(&html_path).await?;
+    std::fs::write(&output, pdf).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+    std::fs::remove_file(&html_path).ok();
+    println!("📋 Deal sheet with {} opportunities written to {}", opportunities.len(), output);
+    Ok(())
+}
@@ -0,0 +1,220 @@
+// KPI digest: an HTML page with daily and weekly KPIs from the stored run
+// history, the latest market heat index, plus a small sold-price trend chart
+// per top device. Charts are PNGs written next to the page and linked by file
+// name, since mail clients strip data: URIs. There is no mail client in the
+// scraper; the page and chart paths are handed to the post-run hook
+// (M3_DIGEST_PATH, M3_DIGEST_CHARTS), which can send them as inline (CID)
+// attachments with whatever mailer the deployment already uses.
+
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use plotters::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::analysis::heat::MarketHeat;
+use crate::attributes::ProductAttributes;
+use crate::config::DigestConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::{stats, storage};
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const CHART_SIZE: (u32, u32) = (360, 120);
+
+#[derive(Debug, Default)]
+struct Kpis {
+    runs: usize,
+    opportunities: usize,
+    best_profit: Option<Money>,
+    avg_margin: Option<f64>,
+}
+
+// The written page and its chart images, in the order they appear
+pub struct Digest {
+    pub path: String,
+    pub charts: Vec<String>,
+}
+
+// Sold prices for one device, by the day the sale first showed up
+struct DeviceTrend {
+    device: String,
+    sales: usize,
+    daily_median: Vec<(NaiveDate, f64)>,
+}
+
+// Line chart without text (no fonts needed); axis values go in the HTML
+fn render_chart(points: &[(NaiveDate, f64)]) -> Result<Vec<u8>, String> {
+    let (width, height) = CHART_SIZE;
+    let mut buffer = vec![0u8; (width * height * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, CHART_SIZE).into_drawing_area();
+        root.fill(&WHITE).map_err(|e| e.to_string())?;
+
+        let min = points.iter().map(|(_, p)| *p).fold(f64::INFINITY, f64::min);
+        let max = points.iter().map(|(_, p)| *p).fold(f64::NEG_INFINITY, f64::max);
+        let pad = ((max - min) * 0.1).max(10.0);
+        let last = points.len().saturating_sub(1).max(1) as f64;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(8)
+            .build_cartesian_2d(0f64..last, (min - pad)..(max + pad))
+            .map_err(|e| e.to_string())?;
+        let series = points.iter().enumerate().map(|(i, (_, price))| (i as f64, *price));
+        chart.draw_series(LineSeries::new(series.clone(), BLUE.stroke_width(2))).map_err(|e| e.to_string())?;
+        chart
+            .draw_series(series.map(|point| Circle::new(point, 3, BLUE.filled())))
+            .map_err(|e| e.to_string())?;
+        root.present().map_err(|e| e.to_string())?;
+    }
+
+    let image = image::RgbImage::from_raw(width, height, buffer).ok_or("chart buffer size mismatch")?;
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, image::ImageFormat::Png).map_err(|e| e.to_string())?;
+    Ok(png.into_inner())
+}
+
+fn kpis_since(runs: &[(NaiveDateTime, crate::RunSnapshot)], since: NaiveDateTime) -> Kpis {
+    let mut kpis = Kpis::default();
+    let mut seen = HashSet::new();
+    let mut margins = Vec::new();
+    for (_, run) in runs.iter().filter(|(timestamp, _)| *timestamp >= since) {
+        kpis.runs += 1;
+        for opp in &run.arbitrage_opportunities {
+            if !seen.insert(opp.buy_url.clone()) {
+                continue;
+            }
+            margins.push(opp.margin_percent);
+            kpis.best_profit = Some(kpis.best_profit.map_or(opp.potential_profit, |b| b.max(opp.potential_profit)));
+        }
+    }
+    kpis.opportunities = seen.len();
+    if !margins.is_empty() {
+        kpis.avg_margin = Some(margins.iter().sum::<f64>() / margins.len() as f64);
+    }
+    kpis
+}
+
+// Daily median sold price per device over the window, busiest devices first.
+// A sold listing shows up in every run until it ages out of eBay's results,
+// so each URL counts once, on the day it was first seen.
+fn device_trends(runs: &[(NaiveDateTime, crate::RunSnapshot)], top_devices: usize) -> Vec<DeviceTrend> {
+    let mut seen = HashSet::new();
+    let mut by_device: HashMap<String, BTreeMap<NaiveDate, Vec<f64>>> = HashMap::new();
+    for (timestamp, run) in runs {
+        for sold in run.ebay_sold_products.iter().chain(run.mercari_sold_products.iter()) {
+            if !seen.insert(sold.url.clone()) {
+                continue;
+            }
+            let (Some(device), Some(price)) = (ProductAttributes::parse(&sold.name).device_key(), sold.amount()) else {
+                continue;
+            };
+            // Plotted, so kept as plain dollars from here on
+            by_device.entry(device).or_default().entry(timestamp.date()).or_default().push(price.amount());
+        }
+    }
+
+    let mut trends: Vec<DeviceTrend> = by_device
+        .into_iter()
+        .map(|(device, days)| DeviceTrend {
+            device,
+            sales: days.values().map(Vec::len).sum(),
+            daily_median: days.into_iter().map(|(day, prices)| (day, stats::median(&prices))).collect(),
+        })
+        .collect();
+    trends.sort_by(|a, b| b.sales.cmp(&a.sales).then_with(|| a.device.cmp(&b.device)));
+    trends.truncate(top_devices);
+    trends
+}
+
+fn kpi_cells(kpis: &Kpis) -> String {
+    format!(
+        "<td>{}</td><td>{}</td><td>{}</td><td>{}</td>",
+        kpis.runs,
+        kpis.opportunities,
+        kpis.best_profit.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+        kpis.avg_margin.map(|m| format!("{:.1}%", m)).unwrap_or_else(|| "-".to_string()),
+    )
+}
+
+pub fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Build the digest over the last `config.days` of stored runs and write it to
+// `config.path`. Returns the path written, if any.
+pub fn write_digest(config: &DigestConfig, market_heat: Option<&MarketHeat>) -> Option<Digest> {
+    if !config.enabled {
+        return None;
+    }
+
+    let now = Local::now().naive_local();
+    let window_start = now - Duration::days(config.days);
+    let runs: Vec<(NaiveDateTime, crate::RunSnapshot)> = storage::load_run_index()
+        .into_iter()
+        .filter_map(|entry| {
+            let timestamp = NaiveDateTime::parse_from_str(&entry.timestamp, TIMESTAMP_FORMAT).ok()?;
+            if timestamp < window_start {
+                return None;
+            }
+            storage::load_run(entry.run_id).map(|run| (timestamp, run))
+        })
+        .collect();
+
+    let daily = kpis_since(&runs, now - Duration::days(1));
+    let weekly = kpis_since(&runs, window_start);
+
+    let mut html = String::new();
+    html.push_str("<html><body style=\"font-family: sans-serif\">\n");
+    html.push_str(&format!("<h2>📊 Arbitrage digest - {}</h2>\n", now.format("%Y-%m-%d")));
+    html.push_str("<table border=\"1\" cellpadding=\"4\" cellspacing=\"0\">\n");
+    html.push_str("<tr><th></th><th>Runs</th><th>Opportunities</th><th>Best profit</th><th>Avg margin</th></tr>\n");
+    html.push_str(&format!("<tr><th>Last 24h</th>{}</tr>\n", kpi_cells(&daily)));
+    html.push_str(&format!("<tr><th>Last {} days</th>{}</tr>\n", config.days, kpi_cells(&weekly)));
+    html.push_str("</table>\n");
+    if let Some(heat) = market_heat {
+        html.push_str(&format!("<p><b>Market heat:</b> {}</p>\n", html_escape(&heat.label())));
+    }
+
+    // "reports/digest.html" -> "reports/digest_chart_1.png", ...
+    let page = Path::new(&config.path);
+    let stem = page.file_stem().and_then(|s| s.to_str()).unwrap_or("digest");
+    let mut charts = Vec::new();
+
+    html.push_str(&format!("<h3>Sold price trend, last {} days</h3>\n", config.days));
+    for trend in device_trends(&runs, config.top_devices) {
+        let (Some((first_day, first)), Some((last_day, last))) = (trend.daily_median.first(), trend.daily_median.last()) else {
+            continue;
+        };
+        let change = (last - first) / first * 100.0;
+        html.push_str(&format!(
+            "<p><b>{}</b> - {} sales, median ${:.2} ({}) → ${:.2} ({}), {:+.1}%<br>\n",
+            html_escape(&trend.device), trend.sales, first, first_day.format("%m-%d"), last, last_day.format("%m-%d"), change
+        ));
+        let file_name = format!("{}_chart_{}.png", stem, charts.len() + 1);
+        let chart_path = page.with_file_name(&file_name);
+        match render_chart(&trend.daily_median).and_then(|png| std::fs::write(&chart_path, png).map_err(|e| e.to_string())) {
+            Ok(()) => {
+                html.push_str(&format!("<img alt=\"{}\" src=\"{}\"></p>\n", html_escape(&trend.device), file_name));
+                charts.push(chart_path.display().to_string());
+            }
+            Err(e) => {
+                eprintln!("⚠️  Failed to write digest chart for {}: {}", trend.device, e);
+                html.push_str("</p>\n");
+            }
+        }
+    }
+    html.push_str("</body></html>\n");
+
+    match std::fs::write(&config.path, html) {
+        Ok(()) => {
+            println!("📧 Wrote KPI digest to {} with {} charts", config.path, charts.len());
+            Some(Digest { path: config.path.clone(), charts })
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to write digest {}: {}", config.path, e);
+            error_journal::record(ErrorCategory::Storage, "Digest", None, e.to_string(), None);
+            None
+        }
+    }
+}
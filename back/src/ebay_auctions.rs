@@ -0,0 +1,179 @@
+// eBay auctions ending soon as a time-sensitive buy source. Searches are
+// sorted by "ending soonest"; auctions closing within the configured window
+// are compared against sold averages like any other buy-side listing.
+
+use chrono::{DateTime, Local};
+use thirtyfour::prelude::*;
+
+use crate::attributes::ProductAttributes;
+use crate::config::{AuctionConfig, BrowserConfig};
+use crate::politeness::Politeness;
+use crate::{ebay_shipping, retry, sessions};
+use crate::error_journal::{self, ErrorCategory};
+use crate::{Product, parse_price};
+
+// An auction listing; `product.price` is the current bid plus shipping
+#[derive(Debug, Clone)]
+pub struct Auction {
+    pub product: Product,
+    pub ends_at: DateTime<Local>,
+    pub bid_count: u32,
+}
+
+const EXTRACT_SCRIPT: &str = r#"
+    var products = [];
+    var seenUrls = new Set();
+    var items = document.querySelectorAll('ul.srp-results li.s-card');
+
+    for (var i = 0; i < items.length && products.length < 60; i++) {
+        var item = items[i];
+        var titleEl = item.querySelector('.s-card__title span');
+        var name = titleEl ? titleEl.innerText.replace(/^NEW LISTING/i, '').trim() : '';
+        if (!name || name.length < 10 || name.toLowerCase().includes('shop on ebay')) continue;
+
+        var priceEl = item.querySelector('.s-card__price');
+        var priceMatch = priceEl ? priceEl.innerText.match(/\$[\d,]+\.?\d{0,2}/) : null;
+        if (!priceMatch) continue;
+
+        var linkEl = item.querySelector('a.s-card__link[href*="/itm/"]') || item.querySelector('a[href*="/itm/"]');
+        var cleanUrl = linkEl ? linkEl.href.split('?')[0] : '';
+        if (!cleanUrl || seenUrls.has(cleanUrl)) continue;
+        seenUrls.add(cleanUrl);
+
+        var text = item.innerText || '';
+        var timeMatch = text.match(/((?:\d+d\s*)?(?:\d+h\s*)?(?:\d+m\s*)?(?:\d+s)?)\s*left/);
+        var bidsMatch = text.match(/(\d+)\s+bids?/);
+        products.push({
+            name: name.substring(0, 200),
+            price: priceMatch[0],
+            url: cleanUrl,
+            timeLeft: timeMatch ? timeMatch[1].trim() : '',
+            bids: bidsMatch ? parseInt(bidsMatch[1], 10) : 0,
+            shipping: shippingText(item)
+        });
+    }
+
+    return { products: products };
+"#;
+
+// "1d 2h", "14m 30s", "45s" -> seconds remaining
+fn parse_time_left(text: &str) -> Option<i64> {
+    let mut seconds = 0;
+    let mut found = false;
+    for part in text.split_whitespace() {
+        let (number, unit) = part.split_at(part.len().checked_sub(1)?);
+        let value: i64 = number.parse().ok()?;
+        seconds += match unit {
+            "d" => value * 86_400,
+            "h" => value * 3_600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+        found = true;
+    }
+    found.then_some(seconds)
+}
+
+pub async fn scrape_ending_auctions(config: &AuctionConfig, browser: &BrowserConfig, politeness: &Politeness) -> Vec<Auction> {
+    let mut auctions = Vec::new();
+
+    println!("  Starting Selenium WebDriver for eBay auctions...");
+
+    let mut caps = DesiredCapabilities::chrome();
+    caps.add_arg("--headless=new").ok();
+    caps.add_arg("--disable-gpu").ok();
+    caps.add_arg("--no-sandbox").ok();
+    caps.add_arg("--disable-dev-shm-usage").ok();
+    caps.add_arg("--window-size=1920,1200").ok();
+    caps.add_arg("--disable-blink-features=AutomationControlled").ok();
+    caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
+
+    let driver = match sessions::open(browser, "eBay Auction", caps).await {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
+            error_journal::record(ErrorCategory::Driver, "eBay Auction", None, &e, None);
+            return auctions;
+        }
+    };
+
+    // LH_Auction=1 limits to auctions, _sop=1 sorts by ending soonest
+    let queries = [
+        ("iPhone 15", "iphone+15+unlocked"),
+        ("iPhone 14", "iphone+14+unlocked"),
+        ("iPhone 13", "iphone+13+unlocked"),
+        ("Galaxy S24", "samsung+galaxy+s24+unlocked"),
+        ("Pixel 8", "google+pixel+8+unlocked"),
+    ];
+    let window_secs = config.window_minutes as i64 * 60;
+
+    for (device, query) in queries.iter() {
+        let url = format!("https://www.ebay.com/sch/i.html?_nkw={}&_sacat=9355&LH_Auction=1&_sop=1", query);
+        println!("  ⏱️ Scraping eBay auctions {}: {}", device, url);
+
+        if let Err(e) = retry::goto(&driver, &url).await {
+            eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
+            error_journal::record(ErrorCategory::Navigation, "eBay Auction", Some(&url), &e, None);
+            continue;
+        }
+        politeness.behavior.wait_for_load(&driver).await;
+
+        let script = [ebay_shipping::SHIPPING_TEXT_JS, EXTRACT_SCRIPT].concat();
+        let Ok(result_value) = driver.execute(&script, vec![]).await else {
+            continue;
+        };
+        let json = result_value.json();
+        let now = Local::now();
+        let mut ending_count = 0;
+
+        if let Some(products_arr) = json.get("products").and_then(|v| v.as_array()) {
+            for product in products_arr {
+                let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                let price = product.get("price").and_then(|v| v.as_str()).unwrap_or("");
+                let prod_url = product.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                let time_left = product.get("timeLeft").and_then(|v| v.as_str()).unwrap_or("");
+                let bids = product.get("bids").and_then(|v| v.as_u64()).unwrap_or(0);
+                let charges = ebay_shipping::parse(product.get("shipping").and_then(|v| v.as_str()).unwrap_or(""));
+
+                let Some(seconds_left) = parse_time_left(time_left) else {
+                    continue;
+                };
+                if seconds_left > window_secs || name.is_empty() || prod_url.is_empty() {
+                    continue;
+                }
+                // Current bid plus shipping and any international import charges
+                let Some(bid) = parse_price(price) else {
+                    continue;
+                };
+
+                let mut product = Product {
+                    name: name.to_string(),
+                    price: Some(charges.landed(bid)),
+                    url: prod_url.to_string(),
+                    source: "eBay Auction".to_string(),
+                    sold_date: None,
+                    grade: None,
+                    warranty: None,
+                    expires_at: None,
+                    region: None,
+                    currency: None,
+                    attributes: ProductAttributes::default(),
+                    listed_at: None,
+                    lot_quantity: None,
+                };
+                product.fill_attributes();
+                auctions.push(Auction { product, ends_at: now + chrono::Duration::seconds(seconds_left), bid_count: bids as u32 });
+                ending_count += 1;
+            }
+        }
+        println!("    Found {} auctions ending within {} minutes", ending_count, config.window_minutes);
+
+        politeness.behavior.between_pages(&driver).await;
+    }
+
+    // Kept for the next scrape of the site this run
+    sessions::release(browser, "eBay Auction", driver).await;
+
+    auctions
+}
@@ -0,0 +1,190 @@
+// Draft eBay listings for inventory items through the Sell Inventory API: an
+// inventory item (title, aspects, condition, photos) plus an offer that is
+// left unpublished, so it waits in Seller Hub for review. The title comes
+// from the parsed attributes, the price from the latest analysis, and the
+// photos from the listing's stored detail images.
+
+use serde_json::{Value, json};
+
+use crate::attributes::{Brand, Carrier, Condition, ProductAttributes};
+use crate::cli::DraftArgs;
+use crate::config::EbaySellConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::inventory::{self, InventoryItem};
+use crate::money::Money;
+use crate::{catalog, load_frontend_data, storage};
+
+const SOURCE_NAME: &str = "eBay Sell";
+// eBay's title limit
+const MAX_TITLE_LEN: usize = 80;
+
+// Display name of a parsed model: the catalog's ("iPhone 14 Pro Max"), else
+// a [categories] keyword as the listing title writes it ("RTX 4090")
+fn display_model(model: &str, name: &str) -> String {
+    if let Some(device) = catalog::device(model) {
+        return device.model.clone();
+    }
+    match name.to_ascii_lowercase().find(model) {
+        Some(start) => name[start..start + model.len()].to_string(),
+        None => model.to_string(),
+    }
+}
+
+fn title_case(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// eBay condition enum for the Inventory API
+fn ebay_condition(condition: Option<Condition>) -> &'static str {
+    match condition {
+        Some(Condition::New) => "NEW",
+        Some(Condition::Excellent) => "USED_EXCELLENT",
+        Some(Condition::Fair) => "USED_ACCEPTABLE",
+        Some(Condition::Refurbished) => "SELLER_REFURBISHED",
+        Some(Condition::Good) | None => "USED_GOOD",
+    }
+}
+
+// Draft content for an item: title, aspects and condition
+struct ListingContent {
+    title: String,
+    aspects: Value,
+    condition: Option<Condition>,
+}
+
+fn listing_content(name: &str) -> ListingContent {
+    let parsed = ProductAttributes::parse(name);
+    let storage = parsed.storage.map(|storage| storage.label().to_uppercase());
+    let (carrier, condition) = (parsed.carrier, parsed.condition);
+
+    let Some(model) = parsed.model.as_deref() else {
+        // Nothing recognizable to build from; list under the recorded name
+        return ListingContent { title: name.chars().take(MAX_TITLE_LEN).collect(), aspects: json!({}), condition };
+    };
+    let model = display_model(model, name);
+    // Left out rather than guessed when the title doesn't tell
+    let brand = parsed.brand.map(Brand::label);
+    let color = parsed.color.as_deref().map(title_case);
+    let title_parts = [brand.map(String::from), Some(model.clone()), storage.clone(), color.clone(), carrier.map(|c| c.label().to_string())];
+    let mut title = title_parts.into_iter().flatten().collect::<Vec<String>>().join(" ");
+    title.truncate(title.char_indices().nth(MAX_TITLE_LEN).map_or(title.len(), |(i, _)| i));
+
+    let mut aspects = json!({ "Model": [model] });
+    if let Some(brand) = brand {
+        aspects["Brand"] = json!([brand]);
+    }
+    if let Some(storage) = storage {
+        aspects["Storage Capacity"] = json!([storage]);
+    }
+    if let Some(color) = color {
+        aspects["Color"] = json!([color]);
+    }
+    if let Some(carrier) = carrier {
+        let network = if carrier == Carrier::Unlocked { "Unlocked" } else { carrier.label() };
+        aspects["Network"] = json!([network]);
+    }
+    ListingContent { title, aspects, condition }
+}
+
+// Market value from the latest run's inventory valuation, else the suggested
+// list price of the opportunity the item was bought from
+fn suggested_price(item: &InventoryItem) -> Option<Money> {
+    let data = load_frontend_data()?;
+    let valued = data.inventory.iter().find(|v| v.id == item.id).and_then(|v| v.market_value);
+    valued.or_else(|| {
+        let url = item.url.as_deref()?;
+        data.arbitrage_opportunities.iter().find(|o| o.buy_url == url).and_then(|o| o.suggested_list_price)
+    })
+}
+
+async fn send(request: reqwest::RequestBuilder, url: &str, body: &Value) -> Result<Value, String> {
+    let response = request
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(reqwest::header::CONTENT_LANGUAGE, "en-US")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|e| {
+            error_journal::record(ErrorCategory::Fetch, SOURCE_NAME, Some(url), &e, None);
+            e.to_string()
+        })?;
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        error_journal::record(ErrorCategory::Http, SOURCE_NAME, Some(url), format!("HTTP {}", status), Some(&text));
+        return Err(format!("eBay returned HTTP {}: {}", status, text));
+    }
+    // createOrReplaceInventoryItem answers 204 with no body
+    Ok(serde_json::from_str(&text).unwrap_or(Value::Null))
+}
+
+// `back draft <inventory id>`
+pub async fn draft(args: &DraftArgs, config: &EbaySellConfig, client: &reqwest::Client) -> Result<(), String> {
+    let item = inventory::load_inventory()
+        .into_iter()
+        .find(|i| i.id == args.id)
+        .ok_or_else(|| format!("No inventory item #{}", args.id))?;
+    let price = args
+        .price
+        .map(Money::usd)
+        .or_else(|| suggested_price(&item))
+        .ok_or("No suggested price for this item yet, pass --price")?;
+    let mut images = args.images.clone();
+    if let Some(stored) = item.url.as_ref().and_then(|url| storage::load_detail_images().remove(url)) {
+        images.extend(stored);
+    }
+    images.truncate(24);
+
+    let content = listing_content(&item.name);
+    let sku = format!("m3-inv-{}", item.id);
+    let inventory_item = json!({
+        "availability": { "shipToLocationAvailability": { "quantity": 1 } },
+        "condition": ebay_condition(content.condition),
+        "product": {
+            "title": content.title,
+            "description": format!("{}. Photos and details from the original listing.", content.title),
+            "aspects": content.aspects,
+            "imageUrls": images,
+        },
+    });
+    let offer = json!({
+        "sku": sku,
+        "marketplaceId": config.marketplace_id,
+        "format": "FIXED_PRICE",
+        "availableQuantity": 1,
+        "categoryId": config.category_id,
+        "merchantLocationKey": config.merchant_location_key,
+        "pricingSummary": { "price": { "value": format!("{:.2}", price.amount()), "currency": price.currency().code() } },
+        "listingPolicies": {
+            "fulfillmentPolicyId": config.fulfillment_policy_id,
+            "paymentPolicyId": config.payment_policy_id,
+            "returnPolicyId": config.return_policy_id,
+        },
+    });
+
+    println!("📝 {} - {}, {} photos", content.title, price, images.len());
+    if args.dry_run {
+        println!("{}", serde_json::to_string_pretty(&json!({ "inventory_item": inventory_item, "offer": offer })).unwrap_or_default());
+        return Ok(());
+    }
+    if config.access_token.is_empty() {
+        return Err("Set [ebay_sell] access_token to create drafts (or use --dry-run)".to_string());
+    }
+
+    let item_url = format!("{}/sell/inventory/v1/inventory_item/{}", config.api_base, sku);
+    send(client.put(&item_url).bearer_auth(&config.access_token), &item_url, &inventory_item).await?;
+    let offer_url = format!("{}/sell/inventory/v1/offer", config.api_base);
+    let created = send(client.post(&offer_url).bearer_auth(&config.access_token), &offer_url, &offer).await?;
+    // The offer stays unpublished until it's published from Seller Hub
+    match created.get("offerId").and_then(|id| id.as_str()) {
+        Some(offer_id) => println!("✅ Draft offer {} created for SKU {}", offer_id, sku),
+        None => println!("✅ Draft created for SKU {}", sku),
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// Shipping and import charges on eBay search cards. Listings shipped through
+// the Global Shipping Program / eBay International Shipping show an
+// international shipping estimate and import charges next to the item price,
+// which can add a third to what the buyer pays. Buy-side listings use the
+// landed cost; sold comps use the price selected by [comps] shipping.
+
+use crate::config::CompShipping;
+use crate::money::Money;
+use crate::parse_price;
+
+// Appended to each extract script: the card lines that mention shipping,
+// import charges or item location, for `parse`
+pub const SHIPPING_TEXT_JS: &str = r#"
+    function shippingText(item) {
+        return (item.innerText || '').split('\n').filter(function (line) {
+            return /shipping|delivery|import|customs|international|located in|^from /i.test(line);
+        }).join('\n');
+    }
+"#;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ShippingCharges {
+    pub shipping: Money,
+    pub import_charges: Money,
+    // Shipped from outside the US, through GSP / eBay International Shipping
+    pub international: bool,
+}
+
+// "+$X" amount on a line containing one of `labels`
+fn charge(line: &str, labels: &[&str]) -> Option<Money> {
+    let lower = line.to_lowercase();
+    if !labels.iter().any(|label| lower.contains(label)) {
+        return None;
+    }
+    let start = line.find("+$")?;
+    parse_price(&line[start + 1..])
+}
+
+fn located_abroad(line: &str) -> bool {
+    let lower = line.trim().to_lowercase();
+    let location = lower.strip_prefix("located in ").or_else(|| lower.strip_prefix("from "));
+    location.is_some_and(|place| {
+        place.starts_with(char::is_alphabetic) && !place.starts_with("united states") && !place.starts_with("usa")
+    })
+}
+
+pub fn parse(text: &str) -> ShippingCharges {
+    let mut charges = ShippingCharges::default();
+    for line in text.lines() {
+        let lower = line.to_lowercase();
+        if let Some(amount) = charge(line, &["import"]) {
+            charges.import_charges += amount;
+        } else if let Some(amount) = charge(line, &["shipping", "delivery"]) {
+            charges.shipping = amount;
+        }
+        if lower.contains("international") || lower.contains("import") || lower.contains("customs") || located_abroad(line) {
+            charges.international = true;
+        }
+    }
+    charges
+}
+
+impl ShippingCharges {
+    // What the buyer pays in total
+    pub fn landed(&self, price: Money) -> Money {
+        price + self.shipping + self.import_charges
+    }
+
+    // Sold price as used for comps
+    pub fn comp_price(&self, price: Money, basis: CompShipping) -> Money {
+        match basis {
+            CompShipping::Item => price,
+            CompShipping::Domestic if self.international => price,
+            CompShipping::Domestic => price + self.shipping,
+            CompShipping::Total => self.landed(price),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Persistent journal of scrape/fetch/parse failures. Every entry is one JSON
+// line in errors.jsonl so intermittent failures can be queried after the fact
+// (`back errors --since 24h --source ebay`) instead of scrolling console output.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use crate::cli::ErrorsArgs;
+
+pub const ERROR_JOURNAL_FILE: &str = "errors.jsonl";
+const EXCERPT_LEN: usize = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    // Request failed before a response arrived
+    Fetch,
+    // Response arrived with an error status
+    Http,
+    // ChromeDriver unavailable or the browser misbehaved
+    Driver,
+    Navigation,
+    // Page or payload didn't have the expected shape
+    Parse,
+    // Captcha or bot wall
+    Blocked,
+    Script,
+    Hook,
+    Storage,
+    // A source's scrape task panicked or its actor died
+    Crash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Local>,
+    pub category: ErrorCategory,
+    pub source: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    pub message: String,
+    // Start of the offending response/page, when there is one
+    #[serde(default)]
+    pub excerpt: Option<String>,
+}
+
+// Append an entry. Journal write failures are only printed, never fatal.
+pub fn record(category: ErrorCategory, source: &str, url: Option<&str>, message: impl Display, excerpt: Option<&str>) {
+    let entry = JournalEntry {
+        timestamp: Local::now(),
+        category,
+        source: source.to_string(),
+        url: url.map(String::from),
+        message: message.to_string(),
+        excerpt: excerpt.map(|e| e.chars().take(EXCERPT_LEN).collect()),
+    };
+    let Ok(line) = serde_json::to_string(&entry) else {
+        return;
+    };
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(ERROR_JOURNAL_FILE)
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = written {
+        eprintln!("Failed to write {}: {}", ERROR_JOURNAL_FILE, e);
+    }
+}
+
+// Source name for a URL, so fetch errors are attributed without callers passing it
+pub fn source_for_url(url: &str) -> String {
+    const KNOWN: [(&str, &str); 19] = [
+        ("newegg", "Newegg"),
+        ("swappa", "Swappa"),
+        ("ebay", "eBay"),
+        ("mercari", "Mercari"),
+        ("gazelle", "Gazelle"),
+        ("backmarket", "Back Market"),
+        ("bestbuy", "Best Buy"),
+        ("amazon", "Amazon"),
+        ("walmart", "Walmart"),
+        ("decluttr", "Decluttr"),
+        ("reebelo", "Reebelo"),
+        ("microcenter", "Micro Center"),
+        ("bhphotovideo", "B&H"),
+        ("woot", "Woot"),
+        ("kijiji", "Kijiji"),
+        ("gumtree", "Gumtree"),
+        ("apple.com", "Apple Refurbished"),
+        ("samsung", "Samsung Re-Newed"),
+        ("store.google", "Google Store"),
+    ];
+    let host = url.split("://").nth(1).unwrap_or(url).split('/').next().unwrap_or("");
+    KNOWN
+        .iter()
+        .find(|(needle, _)| host.contains(needle))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| host.to_string())
+}
+
+// Drop entries older than `cutoff`, rewriting the journal. Returns how many went.
+pub fn evict_before(cutoff: DateTime<Local>) -> Result<usize, String> {
+    let entries = load_entries();
+    let kept: Vec<&JournalEntry> = entries.iter().filter(|e| e.timestamp >= cutoff).collect();
+    let evicted = entries.len() - kept.len();
+    if evicted == 0 {
+        return Ok(0);
+    }
+    let mut content = String::new();
+    for entry in kept {
+        content.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+        content.push('\n');
+    }
+    let tmp = format!("{}.tmp", ERROR_JOURNAL_FILE);
+    fs::write(&tmp, content).map_err(|e| format!("Failed to write {}: {}", tmp, e))?;
+    fs::rename(&tmp, ERROR_JOURNAL_FILE).map_err(|e| format!("Failed to replace {}: {}", ERROR_JOURNAL_FILE, e))?;
+    Ok(evicted)
+}
+
+pub fn load_entries() -> Vec<JournalEntry> {
+    let Ok(content) = fs::read_to_string(ERROR_JOURNAL_FILE) else {
+        return Vec::new();
+    };
+    // Skip lines that don't parse (e.g. a write cut short by a crash)
+    content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()
+}
+
+// `errors` subcommand
+pub fn print_errors(args: &ErrorsArgs) {
+    let cutoff = args.since.map(|since| Local::now() - since);
+    let source = args.source.as_ref().map(|s| s.to_lowercase());
+
+    let matching: Vec<JournalEntry> = load_entries()
+        .into_iter()
+        .filter(|e| cutoff.is_none_or(|cutoff| e.timestamp >= cutoff))
+        .filter(|e| source.as_ref().is_none_or(|s| e.source.to_lowercase().contains(s)))
+        .filter(|e| args.category.is_none_or(|c| e.category == c))
+        .collect();
+
+    if matching.is_empty() {
+        println!("No matching errors in {}", ERROR_JOURNAL_FILE);
+        return;
+    }
+
+    let shown = &matching[matching.len().saturating_sub(args.limit)..];
+    for entry in shown {
+        println!(
+            "{} [{:?}] {}: {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.category,
+            entry.source,
+            entry.message
+        );
+        if let Some(url) = &entry.url {
+            println!("    🔗 {}", url);
+        }
+        if let Some(excerpt) = &entry.excerpt {
+            println!("    📄 {}", excerpt.replace('\n', " "));
+        }
+    }
+    println!("\n{} of {} matching errors shown", shown.len(), matching.len());
+}
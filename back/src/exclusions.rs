@@ -0,0 +1,162 @@
+// Keyword-based accessory exclusion. The `> $50` price floor lets through
+// cases, empty boxes and chargers priced like phones; these keyword lists
+// (per vertical, from [exclusions]) drop them from both the buy side and the
+// sold comps before any price analysis. Damaged phones ("for parts",
+// "cracked", "no power") are dropped the same way, matched against the title
+// and the seller's condition grade, so they neither drag sold averages down
+// nor show up as cheap buys. Lots ("lot of 5", "x3") are either dropped or
+// priced per unit, per [lots].
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::attributes::{Lot, parse_lot};
+use crate::config::{ExclusionConfig, LotConfig, LotMode};
+use crate::{Product, locale};
+
+// Words that mark the keyword as an extra bundled with the device,
+// e.g. "iPhone 13 128GB with charger" is still a phone
+const BUNDLE_MARKERS: &[&str] = &["with", "w/", "+", "&", "and", "includes", "including", "plus"];
+// "no cracks", "not broken": a damage keyword right after these is a denial
+const NEGATIONS: &[&str] = &["no", "not", "never", "without", "zero", "isn't", "is not"];
+
+// Per-run exclusion counts, exported with the run data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExclusionReport {
+    pub buy_side: usize,
+    pub comps: usize,
+    // Of the above, dropped as damaged / for parts
+    #[serde(default)]
+    pub damaged_buy_side: usize,
+    #[serde(default)]
+    pub damaged_comps: usize,
+    // Multi-unit listings dropped, and those repriced per unit
+    #[serde(default)]
+    pub lots_excluded: usize,
+    #[serde(default)]
+    pub lots_per_unit: usize,
+    pub by_keyword: BTreeMap<String, usize>,
+}
+
+// Product vertical a listing title belongs to, selecting its keyword list
+pub fn vertical(name: &str) -> &'static str {
+    let name = name.to_lowercase();
+    if name.contains("ipad") || name.contains("galaxy tab") || name.contains("tablet") {
+        "tablets"
+    } else if name.contains("watch") {
+        "watches"
+    } else {
+        "phones"
+    }
+}
+
+// Keyword as a whole word (or plural), not preceded by a bundle marker
+fn mentions(name: &str, keyword: &str) -> bool {
+    name.match_indices(keyword).any(|(i, _)| {
+        let before = &name[..i];
+        let after = &name[i + keyword.len()..];
+        let after = after.strip_prefix('s').unwrap_or(after);
+        let bounded = !before.ends_with(|c: char| c.is_alphanumeric()) && !after.starts_with(|c: char| c.is_alphanumeric());
+        let bundled = BUNDLE_MARKERS.iter().any(|m| before.trim_end().ends_with(m));
+        bounded && !bundled
+    })
+}
+
+// Damage keyword as a whole word (or plural) that isn't negated
+fn mentions_damage(text: &str, keyword: &str) -> bool {
+    text.match_indices(keyword).any(|(i, _)| {
+        let before = &text[..i];
+        let after = &text[i + keyword.len()..];
+        let after = after.strip_prefix('s').unwrap_or(after);
+        let bounded = !before.ends_with(|c: char| c.is_alphanumeric()) && !after.starts_with(|c: char| c.is_alphanumeric());
+        let negated = NEGATIONS.iter().any(|n| {
+            let before = before.trim_end();
+            before.ends_with(n) && !before[..before.len() - n.len()].ends_with(|c: char| c.is_alphanumeric())
+        });
+        bounded && !negated
+    })
+}
+
+// First damage keyword in the listing's title or condition grade
+pub fn matched_damage<'a>(name: &str, grade: Option<&str>, config: &'a ExclusionConfig) -> Option<&'a str> {
+    let text = locale::to_english(&format!("{} {}", name, grade.unwrap_or_default()));
+    config.damaged.iter().find(|k| mentions_damage(&text, &k.to_lowercase())).map(String::as_str)
+}
+
+// First exclusion keyword of the listing's vertical found in its title
+pub fn matched_keyword<'a>(name: &str, config: &'a ExclusionConfig) -> Option<&'a str> {
+    let keywords = config.verticals.get(vertical(name))?;
+    let name = name.to_lowercase();
+    keywords.iter().find(|k| mentions(&name, &k.to_lowercase())).map(String::as_str)
+}
+
+impl ExclusionReport {
+    // Excluded at all, and whether as damaged
+    fn check(&mut self, product: &Product, config: &ExclusionConfig) -> (bool, bool) {
+        let damaged = matched_damage(&product.name, product.grade.as_deref(), config);
+        match damaged.or_else(|| matched_keyword(&product.name, config)) {
+            Some(keyword) => {
+                *self.by_keyword.entry(keyword.to_string()).or_default() += 1;
+                (true, damaged.is_some())
+            }
+            None => (false, false),
+        }
+    }
+
+    // True (and counted) when a buy-side listing should be dropped
+    pub fn exclude_buy(&mut self, product: &Product, config: &ExclusionConfig) -> bool {
+        let (excluded, damaged) = self.check(product, config);
+        self.buy_side += excluded as usize;
+        self.damaged_buy_side += damaged as usize;
+        excluded
+    }
+
+    // True (and counted) when a sold comp should be dropped
+    pub fn exclude_comp(&mut self, product: &Product, config: &ExclusionConfig) -> bool {
+        let (excluded, damaged) = self.check(product, config);
+        self.comps += excluded as usize;
+        self.damaged_comps += damaged as usize;
+        excluded
+    }
+
+    // Reprice lots per unit (or drop them, per [lots]); lots without a
+    // readable quantity are always dropped
+    pub fn apply_lots(&mut self, products: Vec<Product>, config: &LotConfig) -> Vec<Product> {
+        products
+            .into_iter()
+            .filter_map(|mut product| match parse_lot(&product.name) {
+                None => Some(product),
+                Some(Lot::Quantity(quantity)) if config.mode == LotMode::PerUnit => {
+                    let price = product.amount()?;
+                    product.price = Some(price.scale(1.0 / quantity as f64));
+                    product.lot_quantity = Some(quantity);
+                    self.lots_per_unit += 1;
+                    Some(product)
+                }
+                Some(_) => {
+                    self.lots_excluded += 1;
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn display(&self) {
+        if self.lots_excluded + self.lots_per_unit > 0 {
+            println!("\n📦 Lots: {} dropped, {} priced per unit", self.lots_excluded, self.lots_per_unit);
+        }
+        if self.buy_side + self.comps == 0 {
+            return;
+        }
+
+        println!(
+            "\n🚫 Excluded {} buy-side listings and {} sold comps as accessories or damaged ({} / {} damaged)",
+            self.buy_side, self.comps, self.damaged_buy_side, self.damaged_comps
+        );
+        let mut keywords: Vec<_> = self.by_keyword.iter().collect();
+        keywords.sort_by(|a, b| b.1.cmp(a.1));
+        for (keyword, count) in keywords.iter().take(8) {
+            println!("   \"{}\": {}", keyword, count);
+        }
+    }
+}
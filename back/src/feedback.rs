@@ -0,0 +1,142 @@
+// Matcher feedback. An opportunity marked as a bad match (`back bad-match
+// <buy-url>` or POST /api/feedback/bad-match) is stored with the comps it was
+// matched against in feedback.jsonl, and those (buy listing, comp) pairs are
+// never matched again in later runs.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use crate::{ArbitrageOpportunity, Product, load_frontend_data, storage};
+
+pub const FEEDBACK_FILE: &str = "feedback.jsonl";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BadComp {
+    pub title: String,
+    pub url: String,
+}
+
+// A negative example: this buy listing is not the same item as these comps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BadMatch {
+    pub marked_at: DateTime<Local>,
+    pub buy_name: String,
+    pub buy_url: String,
+    pub buy_source: String,
+    pub comps: Vec<BadComp>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+fn normalize(title: &str) -> String {
+    title.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn load_bad_matches() -> Vec<BadMatch> {
+    let Ok(content) = fs::read_to_string(storage::profile_path(FEEDBACK_FILE)) else {
+        return Vec::new();
+    };
+    content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()
+}
+
+// Why an opportunity couldn't be marked; the API maps each to its own status
+#[derive(Debug)]
+pub enum BadMatchError {
+    // No export to look the opportunity up in yet
+    NoScraperData,
+    UnknownBuyUrl(String),
+    Write(String),
+}
+
+impl fmt::Display for BadMatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BadMatchError::NoScraperData => write!(f, "No scraper data found, run the scraper first"),
+            BadMatchError::UnknownBuyUrl(url) => write!(f, "No opportunity with buy URL {} in the latest run", url),
+            BadMatchError::Write(e) => write!(f, "Failed to write {}: {}", FEEDBACK_FILE, e),
+        }
+    }
+}
+
+// Look the opportunity up in the latest export and store it as a bad match
+pub fn mark_bad_match(buy_url: &str, reason: Option<String>) -> Result<BadMatch, BadMatchError> {
+    let data = load_frontend_data().ok_or(BadMatchError::NoScraperData)?;
+    let opportunity: &ArbitrageOpportunity = data
+        .arbitrage_opportunities
+        .iter()
+        .chain(data.needs_review.iter())
+        .find(|o| o.buy_url == buy_url)
+        .ok_or_else(|| BadMatchError::UnknownBuyUrl(buy_url.to_string()))?;
+
+    // Exports from before comps were recorded only have the sample URLs
+    let comps = if opportunity.comps.is_empty() {
+        opportunity
+            .sample_ebay_urls
+            .iter()
+            .map(|url| BadComp { title: String::new(), url: url.clone() })
+            .collect()
+    } else {
+        opportunity
+            .comps
+            .iter()
+            .map(|c| BadComp { title: c.title.clone(), url: c.url.clone() })
+            .collect()
+    };
+    let bad_match = BadMatch {
+        marked_at: Local::now(),
+        buy_name: opportunity.buy_product_name.clone(),
+        buy_url: opportunity.buy_url.clone(),
+        buy_source: opportunity.buy_source.clone(),
+        comps,
+        reason,
+    };
+
+    let line = serde_json::to_string(&bad_match).map_err(|e| BadMatchError::Write(e.to_string()))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(storage::profile_path(FEEDBACK_FILE))
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|e| BadMatchError::Write(e.to_string()))?;
+    Ok(bad_match)
+}
+
+// (buy, comp) pairs that must not be matched, by URL and by normalized title
+// so a relisted item with the same title stays suppressed
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    pairs: HashSet<(String, String)>,
+}
+
+impl Suppressions {
+    pub fn load() -> Suppressions {
+        let mut pairs = HashSet::new();
+        for bad_match in load_bad_matches() {
+            for comp in &bad_match.comps {
+                pairs.insert((bad_match.buy_url.clone(), comp.url.clone()));
+                if !comp.title.is_empty() {
+                    pairs.insert((normalize(&bad_match.buy_name), normalize(&comp.title)));
+                }
+            }
+        }
+        Suppressions { pairs }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn suppresses(&self, buy: &Product, comp_url: &str, comp_title: &str) -> bool {
+        self.pairs.contains(&(buy.url.clone(), comp_url.to_string()))
+            || self.pairs.contains(&(normalize(&buy.name), normalize(comp_title)))
+    }
+}
+
+pub fn print_bad_match(bad_match: &BadMatch) {
+    println!("🙅 Marked as bad match: {} ({})", bad_match.buy_name, bad_match.buy_source);
+    println!("   {} comps will no longer be matched to this listing", bad_match.comps.len());
+}
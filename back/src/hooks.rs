@@ -0,0 +1,113 @@
+// Post-processing hooks - run a user-supplied command after each run so exports
+// can be uploaded or post-processed without modifying the scraper itself
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::config::HooksConfig;
+use crate::digest::Digest;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+
+// Summary stats passed to hooks as M3_* environment variables
+pub struct RunSummary {
+    pub run_id: u32,
+    pub timestamp: String,
+    pub total_newegg: usize,
+    pub total_swappa: usize,
+    pub total_ebay_sold: usize,
+    pub total_mercari: usize,
+    pub total_mercari_sold: usize,
+    pub total_storefront: usize,
+    pub new_products: usize,
+    pub opportunities: usize,
+    pub needs_review: usize,
+    pub apple_refurb_drops: usize,
+    pub best_profit: Option<Money>,
+    pub digest: Option<Digest>,
+    pub baseline_regressions: usize,
+    pub tracking_alerts: usize,
+    pub notifications: usize,
+}
+
+impl RunSummary {
+    fn env_vars(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("M3_RUN_ID", self.run_id.to_string()),
+            ("M3_RUN_TIMESTAMP", self.timestamp.clone()),
+            ("M3_TOTAL_NEWEGG", self.total_newegg.to_string()),
+            ("M3_TOTAL_SWAPPA", self.total_swappa.to_string()),
+            ("M3_TOTAL_EBAY_SOLD", self.total_ebay_sold.to_string()),
+            ("M3_TOTAL_MERCARI", self.total_mercari.to_string()),
+            ("M3_TOTAL_MERCARI_SOLD", self.total_mercari_sold.to_string()),
+            ("M3_TOTAL_STOREFRONT", self.total_storefront.to_string()),
+            ("M3_NEW_PRODUCTS", self.new_products.to_string()),
+            ("M3_OPPORTUNITIES", self.opportunities.to_string()),
+            ("M3_NEEDS_REVIEW", self.needs_review.to_string()),
+            ("M3_APPLE_REFURB_DROPS", self.apple_refurb_drops.to_string()),
+            ("M3_BEST_PROFIT", self.best_profit.map(|p| format!("{:.2}", p.amount())).unwrap_or_default()),
+            ("M3_DIGEST_PATH", self.digest.as_ref().map(|d| d.path.clone()).unwrap_or_default()),
+            // Chart PNGs the page links by file name, separated like PATH
+            ("M3_DIGEST_CHARTS", self.digest.as_ref().and_then(|d| std::env::join_paths(&d.charts).ok()).and_then(|p| p.into_string().ok()).unwrap_or_default()),
+            ("M3_BASELINE_REGRESSIONS", self.baseline_regressions.to_string()),
+            ("M3_TRACKING_ALERTS", self.tracking_alerts.to_string()),
+            ("M3_NOTIFICATIONS", self.notifications.to_string()),
+        ]
+    }
+}
+
+// Run the configured post-run command (if any) with the export path as its last
+// argument. Failures are logged and never abort the scrape loop.
+pub async fn run_post_run_hook(hooks: &HooksConfig, export_path: &str, summary: &RunSummary) {
+    let Some((program, args)) = hooks.post_run.split_first() else {
+        return;
+    };
+
+    // Hand the hook an absolute path so it doesn't depend on our working directory
+    let export_path = Path::new(export_path)
+        .canonicalize()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| export_path.to_string());
+
+    println!("\n🪝 Running post-run hook: {}", program);
+
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .arg(&export_path)
+        .env("M3_EXPORT_PATH", &export_path)
+        .envs(summary.env_vars())
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    let child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            report(format!("Failed to start post-run hook {}: {}", program, e));
+            return;
+        }
+    };
+
+    match timeout(Duration::from_secs(hooks.timeout_secs), child.wait_with_output()).await {
+        Ok(Ok(output)) if output.status.success() => {
+            println!("  ✓ Post-run hook finished");
+        }
+        Ok(Ok(output)) => {
+            report(format!("Post-run hook exited with {}", output.status));
+        }
+        Ok(Err(e)) => {
+            report(format!("Post-run hook failed: {}", e));
+        }
+        Err(_) => {
+            report(format!("Post-run hook timed out after {}s and was killed", hooks.timeout_secs));
+        }
+    }
+}
+
+fn report(message: String) {
+    eprintln!("  ❌ {}", message);
+    error_journal::record(ErrorCategory::Hook, "Post-run hook", None, &message, None);
+}
@@ -0,0 +1,212 @@
+// Phones actually bought, tracked until they're sold. Every run values the
+// ones still held against that run's sold comps, the same matching as
+// opportunities, and reports unrealized P&L net of selling fees plus how long
+// each has been held, flagging stale stock that may need a price cut.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::attributes::ProductAttributes;
+use crate::cli::{InventoryArgs, InventoryCommand};
+use crate::config::Config;
+use crate::match_overrides::MatchOverrides;
+use crate::money::Money;
+use crate::storage::{profile_path, write_json};
+use crate::{CompStats, Product, load_frontend_data, match_comps};
+
+pub const INVENTORY_FILE: &str = "inventory.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemStatus {
+    #[default]
+    Bought,
+    Sold,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryItem {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    pub cost: Money,
+    pub bought_at: DateTime<Local>,
+    #[serde(default)]
+    pub status: ItemStatus,
+    #[serde(default)]
+    pub sold_price: Option<Money>,
+    #[serde(default)]
+    pub sold_at: Option<DateTime<Local>>,
+}
+
+// Current market value of one held item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Valuation {
+    pub id: u32,
+    pub name: String,
+    pub cost: Money,
+    pub days_held: i64,
+    pub comp_count: usize,
+    // Sold price per [comps] price_basis; None without enough comps
+    pub market_value: Option<Money>,
+    // Market value after [fees], minus cost
+    pub unrealized_pnl: Option<Money>,
+    // Held for [inventory] stale_days or more
+    pub stale: bool,
+}
+
+pub fn load_inventory() -> Vec<InventoryItem> {
+    match fs::read_to_string(profile_path(INVENTORY_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+// Title of a listing from the latest export, so `inventory add <url>` needs no name
+fn name_from_latest_run(url: &str) -> Option<String> {
+    let data = load_frontend_data()?;
+    let opportunity = data
+        .arbitrage_opportunities
+        .iter()
+        .chain(data.needs_review.iter())
+        .find(|o| o.buy_url == url)
+        .map(|o| o.buy_product_name.clone());
+    opportunity.or_else(|| {
+        data.swappa_products
+            .iter()
+            .chain(data.mercari_products.iter())
+            .chain(data.storefront_products.iter())
+            .chain(data.newegg_products.iter())
+            .find(|p| p.url == url)
+            .map(|p| p.name.clone())
+    })
+}
+
+// Value every held item against this run's sold comps
+pub fn value_inventory(sold_comps: &[Product], overrides: &MatchOverrides, config: &Config) -> Vec<Valuation> {
+    let now = Local::now();
+    load_inventory()
+        .into_iter()
+        .filter(|item| item.status == ItemStatus::Bought)
+        .map(|item| {
+            let product = Product {
+                name: item.name.clone(),
+                price: Some(item.cost),
+                url: item.url.clone().unwrap_or_default(),
+                source: "Inventory".to_string(),
+                sold_date: None,
+                grade: None,
+                warranty: None,
+                expires_at: None,
+                region: None,
+                currency: None,
+                attributes: ProductAttributes::parse(&item.name),
+                listed_at: None,
+                lot_quantity: None,
+            };
+            let thresholds = config.thresholds_for(product.attributes.model.as_deref());
+            let stats = CompStats::from_comps(
+                match_comps(&product, &product.attributes, sold_comps, overrides, config, thresholds),
+                &config.comps,
+            );
+            // Same bar as opportunities: a single sale is no market value
+            let market_value = (stats.comps.len() >= 2).then(|| stats.sold_price(config.comps.price_basis));
+            let days_held = (now - item.bought_at).num_days();
+            Valuation {
+                id: item.id,
+                name: item.name,
+                cost: item.cost,
+                days_held,
+                comp_count: stats.comps.len(),
+                market_value,
+                unrealized_pnl: market_value.map(|value| value - config.fees.total(value) - item.cost),
+                stale: days_held >= config.inventory.stale_days,
+            }
+        })
+        .collect()
+}
+
+pub fn display_valuations(valuations: &[Valuation]) {
+    if valuations.is_empty() {
+        return;
+    }
+    println!("\n📦 INVENTORY - {} held", valuations.len());
+    println!("{}", "-".repeat(60));
+    let mut total_pnl = Money::default();
+    for valuation in valuations {
+        let stale = if valuation.stale { " 🧊 stale, consider a price cut" } else { "" };
+        match (valuation.market_value, valuation.unrealized_pnl) {
+            (Some(value), Some(pnl)) => {
+                total_pnl += pnl;
+                println!(
+                    "  #{} {} - cost {}, worth {} ({} comps), {:+} unrealized, {} days held{}",
+                    valuation.id, valuation.name, valuation.cost, value, valuation.comp_count, pnl, valuation.days_held, stale
+                );
+            }
+            _ => println!(
+                "  #{} {} - cost {}, not enough comps to value, {} days held{}",
+                valuation.id, valuation.name, valuation.cost, valuation.days_held, stale
+            ),
+        }
+    }
+    println!("  Total unrealized P&L: {:+}", total_pnl);
+}
+
+fn print_item(item: &InventoryItem) {
+    let status = match (item.status, item.sold_price) {
+        (ItemStatus::Sold, Some(price)) => format!("sold for {} ({:+})", price, price - item.cost),
+        (ItemStatus::Sold, None) => "sold".to_string(),
+        (ItemStatus::Bought, _) => format!("held {} days", (Local::now() - item.bought_at).num_days()),
+    };
+    println!("📦 #{} {} - bought {} on {}, {}", item.id, item.name, item.cost, item.bought_at.format("%Y-%m-%d"), status);
+    if let Some(url) = &item.url {
+        println!("   🔗 {}", url);
+    }
+}
+
+// `back inventory add|sold|list`
+pub fn run(args: &InventoryArgs) -> Result<(), String> {
+    let mut items = load_inventory();
+    match &args.command {
+        InventoryCommand::Add(add) => {
+            let name = match (&add.name, &add.url) {
+                (Some(name), _) => name.clone(),
+                (None, Some(url)) => name_from_latest_run(url)
+                    .ok_or_else(|| format!("{} is not in the latest run, pass --name", url))?,
+                (None, None) => return Err("Pass a listing URL or --name".to_string()),
+            };
+            let item = InventoryItem {
+                id: items.iter().map(|i| i.id).max().unwrap_or(0) + 1,
+                name,
+                url: add.url.clone(),
+                cost: Money::usd(add.cost),
+                bought_at: Local::now(),
+                status: ItemStatus::Bought,
+                sold_price: None,
+                sold_at: None,
+            };
+            print_item(&item);
+            items.push(item);
+        }
+        InventoryCommand::Sold(sold) => {
+            let item = items
+                .iter_mut()
+                .find(|i| i.id == sold.id)
+                .ok_or_else(|| format!("No inventory item #{}", sold.id))?;
+            item.status = ItemStatus::Sold;
+            item.sold_price = Some(Money::usd(sold.price));
+            item.sold_at = Some(Local::now());
+            print_item(item);
+        }
+        InventoryCommand::List => {
+            if items.is_empty() {
+                println!("No items in {}", profile_path(INVENTORY_FILE));
+            }
+            items.iter().for_each(print_item);
+            return Ok(());
+        }
+    }
+    write_json(&profile_path(INVENTORY_FILE), &items)
+}
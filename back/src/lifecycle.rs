@@ -0,0 +1,250 @@
+// Listing lifecycles on the sources we buy from (Swappa, Newegg). Every
+// listing is followed across runs from the first run it shows up in; once it
+// has been missing from [lifecycle] missing_runs scrapes of its source in a
+// row it has ended, and its page is re-fetched to tell a sale from a
+// removal. Pages that give no verdict (blocked, redirected to search) are
+// inferred: a used listing that vanishes has usually sold, a retailer's has
+// been pulled. Days on market run from the listing date when the source
+// states one, else from the first run that saw it, so the sell-through
+// report shows how fast each source actually moves phones.
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use crate::config::{LifecycleConfig, PolitenessConfig};
+use crate::error_journal::{self, ErrorCategory};
+use crate::tracking::ListingStatus;
+use crate::money::Money;
+use crate::{Product, stats, storage, verify};
+
+const LIFECYCLE_FILE: &str = "lifecycle.json";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+// Sources selling new/refurbished stock rather than one person's phone
+const RETAIL_SOURCES: [&str; 1] = ["Newegg"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LifecycleStatus {
+    #[default]
+    Active,
+    Sold,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListingLifecycle {
+    pub url: String,
+    pub source: String,
+    pub name: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    // When the source says it was listed
+    #[serde(default)]
+    pub listed_at: Option<String>,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub first_price: Option<Money>,
+    pub last_price: Option<Money>,
+    pub status: LifecycleStatus,
+    // The listing page confirmed the status, rather than it being inferred
+    #[serde(default)]
+    pub confirmed: bool,
+    #[serde(default)]
+    pub ended_at: Option<String>,
+    // Listed (or first seen) until last seen, set once the listing ends
+    #[serde(default)]
+    pub days_on_market: Option<f64>,
+    // Scrapes of its source in a row that didn't have it
+    #[serde(default)]
+    pub missed_runs: u32,
+}
+
+// Ended listings of one source over the retention window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SellThrough {
+    pub source: String,
+    pub active: usize,
+    pub sold: usize,
+    pub removed: usize,
+    // Sold as a share of ended listings
+    pub sell_through_percent: Option<f64>,
+    pub median_days_to_sell: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LifecycleReport {
+    pub sources: Vec<SellThrough>,
+    // Listings found to have ended this run
+    pub ended: Vec<ListingLifecycle>,
+}
+
+fn load() -> Vec<ListingLifecycle> {
+    match fs::read_to_string(storage::profile_path(LIFECYCLE_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_time(text: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(text, TIMESTAMP_FORMAT).ok()
+}
+
+impl ListingLifecycle {
+    fn new(product: &Product, now: &str) -> ListingLifecycle {
+        let price = product.amount();
+        ListingLifecycle {
+            url: product.url.clone(),
+            source: product.source.clone(),
+            name: product.name.clone(),
+            model: product.attributes.model.clone(),
+            listed_at: product.listed_at.clone(),
+            first_seen: now.to_string(),
+            last_seen: now.to_string(),
+            first_price: price,
+            last_price: price,
+            status: LifecycleStatus::Active,
+            confirmed: false,
+            ended_at: None,
+            days_on_market: None,
+            missed_runs: 0,
+        }
+    }
+
+    fn end(&mut self, status: LifecycleStatus, confirmed: bool, now: &str) {
+        self.status = status;
+        self.confirmed = confirmed;
+        self.ended_at = Some(now.to_string());
+        let start = self.listed_at.as_deref().and_then(parse_time).or_else(|| parse_time(&self.first_seen));
+        if let (Some(start), Some(end)) = (start, parse_time(&self.last_seen)) {
+            self.days_on_market = Some(((end - start).num_minutes() as f64 / 1440.0).max(0.0));
+        }
+    }
+
+    // What a vanished listing most likely did when its page doesn't say
+    fn inferred_status(&self) -> LifecycleStatus {
+        if RETAIL_SOURCES.contains(&self.source.as_str()) { LifecycleStatus::Removed } else { LifecycleStatus::Sold }
+    }
+}
+
+fn sell_through(listings: &[ListingLifecycle]) -> Vec<SellThrough> {
+    let mut by_source: BTreeMap<&str, (SellThrough, Vec<f64>)> = BTreeMap::new();
+    for listing in listings {
+        let (entry, days) = by_source.entry(&listing.source).or_default();
+        match listing.status {
+            LifecycleStatus::Active => entry.active += 1,
+            LifecycleStatus::Sold => {
+                entry.sold += 1;
+                days.extend(listing.days_on_market);
+            }
+            LifecycleStatus::Removed => entry.removed += 1,
+        }
+    }
+    by_source
+        .into_iter()
+        .map(|(source, (mut entry, days))| {
+            entry.source = source.to_string();
+            let ended = entry.sold + entry.removed;
+            entry.sell_through_percent = (ended > 0).then(|| entry.sold as f64 / ended as f64 * 100.0);
+            entry.median_days_to_sell = (!days.is_empty()).then(|| stats::median(&days));
+            entry
+        })
+        .collect()
+}
+
+// Update lifecycles from the sources scraped this run (name, all its
+// listings); sources that failed or weren't due must be left out, or all of
+// their listings would look gone
+pub async fn update(
+    scraped: &[(&str, &[Product])],
+    config: &LifecycleConfig,
+    politeness: &PolitenessConfig,
+    client: &reqwest::Client,
+) -> LifecycleReport {
+    if !config.enabled {
+        return LifecycleReport::default();
+    }
+    let now = Local::now().naive_local();
+    let now_text = now.format(TIMESTAMP_FORMAT).to_string();
+    let mut listings = load();
+    let mut by_url: HashMap<String, usize> = listings.iter().enumerate().map(|(i, l)| (l.url.clone(), i)).collect();
+
+    let mut vanished = Vec::new();
+    for (source, products) in scraped {
+        let present: HashMap<&str, &Product> = products.iter().map(|p| (p.url.as_str(), p)).collect();
+        for (url, product) in &present {
+            match by_url.get(*url) {
+                // A listing that ended and came back is followed as a new one
+                Some(&i) if listings[i].status == LifecycleStatus::Active => {
+                    let listing = &mut listings[i];
+                    listing.last_seen = now_text.clone();
+                    listing.last_price = product.amount().or(listing.last_price);
+                    listing.missed_runs = 0;
+                }
+                Some(&i) => listings[i] = ListingLifecycle::new(product, &now_text),
+                None => {
+                    by_url.insert(url.to_string(), listings.len());
+                    listings.push(ListingLifecycle::new(product, &now_text));
+                }
+            }
+        }
+        for (i, listing) in listings.iter_mut().enumerate() {
+            if listing.status != LifecycleStatus::Active || listing.source != *source || present.contains_key(listing.url.as_str()) {
+                continue;
+            }
+            listing.missed_runs += 1;
+            if listing.missed_runs >= config.missing_runs.max(1) {
+                vanished.push(i);
+            }
+        }
+    }
+
+    let statuses = if config.verify && !vanished.is_empty() {
+        println!("\n🪦 Checking {} listings gone from their source...", vanished.len());
+        verify::check_all(client, politeness, vanished.iter().map(|&i| listings[i].url.clone()).collect()).await
+    } else {
+        BTreeMap::new()
+    };
+    let mut ended = Vec::new();
+    for i in vanished {
+        let listing = &mut listings[i];
+        match statuses.get(&listing.url) {
+            Some(ListingStatus::Sold) => listing.end(LifecycleStatus::Sold, true, &now_text),
+            Some(ListingStatus::Ended | ListingStatus::Gone) => listing.end(LifecycleStatus::Removed, true, &now_text),
+            // Still up, it just dropped out of the scraped pages
+            Some(ListingStatus::Active) => {
+                listing.missed_runs = 0;
+                continue;
+            }
+            _ => {
+                let status = listing.inferred_status();
+                listing.end(status, false, &now_text);
+            }
+        }
+        ended.push(listing.clone());
+    }
+
+    let cutoff = now - TimeDelta::days(config.retention_days);
+    listings.retain(|l| l.status == LifecycleStatus::Active || l.ended_at.as_deref().and_then(parse_time).is_none_or(|at| at >= cutoff));
+    if let Err(e) = storage::write_json(&storage::profile_path(LIFECYCLE_FILE), &listings) {
+        eprintln!("❌ {}", e);
+        error_journal::record(ErrorCategory::Storage, "Lifecycle", None, &e, None);
+    }
+    LifecycleReport { sources: sell_through(&listings), ended }
+}
+
+pub fn display_lifecycle(report: &LifecycleReport) {
+    if report.sources.is_empty() {
+        return;
+    }
+    println!("\n⏳ SELL-THROUGH ({} listings ended this run):", report.ended.len());
+    for source in &report.sources {
+        let rate = source.sell_through_percent.map(|p| format!("{:.0}%", p)).unwrap_or_else(|| "-".to_string());
+        let days = source.median_days_to_sell.map(|d| format!("{:.1} days", d)).unwrap_or_else(|| "-".to_string());
+        println!(
+            "   {:<12} {} active, {} sold, {} removed - sell-through {}, median {} to sell",
+            source.source, source.active, source.sold, source.removed, rate, days
+        );
+    }
+}
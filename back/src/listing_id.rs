@@ -0,0 +1,107 @@
+// Listing identity for deduplication. Scrapers used to collapse listings by
+// title, which silently dropped inventory: Swappa and eBay are full of
+// distinct phones listed as "Apple iPhone 13 128GB Unlocked". A listing is
+// now identified by the marketplace's own item ID or listing code in its URL
+// (eBay item number, Swappa listing code, Newegg item number, Mercari item
+// ID), so tracking parameters and slugs don't matter. Elsewhere the URL
+// without its query identifies the listing, unless several listings share it
+// (a search or device page stood in for a missing link), in which case their
+// title and price tell them apart.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::Product;
+
+// (host contains, path segment the ID comes after, ID prefix)
+const ITEM_PATHS: [(&str, &str, &str); 4] = [
+    ("ebay.", "itm", "ebay"),
+    ("swappa.com", "listing", "swappa"),
+    ("newegg.", "p", "newegg"),
+    ("mercari.", "item", "mercari"),
+];
+
+fn host_and_path(url: &str) -> (String, &str) {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    match without_scheme.split_once('/') {
+        Some((host, path)) if url.contains("://") => (host.to_lowercase(), path),
+        _ => (String::new(), without_scheme),
+    }
+}
+
+fn is_code(segment: &str) -> bool {
+    segment.len() >= 4 && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') && segment.chars().any(|c| c.is_ascii_digit())
+}
+
+// "ebay:123456789012", "swappa:LAAA11111", ... for a marketplace item URL
+pub fn item_id(url: &str) -> Option<String> {
+    let (host, path) = host_and_path(url.trim());
+    // Newegg also links items as ?Item=N82E16875...
+    if host.contains("newegg.")
+        && let Some((_, query)) = path.split_once('?')
+        && let Some(item) = query.split('&').find_map(|pair| pair.split_once('=').filter(|(k, _)| k.eq_ignore_ascii_case("item")).map(|(_, v)| v))
+        && is_code(item)
+    {
+        return Some(format!("newegg:{}", item.to_uppercase()));
+    }
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    for (host_part, marker, prefix) in ITEM_PATHS {
+        if !host.contains(host_part) {
+            continue;
+        }
+        let Some(at) = segments.iter().position(|s| s.eq_ignore_ascii_case(marker)) else {
+            continue;
+        };
+        let after = &segments[at + 1..];
+        let id = if prefix == "ebay" {
+            // /itm/123456789012 or /itm/title-slug/123456789012
+            after.iter().rev().find(|s| s.len() >= 9 && s.chars().all(|c| c.is_ascii_digit()))
+        } else {
+            // /listing/view/LAAA11111, /p/75U-0016-00123, /item/m12345678901
+            after.iter().find(|s| is_code(s))
+        };
+        if let Some(id) = id {
+            return Some(format!("{}:{}", prefix, id.to_uppercase()));
+        }
+    }
+    None
+}
+
+// The URL without query, fragment or trailing slash, host lowercased
+fn clean_url(url: &str) -> String {
+    let url = url.trim().split(['?', '#']).next().unwrap_or_default().trim_end_matches('/');
+    let (host, path) = host_and_path(url);
+    if host.is_empty() { path.to_string() } else { format!("{}/{}", host, path) }
+}
+
+// Identity of each listing in a batch, in order
+pub fn listing_ids(products: &[Product]) -> Vec<String> {
+    // URLs carried by listings with different titles or prices aren't listing links
+    let mut variants: HashMap<String, HashSet<(&str, String)>> = HashMap::new();
+    for product in products {
+        if item_id(&product.url).is_none() {
+            variants.entry(clean_url(&product.url)).or_default().insert((product.name.trim(), product.price_label()));
+        }
+    }
+    products
+        .iter()
+        .map(|product| {
+            if let Some(id) = item_id(&product.url) {
+                return format!("{}|{}", product.source, id);
+            }
+            let url = clean_url(&product.url);
+            if url.is_empty() || variants.get(&url).is_some_and(|v| v.len() > 1) {
+                format!("{}|{}|{}|{}", product.source, url, product.name.trim(), product.price_label())
+            } else {
+                format!("{}|{}", product.source, url)
+            }
+        })
+        .collect()
+}
+
+// Drop repeats of the same listing, keeping the first of each in order
+pub fn dedup_listings(products: Vec<Product>) -> Vec<Product> {
+    let ids = listing_ids(&products);
+    let mut seen = HashSet::new();
+    products.into_iter().zip(ids).filter(|(_, id)| seen.insert(id.clone())).map(|(product, _)| product).collect()
+}
@@ -0,0 +1,169 @@
+// Non-English listing terms mapped to the English words the matcher and the
+// attribute parsers look for. eBay.de/.fr/.es/.it comps say "entsperrt",
+// "débloqué" or "wie neu" and size storage in "Go"/"To", so without this they
+// share only the model keyword with English listings and score near zero.
+
+// (term, English) per locale; multi-word terms come before the single words
+// they contain so "wie neu" wins over "neu"
+const DE: &[(&str, &str)] = &[
+    ("ohne simlock", "unlocked"),
+    ("simlockfrei", "unlocked"),
+    ("entsperrt", "unlocked"),
+    ("wie neu", "like new"),
+    ("neuwertig", "like new"),
+    ("sehr gut", "excellent"),
+    ("hervorragend", "excellent"),
+    ("gut", "good"),
+    ("akzeptabel", "acceptable"),
+    ("generalüberholt", "refurbished"),
+    ("generalueberholt", "refurbished"),
+    ("neu", "new"),
+    ("gebraucht", "pre-owned"),
+    ("für bastler", "for parts"),
+    ("defekt", "broken"),
+    ("gesprungen", "cracked"),
+    ("schwarz", "black"),
+    ("weiß", "white"),
+    ("weiss", "white"),
+    ("blau", "blue"),
+    ("grün", "green"),
+    ("gruen", "green"),
+    ("rot", "red"),
+    ("lila", "purple"),
+    ("silber", "silver"),
+    ("graphit", "graphite"),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("tout opérateur", "unlocked"),
+    ("tout operateur", "unlocked"),
+    ("débloqué", "unlocked"),
+    ("debloque", "unlocked"),
+    ("comme neuf", "like new"),
+    ("très bon état", "excellent"),
+    ("tres bon etat", "excellent"),
+    ("parfait état", "excellent"),
+    ("parfait etat", "excellent"),
+    ("bon état", "good"),
+    ("bon etat", "good"),
+    ("état correct", "fair"),
+    ("etat correct", "fair"),
+    ("reconditionné", "refurbished"),
+    ("reconditionne", "refurbished"),
+    ("neuf", "new"),
+    ("occasion", "pre-owned"),
+    ("pour pièces", "for parts"),
+    ("pour pieces", "for parts"),
+    ("cassé", "broken"),
+    ("fissuré", "cracked"),
+    ("noir", "black"),
+    ("blanc", "white"),
+    ("bleu", "blue"),
+    ("vert", "green"),
+    ("rouge", "red"),
+    ("argent", "silver"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("desbloqueado", "unlocked"),
+    ("libre", "unlocked"),
+    ("como nuevo", "like new"),
+    ("muy bueno", "excellent"),
+    ("buen estado", "good"),
+    ("reacondicionado", "refurbished"),
+    ("nuevo", "new"),
+    ("usado", "pre-owned"),
+    ("para piezas", "for parts"),
+    ("roto", "broken"),
+    ("negro", "black"),
+    ("blanco", "white"),
+    ("azul", "blue"),
+    ("verde", "green"),
+    ("rojo", "red"),
+    ("morado", "purple"),
+    ("plata", "silver"),
+];
+
+const IT: &[(&str, &str)] = &[
+    ("sbloccato", "unlocked"),
+    ("come nuovo", "like new"),
+    ("ottime condizioni", "excellent"),
+    ("buone condizioni", "good"),
+    ("ricondizionato", "refurbished"),
+    ("nuovo", "new"),
+    ("usato", "pre-owned"),
+    ("per ricambi", "for parts"),
+    ("rotto", "broken"),
+    ("nero", "black"),
+    ("bianco", "white"),
+    ("viola", "purple"),
+    ("argento", "silver"),
+];
+
+const LOCALES: [(&str, &[(&str, &str)]); 4] = [("de", DE), ("fr", FR), ("es", ES), ("it", IT)];
+
+// French storage units, only after a storage size: "128 Go", "1To"
+const UNITS: [(&str, &str, &[u32]); 2] = [("go", "gb", &[32, 64, 128, 256, 512]), ("to", "tb", &[1, 2])];
+
+fn is_word_char(c: Option<char>) -> bool {
+    c.is_some_and(char::is_alphanumeric)
+}
+
+// Replace whole-word occurrences of `from` in lowercased `text`
+fn replace_word(text: &str, from: &str, to: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(from) {
+        let end = start + from.len();
+        let before = if start == 0 { result.chars().last() } else { rest[..start].chars().last() };
+        let after = rest[end..].chars().next();
+        result.push_str(&rest[..start]);
+        result.push_str(if is_word_char(before) || is_word_char(after) { from } else { to });
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// Storage size right before a unit at `text[..start]`, with at most one space between
+fn size_before(text: &str) -> Option<u32> {
+    let text = text.strip_suffix(' ').unwrap_or(text);
+    let digits = text.len() - text.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let number = &text[text.len() - digits..];
+    let preceded_by_word = is_word_char(text[..text.len() - digits].chars().last());
+    if digits == 0 || preceded_by_word {
+        return None;
+    }
+    number.parse().ok()
+}
+
+fn replace_units(text: &str) -> String {
+    let mut result = text.to_string();
+    for (from, to, sizes) in UNITS {
+        let mut replaced = String::with_capacity(result.len());
+        let mut rest = result.as_str();
+        while let Some(start) = rest.find(from) {
+            let end = start + from.len();
+            let is_size = size_before(&rest[..start]).is_some_and(|n| sizes.contains(&n));
+            replaced.push_str(&rest[..start]);
+            replaced.push_str(if is_size && !is_word_char(rest[end..].chars().next()) { to } else { from });
+            rest = &rest[end..];
+        }
+        replaced.push_str(rest);
+        result = replaced;
+    }
+    result
+}
+
+// Lowercased title with every locale's terms in English
+pub fn to_english(name: &str) -> String {
+    let mut text = name.to_lowercase();
+    for (_, terms) in LOCALES {
+        for (term, english) in terms {
+            if text.contains(term) {
+                text = replace_word(&text, term, english);
+            }
+        }
+    }
+    replace_units(&text)
+}
@@ -2,48 +2,375 @@ use reqwest::header::USER_AGENT;
 use scraper::{Html, Selector};
 use std::time::Duration;
 use std::fs;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, LazyLock, Mutex};
 use tokio::time::sleep;
 use thirtyfour::prelude::*;
 use serde::{Serialize, Deserialize};
-use chrono::{Local, DateTime};
+use chrono::Local;
+
+mod analysis;
+mod api;
+mod attributes;
+mod baseline;
+mod behavior;
+mod browser;
+mod capture;
+mod catalog;
+mod checkpoint;
+mod chromedriver;
+mod cli;
+mod comp_providers;
+mod config;
+mod deal_sheet;
+mod digest;
+mod ebay_auctions;
+mod ebay_sell;
+mod ebay_shipping;
+mod error_journal;
+mod exclusions;
+mod feedback;
+mod hooks;
+mod inventory;
+mod lifecycle;
+mod listing_id;
+mod locale;
+mod maintenance;
+mod match_overrides;
+mod mercari;
+mod money;
+mod notes;
+mod notify;
+mod page_driver;
+mod politeness;
+mod price;
+mod price_drops;
+mod progress;
+mod proxy;
+mod qr;
+mod rate_limit;
+mod redact;
+mod replay;
+mod retry;
+mod scripting;
+mod sessions;
+mod shutdown;
+mod similarity;
+mod single_source;
+mod sources;
+mod stats;
+mod storage;
+mod supervisor;
+mod swappa_json;
+mod tracking;
+mod tradein;
+mod verify;
+
+// Parser and extraction-script golden tests, Selenium scraper tests, price
+// parser property tests and unit suites, kept under tests/ with their fixtures.
+// They're compiled into the binary's tests rather than built as integration
+// tests, since this is a binary crate and none of what they cover is public.
+#[cfg(test)]
+#[path = "../tests/parsers/mod.rs"]
+mod parser_tests;
+#[cfg(test)]
+#[path = "../tests/selenium/mod.rs"]
+mod selenium_tests;
+#[cfg(test)]
+#[path = "../tests/js_extraction/mod.rs"]
+mod js_extraction_tests;
+#[cfg(test)]
+#[path = "../tests/price/mod.rs"]
+mod price_tests;
+#[cfg(test)]
+#[path = "../tests/similarity/mod.rs"]
+mod similarity_tests;
+#[cfg(test)]
+#[path = "../tests/stats/mod.rs"]
+mod stats_tests;
+#[cfg(test)]
+#[path = "../tests/chromedriver/mod.rs"]
+mod chromedriver_tests;
+#[cfg(test)]
+#[path = "../tests/exclusions/mod.rs"]
+mod exclusions_tests;
+#[cfg(test)]
+#[path = "../tests/lots/mod.rs"]
+mod lots_tests;
+
+use analysis::color::ColorPremium;
+use attributes::{Carrier, Condition, ProductAttributes, Storage};
+use clap::Parser;
+use config::{BrowserConfig, CaptureConfig, ColorConfig, CompsConfig, Config, EbayConfig, NeweggConfig, NeweggMode, OutlierMethod, PriceBasis, SwappaConfig, Thresholds};
+use error_journal::ErrorCategory;
+use match_overrides::{MatchOverrides, Verdict};
+use money::{Currency, Money};
+use page_driver::PageDriver;
+use politeness::Politeness;
+use replay::ReplayMode;
+use similarity::similarity_score;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Product {
     name: String,
-    price: String,
+    // None when the listing shows no readable price ("Please contact")
+    #[serde(default, deserialize_with = "price::deserialize_listing")]
+    price: Option<Money>,
     url: String,
     source: String,
+    // Sale date as shown on the sold listing, when the platform shows one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sold_date: Option<String>,
+    // Seller's condition grade and warranty, for sources that state them separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grade: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    warranty: Option<String>,
+    // End of a time-limited deal (local "%Y-%m-%d %H:%M:%S"), e.g. Woot offers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+    // Market the listing comes from (e.g. "CA", "UK") and the currency it was
+    // priced in there; `price` itself is converted to USD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    region: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    currency: Option<String>,
+    // Brand, model, storage, carrier, condition and color: set by sources
+    // that know them, the rest parsed as products are collected (deduplicate_products)
+    #[serde(flatten)]
+    attributes: ProductAttributes,
+    // When the listing went up (local "%Y-%m-%d %H:%M:%S"), for sources that show it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    listed_at: Option<String>,
+    // Units in a lot listing whose `price` was divided down to one unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lot_quantity: Option<u32>,
 }
 
+impl Product {
+    // `price` once converted to USD, the currency every comparison is made in
+    fn amount(&self) -> Option<Money> {
+        self.price.filter(|price| price.currency() == Currency::Usd)
+    }
+
+    // For console output
+    fn price_label(&self) -> String {
+        self.price.map_or_else(|| "Price not found".to_string(), |price| price.to_string())
+    }
+
+    // Parse the attributes its source didn't set from the title and grade
+    fn fill_attributes(&mut self) {
+        self.attributes.fill(&self.name, self.grade.as_deref());
+    }
+}
+
+// A sold listing matched as a comp for an opportunity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CompListing {
+    title: String,
+    // Adjusted to the buy listing's condition, storage and carrier when they differ
+    price: Money,
+    sold_date: Option<String>,
+    url: String,
+    source: String,
+    similarity: f64,
+    #[serde(default)]
+    condition: Option<Condition>,
+    #[serde(default)]
+    storage_gb: Option<Storage>,
+    #[serde(default)]
+    carrier: Option<Carrier>,
+    // Sold price before the condition/storage/carrier adjustments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unadjusted_price: Option<Money>,
+}
+
+// Sold comps for one canonical device + condition and their price stats,
+// computed once per comp pool and reused for every buy listing of that device
+#[derive(Debug, Clone)]
+struct CompStats {
+    comps: Vec<CompListing>,
+    // Outlier sold prices left out of the stats below
+    rejected: Vec<CompListing>,
+    sources: Vec<String>,
+    avg: Money,
+    median: Money,
+    p25: Money,
+    p75: Money,
+    min: Money,
+    max: Money,
+}
+
+impl CompStats {
+    fn from_comps(comps: Vec<CompListing>, config: &CompsConfig) -> CompStats {
+        let all_prices: Vec<f64> = comps.iter().map(|c| c.price.amount()).collect();
+        let all_sorted = stats::sorted(&all_prices);
+        let (low, high) = match config.outlier_method {
+            _ if comps.len() < config.outlier_min_comps => (f64::NEG_INFINITY, f64::INFINITY),
+            OutlierMethod::Iqr => stats::iqr_bounds(&all_sorted, config.iqr_factor),
+            OutlierMethod::Mad => stats::mad_bounds(&all_sorted, config.mad_threshold),
+            OutlierMethod::None => (f64::NEG_INFINITY, f64::INFINITY),
+        };
+        let (comps, rejected): (Vec<CompListing>, Vec<CompListing>) =
+            comps.into_iter().partition(|c| c.price.amount() >= low && c.price.amount() <= high);
+
+        let mut sources: Vec<String> = Vec::new();
+        for comp in &comps {
+            if !sources.contains(&comp.source) {
+                sources.push(comp.source.clone());
+            }
+        }
+        let mut prices: Vec<Money> = comps.iter().map(|c| c.price).collect();
+        prices.sort();
+        CompStats {
+            avg: Money::mean(&prices).unwrap_or_default(),
+            median: Money::quantile(&prices, 0.5).unwrap_or_default(),
+            p25: Money::quantile(&prices, 0.25).unwrap_or_default(),
+            p75: Money::quantile(&prices, 0.75).unwrap_or_default(),
+            min: prices.first().copied().unwrap_or_default(),
+            max: prices.last().copied().unwrap_or_default(),
+            sources,
+            comps,
+            rejected,
+        }
+    }
+
+    // The sold price profit is measured against
+    fn sold_price(&self, basis: PriceBasis) -> Money {
+        match basis {
+            PriceBasis::Median => self.median,
+            PriceBasis::Mean => self.avg,
+        }
+    }
+}
+
+// Cache key: canonical device, condition bucket, carrier, and the buy color when comps must match it
+type CompKey = (String, &'static str, Option<Carrier>, Option<String>);
+
 // Structure for arbitrage data export
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ArbitrageOpportunity {
     buy_product_name: String,
     buy_source: String,
-    buy_price: f64,
+    buy_price: Money,
     buy_url: String,
-    ebay_avg_sold_price: f64,
+    // Currency of every amount in the opportunity, its comps included
+    #[serde(default)]
+    currency: Currency,
+    ebay_avg_sold_price: Money,
+    // Sold price quartiles; profit is measured against the median unless
+    // [comps] price_basis = "mean"
+    #[serde(default)]
+    ebay_median_sold_price: Money,
+    #[serde(default)]
+    ebay_p25_sold_price: Money,
+    #[serde(default)]
+    ebay_p75_sold_price: Money,
     ebay_sold_count: usize,
     ebay_price_range: String,
-    potential_profit: f64,
+    // Net of [fees]; gross_profit is the plain spread
+    potential_profit: Money,
+    #[serde(default)]
+    gross_profit: Money,
     margin_percent: f64,
     sample_ebay_urls: Vec<String>,
+    // Platforms the sold comps came from (eBay, Mercari)
+    #[serde(default)]
+    comp_sources: Vec<String>,
+    // Score from the user's filter script, if it defines score()
+    #[serde(default)]
+    script_score: Option<f64>,
+    #[serde(default)]
+    color: Option<String>,
+    // Condition of the buy listing; comps of other conditions are excluded or
+    // price-adjusted per [condition]
+    #[serde(default)]
+    buy_condition: Option<Condition>,
+    // Sold average, adjusted for the color premium when enabled
+    #[serde(default)]
+    suggested_list_price: Option<Money>,
+    // Every sold comp behind the average, for auditing the match
+    #[serde(default)]
+    comps: Vec<CompListing>,
+    // Sold comps dropped as price outliers ([comps] outlier_method)
+    #[serde(default)]
+    rejected_comps: Vec<CompListing>,
+    // When the buy-side deal ends, for time-limited deals
+    #[serde(default)]
+    expires_at: Option<String>,
+    // Lowest trade-in quote for the device at its condition: the worst-case exit price
+    #[serde(default)]
+    trade_in_floor: Option<tradein::TradeInFloor>,
+    // User notes/tags on the buy listing (notes.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    annotation: Option<notes::Annotation>,
+    // How well the comps matched the buy listing, with [export] verbose
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    match_audit: Option<MatchAudit>,
+    // Profit/margin change since the listing's last run as an opportunity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trend: Option<analysis::trend::Trend>,
+    // Price cut on the buy listing since the run it was last seen in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    price_drop: Option<price_drops::PriceDrop>,
+    // Whether the buy listing was still up when `back verify` last checked it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification: Option<verify::Verification>,
+}
+
+// Similarity of the sold comps behind an opportunity's price to its buy listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MatchAudit {
+    mean_similarity: f64,
+    // The weakest comp that still counted
+    min_similarity: f64,
+    // What comps had to clear for this model ([thresholds] or a per-model
+    // override); comps forced by match_overrides.toml may score below it
+    required_similarity: f64,
+}
+
+impl MatchAudit {
+    fn of(comps: &[CompListing], required_similarity: f64) -> Option<MatchAudit> {
+        if comps.is_empty() {
+            return None;
+        }
+        Some(MatchAudit {
+            mean_similarity: comps.iter().map(|c| c.similarity).sum::<f64>() / comps.len() as f64,
+            min_similarity: comps.iter().map(|c| c.similarity).fold(f64::INFINITY, f64::min),
+            required_similarity,
+        })
+    }
+}
+
+// An opportunity on an eBay auction that ends soon; the buy price is the current bid plus shipping
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AuctionOpportunity {
+    #[serde(flatten)]
+    opportunity: ArbitrageOpportunity,
+    ends_at: String,
+    // Minutes left when scraped; the frontend counts down from ends_at
+    minutes_left: i64,
+    bid_count: u32,
 }
 
 // Structure for individual product with eBay comparison
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ProductWithComparison {
     name: String,
-    price: String,
-    price_numeric: f64,
+    #[serde(default, deserialize_with = "price::deserialize_listing")]
+    price: Option<Money>,
+    price_numeric: Money,
     url: String,
     source: String,
-    ebay_avg_sold: Option<f64>,
+    ebay_avg_sold: Option<Money>,
     ebay_sold_count: Option<usize>,
     ebay_price_range: Option<String>,
-    potential_profit: Option<f64>,
+    potential_profit: Option<Money>,
+    #[serde(default)]
+    gross_profit: Option<Money>,
     margin_percent: Option<f64>,
+    #[serde(default)]
+    color: Option<String>,
 }
 
 // Structure for a single run snapshot
@@ -53,7 +380,14 @@ struct RunSnapshot {
     timestamp: String,
     swappa_products: Vec<ProductWithComparison>,
     newegg_products: Vec<ProductWithComparison>,
+    #[serde(default)]
+    mercari_products: Vec<ProductWithComparison>,
+    // Certified-used storefronts (Gazelle, Back Market, ...)
+    #[serde(default)]
+    storefront_products: Vec<ProductWithComparison>,
     ebay_sold_products: Vec<Product>,
+    #[serde(default)]
+    mercari_sold_products: Vec<Product>,
     arbitrage_opportunities: Vec<ArbitrageOpportunity>,
     total_swappa: usize,
     total_newegg: usize,
@@ -62,27 +396,105 @@ struct RunSnapshot {
 }
 
 // Structure for frontend data export with history
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct ScraperData {
     last_updated: String,
     run_count: u32,
+    // Written by a checkpoint mid-run: listings of the sources scraped so far
+    // are this run's, everything else is still the last full run's
+    #[serde(default)]
+    partial: bool,
     newegg_products: Vec<Product>,
     swappa_products: Vec<Product>,
     ebay_products: Vec<Product>,
+    #[serde(default)]
+    mercari_products: Vec<Product>,
+    #[serde(default)]
+    mercari_sold_products: Vec<Product>,
+    #[serde(default)]
+    storefront_products: Vec<Product>,
     arbitrage_opportunities: Vec<ArbitrageOpportunity>,
+    // Opportunities over [review] max_margin_percent, likely mismatches or scams
+    #[serde(default)]
+    needs_review: Vec<ArbitrageOpportunity>,
+    // Buy sources ranked against each other per device/condition
+    #[serde(default)]
+    source_comparisons: Vec<analysis::cross_source::SourceComparison>,
+    // Sold-price premium/discount per color within a device
+    #[serde(default)]
+    color_premiums: Vec<ColorPremium>,
+    // Decluttr's fixed price table and how it compares to eBay sold prices
+    #[serde(default)]
+    decluttr_prices: Vec<sources::decluttr::DecluttrPrice>,
+    #[serde(default)]
+    reference_prices: Vec<analysis::reference::ReferencePrice>,
+    // Sold prices by listing end day/hour
+    #[serde(default)]
+    timing_report: analysis::timing::TimingReport,
+    // eBay auctions ending soon with a bid under the sold average, soonest first
+    #[serde(default)]
+    auction_opportunities: Vec<AuctionOpportunity>,
+    // Listings dropped as accessories this run
+    #[serde(default)]
+    exclusions: exclusions::ExclusionReport,
+    // Apple refurb stock per model and arrivals since the previous run
+    #[serde(default)]
+    apple_refurb: sources::apple_refurb::RefurbWatch,
+    // Trade-in quotes per provider and device
+    #[serde(default)]
+    trade_in_quotes: Vec<tradein::TradeInQuote>,
+    // Per-model Swappa ask / eBay sold median for every recorded run
+    #[serde(default)]
+    price_history: Vec<analysis::price_history::PriceHistoryPoint>,
+    // This run's sold medians against their baseline across devices, 100 = steady
+    #[serde(default)]
+    market_heat: Option<analysis::heat::MarketHeat>,
+    // Greedy pick of opportunities within [bankroll] budget
+    #[serde(default)]
+    portfolio: analysis::portfolio::Portfolio,
+    // Held inventory valued against this run's comps
+    #[serde(default)]
+    inventory: Vec<inventory::Valuation>,
+    // User notes/tags for listings in this run, by URL
+    #[serde(default)]
+    annotations: BTreeMap<String, notes::Annotation>,
+    // Listings added with `back track` and what changed on them this run
+    #[serde(default)]
+    tracked_listings: Vec<tracking::TrackedItem>,
+    #[serde(default)]
+    tracking_alerts: Vec<tracking::TrackingAlert>,
+    // Buy-side listings cheaper than in the run they were last seen, biggest drop first
+    #[serde(default)]
+    price_drops: Vec<price_drops::PriceDrop>,
+    // Sell-through and days on market of Swappa/Newegg listings
+    #[serde(default)]
+    listing_lifecycle: lifecycle::LifecycleReport,
+    // Requests this run sent each domain and how long they waited for the rate limiter
+    #[serde(default)]
+    crawl_audit: Vec<rate_limit::DomainAudit>,
     total_tracked: usize,
-    // New: Run history
+    // Run history now lives in runs/ (see storage.rs); only read to migrate old files
+    #[serde(default, skip_serializing)]
     run_history: Vec<RunSnapshot>,
 }
 
 // File paths
 const SEEN_PRODUCTS_FILE: &str = "seen_products.json";
 const FRONTEND_DATA_FILE: &str = "scraper_data.json";
-const MAX_HISTORY_RUNS: usize = 20; // Keep last 20 runs
+// "source|device" -> consecutive runs its buy-side page came up empty
+const EMPTY_STREAKS_FILE: &str = "empty_streaks.json";
+
+static EMPTY_STREAKS: LazyLock<Mutex<HashMap<String, u32>>> = LazyLock::new(|| {
+    let streaks = fs::read_to_string(storage::profile_path(EMPTY_STREAKS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    Mutex::new(streaks)
+});
 
 // Load existing frontend data (for history)
 fn load_frontend_data() -> Option<ScraperData> {
-    match fs::read_to_string(FRONTEND_DATA_FILE) {
+    match fs::read_to_string(storage::profile_path(FRONTEND_DATA_FILE)) {
         Ok(content) => serde_json::from_str(&content).ok(),
         Err(_) => None,
     }
@@ -90,58 +502,53 @@ fn load_frontend_data() -> Option<ScraperData> {
 
 // Save data for frontend
 fn save_frontend_data(data: &ScraperData) {
+    let path = storage::profile_path(FRONTEND_DATA_FILE);
     if let Ok(json) = serde_json::to_string_pretty(data) {
-        if let Err(e) = fs::write(FRONTEND_DATA_FILE, json) {
+        if let Err(e) = fs::write(&path, json) {
             eprintln!("Failed to write frontend data: {}", e);
+            error_journal::record(ErrorCategory::Storage, "Export", Some(&path), &e, None);
         } else {
-            println!("📁 Frontend data saved to {}", FRONTEND_DATA_FILE);
+            println!("📁 Frontend data saved to {}", path);
         }
     }
 }
 
-// Create products with eBay comparison data
+// Create products with sold comp comparison data (eBay + Mercari sold)
 fn create_products_with_comparison(
-    swappa_products: &[Product],
-    ebay_sold: &[Product],
+    products: &[Product],
+    sold_comps: &[Product],
+    overrides: &MatchOverrides,
+    config: &Config,
 ) -> Vec<ProductWithComparison> {
     let mut products_with_comp = Vec::new();
     
-    for product in swappa_products {
-        let price_numeric = parse_price(&product.price).unwrap_or(0.0);
-        
-        // Find similar eBay sold items
-        let mut similar_sold: Vec<f64> = Vec::new();
-        for sold in ebay_sold {
-            let score = similarity_score(product, sold);
-            if score >= 40.0 {
-                if let Some(sold_price) = parse_price(&sold.price) {
-                    if sold_price > 50.0 {
-                        similar_sold.push(sold_price);
-                    }
-                }
-            }
-        }
+    for product in products {
+        let price_numeric = product.amount().unwrap_or_default();
+        let attributes = &product.attributes;
+        let thresholds = config.thresholds_for(attributes.model.as_deref());
         
-        let (ebay_avg, ebay_count, ebay_range, profit, margin) = if similar_sold.len() >= 2 {
-            let avg = similar_sold.iter().sum::<f64>() / similar_sold.len() as f64;
-            let min = similar_sold.iter().cloned().fold(f64::INFINITY, f64::min);
-            let max = similar_sold.iter().cloned().fold(0.0, f64::max);
-            let profit = avg - price_numeric;
-            let margin = if price_numeric > 0.0 { (profit / price_numeric) * 100.0 } else { 0.0 };
+        // Same comps, outlier rejection and price basis as the opportunity search
+        let stats = CompStats::from_comps(match_comps(product, attributes, sold_comps, overrides, config, thresholds), &config.comps);
+        let (ebay_avg, ebay_count, ebay_range, gross_profit, profit, margin) = if stats.comps.len() >= 2 {
+            let sold_price = stats.sold_price(config.comps.price_basis);
+            let gross_profit = sold_price - price_numeric;
+            let profit = gross_profit - config.fees.total(sold_price);
+            let margin = profit.percent_of(price_numeric);
             (
-                Some(avg),
-                Some(similar_sold.len()),
-                Some(format!("${:.2} - ${:.2}", min, max)),
+                Some(stats.avg),
+                Some(stats.comps.len()),
+                Some(format!("{} - {}", stats.min, stats.max)),
+                Some(gross_profit),
                 Some(profit),
                 Some(margin),
             )
         } else {
-            (None, None, None, None, None)
+            (None, None, None, None, None, None)
         };
         
         products_with_comp.push(ProductWithComparison {
             name: product.name.clone(),
-            price: product.price.clone(),
+            price: product.price,
             price_numeric,
             url: product.url.clone(),
             source: product.source.clone(),
@@ -149,15 +556,18 @@ fn create_products_with_comparison(
             ebay_sold_count: ebay_count,
             ebay_price_range: ebay_range,
             potential_profit: profit,
+            gross_profit,
             margin_percent: margin,
+            color: attributes.color.clone(),
         });
     }
     
     products_with_comp
 }
 
-// Convert PriceComparison to ArbitrageOpportunity for frontend export
-fn convert_to_arbitrage_opportunities(comparisons: &[PriceComparison]) -> Vec<ArbitrageOpportunity> {
+// Convert PriceComparison to ArbitrageOpportunity for frontend export; `verbose`
+// attaches the match audit
+fn convert_to_arbitrage_opportunities(comparisons: &[PriceComparison], verbose: bool) -> Vec<ArbitrageOpportunity> {
     let mut opportunities = Vec::new();
     
     for comparison in comparisons {
@@ -166,20 +576,71 @@ fn convert_to_arbitrage_opportunities(comparisons: &[PriceComparison]) -> Vec<Ar
             buy_source: comparison.source_product.source.clone(),
             buy_price: comparison.source_price,
             buy_url: comparison.source_product.url.clone(),
+            currency: comparison.source_price.currency(),
             ebay_avg_sold_price: comparison.ebay_avg_sold,
+            ebay_median_sold_price: comparison.ebay_median_sold,
+            ebay_p25_sold_price: comparison.ebay_p25_sold,
+            ebay_p75_sold_price: comparison.ebay_p75_sold,
             ebay_sold_count: comparison.ebay_sold_count,
-            ebay_price_range: format!("${:.2} - ${:.2}", comparison.ebay_min_price, comparison.ebay_max_price),
+            ebay_price_range: format!("{} - {}", comparison.ebay_min_price, comparison.ebay_max_price),
             potential_profit: comparison.profit,
+            gross_profit: comparison.gross_profit,
             margin_percent: comparison.margin_percent,
             sample_ebay_urls: comparison.sample_ebay_urls.clone(),
+            comp_sources: comparison.comp_sources.clone(),
+            script_score: comparison.script_score,
+            color: comparison.color.clone(),
+            buy_condition: comparison.source_product.attributes.condition,
+            suggested_list_price: Some(comparison.suggested_list_price),
+            comps: comparison.comps.clone(),
+            rejected_comps: comparison.rejected_comps.clone(),
+            expires_at: comparison.source_product.expires_at.clone(),
+            trade_in_floor: None,
+            annotation: None,
+            match_audit: if verbose { MatchAudit::of(&comparison.comps, comparison.required_similarity) } else { None },
+            trend: None,
+            price_drop: None,
+            verification: None,
         });
     }
     
-    // Sort by profit descending
-    opportunities.sort_by(|a, b| b.potential_profit.partial_cmp(&a.potential_profit).unwrap_or(std::cmp::Ordering::Equal));
+    // Sort by profit descending, unless a filter script already ranked them
+    if opportunities.iter().all(|o| o.script_score.is_none()) {
+        opportunities.sort_by_key(|o| std::cmp::Reverse(o.potential_profit));
+    }
     opportunities
 }
 
+// Track consecutive runs with zero listings per buy-side device page, across
+// restarts, and warn once a device has come up empty `alert_runs` times in a row
+fn check_empty_devices(source: &str, device_counts: &HashMap<String, usize>, alert_runs: u32) {
+    if device_counts.is_empty() {
+        return;
+    }
+    let mut devices: Vec<_> = device_counts.iter().collect();
+    devices.sort_by(|a, b| a.0.cmp(b.0));
+    
+    let mut empty_streaks = EMPTY_STREAKS.lock().unwrap();
+    for (device, count) in devices {
+        let key = format!("{}|{}", source, device);
+        if *count > 0 {
+            empty_streaks.remove(&key);
+            continue;
+        }
+        
+        let streak = empty_streaks.entry(key).or_insert(0);
+        *streak += 1;
+        if *streak >= alert_runs {
+            println!("  ⚠️  {} {} has returned 0 listings for {} consecutive runs", source, device, streak);
+            println!("     (The page URL may have changed or the extractor no longer matches it)");
+        }
+    }
+    if let Err(e) = storage::write_json(&storage::profile_path(EMPTY_STREAKS_FILE), &*empty_streaks) {
+        eprintln!("  ⚠️  {}", e);
+        error_journal::record(ErrorCategory::Storage, source, Some(EMPTY_STREAKS_FILE), &e, None);
+    }
+}
+
 // Generate a unique key for a product (using URL as primary key for deduplication)
 fn product_key(product: &Product) -> String {
     // Use URL as the primary key - this ensures same listing isn't duplicated
@@ -188,25 +649,16 @@ fn product_key(product: &Product) -> String {
     format!("{}|{}", product.source, url_clean)
 }
 
-// Deduplicate products by URL
-fn deduplicate_products(products: Vec<Product>) -> Vec<Product> {
-    let mut seen_urls: HashSet<String> = HashSet::new();
-    let mut unique_products = Vec::new();
-    
-    for product in products {
-        let url_clean = product.url.split('?').next().unwrap_or(&product.url).to_string();
-        if !seen_urls.contains(&url_clean) {
-            seen_urls.insert(url_clean);
-            unique_products.push(product);
-        }
-    }
-    
-    unique_products
+// Deduplicate products by listing identity (listing_id.rs), filling in the
+// parsed condition, storage and carrier on the way
+fn deduplicate_products(mut products: Vec<Product>) -> Vec<Product> {
+    products.iter_mut().for_each(Product::fill_attributes);
+    listing_id::dedup_listings(products)
 }
 
 // Load seen products from JSON file
 fn load_seen_products() -> HashSet<String> {
-    match fs::read_to_string(SEEN_PRODUCTS_FILE) {
+    match fs::read_to_string(storage::profile_path(SEEN_PRODUCTS_FILE)) {
         Ok(content) => {
             serde_json::from_str(&content).unwrap_or_else(|_| HashSet::new())
         }
@@ -217,7 +669,7 @@ fn load_seen_products() -> HashSet<String> {
 // Save seen products to JSON file
 fn save_seen_products(seen: &HashSet<String>) {
     if let Ok(json) = serde_json::to_string_pretty(seen) {
-        let _ = fs::write(SEEN_PRODUCTS_FILE, json);
+        let _ = fs::write(storage::profile_path(SEEN_PRODUCTS_FILE), json);
     }
 }
 
@@ -236,202 +688,334 @@ fn filter_new_products(products: Vec<Product>, seen: &mut HashSet<String>) -> Ve
     new_products
 }
 
-// Parse price string to f64
-fn parse_price(price_str: &str) -> Option<f64> {
-    // Remove currency symbols, commas, and extra whitespace
-    let cleaned: String = price_str
-        .replace('$', "")
-        .replace(',', "")
-        .replace(" ", "")
-        .trim()
-        .chars()
-        .take_while(|c| c.is_digit(10) || *c == '.')
-        .collect();
-    
-    cleaned.parse::<f64>().ok()
+// USD amount of a listing price; ranges are priced at their midpoint
+// (price.rs). A price in another currency is None: it only compares once
+// localize_products has converted it.
+fn parse_price(price_str: &str) -> Option<Money> {
+    price::parse_money(price_str).filter(|price| price.currency() == Currency::Usd)
 }
 
-// Extract key product identifiers from name (model numbers, brand, etc.)
-fn extract_keywords(name: &str) -> Vec<String> {
-    let name_lower = name.to_lowercase();
-    
-    // Common phone models and keywords to match
-    let keywords: Vec<&str> = vec![
-        // iPhones
-        "iphone 16 pro max", "iphone 16 pro", "iphone 16", "iphone 16e",
-        "iphone 15 pro max", "iphone 15 pro", "iphone 15 plus", "iphone 15",
-        "iphone 14 pro max", "iphone 14 pro", "iphone 14 plus", "iphone 14",
-        "iphone 13 pro max", "iphone 13 pro", "iphone 13 mini", "iphone 13",
-        "iphone 12 pro max", "iphone 12 pro", "iphone 12 mini", "iphone 12",
-        "iphone se",
-        // Samsung
-        "galaxy s24 ultra", "galaxy s24+", "galaxy s24",
-        "galaxy s23 ultra", "galaxy s23+", "galaxy s23",
-        "galaxy z fold", "galaxy z flip",
-        "galaxy a54", "galaxy a34", "galaxy a14",
-        // Google Pixel
-        "pixel 9 pro xl", "pixel 9 pro", "pixel 9",
-        "pixel 8 pro", "pixel 8a", "pixel 8",
-        "pixel 7 pro", "pixel 7a", "pixel 7",
-        // Storage sizes
-        "128gb", "256gb", "512gb", "1tb",
-        // Conditions
-        "unlocked",
-    ];
-    
-    let mut found_keywords = Vec::new();
-    for kw in keywords {
-        if name_lower.contains(kw) {
-            found_keywords.push(kw.to_string());
-        }
-    }
-    
-    found_keywords
+#[derive(Debug, Clone)]
+struct PriceComparison {
+    product_name: String,
+    source_product: Product,
+    source_price: Money,
+    ebay_avg_sold: Money,
+    ebay_median_sold: Money,
+    ebay_p25_sold: Money,
+    ebay_p75_sold: Money,
+    ebay_sold_count: usize,
+    ebay_min_price: Money,
+    ebay_max_price: Money,
+    sample_ebay_urls: Vec<String>,
+    comp_sources: Vec<String>,
+    // Net of selling fees
+    profit: Money,
+    gross_profit: Money,
+    margin_percent: f64,
+    script_score: Option<f64>,
+    color: Option<String>,
+    suggested_list_price: Money,
+    comps: Vec<CompListing>,
+    rejected_comps: Vec<CompListing>,
+    // min_similarity the comps were matched at
+    required_similarity: f64,
 }
 
-// Calculate similarity score between two products
-fn similarity_score(p1: &Product, p2: &Product) -> f64 {
-    let kw1 = extract_keywords(&p1.name);
-    let kw2 = extract_keywords(&p2.name);
-    
-    if kw1.is_empty() || kw2.is_empty() {
-        return 0.0;
+// Time-limited deals that ended before this run's comparison. Timestamps are
+// local "%Y-%m-%d %H:%M:%S", so they order as strings.
+fn deal_expired(product: &Product, now: &str) -> bool {
+    product.expires_at.as_deref().is_some_and(|expires_at| expires_at < now)
+}
+
+// With color matching enabled, reject comps whose known color differs from
+// the buy item's known color. Unknown colors on either side always match.
+fn colors_compatible(buy: &ProductAttributes, sold: &Product, color_config: &ColorConfig) -> bool {
+    if !color_config.require_match {
+        return true;
     }
-    
-    let mut matches = 0;
-    for k in &kw1 {
-        if kw2.contains(k) {
-            matches += 1;
-        }
+    match (&buy.color, &sold.attributes.color) {
+        (Some(buy_color), Some(sold_color)) => buy_color == sold_color,
+        _ => true,
     }
-    
-    // Higher weight for phone model matches
-    let phone_models = ["iphone", "galaxy", "pixel"];
-    let mut model_match = false;
-    for model in phone_models {
-        let p1_has = p1.name.to_lowercase().contains(model);
-        let p2_has = p2.name.to_lowercase().contains(model);
-        if p1_has && p2_has {
-            model_match = true;
-            break;
+}
+
+// A sold price as comparable to a buy listing of `buy` condition/storage/
+// carrier tier. None when tiers must match and differ; otherwise the price is scaled
+// by the tiers' percent adjustments. Unknown tiers always compare as-is.
+fn tier_adjusted_price<T: PartialEq + Copy>(
+    buy: Option<T>,
+    sold: Option<T>,
+    price: Money,
+    require_match: bool,
+    adjustment: impl Fn(T) -> f64,
+) -> Option<Money> {
+    match (buy, sold) {
+        (Some(buy), Some(sold)) if buy != sold => {
+            if require_match {
+                return None;
+            }
+            Some(price.scale((1.0 + adjustment(buy) / 100.0) / (1.0 + adjustment(sold) / 100.0)))
         }
+        _ => Some(price),
     }
-    
-    if !model_match {
-        return 0.0;
+}
+
+// Sold price adjusted to the buy listing's condition, storage and carrier
+fn adjusted_comp_price(buy: &Product, sold: &Product, price: Money, config: &Config) -> Option<Money> {
+    let (buy, sold) = (&buy.attributes, &sold.attributes);
+    let price = tier_adjusted_price(buy.condition, sold.condition, price, config.condition.require_match, |c| {
+        config.condition.adjustment(c)
+    })?;
+    let price = tier_adjusted_price(buy.storage, sold.storage, price, config.storage.require_match, |storage| {
+        config.storage.adjustment(storage)
+    })?;
+    tier_adjusted_price(buy.carrier, sold.carrier, price, config.carrier.require_match, |c| {
+        config.carrier.adjustment(c)
+    })
+}
+
+// Category models have no model gate in similarity_score, so with
+// strict_model a comp must be the buy listing's model ("rtx 4080" is no comp
+// for "rtx 4090")
+fn same_model(attributes: &ProductAttributes, sold: &ProductAttributes, thresholds: Thresholds) -> bool {
+    match attributes.model.as_deref() {
+        Some(model) if thresholds.strict_model => sold.model.as_deref() == Some(model),
+        _ => true,
     }
-    
-    // Calculate score based on keyword matches
-    let max_keywords = kw1.len().max(kw2.len()) as f64;
-    (matches as f64 / max_keywords) * 100.0
 }
 
-#[derive(Debug, Clone)]
-struct PriceComparison {
-    product_name: String,
-    source_product: Product,
-    source_price: f64,
-    ebay_avg_sold: f64,
-    ebay_sold_count: usize,
-    ebay_min_price: f64,
-    ebay_max_price: f64,
-    sample_ebay_urls: Vec<String>,
-    profit: f64,
-    margin_percent: f64,
+// Sold comps similar to a buy listing (similarity >= min_similarity, compatible
+// color, over the price floor), priced for the buy listing's condition, storage and carrier.
+// match_overrides.toml is consulted first: forbidden pairs are skipped, forced
+// ones match without the similarity, model, color and price floor checks.
+fn match_comps(
+    buy_product: &Product,
+    attributes: &ProductAttributes,
+    sold_comps: &[Product],
+    overrides: &MatchOverrides,
+    config: &Config,
+    thresholds: Thresholds,
+) -> Vec<CompListing> {
+    let mut matched = Vec::new();
+    for sold_product in sold_comps {
+        let verdict = overrides.verdict_for(buy_product, sold_product);
+        if verdict == Some(Verdict::Forbid) {
+            continue;
+        }
+        let forced = verdict == Some(Verdict::Force);
+        let score = similarity_score(buy_product, sold_product, thresholds.fuzzy_weight);
+        if (forced
+            || (score >= thresholds.min_similarity
+                && same_model(attributes, &sold_product.attributes, thresholds)
+                && colors_compatible(attributes, sold_product, &config.color)))
+            && let Some(sold_price) = sold_product.amount()
+            && (forced || sold_price > Money::usd(thresholds.price_floor))  // Filter out accessories/parts
+            && let Some(price) = adjusted_comp_price(buy_product, sold_product, sold_price, config)
+        {
+            matched.push(CompListing {
+                title: sold_product.name.clone(),
+                price,
+                sold_date: sold_product.sold_date.clone(),
+                url: sold_product.url.clone(),
+                source: sold_product.source.clone(),
+                similarity: score,
+                condition: sold_product.attributes.condition,
+                storage_gb: sold_product.attributes.storage,
+                carrier: sold_product.attributes.carrier,
+                unadjusted_price: (price != sold_price).then_some(sold_price),
+            });
+        }
+    }
+    matched
 }
 
-// Find arbitrage opportunities by comparing Swappa prices to eBay SOLD averages
+// Find arbitrage opportunities by comparing buy-side prices (Swappa, Mercari active)
+// to the average of SOLD comps pooled across platforms (eBay, Mercari sold)
 fn find_arbitrage_opportunities(
-    _newegg: &[Product],  // Not using Newegg for comparison anymore
-    swappa: &[Product],
-    ebay_sold: &[Product],
+    buy_side: &[Product],
+    sold_comps: &[Product],
+    color_premiums: &[ColorPremium],
+    suppressions: &feedback::Suppressions,
+    overrides: &MatchOverrides,
+    config: &Config,
 ) -> Vec<PriceComparison> {
+    let (color_config, comps_config, fees) = (&config.color, &config.comps, &config.fees);
     let mut opportunities = Vec::new();
-    
-    // Only use Swappa as buy source
-    for buy_product in swappa {
-        if let Some(buy_price) = parse_price(&buy_product.price) {
-            if buy_price < 50.0 {
+    // Comp stats per canonical device + condition for this comp pool. Listings
+    // without a recognizable device still get their own comp search.
+    let mut comp_cache: HashMap<CompKey, CompStats> = HashMap::new();
+    let mut cache_hits = 0;
+    
+    for buy_product in buy_side {
+        if let Some(buy_price) = buy_product.amount() {
+            let attributes = &buy_product.attributes;
+            let thresholds = config.thresholds_for(attributes.model.as_deref());
+            if buy_price < Money::usd(thresholds.price_floor) {
                 continue; // Skip very low priced items
             }
             
-            // Find similar eBay SOLD items and calculate average
-            let mut similar_sold: Vec<(f64, String)> = Vec::new();
-            
-            for sold_product in ebay_sold {
-                let score = similarity_score(buy_product, sold_product);
-                if score >= 40.0 {  // Lower threshold since we're matching sold items
-                    if let Some(sold_price) = parse_price(&sold_product.price) {
-                        if sold_price > 50.0 {  // Filter out accessories/parts
-                            similar_sold.push((sold_price, sold_product.url.clone()));
-                        }
+            // Find similar SOLD items and calculate average. Listings named in
+            // match_overrides.toml get their own comp search.
+            let cache_key: Option<CompKey> = attributes.device_key().filter(|_| !overrides.applies_to(buy_product)).map(|device| {
+                let color = if color_config.require_match { attributes.color.clone() } else { None };
+                (device, analysis::cross_source::condition_bucket(buy_product), attributes.carrier, color)
+            });
+            let cached = match &cache_key {
+                Some(key) if comp_cache.contains_key(key) => {
+                    cache_hits += 1;
+                    comp_cache[key].clone()
+                }
+                _ => {
+                    let stats = CompStats::from_comps(match_comps(buy_product, attributes, sold_comps, overrides, config, thresholds), comps_config);
+                    if let Some(key) = cache_key {
+                        comp_cache.insert(key, stats.clone());
                     }
+                    stats
                 }
-            }
+            };
+            
+            // Pairs marked as bad matches are dropped per listing, after the shared lookup
+            let stats = if cached.comps.iter().chain(&cached.rejected).any(|c| suppressions.suppresses(buy_product, &c.url, &c.title)) {
+                CompStats::from_comps(
+                    cached.comps.into_iter()
+                        .chain(cached.rejected)
+                        .filter(|c| !suppressions.suppresses(buy_product, &c.url, &c.title))
+                        .collect(),
+                    comps_config,
+                )
+            } else {
+                cached
+            };
             
-            // Need at least 2 sold items to calculate meaningful average
-            if similar_sold.len() >= 2 {
-                let prices: Vec<f64> = similar_sold.iter().map(|(p, _)| *p).collect();
-                let avg_sold = prices.iter().sum::<f64>() / prices.len() as f64;
-                let min_price = prices.iter().cloned().fold(f64::INFINITY, f64::min);
-                let max_price = prices.iter().cloned().fold(0.0, f64::max);
+            // Need at least 2 sold items to calculate meaningful stats
+            if stats.comps.len() >= 2 {
+                let sold_price = stats.sold_price(comps_config.price_basis);
                 
-                // Calculate profit based on average sold price
-                let profit = avg_sold - buy_price;
-                let margin_percent = (profit / buy_price) * 100.0;
+                // Calculate profit based on the median (or average) sold price, after selling fees
+                let gross_profit = sold_price - buy_price;
+                let profit = gross_profit - fees.total(sold_price);
+                let margin_percent = profit.percent_of(buy_price);
                 
-                // Only include if there's meaningful profit (> 10%)
-                if margin_percent > 10.0 && profit > 20.0 {
-                    let sample_urls: Vec<String> = similar_sold.iter()
+                // Only include if there's meaningful profit ([thresholds], per-model overrides)
+                if margin_percent > thresholds.min_margin_percent && profit > Money::usd(thresholds.min_profit) {
+                    let sample_urls: Vec<String> = stats.comps.iter()
                         .take(3)
-                        .map(|(_, url)| url.clone())
+                        .map(|c| c.url.clone())
                         .collect();
                     
+                    // List at the sold price, nudged by the color premium if enabled
+                    let suggested_list_price = match analysis::color::premium_for(color_premiums, attributes) {
+                        Some(premium) if color_config.adjust_list_price => sold_price.scale(1.0 + premium / 100.0),
+                        _ => sold_price,
+                    };
+                    
                     opportunities.push(PriceComparison {
                         product_name: buy_product.name.clone(),
                         source_product: buy_product.clone(),
                         source_price: buy_price,
-                        ebay_avg_sold: avg_sold,
-                        ebay_sold_count: similar_sold.len(),
-                        ebay_min_price: min_price,
-                        ebay_max_price: max_price,
+                        ebay_avg_sold: stats.avg,
+                        ebay_median_sold: stats.median,
+                        ebay_p25_sold: stats.p25,
+                        ebay_p75_sold: stats.p75,
+                        ebay_sold_count: stats.comps.len(),
+                        ebay_min_price: stats.min,
+                        ebay_max_price: stats.max,
                         sample_ebay_urls: sample_urls,
+                        comp_sources: stats.sources,
                         profit,
+                        gross_profit,
                         margin_percent,
+                        script_score: None,
+                        color: attributes.color.clone(),
+                        suggested_list_price,
+                        comps: stats.comps,
+                        rejected_comps: stats.rejected,
+                        required_similarity: thresholds.min_similarity,
                     });
                 }
             }
         }
     }
     
+    if cache_hits > 0 {
+        println!("  ♻️  Comp stats reused for {} listings ({} device/condition groups)", cache_hits, comp_cache.len());
+    }
+    
     // Sort opportunities by profit descending
-    opportunities.sort_by(|a, b| {
-        b.profit.partial_cmp(&a.profit).unwrap_or(std::cmp::Ordering::Equal)
-    });
+    opportunities.sort_by_key(|o| std::cmp::Reverse(o.profit));
     
     opportunities
 }
 
-// Display arbitrage opportunities
-fn display_arbitrage_opportunities(opportunities: &[PriceComparison]) {
+// Display arbitrage opportunities with their trend since earlier runs and any
+// price drop on the listing; `verbose` lists every sold comp with its similarity
+fn display_arbitrage_opportunities(
+    opportunities: &[PriceComparison],
+    trade_in_quotes: &[tradein::TradeInQuote],
+    previous: &analysis::trend::PreviousValues,
+    price_drops: &[price_drops::PriceDrop],
+    verbose: bool,
+) {
     if opportunities.is_empty() {
         println!("\n  ℹ️  No arbitrage opportunities found this run");
-        println!("     (Need similar items sold on eBay to compare prices)");
+        println!("     (Need similar items sold on eBay or Mercari to compare prices)");
         return;
     }
     
     println!("\n📋 ARBITRAGE OPPORTUNITIES ({}):", opportunities.len());
-    println!("   Comparing Swappa prices to eBay SOLD averages\n");
+    println!("   Comparing Swappa/Mercari prices to eBay & Mercari SOLD averages\n");
     
     for (i, opp) in opportunities.iter().take(15).enumerate() {
         println!("{}. {}", i + 1, truncate_string(&opp.product_name, 60));
-        println!("   📥 BUY ON SWAPPA: ${:.2}", opp.source_price);
+        println!("   📥 BUY ON {}: {}", opp.source_product.source.to_uppercase(), opp.source_price);
         println!("      🔗 {}", opp.source_product.url);
-        println!("   📊 EBAY SOLD DATA ({} recent sales):", opp.ebay_sold_count);
-        println!("      Average: ${:.2}", opp.ebay_avg_sold);
-        println!("      Range: ${:.2} - ${:.2}", opp.ebay_min_price, opp.ebay_max_price);
-        println!("   💵 POTENTIAL PROFIT: ${:.2} ({:.1}% margin)", opp.profit, opp.margin_percent);
-        if !opp.sample_ebay_urls.is_empty() {
+        println!("   📊 SOLD DATA ({} recent sales from {}):", opp.ebay_sold_count, opp.comp_sources.join(", "));
+        println!("      Median: {} (P25 {} - P75 {}), average {}", opp.ebay_median_sold, opp.ebay_p25_sold, opp.ebay_p75_sold, opp.ebay_avg_sold);
+        println!("      Range: {} - {}", opp.ebay_min_price, opp.ebay_max_price);
+        if !opp.rejected_comps.is_empty() {
+            let prices: Vec<String> = opp.rejected_comps.iter().map(|c| c.price.to_string()).collect();
+            println!("      🚫 {} outlier comps left out: {}", opp.rejected_comps.len(), prices.join(", "));
+        }
+        let adjusted = opp.comps.iter().filter(|c| c.unadjusted_price.is_some()).count();
+        let tiers = &opp.source_product.attributes;
+        let condition = tiers.condition.map(Condition::label);
+        let storage = tiers.storage.map(Storage::label);
+        let carrier = tiers.carrier.map(Carrier::label);
+        if condition.is_some() || storage.is_some() || carrier.is_some() {
+            let tiers: Vec<String> = condition.map(String::from).into_iter().chain(storage).chain(carrier.map(String::from)).collect();
+            if adjusted > 0 {
+                println!("      🏷️  {} ({} comps price-adjusted from other conditions/storage/carriers)", tiers.join(", "), adjusted);
+            } else {
+                println!("      🏷️  {}", tiers.join(", "));
+            }
+        }
+        println!("   💵 POTENTIAL PROFIT: {} net ({:.1}% margin), {} before fees", opp.profit, opp.margin_percent, opp.gross_profit);
+        match previous.trend(&opp.source_product.url, opp.profit, opp.margin_percent) {
+            Some(trend) => println!("   📈 Trend: {}", trend.label()),
+            None => println!("   🆕 First run as an opportunity"),
+        }
+        if let Some(drop) = price_drops.iter().find(|d| d.url == opp.source_product.url) {
+            println!("   📉 {}", drop.label());
+        }
+        if let Some(color) = &opp.color {
+            println!("   🎨 Color: {} (suggested list {})", color, opp.suggested_list_price);
+        }
+        if let Some(floor) = tradein::floor_for(&opp.product_name, opp.source_product.attributes.condition, trade_in_quotes) {
+            println!("   🛟 Worst case: trade in to {} for {}", floor.provider, floor.price);
+        }
+        if let Some(expires_at) = &opp.source_product.expires_at {
+            println!("   ⏳ Deal ends {}", expires_at);
+        }
+        if let Some(score) = opp.script_score {
+            println!("   📜 Script score: {:.2}", score);
+        }
+        if verbose && let Some(audit) = MatchAudit::of(&opp.comps, opp.required_similarity) {
+            println!("   🎯 Match similarity {:.2} average, {:.2} weakest (needs {:.2}):", audit.mean_similarity, audit.min_similarity, audit.required_similarity);
+            for comp in &opp.comps {
+                println!("      {} ({:.2}) {}", comp.price, comp.similarity, comp.url);
+            }
+        } else if !opp.sample_ebay_urls.is_empty() {
             println!("   🔗 Sample sold listings:");
             for url in &opp.sample_ebay_urls {
                 println!("      {}", url);
@@ -441,18 +1025,72 @@ fn display_arbitrage_opportunities(opportunities: &[PriceComparison]) {
     }
 }
 
+fn display_needs_review(opportunities: &[PriceComparison], max_margin_percent: f64) {
+    if opportunities.is_empty() {
+        return;
+    }
+
+    println!("\n⚠️  NEEDS REVIEW ({} over {:.0}% margin, likely mismatches or scams):", opportunities.len(), max_margin_percent);
+    for opp in opportunities.iter().take(10) {
+        println!("   {:.0}% - {} {} on {} vs sold median {}",
+            opp.margin_percent, truncate_string(&opp.product_name, 50), opp.source_price, opp.source_product.source, opp.ebay_median_sold);
+        println!("      🔗 {}", opp.source_product.url);
+    }
+}
+
+// Match ending-soon auctions against sold comps and attach their countdown
+fn find_auction_opportunities(
+    auctions: &[ebay_auctions::Auction],
+    sold_comps: &[Product],
+    color_premiums: &[ColorPremium],
+    suppressions: &feedback::Suppressions,
+    overrides: &MatchOverrides,
+    config: &Config,
+) -> Vec<AuctionOpportunity> {
+    let products: Vec<Product> = auctions.iter().map(|a| a.product.clone()).collect();
+    let comparisons = find_arbitrage_opportunities(&products, sold_comps, color_premiums, suppressions, overrides, config);
+    let now = Local::now();
+
+    let mut opportunities: Vec<AuctionOpportunity> = convert_to_arbitrage_opportunities(&comparisons, config.export.verbose)
+        .into_iter()
+        .filter_map(|opportunity| {
+            let auction = auctions.iter().find(|a| a.product.url == opportunity.buy_url)?;
+            Some(AuctionOpportunity {
+                ends_at: auction.ends_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                minutes_left: (auction.ends_at - now).num_minutes(),
+                bid_count: auction.bid_count,
+                opportunity,
+            })
+        })
+        .collect();
+    opportunities.sort_by(|a, b| a.ends_at.cmp(&b.ends_at));
+    opportunities
+}
+
+fn display_auction_opportunities(opportunities: &[AuctionOpportunity]) {
+    println!("\n⏱️ AUCTIONS ENDING SOON ({} under sold average):", opportunities.len());
+    for auction in opportunities.iter().take(10) {
+        let opp = &auction.opportunity;
+        println!("\n   ⏳ {} min left ({} bids) - {}", auction.minutes_left, auction.bid_count, truncate_string(&opp.buy_product_name, 60));
+        println!("      Bid + shipping {} vs sold median {} → {} profit at current bid", opp.buy_price, opp.ebay_median_sold_price, opp.potential_profit);
+        println!("      🔗 {}", opp.buy_url);
+    }
+}
+
+// First max_len characters; listing names carry "–", "™" and emoji, so this
+// counts chars rather than bytes
 fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() > max_len {
-        format!("{}...", &s[..max_len])
-    } else {
-        s.to_string()
+    match s.char_indices().nth(max_len) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
     }
 }
 
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 struct ProductDetails {
     name: String,
-    price: String,
+    price: Option<Money>,
     url: String,
     source: String,
     description: String,
@@ -463,22 +1101,48 @@ struct ProductDetails {
 }
 
 async fn fetch_html(client: &reqwest::Client, url: &str) -> Option<String> {
-    let response = client
-        .get(url)
-        .header(USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .send()
-        .await;
+    fetch_html_within(client, url, None).await
+}
+
+// fetch_html, its retries drawn from a source's per-run budget (politeness.rs)
+async fn fetch_html_within(client: &reqwest::Client, url: &str, budget: Option<&retry::Budget>) -> Option<String> {
+    if let Some(html) = replay::lookup(url) {
+        return Some(html);
+    }
+    if replay::mode() == Some(ReplayMode::Offline) {
+        eprintln!("📼 Not recorded, skipping {}", url);
+        return None;
+    }
+    let response = retry::send_within(url, budget, || {
+        client
+            .get(url)
+            .header(USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+    })
+    .await;
 
     match response {
-        Ok(resp) => match resp.text().await {
-            Ok(text) => Some(text),
-            Err(e) => {
-                eprintln!("Failed to read response from {}: {}", url, e);
-                None
+        Ok(resp) => {
+            let status = resp.status();
+            match resp.text().await {
+                Ok(text) => {
+                    // Callers still get the body; error pages often explain themselves
+                    if !status.is_success() {
+                        error_journal::record(ErrorCategory::Http, &error_journal::source_for_url(url), Some(url), format!("HTTP {}", status), Some(&text));
+                    } else {
+                        replay::record(url, &text);
+                    }
+                    Some(text)
+                }
+                Err(e) => {
+                    eprintln!("Failed to read response from {}: {}", url, e);
+                    error_journal::record(ErrorCategory::Fetch, &error_journal::source_for_url(url), Some(url), &e, None);
+                    None
+                }
             }
-        },
+        }
         Err(e) => {
             eprintln!("Failed to fetch {}: {}", url, e);
+            error_journal::record(ErrorCategory::Fetch, &error_journal::source_for_url(url), Some(url), &e, None);
             None
         }
     }
@@ -537,9 +1201,18 @@ fn scrape_newegg_products(html: &str, base_url: &str) -> Vec<Product> {
 
                     products.push(Product {
                         name: name.trim().to_string(),
-                        price: if price.is_empty() { "Price not found".to_string() } else { price.trim().to_string() },
+                        price: price::parse_money(&price),
                         url: full_url,
                         source: "Newegg".to_string(),
+                        sold_date: None,
+                        grade: None,
+                        warranty: None,
+                        expires_at: None,
+                        region: None,
+                        currency: None,
+                        attributes: ProductAttributes::default(),
+                        listed_at: None,
+                        lot_quantity: None,
                     });
                 }
             }
@@ -550,12 +1223,12 @@ fn scrape_newegg_products(html: &str, base_url: &str) -> Vec<Product> {
         }
     }
 
-    // Deduplicate by name
     products.sort_by(|a, b| a.name.cmp(&b.name));
-    products.dedup_by(|a, b| a.name == b.name);
-    products
+    listing_id::dedup_listings(products)
 }
 
+// Static-HTML Swappa parser, superseded by the Selenium path in scrape_swappa
+#[allow(dead_code)]
 fn scrape_swappa_products(html: &str, base_url: &str) -> Vec<Product> {
     let document = Html::parse_document(html);
     let mut products = Vec::new();
@@ -592,7 +1265,7 @@ fn scrape_swappa_products(html: &str, base_url: &str) -> Vec<Product> {
                 ]);
 
                 // Get URL - first check if the item itself is a link
-                let mut url = if let Some(href) = item.value().attr("href") {
+                let url = if let Some(href) = item.value().attr("href") {
                     href.to_string()
                 } else {
                     // Otherwise look for child links
@@ -614,9 +1287,18 @@ fn scrape_swappa_products(html: &str, base_url: &str) -> Vec<Product> {
 
                     products.push(Product {
                         name: name.trim().to_string(),
-                        price: if price.is_empty() { "Price not found".to_string() } else { price.trim().to_string() },
+                        price: price::parse_money(&price),
                         url: full_url,
                         source: "Swappa".to_string(),
+                        sold_date: None,
+                        grade: None,
+                        warranty: None,
+                        expires_at: None,
+                        region: None,
+                        currency: None,
+                        attributes: ProductAttributes::default(),
+                        listed_at: None,
+                        lot_quantity: None,
                     });
                 }
             }
@@ -628,19 +1310,18 @@ fn scrape_swappa_products(html: &str, base_url: &str) -> Vec<Product> {
     }
 
     products.sort_by(|a, b| a.name.cmp(&b.name));
-    products.dedup_by(|a, b| a.name == b.name);
-    products
+    listing_id::dedup_listings(products)
 }
 
 fn get_text_from_selectors(html: &Html, selectors: &[&str]) -> String {
     for sel_str in selectors {
-        if let Ok(selector) = Selector::parse(sel_str) {
-            if let Some(element) = html.select(&selector).next() {
-                let text: String = element.text().collect::<Vec<_>>().join(" ");
-                let cleaned = text.split_whitespace().collect::<Vec<_>>().join(" ");
-                if !cleaned.is_empty() {
-                    return cleaned;
-                }
+        if let Ok(selector) = Selector::parse(sel_str)
+            && let Some(element) = html.select(&selector).next()
+        {
+            let text: String = element.text().collect::<Vec<_>>().join(" ");
+            let cleaned = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !cleaned.is_empty() {
+                return cleaned;
             }
         }
     }
@@ -649,12 +1330,11 @@ fn get_text_from_selectors(html: &Html, selectors: &[&str]) -> String {
 
 fn get_href_from_selectors(html: &Html, selectors: &[&str]) -> String {
     for sel_str in selectors {
-        if let Ok(selector) = Selector::parse(sel_str) {
-            if let Some(element) = html.select(&selector).next() {
-                if let Some(href) = element.value().attr("href") {
-                    return href.to_string();
-                }
-            }
+        if let Ok(selector) = Selector::parse(sel_str)
+            && let Some(element) = html.select(&selector).next()
+            && let Some(href) = element.value().attr("href")
+        {
+            return href.to_string();
         }
     }
     String::new()
@@ -747,7 +1427,7 @@ fn parse_newegg_product_page(html: &str, url: &str) -> ProductDetails {
     
     ProductDetails {
         name: if name.is_empty() { "Unknown".to_string() } else { name.trim().to_string() },
-        price: if price.is_empty() { "Price not found".to_string() } else { price.trim().to_string() },
+        price: price::parse_money(&price),
         url: url.to_string(),
         source: "Newegg".to_string(),
         description: description.trim().to_string(),
@@ -825,10 +1505,10 @@ fn parse_swappa_product_page(html: &str, url: &str) -> ProductDetails {
     for selector_str in &img_selectors {
         if let Ok(selector) = Selector::parse(selector_str) {
             for img in document.select(&selector) {
-                if let Some(src) = img.value().attr("src").or_else(|| img.value().attr("data-src")) {
-                    if !images.contains(&src.to_string()) {
-                        images.push(src.to_string());
-                    }
+                if let Some(src) = img.value().attr("src").or_else(|| img.value().attr("data-src"))
+                    && !images.contains(&src.to_string())
+                {
+                    images.push(src.to_string());
                 }
             }
         }
@@ -846,7 +1526,7 @@ fn parse_swappa_product_page(html: &str, url: &str) -> ProductDetails {
     
     ProductDetails {
         name: if name.is_empty() { "Unknown".to_string() } else { name.trim().to_string() },
-        price: if price.is_empty() { "Price not found".to_string() } else { price.trim().to_string() },
+        price: price::parse_money(&price),
         url: url.to_string(),
         source: "Swappa".to_string(),
         description: description.trim().to_string(),
@@ -868,9 +1548,9 @@ async fn fetch_product_details(client: &reqwest::Client, products: &[Product], m
     
     println!("\n  📋 Fetching detailed info for {} products...\n", products_to_fetch.len());
     
+    let progress = progress::Progress::new(products_to_fetch.len(), "Details");
     for (i, product) in products_to_fetch.iter().enumerate() {
-        println!("    [{}/{}] Fetching details: {}", i + 1, products_to_fetch.len(), 
-            if product.name.len() > 50 { &product.name[..50] } else { &product.name });
+        progress.step(i, &format!("Fetching details: {}", truncate_string(&product.name, 50)));
         
         if let Some(html) = fetch_html(client, &product.url).await {
             let detail = match product.source.as_str() {
@@ -880,10 +1560,8 @@ async fn fetch_product_details(client: &reqwest::Client, products: &[Product], m
             };
             details.push(detail);
         }
-        
-        // Rate limiting - be respectful to servers
-        sleep(Duration::from_millis(2000)).await;
     }
+    progress.finish();
     
     details
 }
@@ -917,10 +1595,9 @@ fn extract_newegg_categories(html: &str, base_url: &str) -> Vec<String> {
                     
                     // Only add Newegg category URLs
                     if full_url.contains("newegg.com") && 
-                       (full_url.contains("/Category/") || full_url.contains("/SubCategory/")) {
-                        if !categories.contains(&full_url) {
-                            categories.push(full_url);
-                        }
+                       (full_url.contains("/Category/") || full_url.contains("/SubCategory/")) &&
+                       !categories.contains(&full_url) {
+                        categories.push(full_url);
                     }
                 }
             }
@@ -930,13 +1607,17 @@ fn extract_newegg_categories(html: &str, base_url: &str) -> Vec<String> {
     categories
 }
 
-async fn scrape_newegg(client: &reqwest::Client) -> Vec<Product> {
+async fn scrape_newegg(client: &reqwest::Client, config: &NeweggConfig, politeness: &Politeness) -> Vec<Product> {
     let mut all_products = Vec::new();
     let base_url = "https://www.newegg.com";
     
+    if config.mode == NeweggMode::Refurbished {
+        return scrape_newegg_refurbished(client, config, politeness).await;
+    }
+    
     // First, fetch the main page to get all category links
     println!("  Fetching main page to discover categories...");
-    let categories = if let Some(html) = fetch_html(client, base_url).await {
+    let categories = if let Some(html) = politeness.fetch(client, base_url).await {
         let cats = extract_newegg_categories(&html, base_url);
         println!("  Found {} categories", cats.len());
         cats
@@ -944,25 +1625,74 @@ async fn scrape_newegg(client: &reqwest::Client) -> Vec<Product> {
         Vec::new()
     };
     
-    sleep(Duration::from_millis(1000)).await;
-    
     // Limit to first 10 categories to avoid overwhelming the server
     let max_categories = 10;
     let categories_to_scrape: Vec<_> = categories.into_iter().take(max_categories).collect();
     
+    let progress = progress::Progress::new(categories_to_scrape.len(), "Newegg");
     for (i, url) in categories_to_scrape.iter().enumerate() {
-        println!("  [{}/{}] Fetching: {}", i + 1, categories_to_scrape.len(), url);
-        if let Some(html) = fetch_html(client, url).await {
+        progress.step(i, &format!("Fetching: {}", url));
+        if let Some(html) = politeness.fetch(client, url).await {
+            let products = scrape_newegg_products(&html, base_url);
+            progress.println(&format!("    Found {} products", products.len()));
+            all_products.extend(products);
+        }
+    }
+    progress.finish();
+
+    all_products
+}
+
+// Refurbished/open-box phone searches instead of the homepage category crawl
+async fn scrape_newegg_refurbished(client: &reqwest::Client, config: &NeweggConfig, politeness: &Politeness) -> Vec<Product> {
+    let mut all_products = Vec::new();
+    let base_url = "https://www.newegg.com";
+    
+    let progress = progress::Progress::new(config.queries.len(), "Newegg");
+    for (i, query) in config.queries.iter().enumerate() {
+        for page in 1..=politeness.page_cap(config.max_pages).max(1) {
+            let url = format!("{}/p/pl?d={}&page={}", base_url, query.replace(' ', "+"), page);
+            progress.step(i, &format!("Searching \"{}\" (page {})", query, page));
+            let Some(html) = politeness.fetch(client, &url).await else {
+                break;
+            };
+            let products = scrape_newegg_products(&html, base_url);
+            progress.println(&format!("    Found {} products", products.len()));
+            let found = products.len();
+            all_products.extend(products);
+            if found == 0 {
+                break;
+            }
+        }
+    }
+    progress.finish();
+    
+    all_products
+}
+
+// [categories] Newegg search pages ([label, url] pairs), first page of each
+async fn scrape_newegg_pages(client: &reqwest::Client, pages: &[(String, String)], politeness: &Politeness) -> Vec<Product> {
+    let mut all_products = Vec::new();
+    let base_url = "https://www.newegg.com";
+    if pages.is_empty() {
+        return all_products;
+    }
+
+    let progress = progress::Progress::new(pages.len(), "Newegg");
+    for (i, (label, url)) in pages.iter().enumerate() {
+        progress.step(i, &format!("Fetching {}: {}", label, url));
+        if let Some(html) = politeness.fetch(client, url).await {
             let products = scrape_newegg_products(&html, base_url);
-            println!("    Found {} products", products.len());
+            progress.println(&format!("    Found {} products", products.len()));
             all_products.extend(products);
         }
-        sleep(Duration::from_millis(1500)).await;
     }
+    progress.finish();
 
     all_products
 }
 
+#[allow(dead_code)]
 fn extract_swappa_categories(html: &str, base_url: &str) -> Vec<String> {
     let document = Html::parse_document(html);
     let mut categories = Vec::new();
@@ -1004,9 +1734,16 @@ fn extract_swappa_categories(html: &str, base_url: &str) -> Vec<String> {
     categories
 }
 
-async fn scrape_swappa(_client: &reqwest::Client) -> Vec<Product> {
-    let mut all_products = Vec::new();
-    
+// Returns the scraped listings plus how many listings each device page yielded
+async fn scrape_swappa(
+    _client: &reqwest::Client,
+    capture: &CaptureConfig,
+    swappa: &SwappaConfig,
+    browser: &BrowserConfig,
+    politeness: &Politeness,
+    category_urls: &[(String, String)],
+    device: Option<&str>,
+) -> (Vec<Product>, HashMap<String, usize>) {
     println!("  Starting Selenium WebDriver for Swappa...");
     
     // Set up Chrome options - headless mode to run without visible browser
@@ -1020,133 +1757,181 @@ async fn scrape_swappa(_client: &reqwest::Client) -> Vec<Product> {
     caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
     
     // Connect to ChromeDriver
-    let driver = match WebDriver::new("http://localhost:9515", caps).await {
+    let driver = match sessions::open(browser, "Swappa", caps).await {
         Ok(d) => d,
         Err(e) => {
             eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
-            eprintln!("  💡 Make sure ChromeDriver is running: chromedriver --port=9515");
-            return all_products;
+            error_journal::record(ErrorCategory::Driver, "Swappa", None, &e, None);
+            return (Vec::new(), HashMap::new());
         }
     };
     
     println!("  ✓ Connected to ChromeDriver");
+    // The hook is always needed here: captured XHR JSON is the primary extraction path
+    if capture::install_hook(&driver, capture.max_body_kb).await && capture.enabled {
+        println!("  📡 Network capture enabled, saving JSON responses to {}/", capture.dir);
+    }
     
     // Create screenshots directory
-    let screenshot_dir = "/tmp/swappa_screenshots";
+    let screenshot_dir = maintenance::SCREENSHOT_DIRS[0];
     let _ = fs::create_dir_all(screenshot_dir);
     
     // URLs to scrape - these are specific device pages with listings
-    let urls: Vec<(&str, &str)> = vec![
+    let mut urls: Vec<(&str, &str)> = vec![
         ("iPhone 15", "https://swappa.com/buy/apple-iphone-15"),
         ("iPhone 14", "https://swappa.com/buy/apple-iphone-14"),
         ("iPhone 13", "https://swappa.com/buy/apple-iphone-13"),
         ("Galaxy S24", "https://swappa.com/buy/samsung-galaxy-s24"),
         ("Pixel 8", "https://swappa.com/buy/google-pixel-8"),
+        // Tablets and watches
+        ("iPad Pro 11", "https://swappa.com/buy/apple-ipad-pro-11-4th-gen"),
+        ("iPad Air", "https://swappa.com/buy/apple-ipad-air-5th-gen"),
+        ("iPad 10", "https://swappa.com/buy/apple-ipad-10th-gen"),
+        ("Apple Watch Series 9", "https://swappa.com/buy/apple-watch-series-9"),
+        ("Apple Watch Ultra 2", "https://swappa.com/buy/apple-watch-ultra-2"),
+        ("Galaxy Watch6", "https://swappa.com/buy/samsung-galaxy-watch6"),
     ];
+    urls.extend(category_urls.iter().map(|(label, url)| (label.as_str(), url.as_str())));
+    urls.retain(|(category, _)| single_source::device_matches(category, device));
     
-    for (category, url) in urls.iter() {
-        println!("  📱 Scraping {}: {}", category, url);
-        
-        if let Err(e) = driver.goto(*url).await {
-            eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
-            continue;
-        }
-        
-        // Wait for page to fully load
-        sleep(Duration::from_secs(4)).await;
-        
-        // Scroll to load all content
-        for i in 0..5 {
-            let scroll_pos = (i + 1) * 600;
-            let _ = driver.execute(&format!("window.scrollTo(0, {})", scroll_pos), vec![]).await;
-            sleep(Duration::from_millis(800)).await;
-        }
+    let (all_products, device_counts) = scrape_swappa_pages(&driver, capture, swappa, politeness, &urls).await;
+    
+    // Kept for the next scrape of the site this run
+    sessions::release(browser, "Swappa", driver).await;
+    
+    println!("  ✓ Swappa scraping complete. Found {} products", all_products.len());
+    println!("  📁 Screenshots saved to: {}", screenshot_dir);
+    
+    (all_products, device_counts)
+}
+
+// Swappa's fallback extraction: the listings on the page from text
+// scanning, up to `max_listings` (0 for all), for when no listing JSON turned up
+fn swappa_text_scan_script(category_name: &str, base_url: &str, max_listings: usize) -> String {
+    format!(r#"
+        var products = [];
+        var categoryName = "{}";
+        var baseUrl = "{}";
+        var maxListings = {};
+        var belowCap = function() {{ return maxListings == 0 || products.length < maxListings; }};
+        var seenKeys = new Set();
+        var method = 'text-scan';
+        var listingIndex = 0;
         
-        // Take and save screenshot
-        let screenshot_path = format!("{}/{}.png", screenshot_dir, category.replace(" ", "_"));
-        if let Ok(png_data) = driver.screenshot_as_png().await {
-            if fs::write(&screenshot_path, &png_data).is_ok() {
-                println!("    📸 Screenshot saved: {}", screenshot_path);
-            }
-        }
+        // Find listing rows/cards with price information
+        // Swappa typically shows listings as rows with price, condition, storage info
+        var cards = document.querySelectorAll('[class*="listing"], [class*="item"], [class*="card"], [class*="row"], [class*="product"], article, [data-listing], tr, [role="row"]');
         
-        // Extract ALL individual listings from the page using text scanning
-        let category_name = *category;
-        let base_url = *url;
-        let script = format!(r#"
-            var products = [];
-            var categoryName = "{}";
-            var baseUrl = "{}";
-            var seenKeys = new Set();
-            var method = 'text-scan';
-            var listingIndex = 0;
+        for (var i = 0; i < cards.length && belowCap(); i++) {{
+            var card = cards[i];
+            var text = card.innerText || '';
+            var priceMatch = text.match(/\$(\d{{2,4}})/);
             
-            // Find listing rows/cards with price information
-            // Swappa typically shows listings as rows with price, condition, storage info
-            var cards = document.querySelectorAll('[class*="listing"], [class*="item"], [class*="card"], [class*="row"], [class*="product"], article, [data-listing], tr, [role="row"]');
+            if (priceMatch) {{
+                var priceNum = parseInt(priceMatch[1]);
+                // Filter to reasonable phone, tablet and watch prices
+                if (priceNum >= 100 && priceNum <= 2500) {{
+                    var price = '$' + priceMatch[1];
+                    
+                    // Find best link in card - prefer prices or listing links
+                    var cardAnchors = card.querySelectorAll('a');
+                    var href = '';
+                    
+                    for (var j = 0; j < cardAnchors.length; j++) {{
+                        var linkHref = cardAnchors[j].href || '';
+                        // Prefer prices page or listing-specific URLs
+                        if (linkHref.includes('/prices/') || linkHref.includes('/listing/')) {{
+                            href = linkHref;
+                            break;
+                        }}
+                        // Fallback to guide page
+                        if (!href && linkHref.includes('/guide/') && !linkHref.includes('/reviews')) {{
+                            href = linkHref;
+                        }}
+                    }}
+                    
+                    // If no good link, use baseUrl with listing index for tracking
+                    if (!href) {{
+                        href = baseUrl;
+                    }}
+                    
+                    // Extract condition
+                    var condition = '';
+                    if (text.includes('Mint')) condition = 'Mint';
+                    else if (text.includes('Good')) condition = 'Good';
+                    else if (text.includes('Fair')) condition = 'Fair';
+                    
+                    // Extract storage
+                    var storage = '';
+                    var storageMatch = text.match(/(\d{{2,3}})\s*GB/i);
+                    if (storageMatch) storage = storageMatch[1] + 'GB';
+                    
+                    // Extract carrier/unlock status
+                    var carrier = '';
+                    if (text.includes('Unlocked')) carrier = 'Unlocked';
+                    else if (text.includes('Verizon')) carrier = 'Verizon';
+                    else if (text.includes('T-Mobile')) carrier = 'T-Mobile';
+                    else if (text.includes('AT&T')) carrier = 'AT&T';
+                    
+                    // Create unique key based on card content to avoid duplicates
+                    var key = price + '-' + storage + '-' + condition + '-' + i;
+                    if (!seenKeys.has(key)) {{
+                        seenKeys.add(key);
+                        method = 'cards';
+                        listingIndex++;
+                        
+                        // Build descriptive name
+                        var name = categoryName;
+                        if (storage) name += ' ' + storage;
+                        if (carrier) name += ' ' + carrier;
+                        if (condition) name += ' (' + condition + ')';
+                        
+                        products.push({{
+                            name: name,
+                            price: price,
+                            condition: condition,
+                            storage: storage,
+                            carrier: carrier,
+                            url: href,
+                            listingNum: listingIndex
+                        }});
+                    }}
+                }}
+            }}
+        }}
+        
+        // Fallback: Text scanning if no cards found
+        if (products.length == 0) {{
+            var bodyText = document.body.innerText;
+            var textLines = bodyText.split('\n');
             
-            for (var i = 0; i < cards.length && products.length < 30; i++) {{
-                var card = cards[i];
-                var text = card.innerText || '';
-                var priceMatch = text.match(/\$(\d{{2,4}})/);
+            for (var i = 0; i < textLines.length && belowCap(); i++) {{
+                var line = textLines[i].trim();
+                var priceMatch = line.match(/\$(\d{{2,4}})/);
                 
                 if (priceMatch) {{
                     var priceNum = parseInt(priceMatch[1]);
-                    // Filter to reasonable phone prices
-                    if (priceNum >= 100 && priceNum <= 1500) {{
+                    if (priceNum >= 100 && priceNum <= 2500) {{
+                        listingIndex++;
                         var price = '$' + priceMatch[1];
                         
-                        // Find best link in card - prefer prices or listing links
-                        var cardAnchors = card.querySelectorAll('a');
-                        var href = '';
-                        
-                        for (var j = 0; j < cardAnchors.length; j++) {{
-                            var linkHref = cardAnchors[j].href || '';
-                            // Prefer prices page or listing-specific URLs
-                            if (linkHref.includes('/prices/') || linkHref.includes('/listing/')) {{
-                                href = linkHref;
-                                break;
-                            }}
-                            // Fallback to guide page
-                            if (!href && linkHref.includes('/guide/') && !linkHref.includes('/reviews')) {{
-                                href = linkHref;
-                            }}
-                        }}
-                        
-                        // If no good link, use baseUrl with listing index for tracking
-                        if (!href) {{
-                            href = baseUrl;
-                        }}
-                        
-                        // Extract condition
                         var condition = '';
-                        if (text.includes('Mint')) condition = 'Mint';
-                        else if (text.includes('Good')) condition = 'Good';
-                        else if (text.includes('Fair')) condition = 'Fair';
-                        
-                        // Extract storage
                         var storage = '';
-                        var storageMatch = text.match(/(\d{{2,3}})\s*GB/i);
-                        if (storageMatch) storage = storageMatch[1] + 'GB';
+                        var contextText = textLines.slice(Math.max(0, i-3), i+3).join(' ');
                         
-                        // Extract carrier/unlock status
-                        var carrier = '';
-                        if (text.includes('Unlocked')) carrier = 'Unlocked';
-                        else if (text.includes('Verizon')) carrier = 'Verizon';
-                        else if (text.includes('T-Mobile')) carrier = 'T-Mobile';
-                        else if (text.includes('AT&T')) carrier = 'AT&T';
+                        if (contextText.includes('Mint')) condition = 'Mint';
+                        else if (contextText.includes('Good')) condition = 'Good';
+                        else if (contextText.includes('Fair')) condition = 'Fair';
                         
-                        // Create unique key based on card content to avoid duplicates
-                        var key = price + '-' + storage + '-' + condition + '-' + i;
+                        var storageMatch = contextText.match(/(\d{{2,3}})\s*GB/i);
+                        if (storageMatch) storage = storageMatch[1] + 'GB';
+                        
+                        var key = 'line-' + i + '-' + price;
                         if (!seenKeys.has(key)) {{
                             seenKeys.add(key);
-                            method = 'cards';
-                            listingIndex++;
                             
-                            // Build descriptive name
                             var name = categoryName;
                             if (storage) name += ' ' + storage;
-                            if (carrier) name += ' ' + carrier;
                             if (condition) name += ' (' + condition + ')';
                             
                             products.push({{
@@ -1154,76 +1939,114 @@ async fn scrape_swappa(_client: &reqwest::Client) -> Vec<Product> {
                                 price: price,
                                 condition: condition,
                                 storage: storage,
-                                carrier: carrier,
-                                url: href,
+                                carrier: '',
+                                url: baseUrl,
                                 listingNum: listingIndex
                             }});
                         }}
                     }}
                 }}
             }}
-            
-            // Fallback: Text scanning if no cards found
-            if (products.length == 0) {{
-                var bodyText = document.body.innerText;
-                var textLines = bodyText.split('\n');
-                
-                for (var i = 0; i < textLines.length && products.length < 30; i++) {{
-                    var line = textLines[i].trim();
-                    var priceMatch = line.match(/\$(\d{{2,4}})/);
-                    
-                    if (priceMatch) {{
-                        var priceNum = parseInt(priceMatch[1]);
-                        if (priceNum >= 100 && priceNum <= 1500) {{
-                            listingIndex++;
-                            var price = '$' + priceMatch[1];
-                            
-                            var condition = '';
-                            var storage = '';
-                            var contextText = textLines.slice(Math.max(0, i-3), i+3).join(' ');
-                            
-                            if (contextText.includes('Mint')) condition = 'Mint';
-                            else if (contextText.includes('Good')) condition = 'Good';
-                            else if (contextText.includes('Fair')) condition = 'Fair';
-                            
-                            var storageMatch = contextText.match(/(\d{{2,3}})\s*GB/i);
-                            if (storageMatch) storage = storageMatch[1] + 'GB';
-                            
-                            var key = 'line-' + i + '-' + price;
-                            if (!seenKeys.has(key)) {{
-                                seenKeys.add(key);
-                                
-                                var name = categoryName;
-                                if (storage) name += ' ' + storage;
-                                if (condition) name += ' (' + condition + ')';
-                                
-                                products.push({{
-                                    name: name,
-                                    price: price,
-                                    condition: condition,
-                                    storage: storage,
-                                    carrier: '',
-                                    url: baseUrl,
-                                    listingNum: listingIndex
-                                }});
-                            }}
-                        }}
-                    }}
-                }}
-            }}
-            
-            return {{ 
-                products: products, 
-                total: products.length, 
-                method: method
-            }};
-            "#, category_name, base_url);
+        }}
         
-        let products_result = driver.execute(&script, vec![]).await;
-        
-        if let Ok(result_value) = products_result {
-            let json = result_value.json();
-            
+        return {{ 
+            products: products, 
+            total: products.length, 
+            method: method
+        }};
+        "#, category_name, base_url, max_listings)
+}
+
+// Click the first showing "load more" control under Swappa's listings;
+// returns whether one was clicked. Links that lead off the page don't count.
+const SWAPPA_LOAD_MORE_JS: &str = r##"
+    var controls = document.querySelectorAll('button, [role="button"], a[href="#"], a:not([href])');
+    for (var i = 0; i < controls.length; i++) {
+        var control = controls[i];
+        var text = (control.innerText || control.value || '').trim();
+        var looksLikeLoadMore = /^(load|show|view|see) more\b/i.test(text) || /load-?more/i.test(control.className || '');
+        var usable = control.offsetParent !== null && !control.disabled && control.getAttribute('aria-disabled') !== 'true';
+        if (looksLikeLoadMore && usable) {
+            control.scrollIntoView({ block: 'center' });
+            control.click();
+            return true;
+        }
+    }
+    return false;
+"##;
+
+// How far down an eBay results page is scrolled so its lazy-loaded listings
+// render ([swappa] scroll_depth_px for Swappa)
+const EBAY_SCROLL_DEPTH_PX: u64 = 4800;
+
+// Swappa device pages through `driver`, apart from launching it so the page
+// handling can run against saved script results in tests
+async fn scrape_swappa_pages(
+    driver: &dyn PageDriver,
+    capture: &CaptureConfig,
+    swappa: &SwappaConfig,
+    politeness: &Politeness,
+    urls: &[(&str, &str)],
+) -> (Vec<Product>, HashMap<String, usize>) {
+    let mut all_products = Vec::new();
+    let mut device_counts: HashMap<String, usize> = HashMap::new();
+    let screenshot_dir = maintenance::SCREENSHOT_DIRS[0];
+    // Every click shows another page of listings, so the page cap bounds them too
+    let max_load_more = if swappa.load_more { politeness.page_cap(swappa.max_load_more + 1).max(1) - 1 } else { 0 };
+    
+    for (category, url) in urls {
+        println!("  📱 Scraping {}: {}", category, url);
+        
+        if let Err(e) = driver.open(url).await {
+            eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
+            error_journal::record(ErrorCategory::Navigation, "Swappa", Some(url), &e, None);
+            continue;
+        }
+        
+        // Wait for the page to load, then scroll through it so all content renders
+        politeness.behavior.wait_for_load(driver).await;
+        politeness.behavior.scroll(driver, swappa.scroll_depth_px).await;
+        
+        // Deep inventories only show their first listings until "load more" is clicked
+        let mut clicks = 0;
+        while clicks < max_load_more && driver.run_script(SWAPPA_LOAD_MORE_JS).await.is_ok_and(|clicked| clicked.as_bool() == Some(true)) {
+            clicks += 1;
+            politeness.behavior.wait_for_load(driver).await;
+            politeness.behavior.scroll(driver, swappa.scroll_depth_px).await;
+        }
+        if clicks > 0 {
+            println!("    ➕ Clicked \"load more\" {} time(s)", clicks);
+        }
+        
+        let captured = capture::drain(driver).await;
+        capture::write(capture, "Swappa", url, &captured);
+        
+        // Take and save screenshot
+        let screenshot_path = format!("{}/{}.png", screenshot_dir, category.replace(" ", "_"));
+        if let Ok(png_data) = driver.screenshot_png().await
+            && fs::write(&screenshot_path, &png_data).is_ok()
+        {
+            println!("    📸 Screenshot saved: {}", screenshot_path);
+        }
+        
+        // Primary path: listings from Swappa's own JSON (XHR responses and embedded data)
+        let embedded = swappa_json::embedded_json(driver).await;
+        let json_products = swappa_json::parse_listings(&captured, &embedded, category);
+        if !json_products.is_empty() {
+            println!("    🧾 Parsed {} listings from Swappa JSON", json_products.len());
+            device_counts.insert(category.to_string(), json_products.len());
+            all_products.extend(json_products);
+            politeness.behavior.between_pages(driver).await;
+            continue;
+        }
+        
+        // Fallback: extract ALL individual listings from the page using text scanning
+        let script = swappa_text_scan_script(category, url, swappa.max_listings);
+        
+        let products_result = driver.run_script(&script).await;
+        let mut added_count = 0;
+        
+        if let Ok(json) = products_result {
             // Get total found
             let total = json.get("total").and_then(|v| v.as_u64()).unwrap_or(0);
             let method = json.get("method").and_then(|v| v.as_str()).unwrap_or("unknown");
@@ -1232,7 +2055,6 @@ async fn scrape_swappa(_client: &reqwest::Client) -> Vec<Product> {
             
             // Get all products
             if let Some(products_arr) = json.get("products").and_then(|v| v.as_array()) {
-                let mut added_count = 0;
                 for product in products_arr {
                     let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
                     let price = product.get("price").and_then(|v| v.as_str()).unwrap_or("");
@@ -1248,9 +2070,18 @@ async fn scrape_swappa(_client: &reqwest::Client) -> Vec<Product> {
                         // Don't filter duplicates by name - allow same model with different conditions/prices
                         all_products.push(Product {
                             name: name.to_string(),
-                            price: price.to_string(),
+                            price: price::parse_money(price),
                             url: final_url,
                             source: "Swappa".to_string(),
+                            sold_date: None,
+                            grade: None,
+                            warranty: None,
+                            expires_at: None,
+                            region: None,
+                            currency: None,
+                            attributes: ProductAttributes::default(),
+                            listed_at: None,
+                            lot_quantity: None,
                         });
                         added_count += 1;
                     }
@@ -1260,24 +2091,24 @@ async fn scrape_swappa(_client: &reqwest::Client) -> Vec<Product> {
                 }
             }
         }
+        device_counts.insert(category.to_string(), added_count);
         
-        sleep(Duration::from_secs(1)).await;
-    }
-    
-    // Close the browser
-    if let Err(e) = driver.quit().await {
-        eprintln!("  Warning: Failed to close browser: {}", e);
+        politeness.behavior.between_pages(driver).await;
     }
     
-    println!("  ✓ Swappa scraping complete. Found {} products", all_products.len());
-    println!("  📁 Screenshots saved to: {}", screenshot_dir);
     
-    all_products
+    (all_products, device_counts)
 }
 
-async fn scrape_ebay(_client: &reqwest::Client) -> Vec<Product> {
-    let mut all_products = Vec::new();
-    
+async fn scrape_ebay(
+    capture: &CaptureConfig,
+    comps: &CompsConfig,
+    ebay: &EbayConfig,
+    browser: &BrowserConfig,
+    politeness: &Politeness,
+    category_urls: &[(String, String)],
+    device: Option<&str>,
+) -> Vec<Product> {
     println!("  Starting Selenium WebDriver for eBay...");
     
     // Set up Chrome options - with extra measures to avoid detection
@@ -1293,23 +2124,24 @@ async fn scrape_ebay(_client: &reqwest::Client) -> Vec<Product> {
     caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
     
     // Connect to ChromeDriver
-    let driver = match WebDriver::new("http://localhost:9515", caps).await {
+    let driver = match sessions::open(browser, "eBay", caps).await {
         Ok(d) => d,
         Err(e) => {
             eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
-            eprintln!("  💡 Make sure ChromeDriver is running: chromedriver --port=9515");
-            return all_products;
+            error_journal::record(ErrorCategory::Driver, "eBay", None, &e, None);
+            return Vec::new();
         }
     };
     
     println!("  ✓ Connected to ChromeDriver");
+    capture::install(&driver, capture).await;
     
     // Create screenshots directory
-    let screenshot_dir = "/tmp/ebay_screenshots";
+    let screenshot_dir = maintenance::SCREENSHOT_DIRS[1];
     let _ = fs::create_dir_all(screenshot_dir);
     
     // eBay SOLD listings URLs - LH_Complete=1&LH_Sold=1 shows recently sold items
-    let urls: Vec<(&str, &str)> = vec![
+    let mut urls: Vec<(&str, &str)> = vec![
         // Phones - SOLD listings
         ("iPhone 15", "https://www.ebay.com/sch/i.html?_nkw=iphone+15+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13"),
         ("iPhone 14", "https://www.ebay.com/sch/i.html?_nkw=iphone+14+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13"),
@@ -1318,171 +2150,516 @@ async fn scrape_ebay(_client: &reqwest::Client) -> Vec<Product> {
         ("Galaxy S23", "https://www.ebay.com/sch/i.html?_nkw=samsung+galaxy+s23+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13"),
         ("Pixel 8", "https://www.ebay.com/sch/i.html?_nkw=google+pixel+8+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13"),
         ("Pixel 7", "https://www.ebay.com/sch/i.html?_nkw=google+pixel+7+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13"),
+        // Tablets (iPads & Tablets) - SOLD listings
+        ("iPad Pro 11", "https://www.ebay.com/sch/i.html?_nkw=ipad+pro+11+m2&_sacat=171485&LH_Sold=1&LH_Complete=1&_sop=13"),
+        ("iPad Air", "https://www.ebay.com/sch/i.html?_nkw=ipad+air+5th+gen&_sacat=171485&LH_Sold=1&LH_Complete=1&_sop=13"),
+        ("iPad 10", "https://www.ebay.com/sch/i.html?_nkw=ipad+10th+gen&_sacat=171485&LH_Sold=1&LH_Complete=1&_sop=13"),
+        // Watches (Smart Watches) - SOLD listings
+        ("Apple Watch Series 9", "https://www.ebay.com/sch/i.html?_nkw=apple+watch+series+9&_sacat=178893&LH_Sold=1&LH_Complete=1&_sop=13"),
+        ("Apple Watch Ultra 2", "https://www.ebay.com/sch/i.html?_nkw=apple+watch+ultra+2&_sacat=178893&LH_Sold=1&LH_Complete=1&_sop=13"),
+        ("Galaxy Watch6", "https://www.ebay.com/sch/i.html?_nkw=samsung+galaxy+watch6&_sacat=178893&LH_Sold=1&LH_Complete=1&_sop=13"),
     ];
+    // [categories] sold searches: laptops, GPUs, consoles, ...
+    urls.extend(category_urls.iter().map(|(label, url)| (label.as_str(), url.as_str())));
+    urls.retain(|(category, _)| single_source::device_matches(category, device));
     
-    for (category, url) in urls.iter() {
-        println!("  🛍️ Scraping eBay {}: {}", category, url);
-        
-        if let Err(e) = driver.goto(*url).await {
-            eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
-            continue;
-        }
+    let mut all_products = scrape_ebay_pages(&driver, capture, comps, ebay, politeness, &urls).await;
+    
+    // Kept for the next scrape of the site this run
+    sessions::release(browser, "eBay", driver).await;
+    
+    // The same sale turns up under several searches
+    all_products.sort_by(|a, b| a.name.cmp(&b.name));
+    let all_products = listing_id::dedup_listings(all_products);
+    
+    println!("  ✓ eBay scraping complete. Found {} products", all_products.len());
+    println!("  📁 Screenshots saved to: {}", screenshot_dir);
+    
+    all_products
+}
+
+// Sold listings from an eBay search results page, with each card's shipping lines
+fn ebay_extract_script() -> String {
+    [ebay_shipping::SHIPPING_TEXT_JS, r#"
+        var products = [];
+        var seenUrls = new Set();
+        var debug = { selectors: [] };
         
-        // Wait for page to load
-        sleep(Duration::from_secs(5)).await;
+        // Updated selector for eBay's new s-card structure
+        var items = document.querySelectorAll('ul.srp-results li.s-card');
+        debug.itemsChecked = items.length;
+        debug.winningSelector = 'ul.srp-results li.s-card';
         
-        // Scroll to load more content
-        for i in 0..6 {
-            let scroll_pos = (i + 1) * 800;
-            let _ = driver.execute(&format!("window.scrollTo(0, {})", scroll_pos), vec![]).await;
-            sleep(Duration::from_millis(600)).await;
+        for (var i = 0; i < items.length && products.length < 50; i++) {
+            var item = items[i];
+            
+            // Get title from s-card__title
+            var titleEl = item.querySelector('.s-card__title span');
+            var name = titleEl ? titleEl.innerText.trim() : '';
+            
+            // Clean up title - remove "NEW LISTING" prefix
+            name = name.replace(/^NEW LISTING/i, '').trim();
+            
+            // Skip invalid names
+            if (!name || name.length < 10 || name.toLowerCase().includes('shop on ebay')) continue;
+            
+            // Get price from s-card__price
+            var priceEl = item.querySelector('.s-card__price');
+            var price = '';
+            if (priceEl) {
+                var priceText = priceEl.innerText.trim();
+                // Variation listings show a range, priced at its midpoint by parse_price
+                var priceMatch = priceText.match(/\$[\d,]+\.?\d{0,2}(\s*to\s*\$[\d,]+\.?\d{0,2})?/);
+                if (priceMatch) {
+                    price = priceMatch[0];
+                }
+            }
+            
+            // Get URL from s-card__link with /itm/
+            var linkEl = item.querySelector('a.s-card__link[href*="/itm/"]');
+            if (!linkEl) {
+                linkEl = item.querySelector('a[href*="/itm/"]');
+            }
+            var href = linkEl ? linkEl.href : '';
+            
+            // Validate and add product
+            if (name && name.length > 5 && price && href && href.includes('/itm/')) {
+                // Clean up URL - remove tracking params
+                var cleanUrl = href.split('?')[0];
+                if (!seenUrls.has(cleanUrl)) {
+                    seenUrls.add(cleanUrl);
+                    // Sold cards carry a "Sold  Oct 3, 2026" caption
+                    var soldMatch = (item.innerText || '').match(/Sold\s+([A-Z][a-z]{2} \d{1,2}, \d{4})/);
+                    products.push({
+                        name: name.substring(0, 200),
+                        price: price,
+                        url: cleanUrl,
+                        soldDate: soldMatch ? soldMatch[1] : '',
+                        shipping: shippingText(item)
+                    });
+                }
+            }
         }
         
-        // Scroll back up
-        let _ = driver.execute("window.scrollTo(0, 0)", vec![]).await;
-        sleep(Duration::from_secs(1)).await;
-        
-        // Take screenshot
-        let screenshot_path = format!("{}/{}.png", screenshot_dir, category.replace(" ", "_"));
-        if let Ok(png_data) = driver.screenshot_as_png().await {
-            if fs::write(&screenshot_path, &png_data).is_ok() {
+        debug.productsFound = products.length;
+        // eBay disables the arrow on the last page rather than dropping it
+        var next = document.querySelector('.pagination__next');
+        var hasNext = !!next && next.getAttribute('aria-disabled') !== 'true';
+        return { products: products, total: products.length, hasNext: hasNext, debug: debug };
+    "#].concat()
+}
+
+// The search at `url` on results page `page`; page 1 is the URL as given
+fn ebay_page_url(url: &str, page: usize) -> String {
+    if page <= 1 {
+        return url.to_string();
+    }
+    let (base, query) = url.split_once('?').unwrap_or((url, ""));
+    let mut params: Vec<String> = query.split('&').filter(|p| !p.is_empty() && !p.starts_with("_pgn=")).map(str::to_string).collect();
+    params.push(format!("_pgn={}", page));
+    format!("{}?{}", base, params.join("&"))
+}
+
+// eBay sold searches through `driver`, apart from launching it so the page
+// handling can run against saved script results in tests. Each search is
+// followed through its _pgn result pages up to [ebay] max_pages.
+async fn scrape_ebay_pages(
+    driver: &dyn PageDriver,
+    capture: &CaptureConfig,
+    comps: &CompsConfig,
+    ebay: &EbayConfig,
+    politeness: &Politeness,
+    urls: &[(&str, &str)],
+) -> Vec<Product> {
+    let mut all_products = Vec::new();
+    let screenshot_dir = maintenance::SCREENSHOT_DIRS[1];
+    let pages = politeness.page_cap(ebay.max_pages).max(1);
+    
+    for (category, url) in urls {
+        // Sales read for this search so far
+        let mut seen = HashSet::new();
+        for page in 1..=pages {
+            let page_url = ebay_page_url(url, page);
+            if page == 1 {
+                println!("  🛍️ Scraping eBay {}: {}", category, url);
+            } else {
+                println!("    📄 Page {}: {}", page, page_url);
+            }
+            
+            if let Err(e) = driver.open(&page_url).await {
+                eprintln!("    ❌ Failed to navigate to {}: {}", page_url, e);
+                error_journal::record(ErrorCategory::Navigation, "eBay", Some(&page_url), &e, None);
+                break;
+            }
+            
+            // Wait for the page to load, scroll through the results so they all render, and back up
+            politeness.behavior.wait_for_load(driver).await;
+            politeness.behavior.scroll(driver, EBAY_SCROLL_DEPTH_PX).await;
+            politeness.behavior.scroll_to_top(driver).await;
+            
+            // Save any JSON the page fetched (no-op unless [capture] is enabled)
+            capture::save(driver, capture, "eBay", &page_url).await;
+            
+            // Take screenshot
+            let page_suffix = if page == 1 { String::new() } else { format!("_p{}", page) };
+            let screenshot_path = format!("{}/{}{}.png", screenshot_dir, category.replace(" ", "_"), page_suffix);
+            if let Ok(png_data) = driver.screenshot_png().await
+                && fs::write(&screenshot_path, &png_data).is_ok()
+            {
                 println!("    📸 Screenshot saved: {}", screenshot_path);
             }
-        }
-        
-        // Extract products using JavaScript - updated selectors for eBay 2026
-        let script = r#"
-            var products = [];
-            var seenUrls = new Set();
-            var debug = { selectors: [] };
             
-            // Updated selector for eBay's new s-card structure
-            var items = document.querySelectorAll('ul.srp-results li.s-card');
-            debug.itemsChecked = items.length;
-            debug.winningSelector = 'ul.srp-results li.s-card';
+            // Extract products using JavaScript - updated selectors for eBay 2026
+            let script = ebay_extract_script();
             
-            for (var i = 0; i < items.length && products.length < 50; i++) {
-                var item = items[i];
-                
-                // Get title from s-card__title
-                var titleEl = item.querySelector('.s-card__title span');
-                var name = titleEl ? titleEl.innerText.trim() : '';
-                
-                // Clean up title - remove "NEW LISTING" prefix
-                name = name.replace(/^NEW LISTING/i, '').trim();
-                
-                // Skip invalid names
-                if (!name || name.length < 10 || name.toLowerCase().includes('shop on ebay')) continue;
-                
-                // Get price from s-card__price
-                var priceEl = item.querySelector('.s-card__price');
-                var price = '';
-                if (priceEl) {
-                    var priceText = priceEl.innerText.trim();
-                    var priceMatch = priceText.match(/\$[\d,]+\.?\d{0,2}/);
-                    if (priceMatch) {
-                        price = priceMatch[0];
-                    }
-                }
+            let products_result = driver.run_script(&script).await;
+            
+            let mut has_next = false;
+            let mut added_count = 0;
+            if let Ok(json) = products_result {
+                let total = json.get("total").and_then(|v| v.as_u64()).unwrap_or(0);
+                has_next = json.get("hasNext").and_then(|v| v.as_bool()).unwrap_or(false);
                 
-                // Get URL from s-card__link with /itm/
-                var linkEl = item.querySelector('a.s-card__link[href*="/itm/"]');
-                if (!linkEl) {
-                    linkEl = item.querySelector('a[href*="/itm/"]');
+                // Debug info
+                if let Some(debug) = json.get("debug") {
+                    let items_checked = debug.get("itemsChecked").and_then(|v| v.as_u64()).unwrap_or(0);
+                    let winning_selector = debug.get("winningSelector").and_then(|v| v.as_str()).unwrap_or("none");
+                    println!("    🔍 Found {} products (checked {} items, selector: {})", total, items_checked, winning_selector);
+                } else {
+                    println!("    🔍 Found {} products", total);
                 }
-                var href = linkEl ? linkEl.href : '';
                 
-                // Validate and add product
-                if (name && name.length > 5 && price && href && href.includes('/itm/')) {
-                    // Clean up URL - remove tracking params
-                    var cleanUrl = href.split('?')[0];
-                    if (!seenUrls.has(cleanUrl)) {
-                        seenUrls.add(cleanUrl);
-                        products.push({
-                            name: name.substring(0, 200),
-                            price: price,
-                            url: cleanUrl
-                        });
+                if let Some(products_arr) = json.get("products").and_then(|v| v.as_array()) {
+                    let mut international = 0;
+                    for product in products_arr {
+                        let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                        let price = product.get("price").and_then(|v| v.as_str()).unwrap_or("");
+                        let prod_url = product.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                        let sold_date = product.get("soldDate").and_then(|v| v.as_str()).unwrap_or("");
+                        let charges = ebay_shipping::parse(product.get("shipping").and_then(|v| v.as_str()).unwrap_or(""));
+                        
+                        // Later pages can repeat sales as new ones push the results down
+                        if !name.is_empty() && !price.is_empty() && !prod_url.is_empty() && seen.insert(prod_url.to_string()) {
+                            if charges.international {
+                                international += 1;
+                            }
+                            // GSP / international charges inflate the buyer-paid total
+                            let price = match parse_price(price) {
+                                Some(item_price) => Some(charges.comp_price(item_price, comps.shipping)),
+                                None => price::parse_money(price),
+                            };
+                            all_products.push(Product {
+                                name: name.to_string(),
+                                price,
+                                url: prod_url.to_string(),
+                                source: "eBay".to_string(),
+                                sold_date: if sold_date.is_empty() { None } else { Some(sold_date.to_string()) },
+                                grade: None,
+                                warranty: None,
+                                expires_at: None,
+                                region: None,
+                                currency: None,
+                                attributes: ProductAttributes::default(),
+                                listed_at: None,
+                                lot_quantity: None,
+                            });
+                            added_count += 1;
+                        }
+                    }
+                    if added_count > 0 {
+                        println!("    ✅ Added {} products from {}", added_count, category);
+                    }
+                    if international > 0 {
+                        println!("    🌍 {} shipped internationally (GSP / eBay International Shipping)", international);
                     }
                 }
             }
             
-            debug.productsFound = products.length;
-            return { products: products, total: products.length, debug: debug };
-        "#;
-        
-        let products_result = driver.execute(script, vec![]).await;
-        
-        if let Ok(result_value) = products_result {
-            let json = result_value.json();
-            let total = json.get("total").and_then(|v| v.as_u64()).unwrap_or(0);
-            
-            // Debug info
-            if let Some(debug) = json.get("debug") {
-                let items_checked = debug.get("itemsChecked").and_then(|v| v.as_u64()).unwrap_or(0);
-                let winning_selector = debug.get("winningSelector").and_then(|v| v.as_str()).unwrap_or("none");
-                println!("    🔍 Found {} products (checked {} items, selector: {})", total, items_checked, winning_selector);
-            } else {
-                println!("    🔍 Found {} products", total);
-            }
+            politeness.behavior.between_pages(driver).await;
             
-            if let Some(products_arr) = json.get("products").and_then(|v| v.as_array()) {
-                let mut added_count = 0;
-                for product in products_arr {
-                    let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
-                    let price = product.get("price").and_then(|v| v.as_str()).unwrap_or("");
-                    let prod_url = product.get("url").and_then(|v| v.as_str()).unwrap_or("");
-                    
-                    if !name.is_empty() && !price.is_empty() && !prod_url.is_empty() {
-                        all_products.push(Product {
-                            name: name.to_string(),
-                            price: price.to_string(),
-                            url: prod_url.to_string(),
-                            source: "eBay".to_string(),
-                        });
-                        added_count += 1;
-                    }
-                }
-                if added_count > 0 {
-                    println!("    ✅ Added {} products from {}", added_count, category);
-                }
+            // The last page, or one with nothing new
+            if !has_next || added_count == 0 {
+                break;
             }
         }
-        
-        sleep(Duration::from_secs(2)).await;
-    }
-    
-    // Close browser
-    if let Err(e) = driver.quit().await {
-        eprintln!("  Warning: Failed to close browser: {}", e);
     }
     
-    // Deduplicate
-    all_products.sort_by(|a, b| a.name.cmp(&b.name));
-    all_products.dedup_by(|a, b| a.name == b.name);
-    
-    println!("  ✓ eBay scraping complete. Found {} products", all_products.len());
-    println!("  📁 Screenshots saved to: {}", screenshot_dir);
     
     all_products
 }
 
+fn http_client() -> reqwest::Client {
+    proxy::apply(reqwest::Client::builder().timeout(Duration::from_secs(30)))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
 #[tokio::main]
 async fn main() {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .expect("Failed to create HTTP client");
+    let cli = cli::Cli::parse();
+    if let Some(profile) = &cli.profile
+        && let Err(e) = storage::set_profile(profile)
+    {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+    if let Some(mode) = cli.replay {
+        replay::set_mode(mode);
+    }
+    if let Some(url) = &cli.chromedriver_url {
+        chromedriver::set_external(url);
+    }
+    match &cli.command {
+        Some(cli::Command::Errors(args)) => {
+            error_journal::print_errors(args);
+            return;
+        }
+        Some(cli::Command::Note(args)) => {
+            let update = notes::AnnotationUpdate { url: args.url.clone(), note: Some(args.text.clone()), ..Default::default() };
+            match notes::annotate(update) {
+                Ok(annotation) => notes::print_annotation(&args.url, &annotation),
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(cli::Command::Tag(args)) => {
+            let mut update = notes::AnnotationUpdate { url: args.url.clone(), ..Default::default() };
+            if args.remove {
+                update.remove_tags = args.tags.clone();
+            } else {
+                update.add_tags = args.tags.clone();
+            }
+            match notes::annotate(update) {
+                Ok(annotation) => notes::print_annotation(&args.url, &annotation),
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(cli::Command::Analysis(args)) => {
+            let cli::AnalysisCommand::Report(report) = &args.command;
+            if report.tuning {
+                analysis::tuning::print_tuning_report(report.window_hours);
+            } else {
+                eprintln!("Choose a report, e.g. --tuning");
+            }
+            return;
+        }
+        Some(cli::Command::Inventory(args)) => {
+            if let Err(e) = inventory::run(args) {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Draft(args)) => {
+            let result = match Config::load(config::CONFIG_FILE) {
+                Ok(config) => ebay_sell::draft(args, &config.ebay_sell, &http_client()).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Track(args)) => {
+            if let Err(e) = tracking::track(args) {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Notes(args)) => {
+            notes::print_notes(args);
+            return;
+        }
+        Some(cli::Command::Scrape(args)) => {
+            shutdown::install();
+            let client = http_client();
+            let result = match Config::load(config::CONFIG_FILE) {
+                Ok(config) => tokio::select! {
+                    result = single_source::run(args, &config, &client) => result,
+                    () = shutdown::requested() => {
+                        shutdown::close_browsers().await;
+                        Err("Interrupted".to_string())
+                    }
+                },
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Baseline(args)) => {
+            let result = match (&args.command, Config::load(config::CONFIG_FILE)) {
+                (cli::BaselineCommand::List, _) => {
+                    baseline::print_baselines();
+                    Ok(())
+                }
+                (cli::BaselineCommand::Save(save), Ok(config)) => baseline::save(save, &config, &http_client()).await,
+                (_, Err(e)) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Canary(args)) => {
+            let result = sources::selectors::run_canary(&http_client(), args.profile.as_deref(), args.old.as_deref(), &args.new).await;
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Verify) => {
+            let result = match Config::load(config::CONFIG_FILE) {
+                Ok(config) => verify::verify(&http_client(), &config.politeness).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Maintenance) => {
+            match Config::load(config::CONFIG_FILE) {
+                Ok(config) => {
+                    maintenance::run(&config.maintenance, &config.capture);
+                }
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(cli::Command::Export(args)) => {
+            let result = if args.features {
+                let output = args.output.clone().unwrap_or_else(|| storage::profile_path("features.csv"));
+                analysis::features::export_features(&output, args.window_hours, args.redact)
+            } else {
+                redact::export(args)
+            };
+            if let Err(e) = result {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::DealSheet(args)) => {
+            if let Err(e) = deal_sheet::export(args).await {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(cli::Command::Match(args)) => {
+            let config = match Config::load(config::CONFIG_FILE) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let attributes = ProductAttributes::parse(&args.first);
+            let thresholds = config.thresholds_for(attributes.model.as_deref());
+            let comparison = similarity::compare(&args.first, &args.second, thresholds.fuzzy_weight);
+            similarity::print_comparison(&args.first, &args.second, &comparison);
+            if !same_model(&attributes, &ProductAttributes::parse(&args.second), thresholds) {
+                println!("   ⚠️  B doesn't name A's model ({}), never a comp", attributes.model.unwrap_or_default());
+            }
+            // Title patterns only; URL rules need real listings
+            if let Some((verdict, rule)) = MatchOverrides::load().rule_for("", &args.first, "", &args.second) {
+                let action = if verdict == Verdict::Force { "📌 Forced as a comp" } else { "🚫 Never a comp" };
+                println!("   {} by {}: {} / {}", action, match_overrides::OVERRIDES_FILE, rule.buy, rule.comp);
+                if let Some(note) = &rule.note {
+                    println!("      {}", note);
+                }
+            }
+            return;
+        }
+        Some(cli::Command::Catalog(args)) => {
+            // Registers [categories] with the catalog
+            if let Err(e) = Config::load(config::CONFIG_FILE) {
+                eprintln!("❌ {}", e);
+                std::process::exit(1);
+            }
+            catalog::print_catalog(args.title.as_deref());
+            return;
+        }
+        Some(cli::Command::BadMatch(args)) => {
+            match feedback::mark_bad_match(&args.buy_url, args.reason.clone()) {
+                Ok(bad_match) => feedback::print_bad_match(&bad_match),
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        None => {}
+    }
 
-    println!("🛒 Product Scraper - Newegg, Swappa & eBay");
+    let mut config = match Config::load(config::CONFIG_FILE) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    };
+    // Command-line thresholds win over the config file (per-model overrides still apply)
+    if let Some(min_margin) = cli.min_margin {
+        config.thresholds.min_margin_percent = min_margin;
+    }
+    if let Some(min_profit) = cli.min_profit {
+        config.thresholds.min_profit = min_profit;
+    }
+    if let Some(min_similarity) = cli.min_similarity {
+        config.thresholds.min_similarity = min_similarity;
+    }
+    if cli.verbose_export {
+        config.export.verbose = true;
+    }
+
+    let client = http_client();
+
+    println!("🛒 Product Scraper - Newegg, Swappa, eBay & Mercari");
     println!("⏰ Running every 1 minute. Press Ctrl+C to stop.");
-    println!("📁 Tracking seen products in: {}\n", SEEN_PRODUCTS_FILE);
+    println!("📁 Tracking seen products in: {}", storage::profile_path(SEEN_PRODUCTS_FILE));
+    println!("🐢 Politeness: {}\n", config.politeness.preset.label());
     
     // Load previously seen products
-    let mut seen_products = load_seen_products();
+    let seen_products = load_seen_products();
     println!("📊 Loaded {} previously seen products\n", seen_products.len());
+    let seen_products: supervisor::SeenProducts = Arc::new(Mutex::new(seen_products));
+    
+    // Older exports kept every run inline; move them to runs/ once
+    if let Some(existing) = load_frontend_data() {
+        storage::migrate_run_history(existing.run_history, config.maintenance.run_history_days);
+    }
     
-    let mut run_count = 0;
+    if config.api.enabled {
+        tokio::spawn(api::serve(config.api.bind.clone()));
+    }
+    
+    // Continue numbering from stored history so run ids stay unique across restarts
+    let mut run_count = storage::load_run_index().last().map_or(0, |entry| entry.run_id);
+    let mut last_maintenance: Option<chrono::NaiveDate> = None;
+    // A run that died partway through is picked up where it stopped
+    let resumed = checkpoint::resumable(run_count + 1, &config.export);
+    let mut supervisor = supervisor::Supervisor::start(Arc::new(config.clone()), client.clone(), seen_products.clone(), resumed);
+    shutdown::install();
     
     loop {
         run_count += 1;
@@ -1492,199 +2669,214 @@ async fn main() {
         println!("🔄 SCRAPE RUN #{} - {}", run_count, now.format("%Y-%m-%d %H:%M:%S"));
         println!("{}", "=".repeat(60));
 
-        // Scrape Newegg
-        println!("\n📦 Scraping Newegg...\n");
-        let all_newegg_products = deduplicate_products(scrape_newegg(&client).await);
-        let newegg_products = filter_new_products(all_newegg_products.clone(), &mut seen_products);
+        // Every source is an actor; the run is assembled from their reports.
+        // Ctrl+C leaves the run unfinished; it's resumed from its checkpoint.
+        let mut reports = tokio::select! {
+            reports = supervisor.run(run_count) => reports,
+            () = shutdown::requested() => break,
+        };
+        sessions::close_all().await;
+        let (newegg_scraped, swappa_scraped) = (reports.scraped_now(supervisor::Role::Newegg), reports.scraped_now(supervisor::Role::Swappa));
+        let newegg = reports.take(supervisor::Role::Newegg);
+        let (all_newegg_products, newegg_category_products) = (newegg.products, newegg.targeted);
+        let swappa = reports.take(supervisor::Role::Swappa);
+        let all_swappa_products = swappa.products;
+        let ebay = reports.take(supervisor::Role::Ebay);
+        let all_ebay_products = ebay.products;
+        let mercari = reports.take(supervisor::Role::Mercari);
+        let (all_mercari_products, all_mercari_sold) = (mercari.products, mercari.sold);
+        let decluttr_prices = reports.take(supervisor::Role::Decluttr).decluttr_prices;
+        let mut auctions = reports.take(supervisor::Role::Auctions).auctions;
+        let (all_storefront_products, storefront_new_count) = reports.take_storefronts();
+        let apple_refurb = sources::apple_refurb::watch(&all_storefront_products);
         
-        println!("\n{}", "-".repeat(60));
-        println!("NEWEGG: {} total, {} NEW", all_newegg_products.len(), newegg_products.len());
-        println!("{}", "-".repeat(60));
+        // Compare extraction quality against saved `baseline save` results
+        let baseline_regressions = baseline::check(
+            all_swappa_products.iter()
+                .chain(all_newegg_products.iter())
+                .chain(all_ebay_products.iter())
+                .chain(all_mercari_products.iter())
+                .chain(all_mercari_sold.iter())
+                .chain(all_storefront_products.iter()),
+            &config.baseline,
+        );
         
-        // Always show all scraped items with links
-        if !all_newegg_products.is_empty() {
-            println!("\n📋 ALL SCRAPED NEWEGG ITEMS ({}):", all_newegg_products.len());
-            for (i, product) in all_newegg_products.iter().enumerate() {
-                println!("\n{}. {}", i + 1, product.name);
-                println!("   💰 Price: {}", product.price);
-                println!("   🔗 {}", product.url);
-            }
+        // Individually tracked listings: price drops, sold, ended
+        let (tracked_listings, tracking_alerts) = tracking::check_all(&client).await;
+
+        // Swappa/Newegg listings that disappeared: sold or removed, and after how long
+        let mut lifecycle_sources: Vec<(&str, &[Product])> = Vec::new();
+        if swappa_scraped {
+            lifecycle_sources.push(("Swappa", &all_swappa_products));
         }
-        
-        if newegg_products.is_empty() {
-            println!("\n  ℹ️  No new Newegg products found this run");
-        } else {
-            println!("\n🆕 NEW NEWEGG PRODUCTS:");
-            for (i, product) in newegg_products.iter().take(15).enumerate() {
-                println!("\n{}. {}", i + 1, product.name);
-                println!("   💰 Price: {}", product.price);
-                println!("   🔗 {}", product.url);
-            }
+        if newegg_scraped {
+            lifecycle_sources.push(("Newegg", &all_newegg_products));
         }
+        let listing_lifecycle = lifecycle::update(&lifecycle_sources, &config.lifecycle, &config.politeness, &client).await;
+        lifecycle::display_lifecycle(&listing_lifecycle);
+        let mut price_drops = price_drops::detect(
+            run_count,
+            all_swappa_products.iter()
+                .chain(all_newegg_products.iter())
+                .chain(all_mercari_products.iter())
+                .chain(all_storefront_products.iter()),
+            &config.price_drops,
+        );
 
-        // Fetch detailed info for new Newegg products
-        let newegg_details = if !newegg_products.is_empty() {
-            fetch_product_details(&client, &newegg_products, 5).await
+        // Trade-in quotes, the guaranteed floor under each opportunity
+        let trade_in_quotes = if config.tradein.enabled {
+            println!("\n\n🛟 Fetching trade-in quotes...\n");
+            tradein::fetch_quotes(&client, &config.tradein).await
         } else {
             Vec::new()
         };
-        
-        if !newegg_details.is_empty() {
-            println!("\n{}", "=".repeat(60));
-            println!("📦 NEW NEWEGG DETAILED PRODUCTS ({})", newegg_details.len());
-            println!("{}", "=".repeat(60));
-            
-            for (i, detail) in newegg_details.iter().enumerate() {
-                println!("\n{}. {}", i + 1, detail.name);
-                println!("   💰 Price: {}", detail.price);
-                println!("   📝 Description: {}", if detail.description.len() > 100 { 
-                    format!("{}...", &detail.description[..100]) 
-                } else { 
-                    detail.description.clone() 
-                });
-                println!("   🏷️  Condition: {}", detail.condition);
-                println!("   👤 Seller: {}", detail.seller);
-                if !detail.specs.is_empty() {
-                    println!("   📋 Specs ({}):", detail.specs.len());
-                    for spec in detail.specs.iter().take(3) {
-                        println!("      - {}", if spec.len() > 60 { format!("{}...", &spec[..60]) } else { spec.clone() });
-                    }
-                }
-                if !detail.images.is_empty() {
-                    println!("   🖼️  Images: {}", detail.images.len());
-                }
-                println!("   🔗 {}", detail.url);
-            }
-        }
-
-        sleep(Duration::from_millis(2000)).await;
 
-        // Scrape Swappa
-        println!("\n\n📱 Scraping Swappa...\n");
-        let all_swappa_products = deduplicate_products(scrape_swappa(&client).await);
-        let swappa_products = filter_new_products(all_swappa_products.clone(), &mut seen_products);
+        // Price Comparison & Arbitrage Analysis
+        println!("\n\n{}", "=".repeat(60));
+        println!("💰 PRICE COMPARISON & PROFIT MARGINS");
+        println!("{}", "=".repeat(60));
         
-        println!("\n{}", "-".repeat(60));
-        println!("SWAPPA: {} total, {} NEW", all_swappa_products.len(), swappa_products.len());
-        println!("{}", "-".repeat(60));
+        // Buy side: Swappa + Mercari active + storefronts (incl. eBay BIN). Comps: per
+        // buy-side device from [comps.providers] (eBay sold + Mercari sold by default).
+        // Newegg is only a buy source in refurbished mode; the category crawl is mostly non-phones.
+        // [categories] Newegg pages are targeted searches, so they count in either mode.
+        // Accessories matching [exclusions] keywords are dropped from both sides,
+        // and lots are priced per unit (or dropped) per [lots].
+        let newegg_buy_side: &[Product] = if config.newegg.mode == NeweggMode::Refurbished { &all_newegg_products } else { &newegg_category_products };
+        let mut exclusion_report = exclusions::ExclusionReport::default();
+        let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let buy_side: Vec<Product> = all_swappa_products.iter()
+            .chain(all_mercari_products.iter())
+            .chain(all_storefront_products.iter())
+            .chain(newegg_buy_side.iter())
+            .filter(|p| !deal_expired(p, &now))
+            .filter(|p| !exclusion_report.exclude_buy(p, &config.exclusions))
+            .cloned()
+            .collect();
+        let comp_devices: BTreeSet<String> = buy_side.iter()
+            .chain(auctions.iter().map(|a| &a.product))
+            .filter_map(|p| p.attributes.model.clone())
+            .collect();
+        let comp_tiers = comp_providers::tiers(&config.comps.providers, &client, &all_ebay_products, &all_mercari_sold);
+        let sold_comps: Vec<Product> = comp_providers::gather(&comp_tiers, &comp_devices, &config.comps.providers).await
+            .into_iter()
+            .filter(|p| !exclusion_report.exclude_comp(p, &config.exclusions))
+            .collect();
+        let buy_side = exclusion_report.apply_lots(buy_side, &config.lots);
+        let sold_comps = exclusion_report.apply_lots(sold_comps, &config.lots);
+        auctions.retain(|a| !exclusion_report.exclude_buy(&a.product, &config.exclusions));
+        let auctions: Vec<_> = auctions
+            .into_iter()
+            .filter_map(|mut auction| {
+                auction.product = exclusion_report.apply_lots(vec![auction.product], &config.lots).pop()?;
+                Some(auction)
+            })
+            .collect();
+        exclusion_report.display();
         
-        // Always show all scraped items with links
-        if !all_swappa_products.is_empty() {
-            println!("\n📋 ALL SCRAPED SWAPPA ITEMS ({}):", all_swappa_products.len());
-            for (i, product) in all_swappa_products.iter().enumerate() {
-                println!("\n{}. {}", i + 1, product.name);
-                println!("   💰 Price: {}", product.price);
-                println!("   🔗 {}", product.url);
-            }
+        let color_premiums = analysis::color::color_premiums(&sold_comps, &config);
+        // Reloaded each run so bad matches marked via CLI/API apply right away
+        let suppressions = feedback::Suppressions::load();
+        if suppressions.len() > 0 {
+            println!("\n🙅 Suppressing {} buy/comp pairs marked as bad matches", suppressions.len());
         }
-        
-        if swappa_products.is_empty() {
-            println!("\n  ℹ️  No new Swappa products found this run");
-        } else {
-            println!("\n🆕 NEW SWAPPA PRODUCTS:");
-            for (i, product) in swappa_products.iter().take(15).enumerate() {
-                println!("\n{}. {}", i + 1, product.name);
-                println!("   💰 Price: {}", product.price);
-                println!("   🔗 {}", product.url);
-            }
+        let overrides = MatchOverrides::load();
+        if overrides.len() > 0 {
+            println!("📌 Applying {} rules from {}", overrides.len(), match_overrides::OVERRIDES_FILE);
         }
-
-        // Fetch detailed info for new Swappa products using Selenium
-        let swappa_details = if !swappa_products.is_empty() {
-            fetch_swappa_details_selenium(&swappa_products, 5).await
-        } else {
-            Vec::new()
-        };
+        let arbitrage_opportunities = find_arbitrage_opportunities(&buy_side, &sold_comps, &color_premiums, &suppressions, &overrides, &config);
+        let auction_opportunities = find_auction_opportunities(&auctions, &sold_comps, &color_premiums, &suppressions, &overrides, &config);
         
-        if !swappa_details.is_empty() {
-            println!("\n{}", "=".repeat(60));
-            println!("📱 NEW SWAPPA DETAILED PRODUCTS ({})", swappa_details.len());
-            println!("{}", "=".repeat(60));
-            
-            for (i, detail) in swappa_details.iter().enumerate() {
-                println!("\n{}. {}", i + 1, detail.name);
-                println!("   💰 Price: {}", detail.price);
-                println!("   📝 Description: {}", if detail.description.len() > 100 { 
-                    format!("{}...", &detail.description[..100]) 
-                } else { 
-                    detail.description.clone() 
-                });
-                println!("   🏷️  Condition: {}", detail.condition);
-                println!("   👤 Seller: {}", detail.seller);
-                if !detail.specs.is_empty() {
-                    println!("   📋 Specs ({}):", detail.specs.len());
-                    for spec in detail.specs.iter().take(3) {
-                        println!("      - {}", if spec.len() > 60 { format!("{}...", &spec[..60]) } else { spec.clone() });
-                    }
-                }
-                if !detail.images.is_empty() {
-                    println!("   🖼️  Images: {}", detail.images.len());
-                }
-                println!("   🔗 {}", detail.url);
-            }
-        }
-
-        sleep(Duration::from_millis(2000)).await;
-
-        // Scrape eBay
-        println!("\n\n🛍️ Scraping eBay...\n");
-        let all_ebay_products = deduplicate_products(scrape_ebay(&client).await);
-        let ebay_products = filter_new_products(all_ebay_products.clone(), &mut seen_products);
+        // Margins this high are almost always matching errors or scams, so they
+        // are set aside for review instead of ranking as opportunities
+        let (needs_review, mut arbitrage_opportunities): (Vec<_>, Vec<_>) = arbitrage_opportunities
+            .into_iter()
+            .partition(|o| o.margin_percent > config.review.max_margin_percent);
         
-        println!("\n{}", "-".repeat(60));
-        println!("EBAY: {} total, {} NEW", all_ebay_products.len(), ebay_products.len());
-        println!("{}", "-".repeat(60));
+        // Drop anything over the per-item / per-device spend limits
+        arbitrage_opportunities.retain(|o| analysis::portfolio::within_limits(&o.product_name, o.source_price, &config.bankroll));
         
-        // Always show all scraped items with links
-        if !all_ebay_products.is_empty() {
-            println!("\n📋 ALL SCRAPED EBAY ITEMS ({}):", all_ebay_products.len());
-            for (i, product) in all_ebay_products.iter().enumerate() {
-                println!("\n{}. {}", i + 1, product.name);
-                println!("   💰 Price: {}", product.price);
-                println!("   🔗 {}", product.url);
+        // Custom accept()/score() filters; reloaded each run so edits apply without a restart
+        if let Some(path) = &config.scripting.filter_script {
+            match scripting::ScriptFilter::load(path) {
+                Ok(filter) => arbitrage_opportunities = filter.apply(arbitrage_opportunities),
+                Err(e) => {
+                    eprintln!("  ❌ {}", e);
+                    error_journal::record(ErrorCategory::Script, "Filter script", Some(path), &e, None);
+                }
             }
         }
         
-        if ebay_products.is_empty() {
-            println!("\n  ℹ️  No new eBay products found this run");
-        } else {
-            println!("\n🆕 NEW EBAY PRODUCTS:");
-            for (i, product) in ebay_products.iter().take(15).enumerate() {
-                println!("\n{}. {}", i + 1, product.name);
-                println!("   💰 Price: {}", product.price);
-                println!("   🔗 {}", product.url);
-            }
+        for drop in &mut price_drops {
+            drop.opportunity = arbitrage_opportunities.iter().any(|o| o.source_product.url == drop.url);
+        }
+        // Read before this run is stored
+        let previous = analysis::trend::previous_values();
+        display_arbitrage_opportunities(&arbitrage_opportunities, &trade_in_quotes, &previous, &price_drops, config.export.verbose);
+        display_needs_review(&needs_review, config.review.max_margin_percent);
+        if config.auctions.enabled {
+            display_auction_opportunities(&auction_opportunities);
         }
-
-        // Price Comparison & Arbitrage Analysis
-        println!("\n\n{}", "=".repeat(60));
-        println!("💰 PRICE COMPARISON & PROFIT MARGINS");
-        println!("{}", "=".repeat(60));
-        
-        let arbitrage_opportunities = find_arbitrage_opportunities(
-            &all_newegg_products,
-            &all_swappa_products,
-            &all_ebay_products,
-        );
-        
-        display_arbitrage_opportunities(&arbitrage_opportunities);
         
         // Show best deals summary
         if !arbitrage_opportunities.is_empty() {
             println!("\n🏆 TOP 5 BEST PROFIT OPPORTUNITIES:");
             for (i, opp) in arbitrage_opportunities.iter().take(5).enumerate() {
-                println!("   {}. ${:.2} potential profit ({:.1}%) - {}", 
+                println!("   {}. {} potential profit ({:.1}%) - {}", 
                     i + 1, opp.profit, opp.margin_percent, truncate_string(&opp.product_name, 40));
             }
         }
 
+        // Compare buy sources against each other, independent of sold comps
+        let all_buy_sources: Vec<Product> = buy_side.iter()
+            .chain(all_newegg_products.iter())
+            .cloned()
+            .collect();
+        let source_comparisons = analysis::cross_source::compare_buy_sources(&all_buy_sources, &config);
+        price_drops::display_price_drops(&price_drops);
+        analysis::cross_source::display_source_comparisons(&source_comparisons);
+        analysis::color::display_color_premiums(&color_premiums);
+        sources::apple_refurb::display_watch(&apple_refurb);
+        let mut reference_prices = analysis::reference::compare_to_sold(&decluttr_prices, &all_ebay_products, &config);
+        reference_prices.extend(analysis::reference::compare_products_to_sold(
+            sources::google_store::SOURCE_NAME,
+            &all_storefront_products,
+            &all_ebay_products,
+            &config,
+        ));
+        analysis::reference::display_reference_prices(&reference_prices);
+        let timing_report = analysis::timing::timing_report(&sold_comps, &config);
+        analysis::timing::display_timing_report(&timing_report);
+
         // Save seen products after each run
-        save_seen_products(&seen_products);
+        let total_tracked = {
+            let seen = seen_products.lock().unwrap_or_else(|e| e.into_inner());
+            save_seen_products(&seen);
+            seen.len()
+        };
 
         // Save data for frontend with run history
-        let frontend_arbitrage = convert_to_arbitrage_opportunities(&arbitrage_opportunities);
-        let swappa_with_comparison = create_products_with_comparison(&all_swappa_products, &all_ebay_products);
-        let newegg_with_comparison = create_products_with_comparison(&all_newegg_products, &all_ebay_products);
+        // Attach trade-in floors and user notes/tags; notes are read fresh so
+        // edits made via CLI/API mid-run are included
+        let annotations = notes::load_annotations();
+        let annotate = |mut opportunities: Vec<ArbitrageOpportunity>| {
+            for opp in &mut opportunities {
+                opp.trade_in_floor = tradein::floor_for(&opp.buy_product_name, opp.buy_condition, &trade_in_quotes);
+                opp.annotation = annotations.get(&opp.buy_url).cloned();
+                opp.trend = previous.trend(&opp.buy_url, opp.potential_profit, opp.margin_percent);
+                opp.price_drop = price_drops.iter().find(|d| d.url == opp.buy_url).cloned();
+            }
+            opportunities
+        };
+        let frontend_arbitrage = annotate(convert_to_arbitrage_opportunities(&arbitrage_opportunities, config.export.verbose));
+        let portfolio = analysis::portfolio::suggest_portfolio(&frontend_arbitrage, &config.bankroll);
+        analysis::portfolio::display_portfolio(&portfolio);
+        let inventory = inventory::value_inventory(&sold_comps, &overrides, &config);
+        inventory::display_valuations(&inventory);
+        let swappa_with_comparison = create_products_with_comparison(&all_swappa_products, &sold_comps, &overrides, &config);
+        let newegg_with_comparison = create_products_with_comparison(&all_newegg_products, &sold_comps, &overrides, &config);
+        let mercari_with_comparison = create_products_with_comparison(&all_mercari_products, &sold_comps, &overrides, &config);
+        let storefront_with_comparison = create_products_with_comparison(&all_storefront_products, &sold_comps, &overrides, &config);
         
         // Create current run snapshot
         let current_run = RunSnapshot {
@@ -1692,7 +2884,10 @@ async fn main() {
             timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             swappa_products: swappa_with_comparison,
             newegg_products: newegg_with_comparison,
+            mercari_products: mercari_with_comparison,
+            storefront_products: storefront_with_comparison,
             ebay_sold_products: all_ebay_products.clone(),
+            mercari_sold_products: all_mercari_sold.clone(),
             arbitrage_opportunities: frontend_arbitrage.clone(),
             total_swappa: all_swappa_products.len(),
             total_newegg: all_newegg_products.len(),
@@ -1700,52 +2895,120 @@ async fn main() {
             best_opportunity: frontend_arbitrage.first().cloned(),
         };
         
-        // Load existing history and append
-        let mut run_history = if let Some(existing) = load_frontend_data() {
-            existing.run_history
-        } else {
-            Vec::new()
-        };
-        
-        run_history.push(current_run);
+        let price_history = analysis::price_history::record(
+            run_count,
+            &current_run.timestamp,
+            analysis::price_history::model_stats(&all_swappa_products, &all_ebay_products, &config),
+        );
+        // The export only carries the last day of history; the baseline needs the file
+        let market_heat = analysis::heat::market_heat(&analysis::price_history::load(), &config.market_heat);
+        let crawl_audit = rate_limit::take_audit();
         
-        // Keep only last MAX_HISTORY_RUNS
-        if run_history.len() > MAX_HISTORY_RUNS {
-            let skip_count = run_history.len() - MAX_HISTORY_RUNS;
-            run_history = run_history.into_iter().skip(skip_count).collect();
+        // Store the run in runs/, keeping [maintenance] run_history_days of them
+        if let Err(e) = storage::save_run(&current_run, config.maintenance.run_history_days) {
+            eprintln!("❌ {}", e);
+            error_journal::record(ErrorCategory::Storage, "Run history", None, &e, None);
         }
         
         let frontend_data = ScraperData {
             last_updated: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             run_count,
+            partial: false,
             newegg_products: all_newegg_products.clone(),
             swappa_products: all_swappa_products.clone(),
             ebay_products: all_ebay_products.clone(),
+            mercari_products: all_mercari_products.clone(),
+            mercari_sold_products: all_mercari_sold.clone(),
+            storefront_products: all_storefront_products.clone(),
             arbitrage_opportunities: frontend_arbitrage,
-            total_tracked: seen_products.len(),
-            run_history,
+            needs_review: annotate(convert_to_arbitrage_opportunities(&needs_review, config.export.verbose)),
+            source_comparisons,
+            color_premiums,
+            decluttr_prices,
+            reference_prices,
+            timing_report,
+            auction_opportunities,
+            exclusions: exclusion_report,
+            apple_refurb,
+            portfolio,
+            inventory,
+            trade_in_quotes,
+            price_history,
+            market_heat,
+            annotations: notes::for_urls(
+                &annotations,
+                all_swappa_products.iter()
+                    .chain(all_newegg_products.iter())
+                    .chain(all_mercari_products.iter())
+                    .chain(all_storefront_products.iter())
+                    .map(|p| &p.url),
+            ),
+            tracked_listings,
+            tracking_alerts,
+            price_drops,
+            listing_lifecycle,
+            crawl_audit,
+            total_tracked,
+            run_history: Vec::new(),
         };
         save_frontend_data(&frontend_data);
+        checkpoint::clear();
 
         // Summary
         println!("\n\n{}", "=".repeat(60));
         println!("📊 SUMMARY - Run #{}", run_count);
         println!("{}", "=".repeat(60));
-        println!("Newegg: {} total scraped, {} NEW", all_newegg_products.len(), newegg_products.len());
-        println!("Swappa: {} total scraped, {} NEW", all_swappa_products.len(), swappa_products.len());
-        println!("eBay: {} total scraped, {} NEW", all_ebay_products.len(), ebay_products.len());
-        println!("Total NEW this run: {}", newegg_products.len() + swappa_products.len() + ebay_products.len());
-        println!("Total products tracked: {}", seen_products.len());
+        let new_products = newegg.new_count + swappa.new_count + ebay.new_count + mercari.new_count + storefront_new_count;
+        println!("Newegg: {} total scraped, {} NEW", all_newegg_products.len(), newegg.new_count);
+        println!("Swappa: {} total scraped, {} NEW", all_swappa_products.len(), swappa.new_count);
+        println!("eBay: {} total scraped, {} NEW", all_ebay_products.len(), ebay.new_count);
+        println!("Mercari: {} active scraped, {} NEW, {} sold", all_mercari_products.len(), mercari.new_count, all_mercari_sold.len());
+        println!("Storefronts: {} total scraped, {} NEW", all_storefront_products.len(), storefront_new_count);
+        println!("Total NEW this run: {}", new_products);
+        println!("Total products tracked: {}", total_tracked);
+        analysis::heat::display_market_heat(frontend_data.market_heat.as_ref());
+        reports.display_health();
+        rate_limit::display_crawl_audit(&frontend_data.crawl_audit);
+        
+        let run_summary = hooks::RunSummary {
+            run_id: run_count,
+            timestamp: frontend_data.last_updated.clone(),
+            total_newegg: all_newegg_products.len(),
+            total_swappa: all_swappa_products.len(),
+            total_ebay_sold: all_ebay_products.len(),
+            total_mercari: all_mercari_products.len(),
+            total_mercari_sold: all_mercari_sold.len(),
+            total_storefront: all_storefront_products.len(),
+            new_products,
+            opportunities: arbitrage_opportunities.len(),
+            needs_review: needs_review.len(),
+            apple_refurb_drops: frontend_data.apple_refurb.new_arrivals.len(),
+            best_profit: arbitrage_opportunities.first().map(|o| o.profit),
+            digest: digest::write_digest(&config.digest, frontend_data.market_heat.as_ref()),
+            baseline_regressions,
+            tracking_alerts: frontend_data.tracking_alerts.len(),
+            notifications: notify::send(&config.notify, &client, &frontend_data.arbitrage_opportunities).await,
+        };
+        hooks::run_post_run_hook(&config.hooks, &storage::profile_path(FRONTEND_DATA_FILE), &run_summary).await;
+        
+        if maintenance::due(&config.maintenance, last_maintenance) {
+            maintenance::run(&config.maintenance, &config.capture);
+            last_maintenance = Some(Local::now().date_naive());
+        }
         
         // Wait 1 minute before next scrape
         println!("\n⏳ Next scrape in 60 seconds...");
         println!("   Press Ctrl+C to stop.");
-        sleep(Duration::from_secs(60)).await;
+        tokio::select! {
+            () = sleep(Duration::from_secs(60)) => {}
+            () = shutdown::requested() => break,
+        }
     }
+    shutdown::close_browsers().await;
 }
 
 // Fetch Swappa product details using Selenium (since regular HTTP doesn't work)
-async fn fetch_swappa_details_selenium(products: &[Product], max_items: usize) -> Vec<ProductDetails> {
+async fn fetch_swappa_details_selenium(products: &[Product], max_items: usize, browser: &BrowserConfig) -> Vec<ProductDetails> {
     let mut details = Vec::new();
     
     // Only process products with actual listing URLs
@@ -1771,24 +3034,27 @@ async fn fetch_swappa_details_selenium(products: &[Product], max_items: usize) -
     caps.add_arg("--disable-blink-features=AutomationControlled").ok();
     caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
     
-    let driver = match WebDriver::new("http://localhost:9515", caps).await {
+    let driver = match sessions::open(browser, "Swappa", caps).await {
         Ok(d) => d,
         Err(e) => {
             eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
+            error_journal::record(ErrorCategory::Driver, "Swappa", None, &e, None);
             return details;
         }
     };
     
+    let progress = progress::Progress::new(products_to_fetch.len(), "Swappa details");
     for (i, product) in products_to_fetch.iter().enumerate() {
-        println!("    [{}/{}] Fetching: {}", i + 1, products_to_fetch.len(), 
-            if product.name.len() > 50 { &product.name[..50] } else { &product.name });
+        progress.step(i, &format!("Fetching: {}", truncate_string(&product.name, 50)));
         
-        if let Err(e) = driver.goto(&product.url).await {
+        if let Err(e) = retry::goto(&driver, &product.url).await {
             eprintln!("      ❌ Failed to navigate: {}", e);
+            error_journal::record(ErrorCategory::Navigation, "Swappa", Some(&product.url), &e, None);
             continue;
         }
         
-        sleep(Duration::from_secs(3)).await;
+        // The listing renders client-side; wait for its title rather than a fixed time
+        let _ = driver.query(By::Css("h1, .listing-title")).wait(Duration::from_secs(10), Duration::from_millis(250)).first().await;
         
         // Extract detailed info using JavaScript
         let detail_result = driver.execute(
@@ -1841,7 +3107,7 @@ async fn fetch_swappa_details_selenium(products: &[Product], max_items: usize) -
             let json = info_value.json();
             
             let name = json.get("name").and_then(|v| v.as_str()).unwrap_or(&product.name).to_string();
-            let price = json.get("price").and_then(|v| v.as_str()).unwrap_or(&product.price).to_string();
+            let price = json.get("price").and_then(|v| v.as_str()).and_then(price::parse_money).or(product.price);
             let description = json.get("description").and_then(|v| v.as_str()).unwrap_or("").to_string();
             let condition = json.get("condition").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string();
             let seller = json.get("seller").and_then(|v| v.as_str()).unwrap_or("Unknown").to_string();
@@ -1868,11 +3134,10 @@ async fn fetch_swappa_details_selenium(products: &[Product], max_items: usize) -
                 seller: if seller.is_empty() { "Unknown".to_string() } else { seller },
             });
         }
-        
-        sleep(Duration::from_secs(2)).await;
     }
+    progress.finish();
     
-    let _ = driver.quit().await;
+    sessions::release(browser, "Swappa", driver).await;
     
     details
 }
\ No newline at end of file
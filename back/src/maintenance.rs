@@ -0,0 +1,93 @@
+// Nightly housekeeping for long-running deployments: compact the run history,
+// evict old error journal entries, and delete stale captures and screenshots,
+// all in one pass with a one-line summary. Run history is plain JSON files,
+// so there is no database to vacuum; compaction rewrites the index instead.
+
+use chrono::{Local, NaiveDate, NaiveTime};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::config::{CaptureConfig, MaintenanceConfig};
+use crate::{error_journal, storage};
+
+// Screenshot directories written by the Selenium scrapers
+pub const SCREENSHOT_DIRS: [&str; 2] = ["/tmp/swappa_screenshots", "/tmp/ebay_screenshots"];
+
+#[derive(Debug, Default)]
+pub struct MaintenanceSummary {
+    pub runs_removed: usize,
+    pub errors_evicted: usize,
+    pub captures_removed: usize,
+    pub screenshots_removed: usize,
+}
+
+// True once the configured time has passed today and maintenance hasn't run today
+pub fn due(config: &MaintenanceConfig, last_run: Option<NaiveDate>) -> bool {
+    if !config.enabled {
+        return false;
+    }
+    let now = Local::now().naive_local();
+    let Ok(at) = NaiveTime::parse_from_str(&config.time, "%H:%M") else {
+        eprintln!("⚠️  Invalid [maintenance] time '{}', expected HH:MM", config.time);
+        return false;
+    };
+    last_run != Some(now.date()) && now.time() >= at
+}
+
+// Delete files under `dir` last modified before `max_age`, then any
+// directories left empty. Returns the number of files removed.
+fn remove_older_than(dir: &Path, max_age: Duration) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            removed += remove_older_than(&path, max_age);
+            let _ = fs::remove_dir(&path); // only succeeds when empty
+            continue;
+        }
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| SystemTime::now().duration_since(modified).unwrap_or_default() > max_age);
+        if expired && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+fn days(n: u64) -> Duration {
+    Duration::from_secs(n * 24 * 60 * 60)
+}
+
+pub fn run(config: &MaintenanceConfig, capture: &CaptureConfig) -> MaintenanceSummary {
+    println!("\n🧹 Running maintenance...");
+    let mut summary = MaintenanceSummary::default();
+
+    match storage::compact_runs(config.run_history_days) {
+        Ok(removed) => summary.runs_removed = removed,
+        Err(e) => eprintln!("  ❌ Run history compaction failed: {}", e),
+    }
+
+    let cutoff = Local::now() - chrono::Duration::days(config.error_ttl_days as i64);
+    match error_journal::evict_before(cutoff) {
+        Ok(evicted) => summary.errors_evicted = evicted,
+        Err(e) => eprintln!("  ❌ Error journal eviction failed: {}", e),
+    }
+
+    summary.captures_removed = remove_older_than(Path::new(&capture.dir), days(config.capture_ttl_days));
+    summary.screenshots_removed = SCREENSHOT_DIRS
+        .iter()
+        .map(|dir| remove_older_than(Path::new(dir), days(config.screenshot_ttl_days)))
+        .sum();
+
+    println!(
+        "🧹 Maintenance done: {} runs compacted, {} journal entries evicted, {} captures and {} screenshots removed",
+        summary.runs_removed, summary.errors_evicted, summary.captures_removed, summary.screenshots_removed
+    );
+    summary
+}
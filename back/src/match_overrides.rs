@@ -0,0 +1,127 @@
+// User-maintained match overrides (match_overrides.toml). Each rule names a
+// buy listing and a comp, by URL or by pattern: `force` rules pair them
+// whatever the similarity score says, `forbid` rules keep them apart. The
+// matcher consults the rules before scoring; when both kinds name a pair,
+// forbid wins. Unlike `back bad-match`, which records pairs from one run, a
+// pattern keeps applying to listings that don't exist yet. The file is reread
+// every run, so edits apply without a restart.
+//
+//   [[forbid]]
+//   buy = "*iphone 13 pro*"
+//   comp = "*pro max*"
+//   note = "eBay keeps pairing the Pro with the Pro Max"
+//
+//   [[force]]
+//   buy = "https://swappa.com/listing/view/LABC12345"
+//   comp = "https://www.ebay.com/itm/123456789012"
+
+use serde::Deserialize;
+
+use crate::Product;
+
+pub const OVERRIDES_FILE: &str = "match_overrides.toml";
+
+// A pattern is an exact URL or title, or uses `*` for any run of
+// characters; titles compare lowercase with whitespace collapsed
+#[derive(Debug, Clone, Deserialize)]
+pub struct OverrideRule {
+    pub buy: String,
+    pub comp: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MatchOverrides {
+    pub force: Vec<OverrideRule>,
+    pub forbid: Vec<OverrideRule>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Force,
+    Forbid,
+}
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// `*` matches any run of characters, everything else itself
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is trying to cover
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Let the `*` swallow one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// Whether a pattern names the listing, by its URL or its title
+fn names(pattern: &str, url: &str, title: &str) -> bool {
+    let pattern: Vec<char> = normalize(pattern).chars().collect();
+    [url, title].iter().any(|text| !text.is_empty() && glob(&pattern, &normalize(text).chars().collect::<Vec<_>>()))
+}
+
+impl OverrideRule {
+    fn pairs(&self, buy_url: &str, buy_title: &str, comp_url: &str, comp_title: &str) -> bool {
+        names(&self.buy, buy_url, buy_title) && names(&self.comp, comp_url, comp_title)
+    }
+}
+
+impl MatchOverrides {
+    pub fn load_from(path: &str) -> Result<MatchOverrides, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        toml::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", path, e))
+    }
+
+    // match_overrides.toml; missing means no overrides, a broken file is reported and ignored
+    pub fn load() -> MatchOverrides {
+        if !std::path::Path::new(OVERRIDES_FILE).exists() {
+            return MatchOverrides::default();
+        }
+        MatchOverrides::load_from(OVERRIDES_FILE).unwrap_or_else(|e| {
+            eprintln!("  ⚠️  {} - matching without overrides", e);
+            MatchOverrides::default()
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.force.len() + self.forbid.len()
+    }
+
+    // Whether any rule names this buy listing; its comps are then matched on
+    // their own rather than shared with other listings of the same device
+    pub fn applies_to(&self, buy: &Product) -> bool {
+        self.force.iter().chain(&self.forbid).any(|rule| names(&rule.buy, &buy.url, &buy.name))
+    }
+
+    // The deciding rule for a (buy, comp) pair, if any
+    pub fn rule_for(&self, buy_url: &str, buy_title: &str, comp_url: &str, comp_title: &str) -> Option<(Verdict, &OverrideRule)> {
+        let pairs = |rule: &&OverrideRule| rule.pairs(buy_url, buy_title, comp_url, comp_title);
+        self.forbid
+            .iter()
+            .find(pairs)
+            .map(|rule| (Verdict::Forbid, rule))
+            .or_else(|| self.force.iter().find(pairs).map(|rule| (Verdict::Force, rule)))
+    }
+
+    pub fn verdict_for(&self, buy: &Product, comp: &Product) -> Option<Verdict> {
+        self.rule_for(&buy.url, &buy.name, &comp.url, &comp.name).map(|(verdict, _)| verdict)
+    }
+}
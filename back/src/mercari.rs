@@ -0,0 +1,175 @@
+// Mercari scraper - active listings are a buy source, sold listings are comps
+// (same role as eBay SOLD data in the arbitrage engine)
+
+use std::collections::HashMap;
+use thirtyfour::prelude::*;
+
+use crate::attributes::ProductAttributes;
+use crate::config::BrowserConfig;
+use crate::politeness::Politeness;
+use crate::{Product, price, retry, sessions, single_source};
+use crate::error_journal::{self, ErrorCategory};
+
+pub struct MercariListings {
+    pub active: Vec<Product>,
+    pub sold: Vec<Product>,
+    // Active listings found per search, for the empty-device check
+    pub device_counts: HashMap<String, usize>,
+}
+
+// Mercari search statuses: 1 = on sale, 2 = sold
+const STATUS_ON_SALE: u8 = 1;
+const STATUS_SOLD: u8 = 2;
+// How far down a results page is scrolled so lazy cards render
+const SCROLL_DEPTH_PX: u64 = 4000;
+
+// Extract item cards from a Mercari search results page
+const EXTRACT_SCRIPT: &str = r#"
+    var products = [];
+    var seenUrls = new Set();
+
+    var links = document.querySelectorAll('a[href*="/item/m"]');
+    for (var i = 0; i < links.length && products.length < 50; i++) {
+        var link = links[i];
+        var cleanUrl = (link.href || '').split('?')[0];
+        if (!cleanUrl || seenUrls.has(cleanUrl)) continue;
+
+        var container = link.closest('[data-testid="ItemContainer"]') || link;
+
+        // Title lives in ItemName, fall back to the link's accessible label
+        var nameEl = container.querySelector('[data-testid="ItemName"]');
+        var name = nameEl ? nameEl.innerText : (link.getAttribute('aria-label') || link.innerText || '');
+        name = name.split('\n')[0].trim();
+        if (!name || name.length < 10) continue;
+
+        var priceEl = container.querySelector('[data-testid="ItemPrice"], [data-testid="ProductThumbItemPrice"]');
+        var priceText = priceEl ? priceEl.innerText : (container.innerText || '');
+        var priceMatch = priceText.match(/\$[\d,]+\.?\d{0,2}/);
+        if (!priceMatch) continue;
+
+        seenUrls.add(cleanUrl);
+        products.push({
+            name: name.substring(0, 200),
+            price: priceMatch[0],
+            url: cleanUrl
+        });
+    }
+
+    return { products: products, total: products.length };
+"#;
+
+fn search_url(query: &str, status: u8) -> String {
+    format!(
+        "https://www.mercari.com/search/?keyword={}&itemStatuses={}",
+        query.replace(' ', "+"),
+        status
+    )
+}
+
+async fn scrape_search(driver: &WebDriver, politeness: &Politeness, url: &str) -> Vec<Product> {
+    let mut products = Vec::new();
+
+    if let Err(e) = retry::goto(driver, url).await {
+        eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
+        error_journal::record(ErrorCategory::Navigation, "Mercari", Some(url), &e, None);
+        return products;
+    }
+
+    // Wait for page to load, then scroll so lazy cards render
+    politeness.behavior.wait_for_load(driver).await;
+    politeness.behavior.scroll(driver, SCROLL_DEPTH_PX).await;
+
+    if let Ok(result_value) = driver.execute(EXTRACT_SCRIPT, vec![]).await {
+        let json = result_value.json();
+        if let Some(products_arr) = json.get("products").and_then(|v| v.as_array()) {
+            for product in products_arr {
+                let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                let price = product.get("price").and_then(|v| v.as_str()).unwrap_or("");
+                let prod_url = product.get("url").and_then(|v| v.as_str()).unwrap_or("");
+
+                if !name.is_empty() && !price.is_empty() && !prod_url.is_empty() {
+                    products.push(Product {
+                        name: name.to_string(),
+                        price: price::parse_money(price),
+                        url: prod_url.to_string(),
+                        source: "Mercari".to_string(),
+                        sold_date: None,
+                        grade: None,
+                        warranty: None,
+                        expires_at: None,
+                        region: None,
+                        currency: None,
+                        attributes: ProductAttributes::default(),
+                        listed_at: None,
+                        lot_quantity: None,
+                    });
+                }
+            }
+        }
+    }
+
+    products
+}
+
+pub async fn scrape_mercari(browser: &BrowserConfig, politeness: &Politeness, device: Option<&str>) -> MercariListings {
+    let mut listings = MercariListings {
+        active: Vec::new(),
+        sold: Vec::new(),
+        device_counts: HashMap::new(),
+    };
+
+    println!("  Starting Selenium WebDriver for Mercari...");
+
+    let mut caps = DesiredCapabilities::chrome();
+    caps.add_arg("--headless=new").ok();
+    caps.add_arg("--disable-gpu").ok();
+    caps.add_arg("--no-sandbox").ok();
+    caps.add_arg("--disable-dev-shm-usage").ok();
+    caps.add_arg("--window-size=1920,1200").ok();
+    caps.add_arg("--disable-blink-features=AutomationControlled").ok();
+    caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
+
+    let driver = match sessions::open(browser, "Mercari", caps).await {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
+            error_journal::record(ErrorCategory::Driver, "Mercari", None, &e, None);
+            return listings;
+        }
+    };
+
+    println!("  ✓ Connected to ChromeDriver");
+
+    // Same device set as the eBay sold searches so comps line up
+    let searches: Vec<(&str, &str)> = vec![
+        ("iPhone 15", "iphone 15 unlocked"),
+        ("iPhone 14", "iphone 14 unlocked"),
+        ("iPhone 13", "iphone 13 unlocked"),
+        ("Galaxy S24", "samsung galaxy s24 unlocked"),
+        ("Galaxy S23", "samsung galaxy s23 unlocked"),
+        ("Pixel 8", "google pixel 8 unlocked"),
+        ("Pixel 7", "google pixel 7 unlocked"),
+    ];
+
+    for (category, query) in searches.iter().filter(|(category, _)| single_source::device_matches(category, device)) {
+        println!("  🏷️ Scraping Mercari {}", category);
+
+        let active = scrape_search(&driver, politeness, &search_url(query, STATUS_ON_SALE)).await;
+        println!("    🔍 {} active listings", active.len());
+        listings.device_counts.insert(category.to_string(), active.len());
+        listings.active.extend(active);
+        politeness.behavior.between_pages(&driver).await;
+
+        let sold = scrape_search(&driver, politeness, &search_url(query, STATUS_SOLD)).await;
+        println!("    🔍 {} sold listings", sold.len());
+        listings.sold.extend(sold);
+        politeness.behavior.between_pages(&driver).await;
+    }
+
+    // Kept for the next scrape of the site this run
+    sessions::release(browser, "Mercari", driver).await;
+
+    println!("  ✓ Mercari scraping complete. Found {} active, {} sold", listings.active.len(), listings.sold.len());
+
+    listings
+}
@@ -0,0 +1,283 @@
+// Money amounts. Prices, sold-comp stats and profits are kept as whole minor
+// units (cents) with their currency, so sums and comparisons are exact
+// ($0.10 + $0.20 is $0.30) and the export never carries float noise like
+// 45.499999999999986. Ratios (margins, similarity) stay f64.
+//
+// An amount serializes as {"amount": "189.99", "currency": "USD"}: the exact
+// decimal as a string, and its currency, so it reads back unchanged. Runs
+// stored before amounts carried a currency hold plain numbers or decimal
+// strings; every price was in USD then, so those read as USD.
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Cad,
+    Aud,
+    Nzd,
+    Jpy,
+}
+
+impl Currency {
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Cad => "CAD",
+            Currency::Aud => "AUD",
+            Currency::Nzd => "NZD",
+            Currency::Jpy => "JPY",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Cad => "C$",
+            Currency::Aud => "A$",
+            Currency::Nzd => "NZ$",
+            Currency::Jpy => "¥",
+        }
+    }
+
+    // Digits after the decimal point: 2, or 0 for yen
+    pub fn minor_digits(self) -> u32 {
+        match self {
+            Currency::Jpy => 0,
+            _ => 2,
+        }
+    }
+
+    // "USD" -> Currency::Usd
+    pub fn from_code(code: &str) -> Option<Currency> {
+        [Currency::Usd, Currency::Eur, Currency::Gbp, Currency::Cad, Currency::Aud, Currency::Nzd, Currency::Jpy]
+            .into_iter()
+            .find(|currency| currency.code().eq_ignore_ascii_case(code))
+    }
+
+    fn minor_per_major(self) -> i64 {
+        10_i64.pow(self.minor_digits())
+    }
+}
+
+// Ordered by amount; amounts of different currencies shouldn't be compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Money {
+    minor: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub const fn from_minor(minor: i64, currency: Currency) -> Money {
+        Money { minor, currency }
+    }
+
+    // Rounded to the minor unit, halves away from zero; NaN and infinities are zero
+    pub fn from_major(amount: f64, currency: Currency) -> Money {
+        let minor = amount * currency.minor_per_major() as f64;
+        Money { minor: if minor.is_finite() { minor.round() as i64 } else { 0 }, currency }
+    }
+
+    pub fn usd(amount: f64) -> Money {
+        Money::from_major(amount, Currency::Usd)
+    }
+
+    // A plain decimal ("189.99", "-5", "0.5") read exactly; None for anything
+    // else, or more decimals than the currency has
+    pub fn parse_decimal(text: &str, currency: Currency) -> Option<Money> {
+        let text = text.trim();
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let places = currency.minor_digits() as usize;
+        let numeric = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !numeric(whole) || !numeric(fraction) || fraction.len() > places {
+            return None;
+        }
+        let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+        let fraction: i64 = format!("{:0<width$}", fraction, width = places).parse().unwrap_or(0);
+        let minor = whole.checked_mul(currency.minor_per_major())?.checked_add(fraction)?;
+        Some(Money { minor: if negative { -minor } else { minor }, currency })
+    }
+
+    pub fn currency(self) -> Currency {
+        self.currency
+    }
+
+    // "189.99", "-0.05", "1500" for yen: exact, without a symbol
+    pub fn decimal(self) -> String {
+        let digits = self.currency.minor_digits() as usize;
+        let sign = if self.minor < 0 { "-" } else { "" };
+        let (amount, per_major) = (self.minor.unsigned_abs(), self.currency.minor_per_major().unsigned_abs());
+        let (major, minor) = (amount / per_major, amount % per_major);
+        if digits == 0 { format!("{}{}", sign, major) } else { format!("{}{}.{:0digits$}", sign, major, minor) }
+    }
+
+    // In major units, for statistics and ratios
+    pub fn amount(self) -> f64 {
+        self.minor as f64 / self.currency.minor_per_major() as f64
+    }
+
+    pub fn abs(self) -> Money {
+        Money { minor: self.minor.abs(), ..self }
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.minor > 0
+    }
+
+    // Multiplied by `factor` (fee rates, condition adjustments), rounded to the minor unit
+    pub fn scale(self, factor: f64) -> Money {
+        Money::from_major(self.amount() * factor, self.currency)
+    }
+
+    // This amount as a percentage of `base`; 0 when `base` is zero
+    pub fn percent_of(self, base: Money) -> f64 {
+        if base.minor == 0 { 0.0 } else { self.minor as f64 / base.minor as f64 * 100.0 }
+    }
+
+    // Compares self/base with other/other_base exactly (profit per dollar spent);
+    // bases must be positive
+    pub fn cmp_ratio(self, base: Money, other: Money, other_base: Money) -> Ordering {
+        (self.minor as i128 * other_base.minor as i128).cmp(&(other.minor as i128 * base.minor as i128))
+    }
+
+    // Mean rounded to the minor unit; None for no amounts
+    pub fn mean(amounts: &[Money]) -> Option<Money> {
+        let first = amounts.first()?;
+        let total: i128 = amounts.iter().map(|m| m.minor as i128).sum();
+        let count = amounts.len() as i128;
+        // Round half away from zero
+        let mean = (2 * total + total.signum() * count) / (2 * count);
+        Some(Money { minor: mean as i64, currency: first.currency })
+    }
+
+    // Quantile `q` (0.0-1.0) of ascending amounts, interpolated linearly
+    // between ranks in minor units; None for no amounts
+    pub fn quantile(sorted: &[Money], q: f64) -> Option<Money> {
+        let last = sorted.len().checked_sub(1)?;
+        let rank = q.clamp(0.0, 1.0) * last as f64;
+        let (lower, upper) = (sorted[rank.floor() as usize], sorted[rank.ceil() as usize]);
+        let step = ((upper.minor - lower.minor) as f64 * rank.fract()).round() as i64;
+        Some(Money { minor: lower.minor + step, ..lower })
+    }
+
+    // Median in any order; None for no amounts
+    pub fn median(amounts: &[Money]) -> Option<Money> {
+        let mut sorted = amounts.to_vec();
+        sorted.sort();
+        Money::quantile(&sorted, 0.5)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        debug_assert_eq!(self.currency, rhs.currency, "adding {} to {}", rhs, self);
+        Money { minor: self.minor + rhs.minor, ..self }
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        debug_assert_eq!(self.currency, rhs.currency, "subtracting {} from {}", rhs, self);
+        Money { minor: self.minor - rhs.minor, ..self }
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Money) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Money) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money { minor: -self.minor, ..self }
+    }
+}
+
+// "$1299.99", "-$5.00", "€450.00", or "+$5.00" with {:+}; width and
+// alignment apply to the whole text
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.currency.minor_digits() as usize;
+        let sign = if self.minor < 0 {
+            "-"
+        } else if f.sign_plus() {
+            "+"
+        } else {
+            ""
+        };
+        let text = format!("{}{}{:.*}", sign, self.currency.symbol(), digits, self.abs().amount());
+        f.pad(&text)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut money = serializer.serialize_struct("Money", 2)?;
+        money.serialize_field("amount", &self.decimal())?;
+        money.serialize_field("currency", &self.currency)?;
+        money.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Amount {
+    Decimal(String),
+    Major(f64),
+}
+
+impl Amount {
+    fn into_money(self, currency: Currency) -> Option<Money> {
+        match self {
+            Amount::Decimal(text) => Money::parse_decimal(&text, currency),
+            Amount::Major(amount) => amount.is_finite().then(|| Money::from_major(amount, currency)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Stored {
+    Tagged { amount: Amount, currency: Currency },
+    // Runs from before amounts carried a currency
+    Untagged(Amount),
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        let (amount, currency) = match Stored::deserialize(deserializer)? {
+            Stored::Tagged { amount, currency } => (amount, currency),
+            Stored::Untagged(amount) => (amount, Currency::Usd),
+        };
+        amount.into_money(currency).ok_or_else(|| serde::de::Error::custom("expected a decimal amount"))
+    }
+}
@@ -0,0 +1,106 @@
+// User notes and tags on listings ("negotiating", "seller slow"), keyed by
+// listing URL so they follow a product or opportunity across runs. Managed via
+// `back note` / `back tag` / `back notes` or POST /api/notes, and copied into
+// the export for every listing in the current run.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::cli::NotesArgs;
+use crate::storage::{profile_path, write_json};
+
+pub const NOTES_FILE: &str = "notes.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    pub added_at: DateTime<Local>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+// A change to one listing's annotation, as sent by the CLI or API
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AnnotationUpdate {
+    pub url: String,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+}
+
+// URL -> annotation
+pub fn load_annotations() -> BTreeMap<String, Annotation> {
+    match fs::read_to_string(profile_path(NOTES_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+pub fn annotate(update: AnnotationUpdate) -> Result<Annotation, String> {
+    if update.url.trim().is_empty() {
+        return Err("A listing URL is required".to_string());
+    }
+    let mut annotations = load_annotations();
+    let annotation = annotations.entry(update.url.clone()).or_default();
+
+    if let Some(text) = update.note.filter(|t| !t.trim().is_empty()) {
+        annotation.notes.push(Note { added_at: Local::now(), text });
+    }
+    for tag in update.add_tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !annotation.tags.contains(&tag) {
+            annotation.tags.push(tag);
+        }
+    }
+    annotation.tags.retain(|t| !update.remove_tags.iter().any(|r| r.trim().eq_ignore_ascii_case(t)));
+
+    let result = annotation.clone();
+    if result.notes.is_empty() && result.tags.is_empty() {
+        annotations.remove(&update.url);
+    }
+    write_json(&profile_path(NOTES_FILE), &annotations)?;
+    Ok(result)
+}
+
+// Annotations for the given URLs only, for the export
+pub fn for_urls<'a>(annotations: &BTreeMap<String, Annotation>, urls: impl Iterator<Item = &'a String>) -> BTreeMap<String, Annotation> {
+    urls.filter_map(|url| annotations.get(url).map(|a| (url.clone(), a.clone()))).collect()
+}
+
+pub fn print_annotation(url: &str, annotation: &Annotation) {
+    println!("🔗 {}", url);
+    if !annotation.tags.is_empty() {
+        println!("   🏷️  {}", annotation.tags.join(", "));
+    }
+    for note in &annotation.notes {
+        println!("   📝 {} {}", note.added_at.format("%Y-%m-%d %H:%M"), note.text);
+    }
+}
+
+pub fn print_notes(args: &NotesArgs) {
+    let annotations = load_annotations();
+    let tag = args.tag.as_ref().map(|t| t.to_lowercase());
+    let matching: Vec<_> = annotations
+        .iter()
+        .filter(|(_, a)| tag.as_ref().is_none_or(|t| a.tags.contains(t)))
+        .collect();
+
+    if matching.is_empty() {
+        println!("No matching notes in {}", profile_path(NOTES_FILE));
+        return;
+    }
+    for (url, annotation) in matching {
+        print_annotation(url, annotation);
+    }
+}
@@ -0,0 +1,215 @@
+// Opportunity notifications to chat webhooks: Google Chat (cardsV2) and
+// Microsoft Teams (Adaptive Cards), one card per opportunity with buttons to
+// the buy listing and its top sold comps. Each [[notify.webhooks]] entry routes
+// on profit, margin, buy source and model; throttling is shared: a listing is
+// announced to a webhook once per cooldown unless its price drops, and at most
+// max_per_run cards go to a webhook per run, most profitable first.
+
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+use crate::attributes::ProductAttributes;
+use crate::config::{NotifyConfig, WebhookConfig, WebhookFormat};
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::{ArbitrageOpportunity, storage};
+
+const NOTIFIED_FILE: &str = "notified.json";
+
+// Comp buttons per card, after the buy button
+const COMP_BUTTONS: usize = 3;
+
+// Last announcement of a buy listing to one webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Notice {
+    at: DateTime<Local>,
+    price: Money,
+}
+
+// Webhook name -> buy URL -> last notice
+type NotifiedState = HashMap<String, HashMap<String, Notice>>;
+
+fn load_state() -> NotifiedState {
+    std::fs::read_to_string(storage::profile_path(NOTIFIED_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+impl WebhookConfig {
+    fn routes(&self, opp: &ArbitrageOpportunity) -> bool {
+        if opp.potential_profit < Money::usd(self.min_profit) || opp.margin_percent < self.min_margin_percent {
+            return false;
+        }
+        if !self.sources.is_empty() && !self.sources.iter().any(|s| s.eq_ignore_ascii_case(&opp.buy_source)) {
+            return false;
+        }
+        if self.models.is_empty() {
+            return true;
+        }
+        let model = ProductAttributes::parse(&opp.buy_product_name).model;
+        model.is_some_and(|model| self.models.iter().any(|m| m.eq_ignore_ascii_case(&model)))
+    }
+}
+
+// Whether a listing was announced within the cooldown at the same or a lower price
+fn throttled(notice: Option<&Notice>, opp: &ArbitrageOpportunity, cooldown: Duration, now: DateTime<Local>) -> bool {
+    notice.is_some_and(|notice| now - notice.at < cooldown && opp.buy_price >= notice.price)
+}
+
+fn summary(opp: &ArbitrageOpportunity) -> (String, String) {
+    let profit = format!("{} net ({:.1}% margin)", opp.potential_profit, opp.margin_percent);
+    let comps = format!(
+        "{} sold from {}, median {} (P25 {} - P75 {})",
+        opp.ebay_sold_count,
+        opp.comp_sources.join(", "),
+        opp.ebay_median_sold_price,
+        opp.ebay_p25_sold_price,
+        opp.ebay_p75_sold_price
+    );
+    (profit, comps)
+}
+
+// (label, url) for the buy listing and the first few comps
+fn links(opp: &ArbitrageOpportunity) -> Vec<(String, String)> {
+    let mut links = vec![(format!("Buy on {} {}", opp.buy_source, opp.buy_price), opp.buy_url.clone())];
+    if opp.comps.is_empty() {
+        links.extend(opp.sample_ebay_urls.iter().take(COMP_BUTTONS).enumerate().map(|(i, url)| (format!("Comp {}", i + 1), url.clone())));
+    } else {
+        links.extend(opp.comps.iter().take(COMP_BUTTONS).map(|c| (format!("{} comp {}", c.source, c.price), c.url.clone())));
+    }
+    links
+}
+
+fn google_chat_card(opp: &ArbitrageOpportunity) -> Value {
+    let (profit, comps) = summary(opp);
+    let buttons: Vec<Value> = links(opp)
+        .into_iter()
+        .map(|(text, url)| json!({"text": text, "onClick": {"openLink": {"url": url}}}))
+        .collect();
+    json!({
+        "text": format!("💰 {}", opp.buy_product_name),
+        "cardsV2": [{
+            "cardId": "opportunity",
+            "card": {
+                "header": {"title": opp.buy_product_name, "subtitle": format!("{} · {}", opp.buy_source, opp.buy_price)},
+                "sections": [{
+                    "widgets": [
+                        {"decoratedText": {"topLabel": "Potential profit", "text": profit}},
+                        {"decoratedText": {"topLabel": "Sold comps", "text": comps, "wrapText": true}},
+                        {"buttonList": {"buttons": buttons}}
+                    ]
+                }]
+            }
+        }]
+    })
+}
+
+fn teams_card(opp: &ArbitrageOpportunity) -> Value {
+    let (profit, comps) = summary(opp);
+    let actions: Vec<Value> = links(opp)
+        .into_iter()
+        .map(|(title, url)| json!({"type": "Action.OpenUrl", "title": title, "url": url}))
+        .collect();
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {"type": "TextBlock", "text": format!("💰 {}", opp.buy_product_name), "weight": "Bolder", "size": "Medium", "wrap": true},
+                    {"type": "FactSet", "facts": [
+                        {"title": "Buy", "value": format!("{} {}", opp.buy_source, opp.buy_price)},
+                        {"title": "Profit", "value": profit},
+                        {"title": "Sold comps", "value": comps}
+                    ]}
+                ],
+                "actions": actions
+            }
+        }]
+    })
+}
+
+async fn post(client: &reqwest::Client, webhook: &WebhookConfig, payload: &Value) -> Result<(), String> {
+    let response = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_string())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("HTTP {}: {}", status, body.chars().take(200).collect::<String>()));
+    }
+    Ok(())
+}
+
+// Announce this run's opportunities to every configured webhook; returns how
+// many cards went out. Failures are journaled and never abort the run.
+pub async fn send(config: &NotifyConfig, client: &reqwest::Client, opportunities: &[ArbitrageOpportunity]) -> usize {
+    if config.webhooks.is_empty() || opportunities.is_empty() {
+        return 0;
+    }
+
+    let now = Local::now();
+    let cooldown = Duration::hours(config.cooldown_hours);
+    let mut state = load_state();
+    let mut sent = 0;
+
+    let mut ranked: Vec<&ArbitrageOpportunity> = opportunities.iter().collect();
+    ranked.sort_by_key(|o| std::cmp::Reverse(o.potential_profit));
+
+    for webhook in &config.webhooks {
+        let notified = state.entry(webhook.name.clone()).or_default();
+        let due: Vec<&ArbitrageOpportunity> = ranked
+            .iter()
+            .filter(|opp| webhook.routes(opp) && !throttled(notified.get(&opp.buy_url), opp, cooldown, now))
+            .take(config.max_per_run)
+            .copied()
+            .collect();
+        if due.is_empty() {
+            continue;
+        }
+
+        let mut delivered = 0;
+        for opp in due {
+            let payload = match webhook.format {
+                WebhookFormat::GoogleChat => google_chat_card(opp),
+                WebhookFormat::Teams => teams_card(opp),
+            };
+            match post(client, webhook, &payload).await {
+                Ok(()) => {
+                    delivered += 1;
+                    notified.insert(opp.buy_url.clone(), Notice { at: now, price: opp.buy_price });
+                }
+                Err(e) => {
+                    // The webhook URL is a credential; journal the name only
+                    eprintln!("  ❌ Webhook {} failed: {}", webhook.name, e);
+                    error_journal::record(ErrorCategory::Http, &format!("Webhook {}", webhook.name), None, &e, None);
+                    break;
+                }
+            }
+        }
+        if delivered > 0 {
+            println!("  📣 Sent {} opportunities to {}", delivered, webhook.name);
+        }
+        sent += delivered;
+    }
+
+    // Forget notices past the cooldown so the file doesn't grow forever
+    for notified in state.values_mut() {
+        notified.retain(|_, notice| now - notice.at < cooldown);
+    }
+    if let Err(e) = storage::write_json(&storage::profile_path(NOTIFIED_FILE), &state) {
+        eprintln!("  ⚠️  {}", e);
+        error_journal::record(ErrorCategory::Storage, "Notify", None, &e, None);
+    }
+    sent
+}
@@ -0,0 +1,74 @@
+// The WebDriver calls the Selenium scrapers make, behind a trait so their
+// page handling (navigation, scrolling, captured JSON, extraction scripts)
+// can run against saved script results instead of a live ChromeDriver.
+// WebDriver is the real implementation; the tests' FixtureDriver answers
+// from a JSON file per scrape. Launching and quitting the browser stay with
+// WebDriver itself.
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::time::Duration;
+use thirtyfour::extensions::cdp::ChromeDevTools;
+use thirtyfour::prelude::*;
+use tokio::time::sleep;
+
+#[async_trait]
+pub trait PageDriver: Send + Sync {
+    async fn open(&self, url: &str) -> Result<(), String>;
+    // Run `script` in the current page and return its result as JSON
+    async fn run_script(&self, script: &str) -> Result<Value, String>;
+    async fn screenshot_png(&self) -> Result<Vec<u8>, String>;
+    // Evaluate `source` before any page script of every document loaded from now on
+    async fn add_init_script(&self, source: &str) -> Result<(), String>;
+    // Trusted pointer input (CDP Input.dispatchMouseEvent) at viewport (x, y):
+    // a move there, or a wheel scroll of `delta_y` pixels
+    async fn move_mouse(&self, x: f64, y: f64) -> Result<(), String>;
+    async fn wheel(&self, x: f64, y: f64, delta_y: f64) -> Result<(), String>;
+    // Give the page time to load or render lazy content
+    async fn settle(&self, duration: Duration);
+}
+
+async fn mouse_event(driver: &WebDriver, event: Value) -> Result<(), String> {
+    ChromeDevTools::new(driver.handle.clone())
+        .execute_cdp_with_params("Input.dispatchMouseEvent", event)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+// Calls go through `handle`: trait methods would shadow its inherent ones on WebDriver
+#[async_trait]
+impl PageDriver for WebDriver {
+    async fn open(&self, url: &str) -> Result<(), String> {
+        crate::retry::goto(self, url).await.map_err(|e| e.to_string())
+    }
+
+    async fn run_script(&self, script: &str) -> Result<Value, String> {
+        let result = self.handle.execute(script, vec![]).await.map_err(|e| e.to_string())?;
+        Ok(result.json().clone())
+    }
+
+    async fn screenshot_png(&self) -> Result<Vec<u8>, String> {
+        self.handle.screenshot_as_png().await.map_err(|e| e.to_string())
+    }
+
+    async fn add_init_script(&self, source: &str) -> Result<(), String> {
+        ChromeDevTools::new(self.handle.clone())
+            .execute_cdp_with_params("Page.addScriptToEvaluateOnNewDocument", json!({ "source": source }))
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn move_mouse(&self, x: f64, y: f64) -> Result<(), String> {
+        mouse_event(self, json!({ "type": "mouseMoved", "x": x, "y": y })).await
+    }
+
+    async fn wheel(&self, x: f64, y: f64, delta_y: f64) -> Result<(), String> {
+        mouse_event(self, json!({ "type": "mouseWheel", "x": x, "y": y, "deltaX": 0, "deltaY": delta_y })).await
+    }
+
+    async fn settle(&self, duration: Duration) {
+        sleep(duration).await;
+    }
+}
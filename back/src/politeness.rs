@@ -0,0 +1,105 @@
+// Scraping etiquette presets. Each preset bundles how long a browser source
+// pauses between pages, how many pages a plain-HTTP source fetches at once,
+// how deep it pages and how many retries it may make per run, so
+// trading speed against block risk is one setting: [politeness] preset for
+// every source, [politeness.sources] to pick a different one per source. A
+// browser source's scrolling and waits come with it, from its behavior
+// profile (behavior.rs). Spacing between requests to a domain is
+// rate_limit.rs's job.
+
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::sleep;
+
+use crate::behavior::{Behavior, BehaviorProfile};
+use crate::fetch_html_within;
+use crate::retry::Budget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    // Slow and shallow, for sites that block quickly
+    Gentle,
+    #[default]
+    Normal,
+    // Short pauses and parallel page fetches
+    Aggressive,
+}
+
+impl Preset {
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Gentle => "gentle",
+            Preset::Normal => "normal",
+            Preset::Aggressive => "aggressive",
+        }
+    }
+}
+
+// A preset's settings for one source during one run
+#[derive(Debug)]
+pub struct Politeness {
+    // Multiplies the browser's pause between pages
+    pub delay_factor: f64,
+    // Pages a plain-HTTP source fetches at once
+    pub concurrency: usize,
+    // Upper bound on result pages per query; None keeps the source's own max_pages
+    pub max_pages: Option<usize>,
+    // Retries ([politeness.retry]) this source may still make this run
+    retries: Arc<Budget>,
+    // How a browser source scrolls and waits on its pages
+    pub behavior: Behavior,
+}
+
+impl Politeness {
+    pub fn new(preset: Preset) -> Politeness {
+        let (delay_factor, concurrency, max_pages, retry_budget) = match preset {
+            Preset::Gentle => (2.0, 1, Some(1), 2),
+            Preset::Normal => (1.0, 1, None, 5),
+            Preset::Aggressive => (0.5, 4, None, 20),
+        };
+        Politeness {
+            delay_factor,
+            concurrency,
+            max_pages,
+            retries: Arc::new(Budget::new(retry_budget)),
+            behavior: Behavior::new(BehaviorProfile::default(), delay_factor),
+        }
+    }
+
+    pub fn with_behavior(mut self, profile: BehaviorProfile) -> Politeness {
+        self.behavior = Behavior::new(profile, self.delay_factor);
+        self
+    }
+
+    // Wait `base_ms` scaled by the preset
+    pub async fn pause(&self, base_ms: u64) {
+        sleep(Duration::from_millis((base_ms as f64 * self.delay_factor) as u64)).await;
+    }
+
+    pub fn page_cap(&self, configured: usize) -> usize {
+        self.max_pages.map_or(configured, |cap| configured.min(cap))
+    }
+
+    // fetch_html, retried per [politeness.retry] while the budget lasts
+    pub async fn fetch(&self, client: &reqwest::Client, url: &str) -> Option<String> {
+        fetch_html_within(client, url, Some(&self.retries)).await
+    }
+
+    // Fetch a batch of pages at once (callers batch by `concurrency`),
+    // results in input order
+    pub async fn fetch_all(&self, client: &reqwest::Client, urls: &[String]) -> Vec<Option<String>> {
+        let mut results = vec![None; urls.len()];
+        let mut fetches = JoinSet::new();
+        for (i, url) in urls.iter().enumerate() {
+            let (client, url, retries) = (client.clone(), url.clone(), Arc::clone(&self.retries));
+            fetches.spawn(async move { (i, fetch_html_within(&client, &url, Some(&retries)).await) });
+        }
+        while let Some(Ok((i, html))) = fetches.join_next().await {
+            results[i] = html;
+        }
+        results
+    }
+}
@@ -0,0 +1,246 @@
+// Listing prices from scraped text. Sites write prices as "$499.99",
+// "US $499.99", "C$1,050", "1.299,00 €", "£450", "$499." or "99¢", and eBay
+// shows variation listings as a range ("$450.00 to $500.00"), which is priced
+// at its midpoint. The amount next to a currency marker wins over other
+// numbers in the text ("2 for $300" is $300); without any marker the first
+// number is the price.
+
+use serde::{Deserialize, Deserializer};
+
+use crate::money::{Currency, Money};
+
+// Digit group separators: "1,299.00", "1.299,00", "1'299.00", "1 299,00"
+const SEPARATORS: [char; 6] = ['.', ',', '\'', '\u{a0}', '\u{202f}', '\u{2009}'];
+// Words joining the two ends of a range
+const RANGE_JOINERS: [&str; 5] = ["to", "-", "–", "—", "~"];
+// Currency codes written out, e.g. "499.99 EUR"
+const CODES: [&str; 7] = ["USD", "EUR", "GBP", "CAD", "AUD", "JPY", "NZD"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Price {
+    pub amount: f64,
+    // ISO code when the text names a currency; a bare "$" is USD
+    pub currency: Option<&'static str>,
+}
+
+// A currency symbol or code at text[start..end]
+#[derive(Debug, Clone, Copy)]
+struct Marker {
+    start: usize,
+    end: usize,
+    currency: &'static str,
+    // "¢": the amount is in cents
+    cents: bool,
+}
+
+#[derive(Debug)]
+struct Number {
+    start: usize,
+    end: usize,
+    amount: f64,
+}
+
+// "C" + "$" -> CAD; the prefix may be separated from the "$" by a space ("US $499")
+fn dollar_prefix(before: &str) -> (&'static str, usize) {
+    let trimmed = before.strip_suffix(' ').unwrap_or(before);
+    for (prefix, currency) in [("US", "USD"), ("CA", "CAD"), ("AU", "AUD"), ("NZ", "NZD"), ("C", "CAD"), ("A", "AUD")] {
+        if let Some(rest) = trimmed.strip_suffix(prefix)
+            && !rest.ends_with(|c: char| c.is_alphanumeric())
+        {
+            return (currency, before.len() - rest.len());
+        }
+    }
+    ("USD", 0)
+}
+
+fn markers(text: &str) -> Vec<Marker> {
+    let mut markers = Vec::new();
+    for (i, c) in text.char_indices() {
+        let end = i + c.len_utf8();
+        let (currency, start, cents) = match c {
+            '$' => {
+                let (currency, prefix_len) = dollar_prefix(&text[..i]);
+                (currency, i - prefix_len, false)
+            }
+            '€' => ("EUR", i, false),
+            '£' => ("GBP", i, false),
+            '¥' | '円' => ("JPY", i, false),
+            '¢' => ("USD", i, true),
+            _ => continue,
+        };
+        markers.push(Marker { start, end, currency, cents });
+    }
+    for code in CODES {
+        for (i, _) in text.match_indices(code) {
+            let end = i + code.len();
+            let standalone = !text[..i].ends_with(|c: char| c.is_alphanumeric()) && !text[end..].starts_with(|c: char| c.is_alphanumeric());
+            // "USD" next to a "$" is already covered by it
+            if standalone && !markers.iter().any(|m| m.start <= i && i < m.end) {
+                markers.push(Marker { start: i, end, currency: code, cents: false });
+            }
+        }
+    }
+    markers.sort_by_key(|m| m.start);
+    markers
+}
+
+// The amount of digit groups joined by separators. The last separator is the
+// decimal point unless exactly three digits follow it (then it groups
+// thousands) or the other separators are of a different kind ("1.299,00").
+// Returns the amount and how many groups it used: a grouping separator not
+// followed by three digits ends the number.
+fn amount(groups: &[&str], separators: &[char]) -> (f64, usize) {
+    let decimal = match (separators.last(), groups.last()) {
+        (Some(&last), Some(digits)) if last == '.' || last == ',' => {
+            digits.len() != 3 || separators[..separators.len() - 1].iter().any(|&s| s != last)
+        }
+        _ => false,
+    };
+    let integer_groups = if decimal { groups.len() - 1 } else { groups.len() };
+
+    let mut integer = groups[0].to_string();
+    let mut used = 1;
+    while used < integer_groups && groups[used].len() == 3 {
+        integer.push_str(groups[used]);
+        used += 1;
+    }
+    let text = if decimal && used == integer_groups {
+        used += 1;
+        format!("{}.{}", integer, groups[groups.len() - 1])
+    } else {
+        integer
+    };
+    (text.parse().unwrap_or(0.0), used)
+}
+
+fn numbers(text: &str) -> Vec<Number> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].1.is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        // Digit groups, and the separator before each group after the first
+        let mut group_bounds = Vec::new();
+        let mut separators = Vec::new();
+        loop {
+            let group_start = i;
+            while i < chars.len() && chars[i].1.is_ascii_digit() {
+                i += 1;
+            }
+            group_bounds.push((group_start, i));
+            // A separator counts only between digits, so "$499." ends at 499
+            if i + 1 < chars.len() && SEPARATORS.contains(&chars[i].1) && chars[i + 1].1.is_ascii_digit() {
+                separators.push(chars[i].1);
+                i += 1;
+            } else {
+                break;
+            }
+        }
+
+        let byte = |index: usize| chars.get(index).map_or(text.len(), |(b, _)| *b);
+        let groups: Vec<&str> = group_bounds.iter().map(|&(s, e)| &text[byte(s)..byte(e)]).collect();
+        let (amount, used) = amount(&groups, &separators);
+        let end = group_bounds[used - 1].1;
+        numbers.push(Number { start: byte(group_bounds[0].0), end: byte(end), amount });
+        // Whatever the number didn't use is read again on its own
+        i = end;
+    }
+    numbers
+}
+
+// Whether only whitespace separates text[a..b]
+fn adjacent(text: &str, a: usize, b: usize) -> bool {
+    a <= b && text[a..b].trim().is_empty()
+}
+
+fn marker_for<'a>(text: &str, number: &Number, markers: &'a [Marker]) -> Option<&'a Marker> {
+    markers
+        .iter()
+        .find(|m| m.end <= number.start && adjacent(text, m.end, number.start))
+        .or_else(|| markers.iter().find(|m| m.start >= number.end && adjacent(text, number.end, m.start)))
+}
+
+// The text between two numbers with currency markers removed: "to" in "$450 to $500"
+fn joiner(text: &str, from: usize, to: usize, markers: &[Marker]) -> String {
+    text[from..to]
+        .char_indices()
+        .filter(|(i, _)| !markers.iter().any(|m| m.start <= from + i && from + i < m.end))
+        .map(|(_, c)| c)
+        .collect::<String>()
+        .trim()
+        .to_lowercase()
+}
+
+// Storefronts set the cents apart ("<strong>689</strong><sup>.99</sup>"),
+// which reads back as "$ 689 .99"
+fn join_split_cents(text: &str) -> String {
+    let mut joined = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find(" .") {
+        joined.push_str(&rest[..i]);
+        let after = &rest[i + 2..];
+        let cents = after.len() >= 2
+            && after.as_bytes()[..2].iter().all(u8::is_ascii_digit)
+            && !after[2..].starts_with(|c: char| c.is_ascii_digit());
+        joined.push_str(if cents && joined.ends_with(|c: char| c.is_ascii_digit()) { "." } else { " ." });
+        rest = after;
+    }
+    joined.push_str(rest);
+    joined
+}
+
+pub fn parse(text: &str) -> Option<Price> {
+    let text = &join_split_cents(text);
+    let markers = markers(text);
+    let numbers = numbers(text);
+    let index = numbers
+        .iter()
+        .position(|n| marker_for(text, n, &markers).is_some())
+        .or_else(|| (!numbers.is_empty()).then_some(0))?;
+    let number = &numbers[index];
+    let marker = marker_for(text, number, &markers);
+
+    let mut amount = number.amount;
+    // Both ends carry the currency if the first does: "$1,299 - 20% off" isn't a range
+    if let Some(next) = numbers.get(index + 1)
+        && RANGE_JOINERS.contains(&joiner(text, number.end, next.start, &markers).as_str())
+        && (marker.is_none() || marker_for(text, next, &markers).is_some())
+    {
+        amount = (amount + next.amount) / 2.0;
+    }
+    if marker.is_some_and(|m| m.cents) {
+        amount /= 100.0;
+    }
+    let currency = marker.or(markers.first()).map(|m| m.currency);
+    Some(Price { amount, currency })
+}
+
+// The price as an amount in the currency the text names; a bare number is USD
+pub fn parse_money(text: &str) -> Option<Money> {
+    let price = parse(text)?;
+    let currency = match price.currency {
+        Some(code) => Currency::from_code(code)?,
+        None => Currency::Usd,
+    };
+    Some(Money::from_major(price.amount, currency))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Listed {
+    Amount(Money),
+    Text(String),
+}
+
+// A listing's price as stored: an amount, or the scraped text older runs
+// kept ("$1,299.99", "Price not found"), parsed here
+pub fn deserialize_listing<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Money>, D::Error> {
+    Ok(match Option::<Listed>::deserialize(deserializer)? {
+        Some(Listed::Amount(money)) => Some(money),
+        Some(Listed::Text(text)) => parse_money(&text),
+        None => None,
+    })
+}
@@ -0,0 +1,120 @@
+// Price drops on listings seen before. The seen-products set only says a
+// listing isn't new, so a seller cutting the price went unnoticed even though
+// that's often the moment it turns into an opportunity. The last price of
+// every buy-side listing is kept by its dedup key (source + URL) in
+// listing_prices.json, and a lower price in a later run is reported as a drop.
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+use crate::config::PriceDropConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::{Product, product_key, storage, truncate_string};
+
+const LISTING_PRICES_FILE: &str = "listing_prices.json";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LastPrice {
+    price: Money,
+    run_id: u32,
+    seen_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceDrop {
+    pub url: String,
+    pub source: String,
+    pub name: String,
+    pub previous_price: Money,
+    pub price: Money,
+    // How much cheaper it is, positive
+    pub drop: Money,
+    pub drop_percent: f64,
+    // Run the previous price was seen in
+    pub previous_run_id: u32,
+    // The listing is an opportunity this run
+    #[serde(default)]
+    pub opportunity: bool,
+}
+
+impl PriceDrop {
+    // "price dropped by $25.00 (6.3%) since run 41"
+    pub fn label(&self) -> String {
+        format!("price dropped by {} ({:.1}%) since run {}", self.drop, self.drop_percent, self.previous_run_id)
+    }
+}
+
+fn load() -> HashMap<String, LastPrice> {
+    match fs::read_to_string(storage::profile_path(LISTING_PRICES_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+// Compare this run's listings with their last prices, record the new ones and
+// return the drops, biggest first
+pub fn detect<'a>(run_id: u32, listings: impl Iterator<Item = &'a Product>, config: &PriceDropConfig) -> Vec<PriceDrop> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    let now = Local::now().naive_local();
+    let seen_at = now.format(TIMESTAMP_FORMAT).to_string();
+    let mut prices = load();
+    let mut drops = Vec::new();
+    for product in listings {
+        let Some(price) = product.amount() else {
+            continue;
+        };
+        let key = product_key(product);
+        if let Some(last) = prices.get(&key)
+            && last.run_id != run_id
+            && price < last.price
+        {
+            let drop = last.price - price;
+            let drop_percent = drop.percent_of(last.price);
+            if drop.amount() >= config.min_drop && drop_percent >= config.min_percent {
+                drops.push(PriceDrop {
+                    url: product.url.clone(),
+                    source: product.source.clone(),
+                    name: product.name.clone(),
+                    previous_price: last.price,
+                    price,
+                    drop,
+                    drop_percent,
+                    previous_run_id: last.run_id,
+                    opportunity: false,
+                });
+            }
+        }
+        prices.insert(key, LastPrice { price, run_id, seen_at: seen_at.clone() });
+    }
+
+    // Listings gone longer than this won't come back at a lower price worth comparing
+    let cutoff = now - TimeDelta::days(config.retention_days);
+    prices.retain(|_, last| NaiveDateTime::parse_from_str(&last.seen_at, TIMESTAMP_FORMAT).is_ok_and(|at| at >= cutoff));
+    if let Err(e) = storage::write_json(&storage::profile_path(LISTING_PRICES_FILE), &prices) {
+        eprintln!("❌ {}", e);
+        error_journal::record(ErrorCategory::Storage, "Price drops", None, &e, None);
+    }
+    drops.sort_by(|a, b| b.drop.cmp(&a.drop).then_with(|| a.url.cmp(&b.url)));
+    drops
+}
+
+pub fn display_price_drops(drops: &[PriceDrop]) {
+    if drops.is_empty() {
+        return;
+    }
+    println!("\n📉 PRICE DROPS ({} listings cheaper than last run):", drops.len());
+    for drop in drops.iter().take(10) {
+        let opportunity = if drop.opportunity { " 💰 now an opportunity" } else { "" };
+        println!(
+            "   -{} ({:.1}%) {} → {} on {} - {}{}",
+            drop.drop, drop.drop_percent, drop.previous_price, drop.price, drop.source, truncate_string(&drop.name, 40), opportunity
+        );
+        println!("      🔗 {}", drop.url);
+    }
+}
@@ -0,0 +1,59 @@
+// Progress reporting for long crawls. On a terminal each loop gets an
+// indicatif bar; when output is piped or redirected to a log the bar is
+// skipped and each step is printed as a numbered line instead. Every bar
+// belongs to one MultiProgress, so sources scraping at once each keep their
+// own line, and console output goes through `println` to print above them.
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::LazyLock;
+
+const TEMPLATE: &str = "  {prefix} [{bar:30}] {pos}/{len} {elapsed_precise} {wide_msg}";
+
+static BARS: LazyLock<MultiProgress> = LazyLock::new(MultiProgress::new);
+
+// Print a line to stdout without tearing the bars that are running
+pub fn println(line: &str) {
+    BARS.suspend(|| println!("{}", line));
+}
+
+pub struct Progress {
+    bar: Option<ProgressBar>,
+    len: usize,
+}
+
+impl Progress {
+    pub fn new(len: usize, prefix: &str) -> Progress {
+        let bar = std::io::stderr().is_terminal().then(|| {
+            let bar = ProgressBar::new(len as u64).with_prefix(prefix.to_string());
+            if let Ok(style) = ProgressStyle::with_template(TEMPLATE) {
+                bar.set_style(style.progress_chars("=> "));
+            }
+            BARS.add(bar)
+        });
+        Progress { bar, len }
+    }
+
+    // Start step `i` (0-based) with a short description of what it fetches
+    pub fn step(&self, i: usize, message: &str) {
+        match &self.bar {
+            Some(bar) => {
+                bar.set_position(i as u64);
+                bar.set_message(message.to_string());
+            }
+            None => println!("  [{}/{}] {}", i + 1, self.len, message),
+        }
+    }
+
+    // Print a line without tearing the bar
+    pub fn println(&self, line: &str) {
+        println(line);
+    }
+
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+            BARS.remove(bar);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// Outbound proxies. With [proxy] urls set, the HTTP client and the Chrome
+// launches of the Selenium sources go out through a pool of HTTP or SOCKS
+// proxies instead of the machine's own address, so a long-running scrape
+// spreads its traffic over several IPs rather than getting one banned by
+// eBay or Newegg. The pool rotates per request (and per browser launch), or
+// sticks each site to one proxy. Local addresses (webhooks, ChromeDriver)
+// always connect directly. Chrome can't log in to a proxy from the command
+// line, so browser sources only use the proxies without credentials.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use thirtyfour::ChromeCapabilities;
+use thirtyfour::prelude::*;
+
+use crate::config::{ProxyConfig, ProxyRotation};
+use crate::rate_limit;
+
+const SCHEMES: [&str; 6] = ["http", "https", "socks4", "socks4a", "socks5", "socks5h"];
+
+struct Pool {
+    urls: Vec<reqwest::Url>,
+    rotation: ProxyRotation,
+    browser: bool,
+    next: AtomicUsize,
+}
+
+static POOL: OnceLock<Pool> = OnceLock::new();
+
+// Called by Config::load; the first config loaded wins
+pub fn configure(config: &ProxyConfig) -> Result<(), String> {
+    let mut urls = Vec::new();
+    for text in &config.urls {
+        let url = reqwest::Url::parse(text.trim()).map_err(|e| format!("Invalid proxy '{}': {}", redacted(text), e))?;
+        if !SCHEMES.contains(&url.scheme()) || url.host_str().is_none() {
+            return Err(format!("Invalid proxy '{}': expected {}://host:port", redacted(text), SCHEMES.join("|")));
+        }
+        urls.push(url);
+    }
+    let _ = POOL.set(Pool { urls, rotation: config.rotation, browser: config.browser, next: AtomicUsize::new(0) });
+    Ok(())
+}
+
+// A proxy URL for logs, without its password
+fn redacted(text: &str) -> String {
+    match reqwest::Url::parse(text.trim()) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("***"));
+            url.to_string()
+        }
+        Ok(url) => url.to_string(),
+        Err(_) => text.to_string(),
+    }
+}
+
+fn is_local(host: &str) -> bool {
+    host == "localhost" || host.ends_with(".localhost") || host.starts_with("127.") || host == "[::1]" || host == "::1"
+}
+
+// The proxy for a request or launch keyed by `key` (a domain or source name)
+fn pick<'a>(pool: &'a Pool, candidates: &[&'a reqwest::Url], key: &str) -> Option<&'a reqwest::Url> {
+    if candidates.is_empty() {
+        return None;
+    }
+    let index = match pool.rotation {
+        ProxyRotation::Request => pool.next.fetch_add(1, Ordering::Relaxed),
+        ProxyRotation::Sticky => {
+            let mut hasher = DefaultHasher::new();
+            key.hash(&mut hasher);
+            hasher.finish() as usize
+        }
+    };
+    Some(candidates[index % candidates.len()])
+}
+
+// Route the client's requests through the pool, if there is one
+pub fn apply(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let Some(pool) = POOL.get().filter(|pool| !pool.urls.is_empty()) else {
+        return builder;
+    };
+    let candidates: Vec<&reqwest::Url> = pool.urls.iter().collect();
+    let rotation = match pool.rotation {
+        ProxyRotation::Request => "rotating per request",
+        ProxyRotation::Sticky => "one per site",
+    };
+    println!("🌐 Proxies: {} in the pool, {}", candidates.len(), rotation);
+    builder.proxy(reqwest::Proxy::custom(move |url| {
+        if url.host_str().is_none_or(is_local) {
+            return None;
+        }
+        pick(pool, &candidates, &rate_limit::domain(url.as_str())).cloned()
+    }))
+}
+
+// Launch Chrome for `source` through a proxy from the pool
+pub fn use_proxy(caps: &mut ChromeCapabilities, source: &str) {
+    let Some(pool) = POOL.get().filter(|pool| pool.browser && !pool.urls.is_empty()) else {
+        return;
+    };
+    let candidates: Vec<&reqwest::Url> = pool.urls.iter().filter(|url| url.username().is_empty() && url.password().is_none()).collect();
+    let Some(url) = pick(pool, &candidates, &source.to_lowercase()) else {
+        eprintln!("  ⚠️  Every proxy needs a login, which Chrome can't take - {} connects directly", source);
+        return;
+    };
+    // Chrome resolves names through a SOCKS5 proxy already and has no socks5h
+    let scheme = if url.scheme() == "socks5h" { "socks5" } else { url.scheme() };
+    let server = format!("{}://{}:{}", scheme, url.host_str().unwrap_or_default(), url.port_or_known_default().unwrap_or(1080));
+    println!("  🌐 {} via proxy {}", source, server);
+    caps.add_arg(&format!("--proxy-server={}", server)).ok();
+}
@@ -0,0 +1,378 @@
+// QR codes for the deal sheet, so a printed sheet links back to each buy
+// listing. Byte mode at error correction level M, versions 1-10: up to 213
+// bytes, which covers any listing URL the sources produce. Longer text gets
+// no code rather than a bigger version; the sheet prints the URL anyway.
+
+// Per version at level M: EC codewords per block, and (blocks, data
+// codewords per block) for each block group
+const VERSIONS: [(usize, &[(usize, usize)]); 10] = [
+    (10, &[(1, 16)]),
+    (16, &[(1, 28)]),
+    (26, &[(1, 44)]),
+    (18, &[(2, 32)]),
+    (24, &[(2, 43)]),
+    (16, &[(4, 27)]),
+    (18, &[(4, 31)]),
+    (22, &[(2, 38), (2, 39)]),
+    (22, &[(3, 36), (2, 37)]),
+    (26, &[(4, 43), (1, 44)]),
+];
+
+// Alignment pattern centres (both axes) per version
+const ALIGNMENT: [&[usize]; 10] = [
+    &[],
+    &[6, 18],
+    &[6, 22],
+    &[6, 26],
+    &[6, 30],
+    &[6, 34],
+    &[6, 22, 38],
+    &[6, 24, 42],
+    &[6, 26, 46],
+    &[6, 28, 50],
+];
+
+// Level M in the format information
+const FORMAT_LEVEL_M: usize = 0b00;
+
+pub struct QrCode {
+    size: usize,
+    dark: Vec<bool>,
+    // Finder, timing, alignment and format modules: never masked or overwritten by data
+    function: Vec<bool>,
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: usize, count: usize) {
+    for i in (0..count).rev() {
+        bits.push((value >> i) & 1 == 1);
+    }
+}
+
+// Mode, length, data, terminator and padding, filling `capacity` codewords
+fn data_codewords(data: &[u8], count_bits: usize, capacity: usize) -> Vec<u8> {
+    let mut bits = Vec::new();
+    push_bits(&mut bits, 0b0100, 4);
+    push_bits(&mut bits, data.len(), count_bits);
+    for byte in data {
+        push_bits(&mut bits, *byte as usize, 8);
+    }
+    let terminator = (capacity * 8 - bits.len()).min(4);
+    push_bits(&mut bits, 0, terminator);
+    let padding = (8 - bits.len() % 8) % 8;
+    push_bits(&mut bits, 0, padding);
+
+    let mut codewords: Vec<u8> = bits.chunks(8).map(|byte| byte.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8)).collect();
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() >= capacity {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+// Multiplication in GF(256) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut product: u16 = 0;
+    for i in (0..8).rev() {
+        product = (product << 1) ^ ((product >> 7) * 0x11D);
+        product ^= ((y as u16 >> i) & 1) * x as u16;
+    }
+    product as u8
+}
+
+// Generator polynomial of the given degree, leading coefficient dropped
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (r, d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(*d, factor);
+        }
+    }
+    result
+}
+
+// Split into blocks, append each block's EC codewords, and interleave
+fn add_error_correction(codewords: &[u8], ec_len: usize, groups: &[(usize, usize)]) -> Vec<u8> {
+    let mut blocks = Vec::new();
+    let mut rest = codewords;
+    for &(count, len) in groups {
+        for _ in 0..count {
+            let (block, tail) = rest.split_at(len);
+            blocks.push(block);
+            rest = tail;
+        }
+    }
+    let divisor = rs_divisor(ec_len);
+    let ecc: Vec<Vec<u8>> = blocks.iter().map(|block| rs_remainder(block, &divisor)).collect();
+
+    let longest = blocks.iter().map(|block| block.len()).max().unwrap_or(0);
+    let mut result = Vec::new();
+    for i in 0..longest {
+        result.extend(blocks.iter().filter_map(|block| block.get(i)));
+    }
+    for i in 0..ec_len {
+        result.extend(ecc.iter().map(|block| block[i]));
+    }
+    result
+}
+
+fn mask_bit(mask: usize, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + x * y % 3).is_multiple_of(2),
+    }
+}
+
+impl QrCode {
+    // The smallest version that fits `text`, or None past version 10
+    pub fn encode(text: &str) -> Option<QrCode> {
+        let data = text.as_bytes();
+        for (index, (ec_len, groups)) in VERSIONS.iter().enumerate() {
+            let version = index + 1;
+            let capacity: usize = groups.iter().map(|(blocks, len)| blocks * len).sum();
+            let count_bits = if version < 10 { 8 } else { 16 };
+            if 4 + count_bits + data.len() * 8 <= capacity * 8 {
+                let codewords = data_codewords(data, count_bits, capacity);
+                return Some(QrCode::build(version, &add_error_correction(&codewords, *ec_len, groups)));
+            }
+        }
+        None
+    }
+
+    fn build(version: usize, codewords: &[u8]) -> QrCode {
+        let size = 17 + 4 * version;
+        let mut qr = QrCode { size, dark: vec![false; size * size], function: vec![false; size * size] };
+        qr.draw_function_patterns(version);
+        qr.draw_codewords(codewords);
+
+        // Keep the mask with the lowest penalty; masking twice undoes it
+        let best = (0..8)
+            .min_by_key(|mask| {
+                qr.apply_mask(*mask);
+                qr.draw_format(*mask);
+                let penalty = qr.penalty();
+                qr.apply_mask(*mask);
+                penalty
+            })
+            .unwrap_or(0);
+        qr.apply_mask(best);
+        qr.draw_format(best);
+        qr
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.dark[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.dark[y * self.size + x]
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        // Finders with their separators, clipped at the edges
+        for (cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            for dy in -4isize..=4 {
+                for dx in -4isize..=4 {
+                    let (x, y) = (cx as isize + dx, cy as isize + dy);
+                    if x < 0 || y < 0 || x >= size as isize || y >= size as isize {
+                        continue;
+                    }
+                    let ring = dx.abs().max(dy.abs());
+                    self.set_function(x as usize, y as usize, ring != 2 && ring != 4);
+                }
+            }
+        }
+
+        let centres = ALIGNMENT[version - 1];
+        if let Some(&last) = centres.last() {
+            for &ay in centres {
+                for &ax in centres {
+                    // The three corners taken by finders
+                    if (ax == 6 && (ay == 6 || ay == last)) || (ax == last && ay == 6) {
+                        continue;
+                    }
+                    for dy in -2isize..=2 {
+                        for dx in -2isize..=2 {
+                            let ring = dx.abs().max(dy.abs());
+                            self.set_function((ax as isize + dx) as usize, (ay as isize + dy) as usize, ring != 1);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Reserve the format areas; the real bits go in once the mask is chosen
+        self.draw_format(0);
+
+        if version >= 7 {
+            let mut remainder = version;
+            for _ in 0..12 {
+                remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+            }
+            let bits = (version << 12) | remainder;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 == 1;
+                let (a, b) = (size - 11 + i % 3, i / 3);
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_format(&mut self, mask: usize) {
+        let size = self.size;
+        let data = (FORMAT_LEVEL_M << 3) | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        // Around the top-left finder
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Split between the other two finders, plus the always-dark module
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    // Two-module-wide columns from the right, zigzagging up and down,
+    // skipping the vertical timing pattern
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let total_bits = codewords.len() * 8;
+        let mut i = 0;
+        let mut right = size as isize - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let x = right as usize - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vertical } else { vertical };
+                    if !self.function[y * size + x] && i < total_bits {
+                        self.dark[y * size + x] = (codewords[i >> 3] >> (7 - (i & 7))) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: usize) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let index = y * self.size + x;
+                if !self.function[index] && mask_bit(mask, x, y) {
+                    self.dark[index] = !self.dark[index];
+                }
+            }
+        }
+    }
+
+    // The standard's four penalty rules: long runs, 2x2 blocks, finder-like
+    // patterns and dark/light imbalance
+    fn penalty(&self) -> usize {
+        const FINDER_LIKE: [[bool; 11]; 2] = [
+            [true, false, true, true, true, false, true, false, false, false, false],
+            [false, false, false, false, true, false, true, true, true, false, true],
+        ];
+        let size = self.size;
+        let mut score = 0;
+
+        let rows = (0..size).map(|y| (0..size).map(|x| self.is_dark(x, y)).collect::<Vec<_>>());
+        let columns = (0..size).map(|x| (0..size).map(|y| self.is_dark(x, y)).collect::<Vec<_>>());
+        for line in rows.chain(columns) {
+            let mut run = 1;
+            for i in 1..=size {
+                if i < size && line[i] == line[i - 1] {
+                    run += 1;
+                    continue;
+                }
+                if run >= 5 {
+                    score += run - 2;
+                }
+                run = 1;
+            }
+            score += line.windows(11).filter(|window| FINDER_LIKE.iter().any(|pattern| pattern == window)).count() * 40;
+        }
+
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.is_dark(x, y);
+                if self.is_dark(x + 1, y) == color && self.is_dark(x, y + 1) == color && self.is_dark(x + 1, y + 1) == color {
+                    score += 3;
+                }
+            }
+        }
+
+        let dark = self.dark.iter().filter(|d| **d).count();
+        score + (dark * 100 / (size * size)).abs_diff(50) / 5 * 10
+    }
+
+    // Inline SVG with a `border`-module quiet zone, scaled by the page's CSS
+    pub fn to_svg(&self, border: usize) -> String {
+        let dimension = self.size + 2 * border;
+        let mut path = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if self.is_dark(x, y) {
+                    path.push_str(&format!("M{},{}h1v1h-1z", x + border, y + border));
+                }
+            }
+        }
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {0} {0}\" shape-rendering=\"crispEdges\"><rect width=\"{0}\" height=\"{0}\" fill=\"#fff\"/><path d=\"{1}\" fill=\"#000\"/></svg>",
+            dimension, path
+        )
+    }
+}
@@ -0,0 +1,149 @@
+// Per-domain rate limiting. Every HTTP fetch and browser navigation asks for
+// a slot on the URL's domain before it goes out, so the spacing between
+// requests is set in one place ([politeness.rate_limit]) instead of sleeps
+// scattered through the scrapers, and holds across sources scraping the same
+// site at once. A domain gets at most requests_per_minute requests in any
+// 60 seconds and min_delay_ms between two of them. What each domain was sent
+// and how long requests waited for it is kept as the run's crawl audit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+use crate::config::{DomainLimit, RateLimitConfig};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+static CONFIG: OnceLock<RateLimitConfig> = OnceLock::new();
+static DOMAINS: LazyLock<Mutex<HashMap<String, DomainState>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Default)]
+struct DomainState {
+    // Slots handed out within the last minute, oldest first
+    recent: VecDeque<Instant>,
+    // Earliest the next request may go
+    next: Option<Instant>,
+    audit: DomainAudit,
+}
+
+// Requests sent to one domain this run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DomainAudit {
+    pub domain: String,
+    pub requests: u32,
+    // Requests that had to wait for their slot
+    pub waited: u32,
+    pub wait_ms: u64,
+    pub longest_wait_ms: u64,
+    pub requests_per_minute: u32,
+    pub min_delay_ms: u64,
+}
+
+// Called by Config::load; the first config loaded wins
+pub fn configure(config: &RateLimitConfig) {
+    let _ = CONFIG.set(config.clone());
+}
+
+fn config() -> &'static RateLimitConfig {
+    static DEFAULT: LazyLock<RateLimitConfig> = LazyLock::new(RateLimitConfig::default);
+    CONFIG.get().unwrap_or(&DEFAULT)
+}
+
+// "https://www.ebay.com:443/sch?..." -> "ebay.com"
+pub fn domain(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = without_scheme.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    let host = host.split(':').next().unwrap_or_default().to_lowercase();
+    host.strip_prefix("www.").map(str::to_string).unwrap_or(host)
+}
+
+// The most specific [politeness.rate_limit.domains] entry covering `domain`
+fn limit_for(config: &RateLimitConfig, domain: &str) -> DomainLimit {
+    config
+        .domains
+        .iter()
+        .filter(|(key, _)| {
+            let key = key.trim_start_matches("www.").to_lowercase();
+            domain == key || domain.ends_with(&format!(".{}", key))
+        })
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, limit)| *limit)
+        .unwrap_or(config.default)
+}
+
+// Wait until the domain of `url` may be sent another request. Local files
+// and replayed pages don't count.
+pub async fn acquire(url: &str) {
+    let config = config();
+    if !config.enabled || !url.starts_with("http") {
+        return;
+    }
+    let domain = domain(url);
+    if domain.is_empty() {
+        return;
+    }
+    let limit = limit_for(config, &domain);
+    let wait = {
+        let mut domains = DOMAINS.lock().unwrap_or_else(|e| e.into_inner());
+        let state = domains.entry(domain.clone()).or_default();
+        let now = Instant::now();
+        let mut slot = state.next.map_or(now, |next| next.max(now));
+        let per_minute = limit.requests_per_minute.max(1) as usize;
+        if state.recent.len() >= per_minute {
+            slot = slot.max(state.recent[state.recent.len() - per_minute] + WINDOW);
+        }
+        while state.recent.front().is_some_and(|&at| at + WINDOW <= slot) {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(slot);
+        state.next = Some(slot + Duration::from_millis(limit.min_delay_ms));
+
+        let wait = slot - now;
+        let audit = &mut state.audit;
+        audit.domain = domain;
+        audit.requests += 1;
+        audit.requests_per_minute = limit.requests_per_minute;
+        audit.min_delay_ms = limit.min_delay_ms;
+        if !wait.is_zero() {
+            audit.waited += 1;
+            audit.wait_ms += wait.as_millis() as u64;
+            audit.longest_wait_ms = audit.longest_wait_ms.max(wait.as_millis() as u64);
+        }
+        wait
+    };
+    if !wait.is_zero() {
+        sleep(wait).await;
+    }
+}
+
+// This run's audit, busiest domain first; counting starts over after
+pub fn take_audit() -> Vec<DomainAudit> {
+    let mut domains = DOMAINS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut audit: Vec<DomainAudit> = domains.values_mut().map(|state| std::mem::take(&mut state.audit)).filter(|a| a.requests > 0).collect();
+    audit.sort_by(|a, b| b.requests.cmp(&a.requests).then_with(|| a.domain.cmp(&b.domain)));
+    audit
+}
+
+pub fn display_crawl_audit(audit: &[DomainAudit]) {
+    if audit.is_empty() {
+        return;
+    }
+    let requests: u32 = audit.iter().map(|a| a.requests).sum();
+    println!("\n🚦 CRAWL AUDIT ({} requests to {} domains):", requests, audit.len());
+    for domain in audit {
+        println!(
+            "   {:<24} {:>4} requests, {} waited {:.1}s (longest {:.1}s) - limit {}/min, {}ms apart",
+            domain.domain,
+            domain.requests,
+            domain.waited,
+            domain.wait_ms as f64 / 1000.0,
+            domain.longest_wait_ms as f64 / 1000.0,
+            domain.requests_per_minute,
+            domain.min_delay_ms
+        );
+    }
+}
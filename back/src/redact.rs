@@ -0,0 +1,63 @@
+// Redacted exports for sharing runs publicly. Seller names and user notes
+// are dropped and every URL is replaced by its host plus a hash, so the
+// numbers stay comparable (the same listing hashes the same everywhere in
+// the file) without handing anyone a listing to go buy.
+
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::cli::ExportArgs;
+use crate::{FRONTEND_DATA_FILE, storage};
+
+const DEFAULT_OUTPUT: &str = "scraper_data.export.json";
+
+// Fields removed outright wherever they appear
+const DROPPED_KEYS: [&str; 4] = ["seller", "annotation", "annotations", "notes"];
+
+// "https://swappa.com/listing/view/LABC123" -> "swappa.com#9f86d081884c7d65"
+pub fn redact_url(url: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    let host = url.split("://").nth(1).and_then(|rest| rest.split('/').next()).unwrap_or("");
+    format!("{}#{:016x}", host, hasher.finish())
+}
+
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !DROPPED_KEYS.contains(&key.as_str()));
+            for child in object.values_mut() {
+                redact(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        Value::String(text) if text.starts_with("http://") || text.starts_with("https://") => {
+            *text = redact_url(text);
+        }
+        _ => {}
+    }
+}
+
+// Write the latest export (or a stored run) to `args.output`, redacted if asked
+pub fn export(args: &ExportArgs) -> Result<(), String> {
+    let mut value = match args.run {
+        Some(run_id) => {
+            let run = storage::load_run(run_id).ok_or(format!("run {} not found", run_id))?;
+            serde_json::to_value(run).map_err(|e| e.to_string())?
+        }
+        None => {
+            let path = storage::profile_path(FRONTEND_DATA_FILE);
+            let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path, e))?
+        }
+    };
+
+    if args.redact {
+        redact(&mut value);
+    }
+    let output = args.output.clone().unwrap_or_else(|| storage::profile_path(DEFAULT_OUTPUT));
+    storage::write_json(&output, &value)?;
+    println!("📁 {} export written to {}", if args.redact { "Redacted" } else { "Full" }, output);
+    Ok(())
+}
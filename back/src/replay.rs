@@ -0,0 +1,66 @@
+// Record/replay for plain HTTP fetches (fetch_html), so parser and analytics
+// work can run against yesterday's pages instead of the live sites. With
+// --replay replay a page is fetched and saved on first use and served from
+// disk after that; --replay offline serves only what was saved and never
+// touches the network. Only successful responses are saved, so a block page
+// isn't replayed forever. Pages are plain HTML files under replay/<host>/,
+// named by a hash of the URL: edit one to try a parser against it, delete it
+// to fetch it again. Selenium sources drive a real browser and aren't
+// replayed; [capture] saves their JSON.
+
+use std::fs;
+use std::sync::OnceLock;
+
+const REPLAY_DIR: &str = "replay";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReplayMode {
+    // Serve saved pages, fetch and save the rest
+    Replay,
+    // Serve saved pages only
+    Offline,
+}
+
+// Set once at startup from --replay; unset means live fetching
+static MODE: OnceLock<ReplayMode> = OnceLock::new();
+
+pub fn set_mode(mode: ReplayMode) {
+    MODE.set(mode).ok();
+    println!("📼 Replay mode {:?}: pages under {}/", mode, REPLAY_DIR);
+}
+
+pub fn mode() -> Option<ReplayMode> {
+    MODE.get().copied()
+}
+
+// FNV-1a: stable across Rust releases, unlike DefaultHasher, so a recording
+// keeps working after a toolchain upgrade
+fn url_hash(url: &str) -> u64 {
+    url.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn page_path(url: &str) -> String {
+    let host = url.split("://").nth(1).and_then(|rest| rest.split('/').next()).unwrap_or("unknown");
+    let host: String = host.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+    format!("{}/{}/{:016x}.html", REPLAY_DIR, host, url_hash(url))
+}
+
+// The saved page for `url`, when replaying
+pub fn lookup(url: &str) -> Option<String> {
+    mode()?;
+    fs::read_to_string(page_path(url)).ok()
+}
+
+pub fn record(url: &str, body: &str) {
+    if mode() != Some(ReplayMode::Replay) {
+        return;
+    }
+    let path = page_path(url);
+    let saved = std::path::Path::new(&path)
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, body));
+    if let Err(e) = saved {
+        eprintln!("  ⚠️  Failed to save {} for replay: {}", path, e);
+    }
+}
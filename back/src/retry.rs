@@ -0,0 +1,160 @@
+// Retries with exponential backoff. A request that times out, can't connect
+// or gets a 408/429/5xx back is tried again after a wait that doubles each
+// time ([politeness.retry]), with random jitter so parallel sources don't
+// retry in lockstep; a 429 or 503 that says Retry-After waits that long
+// instead. A browser navigation is retried when Chrome reports a network
+// error or a timeout. Anything else (404, a dead WebDriver session) fails on
+// the first try. Every attempt takes its own rate limiter slot. A source's
+// politeness preset can also cap how many retries it makes in a whole run.
+
+use reqwest::StatusCode;
+use reqwest::header::RETRY_AFTER;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thirtyfour::prelude::*;
+use tokio::time::sleep;
+
+use crate::config::RetryConfig;
+use crate::rate_limit;
+
+static CONFIG: OnceLock<RetryConfig> = OnceLock::new();
+static JITTER_STATE: AtomicU64 = AtomicU64::new(0);
+
+// Chrome's net::ERR_ codes worth another try, lowercased
+const TRANSIENT_NAVIGATION: [&str; 10] = [
+    "timeout",
+    "timed out",
+    "err_timed_out",
+    "err_connection_reset",
+    "err_connection_closed",
+    "err_connection_refused",
+    "err_network_changed",
+    "err_internet_disconnected",
+    "err_empty_response",
+    "err_name_not_resolved",
+];
+
+// Retries one source may still make this run; each retry takes one, and once
+// none are left its failed requests fail on the first try
+#[derive(Debug)]
+pub struct Budget(AtomicU32);
+
+impl Budget {
+    pub fn new(retries: u32) -> Budget {
+        Budget(AtomicU32::new(retries))
+    }
+
+    fn take(&self) -> bool {
+        self.0.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| left.checked_sub(1)).is_ok()
+    }
+}
+
+// Called by Config::load; the first config loaded wins
+pub fn configure(config: &RetryConfig) {
+    let _ = CONFIG.set(*config);
+}
+
+fn config() -> RetryConfig {
+    CONFIG.get().copied().unwrap_or_default()
+}
+
+// Uniform in [-1, 1), from a xorshift stream seeded by the clock
+fn jitter() -> f64 {
+    let step = |mut x: u64| {
+        if x == 0 {
+            x = SystemTime::now().duration_since(UNIX_EPOCH).map_or(1, |d| d.as_nanos() as u64) | 1;
+        }
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        x
+    };
+    let previous = JITTER_STATE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| Some(step(x))).unwrap_or(1);
+    let value = step(previous).wrapping_mul(0x2545f4914f6cdd1d);
+    (value >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+// Wait before try `attempt + 1`, after `attempt` failed ones
+fn backoff(config: &RetryConfig, attempt: u32) -> Duration {
+    let base = config.base_delay_ms.saturating_mul(1u64 << (attempt - 1).min(16)).min(config.max_delay_ms);
+    let spread = 1.0 + config.jitter.clamp(0.0, 1.0) * jitter();
+    Duration::from_millis((base as f64 * spread) as u64)
+}
+
+pub fn transient_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 500 | 502 | 503 | 504)
+}
+
+fn transient_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_request() || error.is_body()
+}
+
+pub fn transient_navigation(message: &str) -> bool {
+    let message = message.to_lowercase();
+    TRANSIENT_NAVIGATION.iter().any(|marker| message.contains(marker))
+}
+
+// Seconds from a Retry-After header; HTTP dates aren't worth parsing here
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let seconds: u64 = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+async fn wait(url: &str, attempt: u32, config: &RetryConfig, reason: &str, hint: Option<Duration>) {
+    let delay = hint.map_or_else(|| backoff(config, attempt), |hint| hint.min(Duration::from_millis(config.max_delay_ms)));
+    eprintln!("  ↻ {} from {}, retrying in {:.1}s ({}/{})", reason, url, delay.as_secs_f64(), attempt + 1, config.attempts);
+    sleep(delay).await;
+}
+
+// Send the request `build` makes, again while it fails transiently; the last
+// response is returned even when its status still says to retry
+pub async fn send(url: &str, build: impl Fn() -> reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    send_within(url, None, build).await
+}
+
+// `send`, its retries also drawn from a source's `budget`
+pub async fn send_within(
+    url: &str,
+    budget: Option<&Budget>,
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let config = config();
+    let mut attempt = 1;
+    loop {
+        rate_limit::acquire(url).await;
+        let result = build().send().await;
+        if attempt >= config.attempts.max(1) {
+            return result;
+        }
+        let (reason, hint) = match &result {
+            Ok(response) if transient_status(response.status()) => {
+                (format!("HTTP {}", response.status().as_u16()), retry_after(response))
+            }
+            Err(e) if transient_error(e) => ("Request failed".to_string(), None),
+            _ => return result,
+        };
+        if budget.is_some_and(|budget| !budget.take()) {
+            return result;
+        }
+        wait(url, attempt, &config, &reason, hint).await;
+        attempt += 1;
+    }
+}
+
+// Navigate the browser to `url`, again while it fails on the network
+pub async fn goto(driver: &WebDriver, url: &str) -> WebDriverResult<()> {
+    let config = config();
+    let mut attempt = 1;
+    loop {
+        rate_limit::acquire(url).await;
+        let result = driver.goto(url).await;
+        match &result {
+            Err(e) if attempt < config.attempts.max(1) && transient_navigation(&e.to_string()) => {
+                wait(url, attempt, &config, "Navigation failed", None).await;
+            }
+            _ => return result,
+        }
+        attempt += 1;
+    }
+}
@@ -0,0 +1,148 @@
+// User-defined opportunity filters written in Rhai. The script may define
+//   fn accept(opp) -> bool   - drop opportunities that return false
+//   fn score(opp) -> number  - rank opportunities by score (highest first)
+// Each `opp` is an object map; see opportunity_map for the available fields.
+// Calls are capped in operations, call depth, data size and time, so a
+// runaway script is reported like any other script error instead of hanging
+// the run.
+
+use rhai::{AST, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::PriceComparison;
+use crate::error_journal::{self, ErrorCategory};
+
+const MAX_OPERATIONS: u64 = 100_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_DATA_SIZE: usize = 10_000;
+const CALL_TIMEOUT: Duration = Duration::from_millis(250);
+
+pub struct ScriptFilter {
+    engine: Engine,
+    ast: AST,
+    has_accept: bool,
+    has_score: bool,
+    // When the current accept()/score() call started, for the time limit
+    call_started: Rc<Cell<Instant>>,
+}
+
+impl ScriptFilter {
+    pub fn load(path: &str) -> Result<ScriptFilter, String> {
+        let call_started = Rc::new(Cell::new(Instant::now()));
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+            .set_max_string_size(MAX_DATA_SIZE)
+            .set_max_array_size(MAX_DATA_SIZE)
+            .set_max_map_size(MAX_DATA_SIZE);
+        let started = Rc::clone(&call_started);
+        engine.on_progress(move |_| (started.get().elapsed() > CALL_TIMEOUT).then_some(Dynamic::UNIT));
+        let ast = engine
+            .compile_file(path.into())
+            .map_err(|e| format!("Failed to compile filter script {}: {}", path, e))?;
+
+        let has_fn = |name: &str| ast.iter_functions().any(|f| f.name == name && f.params.len() == 1);
+        let has_accept = has_fn("accept");
+        let has_score = has_fn("score");
+        if !has_accept && !has_score {
+            return Err(format!("Filter script {} defines neither accept(opp) nor score(opp)", path));
+        }
+
+        Ok(ScriptFilter { engine, ast, has_accept, has_score, call_started })
+    }
+
+    fn call(&self, name: &str, opp: &Map) -> Result<Dynamic, String> {
+        let mut scope = Scope::new();
+        self.call_started.set(Instant::now());
+        self.engine
+            .call_fn::<Dynamic>(&mut scope, &self.ast, name, (opp.clone(),))
+            .map_err(|e| match *e {
+                EvalAltResult::ErrorTerminated(..) => format!("{}() ran longer than {} ms", name, CALL_TIMEOUT.as_millis()),
+                _ => format!("{}() failed: {}", name, e),
+            })
+    }
+
+    // Apply accept/score to the opportunities. Script errors keep the
+    // opportunity (and leave it unscored) rather than silently dropping it.
+    pub fn apply(&self, opportunities: Vec<PriceComparison>) -> Vec<PriceComparison> {
+        let mut kept = Vec::new();
+        let mut rejected = 0;
+
+        for mut opp in opportunities {
+            let map = opportunity_map(&opp);
+
+            if self.has_accept {
+                match self.call("accept", &map).map(|v| v.as_bool()) {
+                    Ok(Ok(false)) => {
+                        rejected += 1;
+                        continue;
+                    }
+                    Ok(Ok(true)) => {}
+                    Ok(Err(type_name)) => {
+                        report(format!("accept() returned {} instead of bool for {}", type_name, opp.product_name));
+                    }
+                    Err(e) => report(format!("{} ({})", e, opp.product_name)),
+                }
+            }
+
+            if self.has_score {
+                match self.call("score", &map) {
+                    Ok(value) => {
+                        opp.script_score = value.as_float().ok().or_else(|| value.as_int().ok().map(|i| i as f64));
+                        if opp.script_score.is_none() {
+                            report(format!("score() returned {} instead of a number for {}", value.type_name(), opp.product_name));
+                        }
+                    }
+                    Err(e) => report(format!("{} ({})", e, opp.product_name)),
+                }
+            }
+
+            kept.push(opp);
+        }
+
+        if self.has_accept {
+            println!("  📜 Filter script rejected {} opportunities", rejected);
+        }
+        if self.has_score {
+            // Unscored opportunities sort last
+            kept.sort_by(|a, b| {
+                let a_score = a.script_score.unwrap_or(f64::NEG_INFINITY);
+                let b_score = b.script_score.unwrap_or(f64::NEG_INFINITY);
+                b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+
+        kept
+    }
+}
+
+fn report(message: String) {
+    eprintln!("  ⚠️  {}", message);
+    error_journal::record(ErrorCategory::Script, "Filter script", None, &message, None);
+}
+
+fn opportunity_map(opp: &PriceComparison) -> Map {
+    let mut map = Map::new();
+    map.insert("name".into(), opp.product_name.clone().into());
+    map.insert("source".into(), opp.source_product.source.clone().into());
+    map.insert("url".into(), opp.source_product.url.clone().into());
+    map.insert("buy_price".into(), opp.source_price.amount().into());
+    map.insert("sold_avg".into(), opp.ebay_avg_sold.amount().into());
+    map.insert("sold_median".into(), opp.ebay_median_sold.amount().into());
+    map.insert("sold_count".into(), (opp.ebay_sold_count as i64).into());
+    map.insert("sold_min".into(), opp.ebay_min_price.amount().into());
+    map.insert("sold_max".into(), opp.ebay_max_price.amount().into());
+    map.insert("profit".into(), opp.profit.amount().into());
+    map.insert("gross_profit".into(), opp.gross_profit.amount().into());
+    map.insert("margin_percent".into(), opp.margin_percent.into());
+    let comp_sources: rhai::Array = opp.comp_sources.iter().map(|s| s.clone().into()).collect();
+    map.insert("comp_sources".into(), comp_sources.into());
+    map.insert("color".into(), opp.color.clone().map_or(Dynamic::UNIT, Dynamic::from));
+    map.insert("suggested_list_price".into(), opp.suggested_list_price.amount().into());
+    map
+}
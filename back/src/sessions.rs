@@ -0,0 +1,82 @@
+// Browser sessions shared within a run. Starting Chrome for every scrape
+// meant a cold browser for Swappa's listings, another for its detail pages
+// and one per eBay scraper; now a scraper hands its session back when done
+// and the next scrape of the same site (same Chrome profile, see
+// browser::session_key) picks it up where it left off. A session isn't
+// reused once the site has blocked it, or when it stopped answering; the
+// profile and proxy only apply to newly started ones. Every session is quit
+// at the end of the scraping phase of a run.
+
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use thirtyfour::ChromeCapabilities;
+use thirtyfour::prelude::*;
+use tokio::sync::Mutex;
+
+use crate::config::BrowserConfig;
+use crate::{browser, chromedriver, proxy};
+
+struct Session {
+    driver: WebDriver,
+    started: DateTime<Local>,
+}
+
+// Sessions waiting for their next scrape, by session key
+static IDLE: LazyLock<Mutex<HashMap<String, Session>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+// Sessions a scraper holds, by session key, so shutdown can quit them too
+static IN_USE: LazyLock<Mutex<HashMap<String, Session>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// A browser for `source`: its site's idle session, else a new one from `caps`
+// with the source's profile and proxy
+pub async fn open(config: &BrowserConfig, source: &str, mut caps: ChromeCapabilities) -> Result<WebDriver, String> {
+    let key = browser::session_key(config, source);
+    let idle = IDLE.lock().await.remove(&key);
+    if let Some(session) = idle {
+        if browser::blocked_since(source, session.started) {
+            println!("  🧹 {} was blocked in the last session - starting a new one", source);
+        } else if session.driver.title().await.is_ok() {
+            println!("  ♻️  {} reuses the browser session from {}", source, session.started.format("%H:%M:%S"));
+            IN_USE.lock().await.insert(key, Session { driver: session.driver.clone(), started: session.started });
+            return Ok(session.driver);
+        }
+        let _ = session.driver.quit().await;
+    }
+
+    browser::use_profile(&mut caps, config, source);
+    proxy::use_proxy(&mut caps, source);
+    let driver = chromedriver::connect(caps).await?;
+    IN_USE.lock().await.insert(key, Session { driver: driver.clone(), started: Local::now() });
+    Ok(driver)
+}
+
+// Hand the session back for the next scrape of the site this run
+pub async fn release(config: &BrowserConfig, source: &str, driver: WebDriver) {
+    let key = browser::session_key(config, source);
+    let started = IN_USE.lock().await.remove(&key).map_or_else(Local::now, |session| session.started);
+    let previous = IDLE.lock().await.insert(key, Session { driver, started });
+    if let Some(previous) = previous {
+        let _ = previous.driver.quit().await;
+    }
+}
+
+// Quit every idle session
+pub async fn close_all() {
+    let sessions: Vec<(String, Session)> = IDLE.lock().await.drain().collect();
+    quit(sessions).await;
+}
+
+// Quit the idle sessions and the ones scrapers still hold, on shutdown
+pub async fn quit_all() {
+    let mut sessions: Vec<(String, Session)> = IN_USE.lock().await.drain().collect();
+    sessions.extend(IDLE.lock().await.drain());
+    quit(sessions).await;
+}
+
+async fn quit(sessions: Vec<(String, Session)>) {
+    for (key, session) in sessions {
+        if let Err(e) = session.driver.quit().await {
+            eprintln!("  Warning: Failed to close the {} browser: {}", key, e);
+        }
+    }
+}
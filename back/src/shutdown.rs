@@ -0,0 +1,41 @@
+// Ctrl+C. The first one asks whatever is listening (the scrape loop, `back
+// scrape`) to stop: it leaves its scrape or wait, quits the browser sessions
+// and only then kills the ChromeDriver this process started, so no Chrome is
+// left behind. With nothing listening, or at a second Ctrl+C, the process
+// exits at once; the ChromeDriver still goes with it (chromedriver.rs).
+
+use std::sync::{LazyLock, Once};
+use tokio::sync::watch;
+
+use crate::{chromedriver, sessions};
+
+static REQUESTED: LazyLock<watch::Sender<bool>> = LazyLock::new(|| watch::channel(false).0);
+static INSTALL: Once = Once::new();
+
+pub fn install() {
+    INSTALL.call_once(|| {
+        tokio::spawn(async {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            if REQUESTED.send(true).is_err() {
+                std::process::exit(130);
+            }
+            println!("\n🛑 Stopping once the browsers are closed (Ctrl+C again to quit now)");
+            let _ = tokio::signal::ctrl_c().await;
+            std::process::exit(130);
+        });
+    });
+}
+
+// Resolves at the first Ctrl+C; listening while it's being awaited
+pub async fn requested() {
+    let mut receiver = REQUESTED.subscribe();
+    let _ = receiver.wait_for(|stop| *stop).await;
+}
+
+// Quit every browser session, then the ChromeDriver
+pub async fn close_browsers() {
+    sessions::quit_all().await;
+    chromedriver::stop();
+}
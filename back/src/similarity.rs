@@ -0,0 +1,344 @@
+// How alike two listing titles are, 0-100. Titles are split into tokens and
+// compared as weighted sets (weighted Jaccard): model tokens count most,
+// storage and carrier less, colors and condition words barely, so sold titles
+// padded with "Sierra Blue - Excellent Condition" still match and nothing is
+// tied to a hardcoded phone list. `back match` prints each token's share.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::attributes::COLORS;
+use crate::{Product, locale};
+
+// Brand words sellers add or leave out; they say nothing about the device
+const BRAND_WORDS: [&str; 3] = ["apple", "samsung", "google"];
+// "5th gen" and "5th generation" are both just "5", "Series 9" just "9"
+// "inch" goes too: the number before it says what it measures
+const STOP_WORDS: [&str; 14] =
+    ["with", "and", "the", "for", "in", "of", "on", "to", "only", "gen", "generation", "series", "inch", "inches"];
+// Product lines and variants: "iphone", "pro", "max", "ipad", "watch", ...
+const MODEL_WORDS: [&str; 17] = [
+    "iphone", "galaxy", "pixel", "ipad", "watch", "pro", "max", "plus", "mini", "ultra", "air", "classic", "fold", "flip", "se",
+    "xl", "fe",
+];
+const CARRIER_WORDS: [&str; 8] = ["unlocked", "locked", "verizon", "att", "tmobile", "sprint", "cricket", "carrier"];
+// Tablet and watch connectivity, priced like a carrier lock
+const CONNECTIVITY_WORDS: [&str; 3] = ["wifi", "cellular", "gps"];
+const CONDITION_WORDS: [&str; 16] = [
+    "new", "used", "like", "mint", "pristine", "excellent", "good", "fair", "acceptable", "grade", "refurbished", "renewed",
+    "pre", "owned", "condition", "cosmetic",
+];
+// Tokens with digits that are not model numbers
+const NETWORK_WORDS: [&str; 3] = ["5g", "4g", "lte"];
+// What a number counts when one of these follows it: "89% battery", "2 day shipping"
+const QUANTITY_UNITS: [&str; 5] = ["%", "day", "hour", "hr", "week"];
+// A screen size: 6.1", "13-inch"
+const SCREEN_UNITS: [&str; 4] = ["\"", "\u{201d}", "\u{2033}", "inch"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    // "13", "s24", "8a"
+    ModelNumber,
+    // Product line or variant word: "iphone", "pro", "max"
+    Model,
+    // "256gb", "1tb"
+    Storage,
+    // Watch case size: "45mm"
+    Size,
+    Carrier,
+    // Colors and condition words
+    Cosmetic,
+    Other,
+}
+
+impl TokenKind {
+    pub fn weight(self) -> f64 {
+        match self {
+            TokenKind::ModelNumber => 4.0,
+            TokenKind::Model => 3.0,
+            TokenKind::Storage | TokenKind::Size => 1.5,
+            TokenKind::Carrier => 1.0,
+            TokenKind::Cosmetic => 0.25,
+            TokenKind::Other => 0.5,
+        }
+    }
+
+    // Tokens that identify the device: two titles naming different ones are different devices
+    fn identifies_model(self) -> bool {
+        matches!(self, TokenKind::ModelNumber | TokenKind::Model)
+    }
+}
+
+// The kind of `token` on its own; a number is only a candidate model number,
+// see classify
+fn kind_of(token: &str) -> TokenKind {
+    let is_measure = |units: &[&str]| {
+        token.len() > 2 && units.iter().any(|unit| token.ends_with(unit)) && token[..token.len() - 2].chars().all(|c| c.is_ascii_digit())
+    };
+    // Model numbers are short; longer numbers are years, part numbers, ...
+    let digits = token.chars().filter(char::is_ascii_digit).count();
+    if is_measure(&["gb", "tb"]) {
+        TokenKind::Storage
+    } else if is_measure(&["mm"]) {
+        TokenKind::Size
+    } else if MODEL_WORDS.contains(&token) {
+        TokenKind::Model
+    } else if (1..=2).contains(&digits) && !NETWORK_WORDS.contains(&token) {
+        TokenKind::ModelNumber
+    } else if CARRIER_WORDS.contains(&token) || CONNECTIVITY_WORDS.contains(&token) {
+        TokenKind::Carrier
+    } else if CONDITION_WORDS.contains(&token) || COLORS.iter().any(|color| color.split(' ').any(|word| word == token)) {
+        TokenKind::Cosmetic
+    } else {
+        TokenKind::Other
+    }
+}
+
+// "promax" -> ["pro", "max"], "iphone13" -> ["iphone", "13"]; None unless the
+// whole word splits into model words and a trailing model number
+fn split_glued(word: &str) -> Option<Vec<String>> {
+    if MODEL_WORDS.contains(&word) || (word.starts_with(|c: char| c.is_ascii_digit()) && kind_of(word) == TokenKind::ModelNumber) {
+        return Some(vec![word.to_string()]);
+    }
+    MODEL_WORDS.iter().filter(|model_word| word.len() > model_word.len() && word.starts_with(*model_word)).find_map(|model_word| {
+        let mut parts = split_glued(&word[model_word.len()..])?;
+        parts.insert(0, model_word.to_string());
+        Some(parts)
+    })
+}
+
+// "5th" -> "5", so ordinal generations compare equal to plain numbers
+fn strip_ordinal(word: &str) -> &str {
+    ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(word)
+}
+
+// What the unit after a number makes of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    None,
+    Quantity,
+    Screen,
+}
+
+// The unit after `word` if it's a number, looking past the fraction of a
+// decimal so both halves of 6.1" are a screen size
+fn unit_after(word: &str, after: &str) -> Unit {
+    if !word.chars().all(|c| c.is_ascii_digit()) {
+        return Unit::None;
+    }
+    let after = match after.strip_prefix('.') {
+        Some(fraction) if fraction.starts_with(|c: char| c.is_ascii_digit()) => fraction.trim_start_matches(|c: char| c.is_ascii_digit()),
+        _ => after,
+    };
+    let unit = after.trim_start_matches([' ', '-']);
+    if QUANTITY_UNITS.iter().any(|u| unit.starts_with(u)) {
+        Unit::Quantity
+    } else if SCREEN_UNITS.iter().any(|u| unit.starts_with(u)) {
+        Unit::Screen
+    } else {
+        Unit::None
+    }
+}
+
+// Lowercased, locale-normalized tokens with their kinds, brand and stop
+// words dropped. "256 GB" becomes "256gb", "45 mm" becomes "45mm", "S24+"
+// becomes "s24 plus", "AT&T" becomes "att", "ProMax" becomes "pro max",
+// "10th" becomes "10", and a watch's "S9" becomes "9". A number is a model
+// number only next to a model word or right after another model number
+// ("iPhone 13", "13 Pro", "iPad Pro 11 M2"), never when it's a quantity, so
+// "89% battery" or "2 day shipping" don't read as a different model. A
+// screen size is one only where it names the model ("iPad Air 13-inch"),
+// not after it ("iPhone 15 Pro Max 6.7\"").
+pub fn classify(name: &str) -> Vec<(String, TokenKind)> {
+    let text = locale::to_english(name)
+        .replace("at&t", "att")
+        .replace("at & t", "att")
+        .replace("t-mobile", "tmobile")
+        .replace("t mobile", "tmobile")
+        .replace("wi-fi", "wifi");
+    // "+" on a model name is a variant; a free-standing one ("+ charger") is not
+    let mut expanded = String::with_capacity(text.len());
+    let mut previous = None;
+    for c in text.chars() {
+        if c == '+' && previous.is_some_and(char::is_alphanumeric) {
+            expanded.push_str(" plus ");
+        } else {
+            expanded.push(c);
+        }
+        previous = Some(c);
+    }
+    // Each word with its unit and whether it's the fraction of a decimal,
+    // which depend on the text around it
+    let mut words: Vec<(&str, Unit, bool)> = Vec::new();
+    let mut start = None;
+    for (i, c) in expanded.char_indices().chain([(expanded.len(), ' ')]) {
+        match (c.is_alphanumeric() && i < expanded.len(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(from)) => {
+                let word = &expanded[from..i];
+                let fraction = expanded[..from].strip_suffix('.').is_some_and(|b| b.ends_with(|c: char| c.is_ascii_digit()));
+                words.push((word, unit_after(word, &expanded[i..]), fraction));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words.retain(|(word, _, _)| !BRAND_WORDS.contains(word) && !STOP_WORDS.contains(word));
+
+    // A watch's "S9" is its Series 9; a phone's "S9" keeps its letter
+    let watch = words.iter().any(|(word, _, _)| *word == "watch");
+    let mut tokens: Vec<(String, Unit, bool)> = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let (word, unit, fraction) = words[i];
+        match words.get(i + 1) {
+            Some((measure, _, _)) if matches!(*measure, "gb" | "tb" | "mm") && word.chars().all(|c| c.is_ascii_digit()) => {
+                tokens.push((format!("{}{}", word, measure), Unit::None, false));
+                i += 2;
+            }
+            _ => {
+                let mut word = strip_ordinal(word);
+                if watch && word.len() > 1 && word.starts_with('s') && word[1..].chars().all(|c| c.is_ascii_digit()) {
+                    word = &word[1..];
+                }
+                match split_glued(word) {
+                    Some(parts) => tokens.extend(parts.into_iter().map(|part| (part, unit, fraction))),
+                    None => tokens.push((word.to_string(), unit, fraction)),
+                }
+                i += 1;
+            }
+        }
+    }
+
+    let mut kinds: Vec<TokenKind> = tokens.iter().map(|(token, _, _)| kind_of(token)).collect();
+    let mut numbered = false;
+    for i in 0..kinds.len() {
+        if kinds[i] != TokenKind::ModelNumber {
+            continue;
+        }
+        let after_model = i > 0 && kinds[i - 1].identifies_model();
+        let before_model = kinds.get(i + 1) == Some(&TokenKind::Model);
+        let model = match tokens[i] {
+            (_, Unit::Quantity, _) => false,
+            // The fraction of a decimal goes with its whole part
+            (_, Unit::Screen, true) => after_model,
+            (_, Unit::Screen, false) => after_model && !numbered,
+            (_, Unit::None, _) => after_model || before_model,
+        };
+        if model {
+            numbered = true;
+        } else {
+            kinds[i] = TokenKind::Other;
+        }
+    }
+    tokens.into_iter().map(|(token, _, _)| token).zip(kinds).collect()
+}
+
+// One token's part in a comparison
+#[derive(Debug, Clone)]
+pub struct TokenContribution {
+    pub token: String,
+    pub kind: TokenKind,
+    pub in_first: bool,
+    pub in_second: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub contributions: Vec<TokenContribution>,
+    // Titles name different models ("13" vs "14", "pro" vs "pro max")
+    pub model_mismatch: bool,
+    pub jaccard: f64,
+    pub fuzzy: f64,
+    pub score: f64,
+}
+
+fn model_tokens(classified: &[(String, TokenKind)]) -> BTreeSet<&str> {
+    classified.iter().filter(|(_, kind)| kind.identifies_model()).map(|(token, _)| token.as_str()).collect()
+}
+
+// Jaro-Winkler over the joined tokens, so "Apple iPhone 13 ProMax 256GB" and
+// "iPhone 13 Pro Max 256 GB" compare equal
+fn fuzzy_similarity(tokens1: &[String], tokens2: &[String]) -> f64 {
+    strsim::jaro_winkler(&tokens1.concat(), &tokens2.concat()) * 100.0
+}
+
+// Weighted Jaccard, lifted toward the fuzzy score by `fuzzy_weight` when that
+// is higher. 0 when both titles carry a model number and their model tokens
+// differ.
+pub fn compare(name1: &str, name2: &str, fuzzy_weight: f64) -> Comparison {
+    let (classified1, classified2) = (classify(name1), classify(name2));
+    let mut by_token: BTreeMap<&str, TokenContribution> = BTreeMap::new();
+    for ((token, kind), first) in classified1.iter().map(|t| (t, true)).chain(classified2.iter().map(|t| (t, false))) {
+        let entry = by_token.entry(token).or_insert_with(|| TokenContribution {
+            token: token.clone(),
+            kind: *kind,
+            in_first: false,
+            in_second: false,
+        });
+        // A model number in either title weighs as one
+        if *kind == TokenKind::ModelNumber {
+            entry.kind = TokenKind::ModelNumber;
+        }
+        if first {
+            entry.in_first = true;
+        } else {
+            entry.in_second = true;
+        }
+    }
+    let contributions: Vec<TokenContribution> = by_token.into_values().collect();
+
+    let has_number = |classified: &[(String, TokenKind)]| classified.iter().any(|(_, kind)| *kind == TokenKind::ModelNumber);
+    let model_mismatch =
+        has_number(&classified1) && has_number(&classified2) && model_tokens(&classified1) != model_tokens(&classified2);
+
+    let union: f64 = contributions.iter().map(|c| c.kind.weight()).sum();
+    let shared: f64 = contributions.iter().filter(|c| c.in_first && c.in_second).map(|c| c.kind.weight()).sum();
+    let jaccard = if union > 0.0 { shared / union * 100.0 } else { 0.0 };
+    let tokens = |classified: &[(String, TokenKind)]| classified.iter().map(|(token, _)| token.clone()).collect::<Vec<_>>();
+    let fuzzy = fuzzy_similarity(&tokens(&classified1), &tokens(&classified2));
+
+    let score = if model_mismatch {
+        0.0
+    } else if fuzzy_weight > 0.0 {
+        // Fuzzy only rescues differently phrased titles; noisy sold titles
+        // that already match on tokens keep their score
+        jaccard.max(jaccard * (1.0 - fuzzy_weight) + fuzzy * fuzzy_weight)
+    } else {
+        jaccard
+    };
+    Comparison { contributions, model_mismatch, jaccard, fuzzy, score }
+}
+
+pub fn similarity_score(p1: &Product, p2: &Product, fuzzy_weight: f64) -> f64 {
+    compare(&p1.name, &p2.name, fuzzy_weight).score
+}
+
+pub fn print_comparison(name1: &str, name2: &str, comparison: &Comparison) {
+    println!("🔍 A: {}", name1);
+    println!("   B: {}\n", name2);
+    println!("   {:<16} {:<12} {:>6}  In", "Token", "Kind", "Weight");
+    for contribution in &comparison.contributions {
+        let side = match (contribution.in_first, contribution.in_second) {
+            (true, true) => "both",
+            (true, false) => "A only",
+            _ => "B only",
+        };
+        println!(
+            "   {:<16} {:<12} {:>6.2}  {}",
+            contribution.token,
+            format!("{:?}", contribution.kind),
+            contribution.kind.weight(),
+            side
+        );
+    }
+    println!("\n   Weighted Jaccard: {:.1}", comparison.jaccard);
+    println!("   Fuzzy:            {:.1}", comparison.fuzzy);
+    if comparison.model_mismatch {
+        println!("   ⚠️  Different models, score forced to 0");
+    }
+    println!("   Score:            {:.1}", comparison.score);
+}
@@ -0,0 +1,100 @@
+// `back scrape <source> [--device iphone-14] [--json]`: run one source once
+// and print what it extracted, for developing and checking selectors without
+// running the whole loop.
+
+use std::io::Write;
+
+use crate::cli::ScrapeArgs;
+use crate::config::Config;
+use crate::{Product, mercari, sessions, sources};
+
+// "iPhone 14", "apple-iphone-14" and "iphone 14" all normalize to contain "iphone-14"
+fn normalize(text: &str) -> String {
+    let dashed: String = text.to_lowercase().chars().map(|c| if c.is_alphanumeric() { c } else { '-' }).collect();
+    dashed.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+// Whether a device page/search (or product name) is selected by `--device`
+pub fn device_matches(label: &str, device: Option<&str>) -> bool {
+    device.is_none_or(|device| normalize(label).contains(&normalize(device)))
+}
+
+// Send everything printed to stdout from here on to stderr, returning a
+// handle to the original stdout so --json output can be piped cleanly
+#[cfg(unix)]
+fn divert_stdout() -> Option<std::fs::File> {
+    use std::os::fd::FromRawFd;
+    let _ = std::io::stdout().flush();
+    // SAFETY: dup/dup2 on the process's own standard descriptors; the
+    // duplicate is owned by the returned File and closed with it
+    unsafe {
+        let original = libc::dup(libc::STDOUT_FILENO);
+        if original < 0 || libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            return None;
+        }
+        Some(std::fs::File::from_raw_fd(original))
+    }
+}
+
+#[cfg(not(unix))]
+fn divert_stdout() -> Option<std::fs::File> {
+    None
+}
+
+pub async fn scrape(source: &str, device: Option<&str>, config: &Config, client: &reqwest::Client) -> Result<Vec<Product>, String> {
+    let products = match normalize(source).as_str() {
+        "swappa" => crate::scrape_swappa(client, &config.capture, &config.swappa, &config.browser, &config.politeness.for_source("Swappa"), &config.category_urls("swappa"), device).await.0,
+        "ebay" => crate::scrape_ebay(&config.capture, &config.comps, &config.ebay, &config.browser, &config.politeness.for_source("eBay"), &config.category_urls("ebay"), device).await,
+        "mercari" => {
+            let listings = mercari::scrape_mercari(&config.browser, &config.politeness.for_source("Mercari"), device).await;
+            listings.active.into_iter().chain(listings.sold).collect()
+        }
+        "newegg" => {
+            let politeness = config.politeness.for_source("Newegg");
+            let mut products = crate::scrape_newegg(client, &config.newegg, &politeness).await;
+            products.extend(crate::scrape_newegg_pages(client, &config.category_urls("newegg"), &politeness).await);
+            products
+        }
+        name => {
+            let buy_sources = sources::buy_sources(&config.sources, &config.browser);
+            let Some(source) = buy_sources.iter().find(|s| normalize(s.name()).replace('-', "") == name.replace('-', "")) else {
+                let mut known = vec!["swappa", "ebay", "mercari", "newegg"];
+                known.extend(buy_sources.iter().map(|s| s.name()));
+                return Err(format!("Unknown source '{}'. Known sources: {}", source, known.join(", ")));
+            };
+            source.scrape(client, &config.politeness.for_source(source.name())).await.products
+        }
+    };
+    sessions::close_all().await;
+    // Sources without per-device pages are filtered by product name
+    Ok(products.into_iter().filter(|p| device_matches(&p.name, device)).collect())
+}
+
+pub async fn run(args: &ScrapeArgs, config: &Config, client: &reqwest::Client) -> Result<(), String> {
+    let json_out = if args.json { divert_stdout() } else { None };
+
+    let products = scrape(&args.source, args.device.as_deref(), config, client).await?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&products).map_err(|e| e.to_string())?;
+        match json_out {
+            Some(mut out) => writeln!(out, "{}", json).map_err(|e| e.to_string())?,
+            None => println!("{}", json),
+        }
+        return Ok(());
+    }
+
+    println!("\n📦 {} products from {}:", products.len(), args.source);
+    for (i, product) in products.iter().enumerate() {
+        println!("{:>4}. {} - {}", i + 1, product.name, product.price_label());
+        let details: Vec<&str> = [product.grade.as_deref(), product.warranty.as_deref(), product.sold_date.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !details.is_empty() {
+            println!("      {}", details.join(" | "));
+        }
+        println!("      🔗 {}", product.url);
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+// Amazon Renewed + Amazon Warehouse (used) phone listings via Selenium.
+// Amazon bot-walls plain HTTP clients, so this follows the eBay path: headless
+// Chrome with automation flags hidden, and a bail-out when a captcha shows up.
+
+use async_trait::async_trait;
+use serde_json::json;
+use thirtyfour::extensions::cdp::ChromeDevTools;
+use thirtyfour::prelude::*;
+
+use super::{Source, SourceListings};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::BrowserConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::politeness::Politeness;
+use crate::price;
+use crate::retry;
+use crate::sessions;
+
+const BASE_URL: &str = "https://www.amazon.com";
+// How far down a results page is scrolled so lazy results render
+const SCROLL_DEPTH_PX: u64 = 3600;
+
+// Hide the webdriver flag before any page script runs
+const STEALTH_SCRIPT: &str = "Object.defineProperty(navigator, 'webdriver', { get: () => undefined });";
+
+const EXTRACT_SCRIPT: &str = r#"
+    if (document.querySelector('form[action*="validateCaptcha"]') || document.title.indexOf('Robot Check') !== -1) {
+        return { captcha: true, products: [] };
+    }
+
+    var products = [];
+    var items = document.querySelectorAll('div[data-component-type="s-search-result"][data-asin]');
+    for (var i = 0; i < items.length && products.length < 40; i++) {
+        var item = items[i];
+        if (!item.getAttribute('data-asin')) continue;
+        // Skip sponsored placements, they're usually accessories
+        if (item.querySelector('.puis-sponsored-label-text, [aria-label="Sponsored"]')) continue;
+
+        var titleEl = item.querySelector('h2 span, h2 a span');
+        var name = titleEl ? titleEl.innerText.trim() : '';
+        if (!name || name.length < 10) continue;
+
+        var priceEl = item.querySelector('.a-price:not(.a-text-price) .a-offscreen');
+        var text = item.innerText || '';
+        var price = priceEl ? priceEl.textContent.trim() : '';
+        if (!price) {
+            // Warehouse cards often only show "More Buying Choices $x (n used offers)"
+            var usedMatch = text.match(/\$[\d,]+\.?\d{0,2}(?=\s*\(\d+ used)/);
+            if (usedMatch) price = usedMatch[0];
+        }
+        if (!price) continue;
+
+        var linkEl = item.querySelector('h2 a, a.a-link-normal[href*="/dp/"]');
+        var href = linkEl ? linkEl.href.split('?')[0] : '';
+        if (!href) continue;
+
+        products.push({ name: name.substring(0, 200), price: price, url: href, text: text.substring(0, 500) });
+    }
+
+    return { captcha: false, products: products };
+"#;
+
+// Normalize Amazon's condition wording into a grade appended to the title
+fn parse_condition_grade(text: &str) -> Option<&'static str> {
+    let text = text.to_lowercase();
+    if text.contains("renewed premium") {
+        Some("Renewed Premium")
+    } else if text.contains("renewed") {
+        Some("Renewed")
+    } else if text.contains("used - like new") || text.contains("used: like new") {
+        Some("Used - Like New")
+    } else if text.contains("used - very good") || text.contains("used: very good") {
+        Some("Used - Very Good")
+    } else if text.contains("used - good") || text.contains("used: good") {
+        Some("Used - Good")
+    } else if text.contains("used - acceptable") || text.contains("used: acceptable") {
+        Some("Used - Acceptable")
+    } else {
+        None
+    }
+}
+
+pub struct Amazon {
+    browser: BrowserConfig,
+}
+
+impl Amazon {
+    pub fn new(browser: BrowserConfig) -> Self {
+        Amazon { browser }
+    }
+}
+
+#[async_trait]
+impl Source for Amazon {
+    fn name(&self) -> &'static str {
+        "Amazon"
+    }
+
+    async fn scrape(&self, _client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        println!("  Starting Selenium WebDriver for Amazon...");
+
+        let mut caps = DesiredCapabilities::chrome();
+        caps.add_arg("--headless=new").ok();
+        caps.add_arg("--disable-gpu").ok();
+        caps.add_arg("--no-sandbox").ok();
+        caps.add_arg("--disable-dev-shm-usage").ok();
+        caps.add_arg("--window-size=1920,1200").ok();
+        caps.add_arg("--disable-blink-features=AutomationControlled").ok();
+        caps.add_arg("--lang=en-US").ok();
+        caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
+
+        let driver = match sessions::open(&self.browser, "Amazon", caps).await {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
+                error_journal::record(ErrorCategory::Driver, "Amazon", None, &e, None);
+                return listings;
+            }
+        };
+
+        let dev_tools = ChromeDevTools::new(driver.handle.clone());
+        if let Err(e) = dev_tools
+            .execute_cdp_with_params("Page.addScriptToEvaluateOnNewDocument", json!({ "source": STEALTH_SCRIPT }))
+            .await
+        {
+            eprintln!("  Warning: Failed to install stealth script: {}", e);
+        }
+
+        // Visit the homepage first so search requests carry normal session cookies
+        let _ = retry::goto(&driver, BASE_URL).await;
+        politeness.behavior.wait_for_load(&driver).await;
+
+        let searches: Vec<(&str, &str)> = vec![
+            ("iPhone 15", "iphone 15 unlocked"),
+            ("iPhone 14", "iphone 14 unlocked"),
+            ("iPhone 13", "iphone 13 unlocked"),
+            ("Galaxy S24", "samsung galaxy s24 unlocked"),
+            ("Pixel 8", "google pixel 8 unlocked"),
+        ];
+
+        'searches: for (device, query) in searches.iter() {
+            let query = query.replace(' ', "+");
+            let urls = [
+                ("Renewed", format!("{}/s?k={}+renewed", BASE_URL, query)),
+                ("Warehouse", format!("{}/s?k={}&i=warehouse-deals", BASE_URL, query)),
+            ];
+            let mut device_count = 0;
+
+            for (section, url) in urls.iter() {
+                println!("  🅰️ Scraping Amazon {} {}", section, device);
+
+                if let Err(e) = retry::goto(&driver, url).await {
+                    eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
+                    error_journal::record(ErrorCategory::Navigation, "Amazon", Some(url), &e, None);
+                    continue;
+                }
+                politeness.behavior.wait_for_load(&driver).await;
+                politeness.behavior.scroll(&driver, SCROLL_DEPTH_PX).await;
+
+                let Ok(result_value) = driver.execute(EXTRACT_SCRIPT, vec![]).await else {
+                    continue;
+                };
+                let json = result_value.json();
+
+                if json.get("captcha").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    eprintln!("    🤖 Amazon served a captcha - skipping the rest of Amazon this run");
+                    error_journal::record(ErrorCategory::Blocked, "Amazon", Some(url), "captcha served", None);
+                    break 'searches;
+                }
+
+                if let Some(products_arr) = json.get("products").and_then(|v| v.as_array()) {
+                    for product in products_arr {
+                        let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                        let price = product.get("price").and_then(|v| v.as_str()).unwrap_or("");
+                        let prod_url = product.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                        let text = product.get("text").and_then(|v| v.as_str()).unwrap_or("");
+
+                        if name.is_empty() || price.is_empty() || prod_url.is_empty() {
+                            continue;
+                        }
+
+                        // Warehouse cards carry the grade in the card text, Renewed in the title
+                        let grade = parse_condition_grade(text).or(if *section == "Renewed" { Some("Renewed") } else { None });
+                        let name = match grade {
+                            Some(grade) if !name.contains(grade) => format!("{} ({})", name, grade),
+                            _ => name.to_string(),
+                        };
+
+                        listings.products.push(Product {
+                            name,
+                            price: price::parse_money(price),
+                            url: prod_url.to_string(),
+                            source: "Amazon".to_string(),
+                            sold_date: None,
+                            grade: grade.map(String::from),
+                            warranty: None,
+                            expires_at: None,
+                            region: None,
+                            currency: None,
+                            attributes: ProductAttributes::default(),
+                            listed_at: None,
+                            lot_quantity: None,
+                        });
+                        device_count += 1;
+                    }
+                }
+            }
+
+            listings.device_counts.insert(device.to_string(), device_count);
+        }
+
+        // Kept for the next scrape of the site this run
+        sessions::release(&self.browser, "Amazon", driver).await;
+
+        println!("  ✓ Amazon scraping complete. Found {} products", listings.products.len());
+
+        listings
+    }
+}
@@ -0,0 +1,143 @@
+// Apple Certified Refurbished store. The product grid is bootstrapped from a
+// JSON blob (window.REFURB_GRID_BOOTSTRAP) rather than rendered markup.
+// Refurb iPhones appear rarely and sell out fast, so besides emitting products
+// the watcher keeps per-model stock and flags new arrivals between runs for
+// the post-run hook (M3_APPLE_REFURB_DROPS).
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+use super::{Source, SourceListings, resolve_url, scrape_pages};
+use crate::attributes::ProductAttributes;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::politeness::Politeness;
+use crate::{Product, price, storage};
+
+const BASE_URL: &str = "https://www.apple.com";
+const SOURCE_NAME: &str = "Apple Refurbished";
+const BOOTSTRAP_MARKER: &str = "REFURB_GRID_BOOTSTRAP = ";
+// URLs in stock on the previous run, to tell new arrivals apart
+const STOCK_FILE: &str = "apple_refurb_stock.json";
+
+fn parse_grid(html: &str) -> Vec<Product> {
+    let Some(start) = html.find(BOOTSTRAP_MARKER) else {
+        return Vec::new();
+    };
+    // The blob is followed by more script; read just the first JSON value
+    let rest = &html[start + BOOTSTRAP_MARKER.len()..];
+    let grid = match serde_json::Deserializer::from_str(rest).into_iter::<Value>().next() {
+        Some(Ok(grid)) => grid,
+        Some(Err(e)) => {
+            error_journal::record(ErrorCategory::Parse, SOURCE_NAME, None, &e, Some(rest));
+            return Vec::new();
+        }
+        None => return Vec::new(),
+    };
+
+    let tiles = grid.get("tiles").and_then(|t| t.as_array()).cloned().unwrap_or_default();
+    tiles
+        .iter()
+        .filter_map(|tile| {
+            let title = tile.get("title")?.as_str()?;
+            let href = tile.get("productDetailsUrl")?.as_str()?;
+            let price = tile.pointer("/price/currentPrice/raw_amount").and_then(|p| p.as_str())
+                .or_else(|| tile.pointer("/price/currentPrice/amount").and_then(|p| p.as_str()))?;
+            Some(Product {
+                name: title.to_string(),
+                price: price::parse_money(price),
+                url: resolve_url(href.split('?').next().unwrap_or(href), BASE_URL),
+                source: SOURCE_NAME.to_string(),
+                sold_date: None,
+                grade: Some("Apple Certified Refurbished".to_string()),
+                // Refurbs carry Apple's standard one-year warranty
+                warranty: Some("1 year".to_string()),
+                expires_at: None,
+                region: None,
+                currency: None,
+                attributes: ProductAttributes::default(),
+                listed_at: None,
+                lot_quantity: None,
+            })
+        })
+        .collect()
+}
+
+pub struct AppleRefurbished;
+
+#[async_trait]
+impl Source for AppleRefurbished {
+    fn name(&self) -> &'static str {
+        SOURCE_NAME
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let pages = vec![("iPhone", format!("{}/shop/refurbished/iphone", BASE_URL))];
+        scrape_pages(client, self.name(), &pages, politeness, parse_grid).await
+    }
+}
+
+// In-stock count and cheapest price for one model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelStock {
+    pub device: String,
+    pub in_stock: usize,
+    pub min_price: Money,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RefurbWatch {
+    pub stock: Vec<ModelStock>,
+    // Listed now but not on the previous run
+    pub new_arrivals: Vec<Product>,
+}
+
+// Per-model stock from this run's Apple refurb products, diffed against the last run
+pub fn watch(storefront_products: &[Product]) -> RefurbWatch {
+    let products: Vec<&Product> = storefront_products.iter().filter(|p| p.source == SOURCE_NAME).collect();
+
+    let mut by_device: BTreeMap<String, ModelStock> = BTreeMap::new();
+    for product in &products {
+        let (Some(device), Some(price)) = (product.attributes.device_key(), product.amount()) else {
+            continue;
+        };
+        let stock = by_device.entry(device.clone()).or_insert(ModelStock { device, in_stock: 0, min_price: price });
+        stock.in_stock += 1;
+        stock.min_price = stock.min_price.min(price);
+    }
+
+    let previous: Option<HashSet<String>> = fs::read_to_string(storage::profile_path(STOCK_FILE)).ok().and_then(|c| serde_json::from_str(&c).ok());
+    // Everything would look new on the very first run, so only diff once there is a baseline
+    let new_arrivals = match &previous {
+        Some(previous) => products.iter().filter(|p| !previous.contains(&p.url)).map(|p| (*p).clone()).collect(),
+        None => Vec::new(),
+    };
+
+    let current: HashSet<&String> = products.iter().map(|p| &p.url).collect();
+    if let Ok(json) = serde_json::to_string(&current)
+        && let Err(e) = fs::write(storage::profile_path(STOCK_FILE), json)
+    {
+        eprintln!("Failed to write {}: {}", STOCK_FILE, e);
+        error_journal::record(ErrorCategory::Storage, SOURCE_NAME, Some(STOCK_FILE), &e, None);
+    }
+
+    RefurbWatch { stock: by_device.into_values().collect(), new_arrivals }
+}
+
+pub fn display_watch(watch: &RefurbWatch) {
+    if watch.stock.is_empty() {
+        return;
+    }
+
+    println!("\n🍎 APPLE REFURBISHED STOCK:");
+    for stock in &watch.stock {
+        println!("   {}: {} in stock from {}", stock.device, stock.in_stock, stock.min_price);
+    }
+    for product in &watch.new_arrivals {
+        println!("   🆕 Just dropped: {} - {}", product.name, product.price_label());
+        println!("      🔗 {}", product.url);
+    }
+}
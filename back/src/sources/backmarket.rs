@@ -0,0 +1,60 @@
+// Back Market refurbished marketplace (server-rendered search results)
+
+use async_trait::async_trait;
+
+use super::{CardSelectors, Source, SourceListings, parse_product_cards, scrape_pages};
+use crate::politeness::Politeness;
+
+pub(super) const BASE_URL: &str = "https://www.backmarket.com";
+
+pub(super) const SELECTORS: CardSelectors = CardSelectors {
+    profile: "back market",
+    items: &[
+        "[data-qa='productCard']",
+        "[data-test='product-thumb']",
+        "a[href*='/p/']",
+    ],
+    name: &[
+        "h2",
+        "h3",
+        "[data-qa='productCardTitle']",
+        "[class*='title']",
+    ],
+    price: &[
+        "[data-qa='productCardPrice']",
+        "[data-qa='price']",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[href*='/p/']",
+        "a",
+    ],
+};
+
+pub struct BackMarket;
+
+#[async_trait]
+impl Source for BackMarket {
+    fn name(&self) -> &'static str {
+        "Back Market"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let queries = [
+            ("iPhone 15", "iphone 15"),
+            ("iPhone 14", "iphone 14"),
+            ("iPhone 13", "iphone 13"),
+            ("Galaxy S24", "galaxy s24"),
+            ("Pixel 8", "pixel 8"),
+        ];
+        let pages: Vec<(&str, String)> = queries
+            .iter()
+            .map(|(device, q)| (*device, format!("{}/en-us/search?q={}", BASE_URL, q.replace(' ', "+"))))
+            .collect();
+
+        scrape_pages(client, self.name(), &pages, politeness, |html| {
+            parse_product_cards(html, BASE_URL, self.name(), &SELECTORS)
+        })
+        .await
+    }
+}
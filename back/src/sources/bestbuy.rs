@@ -0,0 +1,160 @@
+// Best Buy open-box phones via the public Open Box API
+// (https://bestbuyapis.github.io/api-documentation/#open-box-offers-api).
+// Only registered when [sources.bestbuy] api_key is set.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{Source, SourceListings};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::BestBuyConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::politeness::Politeness;
+
+const API_URL: &str = "https://api.bestbuy.com/beta/products/openBox";
+const PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct OpenBoxResponse {
+    #[serde(default)]
+    metadata: Option<Metadata>,
+    #[serde(default)]
+    results: Vec<OpenBoxItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    page: Option<PageInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageInfo {
+    total: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenBoxItem {
+    names: Names,
+    links: Links,
+    #[serde(default)]
+    offers: Vec<Offer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Names {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Links {
+    web: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Offer {
+    condition: String,
+    prices: Prices,
+}
+
+#[derive(Debug, Deserialize)]
+struct Prices {
+    current: f64,
+}
+
+// One product per open-box offer - the same SKU is often available in
+// several conditions at different prices
+fn offers_to_products(response: OpenBoxResponse) -> Vec<Product> {
+    let mut products = Vec::new();
+
+    for item in response.results {
+        let Some(url) = item.links.web else {
+            continue;
+        };
+        for offer in item.offers {
+            let mut condition = offer.condition.clone();
+            if let Some(first) = condition.get_mut(0..1) {
+                first.make_ascii_uppercase();
+            }
+            products.push(Product {
+                name: format!("{} (Open-Box {})", item.names.title, condition),
+                price: Some(Money::usd(offer.prices.current)),
+                url: format!("{}#{}", url, offer.condition),
+                source: "Best Buy".to_string(),
+                sold_date: None,
+                grade: Some(format!("Open-Box {}", condition)),
+                warranty: None,
+                expires_at: None,
+                region: None,
+                currency: None,
+                attributes: ProductAttributes::default(),
+                listed_at: None,
+                lot_quantity: None,
+            });
+        }
+    }
+
+    products
+}
+
+pub struct BestBuy {
+    config: BestBuyConfig,
+}
+
+impl BestBuy {
+    pub fn new(config: BestBuyConfig) -> Self {
+        BestBuy { config }
+    }
+}
+
+#[async_trait]
+impl Source for BestBuy {
+    fn name(&self) -> &'static str {
+        "Best Buy"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        for category in &self.config.categories {
+            let mut category_count = 0;
+
+            for page in 1..=politeness.page_cap(self.config.max_pages) {
+                let url = format!(
+                    "{}(categoryId={})?apiKey={}&pageSize={}&page={}",
+                    API_URL, category, self.config.api_key, PAGE_SIZE, page
+                );
+                println!("  Best Buy open-box category {} page {}", category, page);
+
+                let Some(body) = politeness.fetch(client, &url).await else {
+                    break;
+                };
+                let response: OpenBoxResponse = match serde_json::from_str(&body) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        eprintln!("    ❌ Unexpected Best Buy API response: {}", e);
+                        // Logged without the query string so the API key stays out of the journal
+                        let endpoint = format!("{}(categoryId={})", API_URL, category);
+                        error_journal::record(ErrorCategory::Parse, "Best Buy", Some(&endpoint), &e, Some(&body));
+                        break;
+                    }
+                };
+
+                let total = response.metadata.as_ref().and_then(|m| m.page.as_ref()).and_then(|p| p.total).unwrap_or(1);
+                let products = offers_to_products(response);
+                println!("    Found {} open-box offers", products.len());
+                category_count += products.len();
+                listings.products.extend(products);
+
+                if page >= total {
+                    break;
+                }
+            }
+
+            listings.device_counts.insert(category.clone(), category_count);
+        }
+
+        listings
+    }
+}
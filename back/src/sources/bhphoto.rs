@@ -0,0 +1,110 @@
+// B&H Photo used department. Used items carry a numeric condition rating
+// ("Used - 9+") which is mapped onto the grade names the other sources use.
+
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+
+use super::{Source, SourceListings, resolve_url, scrape_pages};
+use crate::attributes::ProductAttributes;
+use crate::politeness::Politeness;
+use crate::{Product, get_href_from_selectors, get_text_from_selectors, price};
+
+const BASE_URL: &str = "https://www.bhphotovideo.com";
+
+const ITEM_SELECTORS: &[&str] = &["[data-selenium='miniProductPage']", "[data-selenium*='productItem']", "[class*='product_']"];
+const NAME_SELECTORS: &[&str] = &["[data-selenium='miniProductPageProductName']", "h3", "[class*='title']"];
+const PRICE_SELECTORS: &[&str] = &["[data-selenium='uppedDecimalPriceFirst']", "[data-selenium*='Price']", "[class*='price']"];
+const LINK_SELECTORS: &[&str] = &["a[data-selenium='miniProductPageProductNameLink']", "a[href*='/c/product/']", "a"];
+
+// B&H's used rating scale
+fn grade_for_rating(rating: &str) -> &'static str {
+    match rating {
+        "10" => "Like New",
+        "9+" | "9" => "Excellent",
+        "8+" | "8" => "Good",
+        "7" => "Fair",
+        _ => "Acceptable",
+    }
+}
+
+// Grade from the card text: "Used - 9+" ratings, or "Open Box"
+fn parse_grade(text: &str) -> Option<String> {
+    if let Some(start) = text.find("Used - ") {
+        let rating: String = text[start + 7..].chars().take_while(|c| c.is_ascii_digit() || *c == '+').collect();
+        if !rating.is_empty() {
+            return Some(format!("{} ({})", grade_for_rating(&rating), rating));
+        }
+    }
+    text.contains("Open Box").then(|| "Open Box".to_string())
+}
+
+fn parse_used_cards(html: &str) -> Vec<Product> {
+    let document = Html::parse_document(html);
+    let mut products = Vec::new();
+
+    for selector_str in ITEM_SELECTORS {
+        let Ok(item_selector) = Selector::parse(selector_str) else {
+            continue;
+        };
+        for item in document.select(&item_selector) {
+            let item_html = Html::parse_fragment(&item.html());
+            let name = get_text_from_selectors(&item_html, NAME_SELECTORS);
+            let price = get_text_from_selectors(&item_html, PRICE_SELECTORS);
+            let url = get_href_from_selectors(&item_html, LINK_SELECTORS);
+            if name.len() <= 5 || url.is_empty() {
+                continue;
+            }
+
+            let grade = parse_grade(&item.text().collect::<Vec<_>>().join(" "));
+            // Name carries the grade too, so condition matching sees it
+            let name = match &grade {
+                Some(grade) => format!("{} ({})", name.trim(), grade),
+                None => name.trim().to_string(),
+            };
+            products.push(Product {
+                name,
+                price: price::parse_money(&price),
+                url: resolve_url(&url, BASE_URL),
+                source: "B&H".to_string(),
+                sold_date: None,
+                grade,
+                warranty: None,
+                expires_at: None,
+                region: None,
+                currency: None,
+                attributes: ProductAttributes::default(),
+                listed_at: None,
+                lot_quantity: None,
+            });
+        }
+
+        if !products.is_empty() {
+            break;
+        }
+    }
+
+    products
+}
+
+pub struct BhPhoto;
+
+#[async_trait]
+impl Source for BhPhoto {
+    fn name(&self) -> &'static str {
+        "B&H"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let queries = [
+            ("iPhone", "iphone"),
+            ("Galaxy", "samsung galaxy"),
+            ("Pixel", "google pixel"),
+        ];
+        let pages: Vec<(&str, String)> = queries
+            .iter()
+            .map(|(device, q)| (*device, format!("{}/c/search?q={}&filters=fct_condition%3Aused", BASE_URL, q.replace(' ', "%20"))))
+            .collect();
+
+        scrape_pages(client, self.name(), &pages, politeness, parse_used_cards).await
+    }
+}
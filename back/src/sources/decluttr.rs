@@ -0,0 +1,181 @@
+// Decluttr refurbished store. Decluttr sells at fixed prices per
+// model/storage/condition, so instead of scraping cards we read the Shopify
+// products.json feed and keep every variant as a row of a price table.
+// The table doubles as a baseline reference price (analysis::reference).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::attributes::ProductAttributes;
+use crate::{Product, fetch_html};
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::{Currency, Money};
+
+const BASE_URL: &str = "https://store.decluttr.com";
+const PAGE_LIMIT: usize = 250;
+const MAX_PAGES: usize = 4;
+
+// One fixed Decluttr price for a model/storage/condition (and color) variant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecluttrPrice {
+    pub model: String,
+    pub storage: Option<String>,
+    pub condition: String,
+    pub color: Option<String>,
+    pub price: Money,
+    pub available: bool,
+    pub url: String,
+}
+
+impl DecluttrPrice {
+    pub fn to_product(&self) -> Product {
+        let mut name = self.model.clone();
+        for part in [&self.storage, &self.color].into_iter().flatten() {
+            name.push(' ');
+            name.push_str(part);
+        }
+        Product {
+            name: format!("{} ({})", name, self.condition),
+            price: Some(self.price),
+            url: self.url.clone(),
+            source: "Decluttr".to_string(),
+            sold_date: None,
+            grade: Some(self.condition.clone()),
+            warranty: None,
+            expires_at: None,
+            region: None,
+            currency: None,
+            attributes: ProductAttributes::default(),
+            listed_at: None,
+            lot_quantity: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProductsResponse {
+    #[serde(default)]
+    products: Vec<ShopifyProduct>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyProduct {
+    title: String,
+    handle: String,
+    #[serde(default)]
+    options: Vec<ShopifyOption>,
+    #[serde(default)]
+    variants: Vec<ShopifyVariant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyOption {
+    name: String,
+    position: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyVariant {
+    id: u64,
+    price: String,
+    #[serde(default)]
+    available: bool,
+    option1: Option<String>,
+    option2: Option<String>,
+    option3: Option<String>,
+}
+
+impl ShopifyVariant {
+    fn option(&self, position: usize) -> Option<&str> {
+        match position {
+            1 => self.option1.as_deref(),
+            2 => self.option2.as_deref(),
+            3 => self.option3.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+// Variant option value for the first option whose name contains one of `names`
+fn option_value<'a>(product: &ShopifyProduct, variant: &'a ShopifyVariant, names: &[&str]) -> Option<&'a str> {
+    product
+        .options
+        .iter()
+        .find(|o| names.iter().any(|n| o.name.to_lowercase().contains(n)))
+        .and_then(|o| variant.option(o.position))
+}
+
+fn products_to_prices(response: ProductsResponse) -> Vec<DecluttrPrice> {
+    let mut prices = Vec::new();
+
+    for product in &response.products {
+        for variant in &product.variants {
+            let Some(price) = Money::parse_decimal(&variant.price, Currency::Usd) else {
+                continue;
+            };
+            let Some(condition) = option_value(product, variant, &["condition", "grade"]) else {
+                continue;
+            };
+            prices.push(DecluttrPrice {
+                model: product.title.trim().to_string(),
+                storage: option_value(product, variant, &["storage", "capacity", "memory"]).map(String::from),
+                condition: condition.to_string(),
+                color: option_value(product, variant, &["colo"]).map(String::from),
+                price,
+                available: variant.available,
+                url: format!("{}/products/{}?variant={}", BASE_URL, product.handle, variant.id),
+            });
+        }
+    }
+
+    prices
+}
+
+// Fetch the full price table, walking each collection's products.json pages.
+// Also returns per-device row counts for the empty-device check.
+pub async fn scrape_price_table(client: &reqwest::Client) -> (Vec<DecluttrPrice>, HashMap<String, usize>) {
+    let collections = [
+        ("iPhone 15", "iphone-15"),
+        ("iPhone 14", "iphone-14"),
+        ("iPhone 13", "iphone-13"),
+        ("Galaxy S24", "samsung-galaxy-s24"),
+        ("Pixel 8", "google-pixel-8"),
+    ];
+    let mut table = Vec::new();
+    let mut device_counts = HashMap::new();
+
+    for (device, handle) in collections.iter() {
+        let mut device_count = 0;
+
+        for page in 1..=MAX_PAGES {
+            let url = format!("{}/collections/{}/products.json?limit={}&page={}", BASE_URL, handle, PAGE_LIMIT, page);
+            println!("  Decluttr {} page {}: {}", device, page, url);
+
+            let Some(body) = fetch_html(client, &url).await else {
+                break;
+            };
+            let response: ProductsResponse = match serde_json::from_str(&body) {
+                Ok(r) => r,
+                Err(e) => {
+                    eprintln!("    ❌ Unexpected Decluttr response: {}", e);
+                    error_journal::record(ErrorCategory::Parse, "Decluttr", Some(&url), &e, Some(&body));
+                    break;
+                }
+            };
+
+            let product_count = response.products.len();
+            let prices = products_to_prices(response);
+            println!("    Found {} priced variants", prices.len());
+            device_count += prices.len();
+            table.extend(prices);
+
+            if product_count < PAGE_LIMIT {
+                break;
+            }
+        }
+
+        device_counts.insert(device.to_string(), device_count);
+    }
+
+    (table, device_counts)
+}
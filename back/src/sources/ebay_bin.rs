@@ -0,0 +1,155 @@
+// Active eBay Buy-It-Now listings as a buy source, so underpriced eBay
+// listings are compared against sold averages like Swappa. Selenium, same as
+// the eBay sold scraper; results are sorted by price + shipping.
+
+use async_trait::async_trait;
+use thirtyfour::prelude::*;
+
+use super::{Source, SourceListings};
+use crate::attributes::ProductAttributes;
+use crate::config::BrowserConfig;
+use crate::{ebay_shipping, retry, sessions};
+use crate::error_journal::{self, ErrorCategory};
+use crate::politeness::Politeness;
+use crate::{Product, parse_price};
+
+const EXTRACT_SCRIPT: &str = r#"
+    var products = [];
+    var seenUrls = new Set();
+    var items = document.querySelectorAll('ul.srp-results li.s-card');
+
+    for (var i = 0; i < items.length && products.length < 50; i++) {
+        var item = items[i];
+        var titleEl = item.querySelector('.s-card__title span');
+        var name = titleEl ? titleEl.innerText.replace(/^NEW LISTING/i, '').trim() : '';
+        if (!name || name.length < 10 || name.toLowerCase().includes('shop on ebay')) continue;
+
+        var priceEl = item.querySelector('.s-card__price');
+        var priceMatch = priceEl ? priceEl.innerText.match(/\$[\d,]+\.?\d{0,2}/) : null;
+        // Price ranges ("$100 to $300") are multi-variant listings; skip them
+        if (!priceMatch || priceEl.innerText.indexOf(' to ') !== -1) continue;
+
+        var linkEl = item.querySelector('a.s-card__link[href*="/itm/"]') || item.querySelector('a[href*="/itm/"]');
+        var cleanUrl = linkEl ? linkEl.href.split('?')[0] : '';
+        if (!cleanUrl || seenUrls.has(cleanUrl)) continue;
+        seenUrls.add(cleanUrl);
+
+        products.push({
+            name: name.substring(0, 200),
+            price: priceMatch[0],
+            shipping: shippingText(item),
+            url: cleanUrl
+        });
+    }
+
+    return { products: products };
+"#;
+
+pub struct EbayBuyItNow {
+    browser: BrowserConfig,
+}
+
+impl EbayBuyItNow {
+    pub fn new(browser: BrowserConfig) -> Self {
+        EbayBuyItNow { browser }
+    }
+}
+
+#[async_trait]
+impl Source for EbayBuyItNow {
+    fn name(&self) -> &'static str {
+        "eBay BIN"
+    }
+
+    async fn scrape(&self, _client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        println!("  Starting Selenium WebDriver for eBay Buy-It-Now...");
+
+        let mut caps = DesiredCapabilities::chrome();
+        caps.add_arg("--headless=new").ok();
+        caps.add_arg("--disable-gpu").ok();
+        caps.add_arg("--no-sandbox").ok();
+        caps.add_arg("--disable-dev-shm-usage").ok();
+        caps.add_arg("--window-size=1920,1200").ok();
+        caps.add_arg("--disable-blink-features=AutomationControlled").ok();
+        caps.add_arg("--user-agent=Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36").ok();
+
+        let driver = match sessions::open(&self.browser, self.name(), caps).await {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("  ❌ Failed to connect to ChromeDriver: {}", e);
+                error_journal::record(ErrorCategory::Driver, self.name(), None, &e, None);
+                return listings;
+            }
+        };
+
+        // LH_BIN=1 limits to Buy-It-Now, _sop=15 sorts by price + shipping
+        let queries = [
+            ("iPhone 15", "iphone+15+unlocked"),
+            ("iPhone 14", "iphone+14+unlocked"),
+            ("iPhone 13", "iphone+13+unlocked"),
+            ("Galaxy S24", "samsung+galaxy+s24+unlocked"),
+            ("Pixel 8", "google+pixel+8+unlocked"),
+        ];
+
+        for (device, query) in queries.iter() {
+            // Floor the price so cheapest-first sorting isn't all cases and parts
+            let url = format!("https://www.ebay.com/sch/i.html?_nkw={}&_sacat=9355&LH_BIN=1&_sop=15&_udlo=100", query);
+            println!("  🛒 Scraping eBay Buy-It-Now {}: {}", device, url);
+
+            if let Err(e) = retry::goto(&driver, &url).await {
+                eprintln!("    ❌ Failed to navigate to {}: {}", url, e);
+                error_journal::record(ErrorCategory::Navigation, self.name(), Some(&url), &e, None);
+                continue;
+            }
+            politeness.behavior.wait_for_load(&driver).await;
+
+            let mut device_count = 0;
+            let script = [ebay_shipping::SHIPPING_TEXT_JS, EXTRACT_SCRIPT].concat();
+            if let Ok(result_value) = driver.execute(&script, vec![]).await
+                && let Some(products_arr) = result_value.json().get("products").and_then(|v| v.as_array())
+            {
+                for product in products_arr {
+                    let name = product.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                    let prod_url = product.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                    let Some(price) = product.get("price").and_then(|v| v.as_str()).and_then(parse_price) else {
+                        continue;
+                    };
+                    let charges = ebay_shipping::parse(product.get("shipping").and_then(|v| v.as_str()).unwrap_or(""));
+                    if name.is_empty() || prod_url.is_empty() {
+                        continue;
+                    }
+
+                    // Compare what the buyer actually pays, shipping and any
+                    // international import charges included
+                    listings.products.push(Product {
+                        name: name.to_string(),
+                        price: Some(charges.landed(price)),
+                        url: prod_url.to_string(),
+                        source: self.name().to_string(),
+                        sold_date: None,
+                        grade: None,
+                        warranty: None,
+                        expires_at: None,
+                        region: None,
+                        currency: None,
+                        attributes: ProductAttributes::default(),
+                        listed_at: None,
+                        lot_quantity: None,
+                    });
+                    device_count += 1;
+                }
+            }
+            println!("    Found {} listings", device_count);
+            listings.device_counts.insert(device.to_string(), device_count);
+
+            politeness.behavior.between_pages(&driver).await;
+        }
+
+        // Kept for the next scrape of the site this run
+        sessions::release(&self.browser, self.name(), driver).await;
+
+        listings
+    }
+}
@@ -0,0 +1,58 @@
+// Gazelle certified pre-owned store (Shopify storefront, server-rendered)
+
+use async_trait::async_trait;
+
+use super::{CardSelectors, Source, SourceListings, parse_product_cards, scrape_pages};
+use crate::politeness::Politeness;
+
+pub(super) const BASE_URL: &str = "https://buy.gazelle.com";
+
+pub(super) const SELECTORS: CardSelectors = CardSelectors {
+    profile: "gazelle",
+    items: &[
+        ".product-card",
+        ".grid-product",
+        ".product-item",
+        "[class*='product-card']",
+    ],
+    name: &[
+        ".product-card__title",
+        ".grid-product__title",
+        ".product-item__title",
+        "[class*='title']",
+    ],
+    price: &[
+        ".price-item--sale",
+        ".price-item--regular",
+        ".product-card__price",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[href*='/products/']",
+        "a",
+    ],
+};
+
+pub struct Gazelle;
+
+#[async_trait]
+impl Source for Gazelle {
+    fn name(&self) -> &'static str {
+        "Gazelle"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let pages: Vec<(&str, String)> = vec![
+            ("iPhone 15", format!("{}/collections/iphone-15", BASE_URL)),
+            ("iPhone 14", format!("{}/collections/iphone-14", BASE_URL)),
+            ("iPhone 13", format!("{}/collections/iphone-13", BASE_URL)),
+            ("Galaxy S24", format!("{}/collections/samsung-galaxy-s24", BASE_URL)),
+            ("Pixel 8", format!("{}/collections/google-pixel-8", BASE_URL)),
+        ];
+
+        scrape_pages(client, self.name(), &pages, politeness, |html| {
+            parse_product_cards(html, BASE_URL, self.name(), &SELECTORS)
+        })
+        .await
+    }
+}
@@ -0,0 +1,81 @@
+// Google Store Pixel deals and certified refurbished phones. Besides joining
+// the buy side, these first-party prices are a reference point for Pixel
+// resale values (analysis::reference).
+
+use async_trait::async_trait;
+
+use super::{CardSelectors, Source, SourceListings, parse_product_cards, scrape_pages};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::politeness::Politeness;
+
+pub(super) const BASE_URL: &str = "https://store.google.com";
+pub const SOURCE_NAME: &str = "Google Store";
+
+pub(super) const SELECTORS: CardSelectors = CardSelectors {
+    profile: "google store",
+    items: &[
+        "[data-test='product-card']",
+        "[class*='product-card']",
+        "[class*='ProductCard']",
+        "li[class*='collection']",
+    ],
+    name: &[
+        "[data-test='product-card-title']",
+        "h2",
+        "h3",
+        "[class*='title']",
+    ],
+    price: &[
+        "[data-test='product-card-price']",
+        "[class*='sale-price']",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[href*='/product/']",
+        "a",
+    ],
+};
+
+// Pixel phones only (no Buds, Watch or cases), graded by which collection they came from
+fn parse_collection(html: &str, grade: Option<&str>) -> Vec<Product> {
+    parse_product_cards(html, BASE_URL, SOURCE_NAME, &SELECTORS)
+        .into_iter()
+        .filter(|p| {
+            let name = p.name.to_lowercase();
+            ProductAttributes::parse(&p.name).model.is_some() && !name.contains("watch") && !name.contains("buds")
+        })
+        .map(|p| Product {
+            name: match grade {
+                Some(grade) => format!("{} ({})", p.name, grade),
+                None => p.name,
+            },
+            grade: grade.map(String::from),
+            ..p
+        })
+        .collect()
+}
+
+pub struct GoogleStore;
+
+#[async_trait]
+impl Source for GoogleStore {
+    fn name(&self) -> &'static str {
+        SOURCE_NAME
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        let deals = vec![("Pixel deals", format!("{}/us/collection/offers?hl=en-US", BASE_URL))];
+        let new = scrape_pages(client, self.name(), &deals, politeness, |html| parse_collection(html, Some("New"))).await;
+        let refurbished = vec![("Pixel refurbished", format!("{}/us/collection/certified_refurbished_phones?hl=en-US", BASE_URL))];
+        let refurb = scrape_pages(client, self.name(), &refurbished, politeness, |html| parse_collection(html, Some("Certified Refurbished"))).await;
+
+        for part in [new, refurb] {
+            listings.device_counts.extend(part.device_counts);
+            listings.products.extend(part.products);
+        }
+        listings
+    }
+}
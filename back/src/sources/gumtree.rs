@@ -0,0 +1,120 @@
+// Gumtree classifieds in the UK and Australia. Prices are converted from
+// GBP/AUD to USD with [sources.classifieds.usd_rates]; each region is kept
+// as its own source name ("Gumtree UK") so buy-source comparison separates them.
+
+use async_trait::async_trait;
+
+use super::{CardSelectors, Source, SourceListings, localize_products, parse_product_cards, scrape_pages};
+use crate::config::ClassifiedsConfig;
+use crate::politeness::Politeness;
+
+// (region, site, currency)
+const REGIONS: &[(&str, &str, &str)] = &[
+    ("uk", "https://www.gumtree.com", "GBP"),
+    ("au", "https://www.gumtree.com.au", "AUD"),
+];
+
+pub(super) const UK_SELECTORS: CardSelectors = CardSelectors {
+    profile: "gumtree uk",
+    items: &[
+        "article[data-q='search-result']",
+        "[data-q='search-result']",
+        "li.natural",
+    ],
+    name: &[
+        "[data-q='tile-title']",
+        "h2",
+        "[class*='title']",
+    ],
+    price: &[
+        "[data-testid='price']",
+        "[data-q='tile-price']",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[data-q='search-result-anchor']",
+        "a[href*='/p/']",
+        "a",
+    ],
+};
+
+pub(super) const AU_SELECTORS: CardSelectors = CardSelectors {
+    profile: "gumtree au",
+    items: &[
+        "a.user-ad-row-new-design",
+        "[class*='user-ad-row']",
+        "article",
+    ],
+    name: &[
+        ".user-ad-row-new-design__title-span",
+        "[class*='title']",
+    ],
+    price: &[
+        ".user-ad-price-new-design__price",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[href*='/s-ad/']",
+        "a",
+    ],
+};
+
+pub struct Gumtree {
+    config: ClassifiedsConfig,
+}
+
+impl Gumtree {
+    pub fn new(config: ClassifiedsConfig) -> Self {
+        Gumtree { config }
+    }
+}
+
+#[async_trait]
+impl Source for Gumtree {
+    fn name(&self) -> &'static str {
+        "Gumtree"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+        let queries = [("iPhone", "iphone"), ("Galaxy", "samsung galaxy"), ("Pixel", "google pixel")];
+
+        for region in &self.config.gumtree_regions {
+            let Some((_, base_url, currency)) = REGIONS.iter().find(|(r, _, _)| r == region) else {
+                eprintln!("  ⚠️  Unknown Gumtree region '{}'", region);
+                continue;
+            };
+            let Some(usd_rate) = self.config.usd_rate(currency) else {
+                eprintln!("  ⚠️  No usd_rates entry for {} - skipping Gumtree {}", currency, region);
+                continue;
+            };
+            let source = format!("Gumtree {}", region.to_uppercase());
+            let selectors = if *region == "uk" { &UK_SELECTORS } else { &AU_SELECTORS };
+
+            let pages: Vec<(&str, String)> = queries
+                .iter()
+                .map(|(device, q)| {
+                    let url = if *region == "uk" {
+                        format!("{}/search?search_category=mobile-phones&q={}", base_url, q.replace(' ', "+"))
+                    } else {
+                        // c18585 = Mobile Phones
+                        format!("{}/s-mobile-phones/{}/k0c18585", base_url, q.replace(' ', "-"))
+                    };
+                    (*device, url)
+                })
+                .collect();
+
+            let region_listings = scrape_pages(client, &source, &pages, politeness, |html| {
+                let products = parse_product_cards(html, base_url, &source, selectors);
+                localize_products(products, &region.to_uppercase(), currency, usd_rate)
+            })
+            .await;
+            for (device, count) in region_listings.device_counts {
+                listings.device_counts.insert(format!("{} {}", device, region), count);
+            }
+            listings.products.extend(region_listings.products);
+        }
+
+        listings
+    }
+}
@@ -0,0 +1,77 @@
+// Kijiji (Canada) classifieds. Prices are in CAD and converted to USD with
+// [sources.classifieds.usd_rates] so they compare against US sold comps;
+// the original market and currency stay on the product.
+
+use async_trait::async_trait;
+
+use super::{CardSelectors, Source, SourceListings, localize_products, parse_product_cards, scrape_pages};
+use crate::config::ClassifiedsConfig;
+use crate::politeness::Politeness;
+
+pub(super) const BASE_URL: &str = "https://www.kijiji.ca";
+
+pub(super) const SELECTORS: CardSelectors = CardSelectors {
+    profile: "kijiji",
+    items: &[
+        "[data-testid='listing-card']",
+        "section[data-listingid]",
+        "[class*='search-item']",
+    ],
+    name: &[
+        "[data-testid='listing-title']",
+        "h3",
+        "[class*='title']",
+    ],
+    price: &[
+        "[data-testid='listing-price']",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[data-testid='listing-link']",
+        "a[href*='/v-']",
+        "a",
+    ],
+};
+
+pub struct Kijiji {
+    config: ClassifiedsConfig,
+}
+
+impl Kijiji {
+    pub fn new(config: ClassifiedsConfig) -> Self {
+        Kijiji { config }
+    }
+}
+
+#[async_trait]
+impl Source for Kijiji {
+    fn name(&self) -> &'static str {
+        "Kijiji"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let Some(usd_rate) = self.config.usd_rate("CAD") else {
+            eprintln!("  ⚠️  No usd_rates entry for CAD - skipping Kijiji");
+            return SourceListings::default();
+        };
+
+        // c760 = Cell Phones; the location slug and id come from a Kijiji search URL
+        let queries = [("iPhone", "iphone"), ("Galaxy", "samsung-galaxy"), ("Pixel", "google-pixel")];
+        let pages: Vec<(&str, String)> = queries
+            .iter()
+            .map(|(device, q)| {
+                let url = format!(
+                    "{}/b-cell-phone/{}/{}/k0c760l{}",
+                    BASE_URL, self.config.kijiji_location, q, self.config.kijiji_location_id
+                );
+                (*device, url)
+            })
+            .collect();
+
+        scrape_pages(client, self.name(), &pages, politeness, |html| {
+            let products = parse_product_cards(html, BASE_URL, self.name(), &SELECTORS);
+            localize_products(products, "CA", "CAD", usd_rate)
+        })
+        .await
+    }
+}
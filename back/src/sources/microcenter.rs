@@ -0,0 +1,109 @@
+// Micro Center open-box phones for one store. Open-box stock and pricing are
+// per location, so search results are pinned to [sources.microcenter]
+// store_id; the source is only registered when a store is set.
+
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+
+use super::{Source, SourceListings, resolve_url, scrape_pages};
+use crate::attributes::ProductAttributes;
+use crate::config::MicroCenterConfig;
+use crate::politeness::Politeness;
+use crate::{Product, get_href_from_selectors, get_text_from_selectors, parse_price, price};
+
+const BASE_URL: &str = "https://www.microcenter.com";
+
+const ITEM_SELECTORS: &[&str] = &["li.product_wrapper", ".product_wrapper", "[class*='product_wrapper']"];
+const NAME_SELECTORS: &[&str] = &[".pDescription a", "a[data-name]", "h2 a"];
+const PRICE_SELECTORS: &[&str] = &["[itemprop='price']", ".price", "[class*='price']"];
+// "Open Box: from $649.99" line under the regular price
+const OPEN_BOX_SELECTORS: &[&str] = &[".clearance", "[class*='openbox']", "[class*='open-box']"];
+const LINK_SELECTORS: &[&str] = &[".pDescription a", "a[href*='/product/']", "a"];
+
+pub struct MicroCenter {
+    config: MicroCenterConfig,
+}
+
+impl MicroCenter {
+    pub fn new(config: MicroCenterConfig) -> Self {
+        MicroCenter { config }
+    }
+
+    fn parse(&self, html: &str) -> Vec<Product> {
+        let document = Html::parse_document(html);
+        let mut products = Vec::new();
+
+        for selector_str in ITEM_SELECTORS {
+            let Ok(item_selector) = Selector::parse(selector_str) else {
+                continue;
+            };
+            for item in document.select(&item_selector) {
+                let item_html = Html::parse_fragment(&item.html());
+                let name = get_text_from_selectors(&item_html, NAME_SELECTORS);
+                let url = get_href_from_selectors(&item_html, LINK_SELECTORS);
+                if name.len() <= 5 || url.is_empty() {
+                    continue;
+                }
+
+                let open_box = parse_price(&get_text_from_selectors(&item_html, OPEN_BOX_SELECTORS));
+                let (price, grade) = match open_box {
+                    Some(price) => (Some(price), Some("Open Box".to_string())),
+                    None if self.config.open_box_only => continue,
+                    None => (price::parse_money(&get_text_from_selectors(&item_html, PRICE_SELECTORS)), None),
+                };
+
+                products.push(Product {
+                    name: name.trim().to_string(),
+                    price,
+                    url: resolve_url(&url, BASE_URL),
+                    source: self.name().to_string(),
+                    sold_date: None,
+                    grade,
+                    warranty: None,
+                    expires_at: None,
+                    region: None,
+                    currency: None,
+                    attributes: ProductAttributes::default(),
+                    listed_at: None,
+                    lot_quantity: None,
+                });
+            }
+
+            if !products.is_empty() {
+                break;
+            }
+        }
+
+        products
+    }
+}
+
+#[async_trait]
+impl Source for MicroCenter {
+    fn name(&self) -> &'static str {
+        "Micro Center"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        println!("  Store: {}", self.config.store_id);
+        let pages: Vec<(&str, String)> = [
+            ("iPhone", "iphone"),
+            ("Galaxy", "samsung galaxy"),
+            ("Pixel", "google pixel"),
+        ]
+        .iter()
+        .map(|(device, query)| {
+            let url = format!(
+                "{}/search/search_results.aspx?Ntt={}&storeid={}&myStore=true",
+                BASE_URL,
+                query.replace(' ', "+"),
+                self.config.store_id
+            );
+            (*device, url)
+        })
+        .collect();
+
+        // scrape_pages paces requests between pages
+        scrape_pages(client, self.name(), &pages, politeness, |html| self.parse(html)).await
+    }
+}
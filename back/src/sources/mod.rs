@@ -0,0 +1,231 @@
+// Buy-side storefront sources. Each source implements `Source`; plain-HTTP
+// storefronts describe their listing cards with `CardSelectors` and reuse
+// `scrape_pages` / `parse_product_cards` instead of hand-rolling a crawler.
+
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+use std::collections::HashMap;
+
+use crate::attributes::ProductAttributes;
+use crate::config::{BrowserConfig, SourcesConfig};
+use crate::money::Money;
+use crate::politeness::Politeness;
+use crate::progress::Progress;
+use selectors::SelectorProfile;
+use crate::{Product, get_href_from_selectors, get_text_from_selectors, price};
+
+pub mod amazon;
+pub mod apple_refurb;
+pub mod backmarket;
+pub mod bestbuy;
+pub mod bhphoto;
+pub mod decluttr;
+pub mod ebay_bin;
+pub mod gazelle;
+pub mod google_store;
+pub mod gumtree;
+pub mod kijiji;
+pub mod microcenter;
+pub mod reebelo;
+pub mod samsung_renewed;
+pub mod selectors;
+pub mod walmart;
+pub mod woot;
+
+// Listings from one source plus how many each device page yielded
+#[derive(Debug, Default)]
+pub struct SourceListings {
+    pub products: Vec<Product>,
+    pub device_counts: HashMap<String, usize>,
+}
+
+#[async_trait]
+pub trait Source: Send + Sync {
+    // Display name, also stored as `Product.source`
+    fn name(&self) -> &'static str;
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings;
+}
+
+// All enabled storefront buy sources, in the order they are scraped each run
+pub fn buy_sources(config: &SourcesConfig, browser: &BrowserConfig) -> Vec<Box<dyn Source>> {
+    let mut sources: Vec<Box<dyn Source>> = vec![
+        Box::new(gazelle::Gazelle),
+        Box::new(backmarket::BackMarket),
+        Box::new(amazon::Amazon::new(browser.clone())),
+        Box::new(walmart::Walmart::new(config.walmart.clone())),
+        Box::new(reebelo::Reebelo::new(config.reebelo.clone())),
+        Box::new(ebay_bin::EbayBuyItNow::new(browser.clone())),
+        Box::new(bhphoto::BhPhoto),
+        Box::new(apple_refurb::AppleRefurbished),
+        Box::new(samsung_renewed::SamsungRenewed),
+        Box::new(google_store::GoogleStore),
+    ];
+    if !config.bestbuy.api_key.is_empty() {
+        sources.push(Box::new(bestbuy::BestBuy::new(config.bestbuy.clone())));
+    }
+    if config.classifieds.kijiji {
+        sources.push(Box::new(kijiji::Kijiji::new(config.classifieds.clone())));
+    }
+    if !config.classifieds.gumtree_regions.is_empty() {
+        sources.push(Box::new(gumtree::Gumtree::new(config.classifieds.clone())));
+    }
+    if !config.woot.api_key.is_empty() {
+        sources.push(Box::new(woot::Woot::new(config.woot.clone())));
+    }
+    if !config.microcenter.store_id.is_empty() {
+        sources.push(Box::new(microcenter::MicroCenter::new(config.microcenter.clone())));
+    }
+    sources
+}
+
+// CSS selectors describing a storefront's product cards, tried in order.
+// `profile` names them in selectors.toml (selectors.rs).
+pub struct CardSelectors {
+    pub profile: &'static str,
+    pub items: &'static [&'static str],
+    pub name: &'static [&'static str],
+    pub price: &'static [&'static str],
+    pub link: &'static [&'static str],
+}
+
+// Parsed JSON from a Next.js page's __NEXT_DATA__ script, which carries the
+// server-rendered page state and is steadier than the markup built from it
+pub fn next_data(html: &str) -> Option<serde_json::Value> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("script#__NEXT_DATA__").ok()?;
+    let script = document.select(&selector).next()?.text().collect::<String>();
+    serde_json::from_str(&script).ok()
+}
+
+// Turn a relative or protocol-relative href into an absolute URL
+pub fn resolve_url(href: &str, base_url: &str) -> String {
+    if href.starts_with("http") {
+        href.to_string()
+    } else if href.starts_with("//") {
+        format!("https:{}", href)
+    } else if href.starts_with('/') {
+        format!("{}{}", base_url, href)
+    } else {
+        href.to_string()
+    }
+}
+
+// Amount from a price in any currency format: "C$1,050", "£450", "1.299,00 €"
+pub fn parse_local_price(text: &str) -> Option<f64> {
+    crate::price::parse(text).map(|price| price.amount)
+}
+
+// Convert listing prices from a foreign market to USD, recording the market
+// and original currency. The amount is taken in the market's currency
+// whatever symbol it was scraped with (Kijiji writes "$" for CAD). Listings
+// without a price ("Please contact") are dropped.
+pub fn localize_products(products: Vec<Product>, region: &str, currency: &str, usd_rate: f64) -> Vec<Product> {
+    products
+        .into_iter()
+        .filter_map(|product| {
+            let price = product.price?.amount();
+            Some(Product {
+                price: Some(Money::usd(price * usd_rate)),
+                region: Some(region.to_string()),
+                currency: Some(currency.to_string()),
+                ..product
+            })
+        })
+        .collect()
+}
+
+// Parse listing cards from a storefront page with the source's active
+// selector profile (selectors.toml or built-in)
+pub fn parse_product_cards(html: &str, base_url: &str, source: &str, selectors: &CardSelectors) -> Vec<Product> {
+    parse_with_profile(html, base_url, source, &selectors::active(selectors))
+}
+
+fn as_strs(list: &[String]) -> Vec<&str> {
+    list.iter().map(String::as_str).collect()
+}
+
+// The first item selector that yields products wins, same as the Newegg parser
+pub fn parse_with_profile(html: &str, base_url: &str, source: &str, profile: &SelectorProfile) -> Vec<Product> {
+    let document = Html::parse_document(html);
+    let mut products = Vec::new();
+    let (name_selectors, price_selectors, link_selectors) = (as_strs(&profile.name), as_strs(&profile.price), as_strs(&profile.link));
+
+    for selector_str in &profile.items {
+        if let Ok(item_selector) = Selector::parse(selector_str) {
+            for item in document.select(&item_selector) {
+                let item_html = Html::parse_fragment(&item.html());
+
+                let name = get_text_from_selectors(&item_html, &name_selectors);
+                let price = get_text_from_selectors(&item_html, &price_selectors);
+                // The card itself may be the link
+                let url = match item.value().attr("href") {
+                    Some(href) => href.to_string(),
+                    None => get_href_from_selectors(&item_html, &link_selectors),
+                };
+
+                if name.len() > 5 && !url.is_empty() {
+                    products.push(Product {
+                        name: name.trim().to_string(),
+                        price: price::parse_money(&price),
+                        url: resolve_url(&url, base_url),
+                        source: source.to_string(),
+                        sold_date: None,
+                        grade: None,
+                        warranty: None,
+                        expires_at: None,
+                        region: None,
+                        currency: None,
+                        attributes: ProductAttributes::default(),
+                        listed_at: None,
+                        lot_quantity: None,
+                    });
+                }
+            }
+        }
+
+        if !products.is_empty() {
+            break;
+        }
+    }
+
+    products
+}
+
+// Fetch each (device, url) page over plain HTTP and parse it with `parse`,
+// recording per-device counts for the empty-device check. Pages are fetched
+// `politeness.concurrency` at a time.
+pub async fn scrape_pages<F>(
+    client: &reqwest::Client,
+    source: &str,
+    pages: &[(&str, String)],
+    politeness: &Politeness,
+    parse: F,
+) -> SourceListings
+where
+    F: Fn(&str) -> Vec<Product>,
+{
+    let mut listings = SourceListings::default();
+
+    let progress = Progress::new(pages.len(), source);
+    let batch_size = politeness.concurrency.max(1);
+    for (batch_index, batch) in pages.chunks(batch_size).enumerate() {
+        let urls: Vec<String> = batch.iter().map(|(_, url)| url.clone()).collect();
+        for (i, (device, url)) in batch.iter().enumerate() {
+            progress.step(batch_index * batch_size + i, &format!("{} {}: {}", source, device, url));
+        }
+        let pages_html = politeness.fetch_all(client, &urls).await;
+        for ((device, _), html) in batch.iter().zip(pages_html) {
+            let Some(html) = html else {
+                continue;
+            };
+            let products = parse(&html);
+            progress.println(&format!("    Found {} products", products.len()));
+            listings.device_counts.insert(device.to_string(), products.len());
+            listings.products.extend(products);
+        }
+    }
+    progress.finish();
+
+    listings
+}
@@ -0,0 +1,189 @@
+// Reebelo refurbished marketplace. Reebelo runs the same Next.js storefront in
+// several countries, so regions are configurable and non-USD prices are
+// converted with the configured rates to compare against US sold comps.
+// Grade and warranty come from the page state rather than the card text.
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{Source, SourceListings, next_data, resolve_url};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::ReebeloConfig;
+use crate::money::Money;
+use crate::politeness::Politeness;
+
+// (region, storefront, currency)
+const REGIONS: &[(&str, &str, &str)] = &[
+    ("us", "https://reebelo.com", "USD"),
+    ("ca", "https://reebelo.ca", "CAD"),
+    ("au", "https://reebelo.com.au", "AUD"),
+    ("nz", "https://reebelo.co.nz", "NZD"),
+    ("sg", "https://reebelo.sg", "SGD"),
+];
+
+const GRADES: [&str; 5] = ["Premium", "Pristine", "Excellent", "Good", "Acceptable"];
+
+// An offer as found in the page state
+struct Offer {
+    title: String,
+    price: f64,
+    href: String,
+    grade: Option<String>,
+    warranty: Option<String>,
+}
+
+fn number(value: Option<&Value>) -> Option<f64> {
+    let value = value?;
+    value.as_f64().or_else(|| value.as_str().and_then(|s| s.trim_start_matches('$').replace(',', "").parse().ok()))
+}
+
+fn parse_grade(object: &Value, title: &str) -> Option<String> {
+    let field = ["grade", "condition", "conditionName"]
+        .iter()
+        .find_map(|key| object.get(*key).and_then(|v| v.as_str()));
+    let text = field.unwrap_or(title).to_lowercase();
+    GRADES.iter().find(|g| text.contains(&g.to_lowercase())).map(|g| g.to_string())
+}
+
+// Warranty as "N months"; the page state uses either a month count or a label
+fn parse_warranty(object: &Value) -> Option<String> {
+    let value = object.get("warranty").or_else(|| object.get("warrantyMonths"))?;
+    if let Some(months) = value.as_u64() {
+        return Some(format!("{} months", months));
+    }
+    let label = value.as_str()?.trim();
+    if label.is_empty() { None } else { Some(label.to_string()) }
+}
+
+// The page state nests offers differently per page type, so walk the whole
+// tree and keep anything that looks like a priced product with a link
+fn collect_offers(value: &Value, offers: &mut Vec<Offer>) {
+    match value {
+        Value::Object(object) => {
+            let title = object.get("title").or_else(|| object.get("name")).and_then(|v| v.as_str());
+            let price = number(object.get("price"))
+                .or_else(|| number(object.get("minPrice")))
+                .or_else(|| number(object.get("salePrice")));
+            let href = object
+                .get("url")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .or_else(|| object.get("handle").and_then(|v| v.as_str()).map(|h| format!("/products/{}", h)));
+
+            if let (Some(title), Some(price), Some(href)) = (title, price, href) {
+                offers.push(Offer {
+                    title: title.trim().to_string(),
+                    price,
+                    href,
+                    grade: parse_grade(value, title),
+                    warranty: parse_warranty(value),
+                });
+                return;
+            }
+            for child in object.values() {
+                collect_offers(child, offers);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_offers(item, offers);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_collection(html: &str, base_url: &str, source: &str, region: &str, currency: &str, usd_rate: f64) -> Vec<Product> {
+    let Some(data) = next_data(html) else {
+        return Vec::new();
+    };
+    let mut offers = Vec::new();
+    collect_offers(&data, &mut offers);
+
+    offers
+        .into_iter()
+        .filter(|o| o.title.len() > 5 && o.price > 0.0)
+        .map(|offer| {
+            let name = match &offer.grade {
+                Some(grade) if !offer.title.contains(grade.as_str()) => format!("{} ({})", offer.title, grade),
+                _ => offer.title.clone(),
+            };
+            Product {
+                name,
+                price: Some(Money::usd(offer.price * usd_rate)),
+                url: resolve_url(&offer.href, base_url),
+                source: source.to_string(),
+                sold_date: None,
+                grade: offer.grade,
+                warranty: offer.warranty,
+                expires_at: None,
+                region: Some(region.to_uppercase()),
+                currency: Some(currency.to_string()),
+                attributes: ProductAttributes::default(),
+                listed_at: None,
+                lot_quantity: None,
+            }
+        })
+        .collect()
+}
+
+pub struct Reebelo {
+    config: ReebeloConfig,
+}
+
+impl Reebelo {
+    pub fn new(config: ReebeloConfig) -> Self {
+        Reebelo { config }
+    }
+}
+
+#[async_trait]
+impl Source for Reebelo {
+    fn name(&self) -> &'static str {
+        "Reebelo"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        let collections = [
+            ("iPhone 15", "iphone-15"),
+            ("iPhone 14", "iphone-14"),
+            ("iPhone 13", "iphone-13"),
+            ("Galaxy S24", "samsung-galaxy-s24"),
+            ("Pixel 8", "google-pixel-8"),
+        ];
+
+        for region in &self.config.regions {
+            let Some((_, base_url, currency)) = REGIONS.iter().find(|(r, _, _)| r == region) else {
+                eprintln!("  ⚠️  Unknown Reebelo region '{}'", region);
+                continue;
+            };
+            let usd_rate = if *currency == "USD" {
+                1.0
+            } else if let Some(rate) = self.config.usd_rates.get(*currency) {
+                *rate
+            } else {
+                eprintln!("  ⚠️  No usd_rates entry for {} - skipping Reebelo {}", currency, region);
+                continue;
+            };
+            // Keep regions apart so cross-source comparison shows them separately
+            let source = if region == "us" { "Reebelo".to_string() } else { format!("Reebelo {}", region.to_uppercase()) };
+
+            for (device, handle) in collections.iter() {
+                let url = format!("{}/collections/{}", base_url, handle);
+                println!("  {} {}: {}", source, device, url);
+
+                if let Some(html) = politeness.fetch(client, &url).await {
+                    let products = parse_collection(&html, base_url, &source, region, currency, usd_rate);
+                    println!("    Found {} products", products.len());
+                    *listings.device_counts.entry(format!("{} {}", device, region)).or_default() += products.len();
+                    listings.products.extend(products);
+                }
+            }
+        }
+
+        listings
+    }
+}
@@ -0,0 +1,170 @@
+// Samsung Certified Re-Newed Galaxy phones. Variants are read from the JSON
+// embedded in the page (Next.js state or JSON-LD) so each product carries its
+// storage and color in a name the Galaxy keyword matching understands, e.g.
+// "Samsung Galaxy S23 Ultra 256GB Phantom Black (Certified Re-Newed)".
+// Card scraping is the fallback when no JSON is found.
+
+use async_trait::async_trait;
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+use super::{CardSelectors, Source, SourceListings, next_data, parse_local_price, parse_product_cards, resolve_url, scrape_pages};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::money::Money;
+use crate::politeness::Politeness;
+
+pub(super) const BASE_URL: &str = "https://www.samsung.com";
+const SOURCE_NAME: &str = "Samsung Re-Newed";
+const GRADE: &str = "Certified Re-Newed";
+
+pub(super) const SELECTORS: CardSelectors = CardSelectors {
+    profile: "samsung renewed",
+    items: &[
+        "[class*='ProductCard']",
+        "[class*='product-card']",
+        "[data-testid*='product-card']",
+    ],
+    name: &[
+        "[class*='ProductCard-name']",
+        "[class*='title']",
+        "h3",
+    ],
+    price: &[
+        "[class*='ProductCard-price']",
+        "[class*='price']",
+    ],
+    link: &[
+        "a[href*='/smartphones/']",
+        "a",
+    ],
+};
+
+struct Variant {
+    model: String,
+    storage: Option<String>,
+    color: Option<String>,
+    price: f64,
+    href: String,
+}
+
+fn text(object: &serde_json::Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match object.get(*key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+fn price(object: &serde_json::Map<String, Value>) -> Option<f64> {
+    ["price", "salePrice", "promotionPrice", "lowPrice"].iter().find_map(|key| match object.get(*key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => parse_local_price(s),
+        // JSON-LD nests the price in an offers object
+        Value::Object(offer) => price(offer),
+        _ => None,
+    })
+    .or_else(|| object.get("offers").and_then(|o| o.as_object()).and_then(price))
+}
+
+// Galaxy variants anywhere in the page state: a model name, a price and a link
+fn collect_variants(value: &Value, variants: &mut Vec<Variant>) {
+    match value {
+        Value::Object(object) => {
+            let model = text(object, &["displayName", "modelName", "name", "title"]);
+            let href = text(object, &["pdpUrl", "url", "linkUrl"]);
+            if let (Some(model), Some(price), Some(href)) = (model, price(object), href)
+                && model.to_lowercase().contains("galaxy")
+                && price > 0.0
+            {
+                variants.push(Variant {
+                    model,
+                    storage: text(object, &["memory", "storage", "capacity"]),
+                    color: text(object, &["colorName", "color"]),
+                    price,
+                    href,
+                });
+                return;
+            }
+            for child in object.values() {
+                collect_variants(child, variants);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_variants(item, variants);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn variant_to_product(variant: Variant) -> Product {
+    let mut name = if variant.model.to_lowercase().starts_with("samsung") {
+        variant.model.clone()
+    } else {
+        format!("Samsung {}", variant.model)
+    };
+    for part in [&variant.storage, &variant.color].into_iter().flatten() {
+        // "256 GB" -> "256GB" so storage parsing picks it up
+        let part = part.replace(" GB", "GB").replace(" TB", "TB");
+        if !name.to_lowercase().contains(&part.to_lowercase()) {
+            name = format!("{} {}", name, part);
+        }
+    }
+
+    Product {
+        name: format!("{} ({})", name, GRADE),
+        price: Some(Money::usd(variant.price)),
+        url: resolve_url(&variant.href, BASE_URL),
+        source: SOURCE_NAME.to_string(),
+        sold_date: None,
+        grade: Some(GRADE.to_string()),
+        warranty: None,
+        expires_at: None,
+        region: None,
+        currency: None,
+        attributes: ProductAttributes::default(),
+        listed_at: None,
+        lot_quantity: None,
+    }
+}
+
+fn parse_page(html: &str) -> Vec<Product> {
+    let mut blobs: Vec<Value> = next_data(html).into_iter().collect();
+    let document = Html::parse_document(html);
+    if let Ok(selector) = Selector::parse("script[type='application/ld+json']") {
+        for script in document.select(&selector) {
+            if let Ok(blob) = serde_json::from_str(&script.text().collect::<String>()) {
+                blobs.push(blob);
+            }
+        }
+    }
+
+    let mut variants = Vec::new();
+    for blob in &blobs {
+        collect_variants(blob, &mut variants);
+    }
+    if !variants.is_empty() {
+        return variants.into_iter().map(variant_to_product).collect();
+    }
+
+    parse_product_cards(html, BASE_URL, SOURCE_NAME, &SELECTORS)
+        .into_iter()
+        .map(|product| Product { grade: Some(GRADE.to_string()), ..product })
+        .collect()
+}
+
+pub struct SamsungRenewed;
+
+#[async_trait]
+impl Source for SamsungRenewed {
+    fn name(&self) -> &'static str {
+        SOURCE_NAME
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let pages = vec![("Galaxy", format!("{}/us/smartphones/certified-re-newed/", BASE_URL))];
+        scrape_pages(client, self.name(), &pages, politeness, parse_page).await
+    }
+}
@@ -0,0 +1,172 @@
+// Selector profiles for the card-based storefronts. Each source ships its
+// CardSelectors in code; selectors.toml can replace any of them by profile
+// name without a rebuild. `back canary` fetches each storefront's sample page
+// once and extracts it with the old and the new profile side by side, so a
+// selectors.toml edit can be checked before it goes live.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::{CardSelectors, backmarket, gazelle, google_store, gumtree, kijiji, samsung_renewed};
+use crate::{Product, fetch_html};
+
+pub const SELECTORS_FILE: &str = "selectors.toml";
+
+// One storefront's card selectors, as written in selectors.toml:
+//   [gazelle]
+//   items = [".product-card"]
+//   name = [".product-card__title"]
+//   price = [".price-item--sale"]
+//   link = ["a[href*='/products/']"]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SelectorProfile {
+    pub items: Vec<String>,
+    pub name: Vec<String>,
+    pub price: Vec<String>,
+    pub link: Vec<String>,
+}
+
+impl SelectorProfile {
+    fn from_cards(selectors: &CardSelectors) -> SelectorProfile {
+        let owned = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        SelectorProfile {
+            items: owned(selectors.items),
+            name: owned(selectors.name),
+            price: owned(selectors.price),
+            link: owned(selectors.link),
+        }
+    }
+}
+
+pub fn load_profiles(path: &str) -> Result<HashMap<String, SelectorProfile>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    toml::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+static OVERRIDES: OnceLock<HashMap<String, SelectorProfile>> = OnceLock::new();
+
+// selectors.toml, read once per process; missing means no overrides
+fn overrides() -> &'static HashMap<String, SelectorProfile> {
+    OVERRIDES.get_or_init(|| {
+        if !std::path::Path::new(SELECTORS_FILE).exists() {
+            return HashMap::new();
+        }
+        load_profiles(SELECTORS_FILE).unwrap_or_else(|e| {
+            eprintln!("  ⚠️  {} - using built-in selectors", e);
+            HashMap::new()
+        })
+    })
+}
+
+// The profile scraping uses: selectors.toml's entry if it has one, else the built-in
+pub fn active(selectors: &CardSelectors) -> SelectorProfile {
+    overrides()
+        .get(selectors.profile)
+        .cloned()
+        .unwrap_or_else(|| SelectorProfile::from_cards(selectors))
+}
+
+// (built-in selectors, base URL, page the canary fetches)
+fn canary_pages() -> Vec<(&'static CardSelectors, &'static str, String)> {
+    vec![
+        (&gazelle::SELECTORS, gazelle::BASE_URL, format!("{}/collections/iphone-15", gazelle::BASE_URL)),
+        (&backmarket::SELECTORS, backmarket::BASE_URL, format!("{}/en-us/search?q=iphone+15", backmarket::BASE_URL)),
+        (&kijiji::SELECTORS, kijiji::BASE_URL, format!("{}/b-cell-phone/canada/iphone/k0c760l0", kijiji::BASE_URL)),
+        (&gumtree::UK_SELECTORS, "https://www.gumtree.com", "https://www.gumtree.com/search?q=iphone&search_category=mobile-phones".to_string()),
+        (&gumtree::AU_SELECTORS, "https://www.gumtree.com.au", "https://www.gumtree.com.au/s-mobile-phones/iphone/k0c18560".to_string()),
+        (
+            &samsung_renewed::SELECTORS,
+            samsung_renewed::BASE_URL,
+            format!("{}/us/smartphones/certified-re-newed/", samsung_renewed::BASE_URL),
+        ),
+        (
+            &google_store::SELECTORS,
+            google_store::BASE_URL,
+            format!("{}/us/collection/certified_refurbished_phones?hl=en-US", google_store::BASE_URL),
+        ),
+    ]
+}
+
+// A file's profiles, or the built-ins when no file is given
+fn profiles_from(path: Option<&str>) -> Result<HashMap<String, SelectorProfile>, String> {
+    match path {
+        Some(path) => load_profiles(path),
+        None => Ok(HashMap::new()),
+    }
+}
+
+fn print_field_diff(field: &str, old: &str, new: &str) {
+    if old != new {
+        println!("        {:<6} {} -> {}", field, if old.is_empty() { "(none)" } else { old }, if new.is_empty() { "(none)" } else { new });
+    }
+}
+
+// Cards are paired by position on the page, so a changed link selector shows
+// up as changed urls rather than as every product vanishing
+fn print_diff(old: &[Product], new: &[Product]) {
+    let mut changed = 0;
+    for i in 0..old.len().max(new.len()) {
+        match (old.get(i), new.get(i)) {
+            (Some(o), Some(n)) if o.name == n.name && o.price == n.price && o.url == n.url => {}
+            (Some(o), Some(n)) => {
+                changed += 1;
+                println!("     ~ card {}", i + 1);
+                print_field_diff("name", &o.name, &n.name);
+                print_field_diff("price", &o.price.map(|p| p.to_string()).unwrap_or_default(), &n.price.map(|p| p.to_string()).unwrap_or_default());
+                print_field_diff("url", &o.url, &n.url);
+            }
+            (Some(o), None) => {
+                changed += 1;
+                println!("     - card {} only with old: {} ({})", i + 1, o.name, o.price_label());
+            }
+            (None, Some(n)) => {
+                changed += 1;
+                println!("     + card {} only with new: {} ({})", i + 1, n.name, n.price_label());
+            }
+            (None, None) => {}
+        }
+    }
+    if changed == 0 {
+        println!("     ✅ Identical results");
+    }
+}
+
+// `back canary`: old and new profiles on the same fetched pages. `old` and
+// `new` are selectors files, no `old` meaning the built-ins. Without a
+// `profile`, ones that are the same in both are skipped.
+pub async fn run_canary(client: &reqwest::Client, profile: Option<&str>, old: Option<&str>, new: &str) -> Result<(), String> {
+    let old_profiles = profiles_from(old)?;
+    let new_profiles = load_profiles(new)?;
+    let pages: Vec<_> = canary_pages().into_iter().filter(|(selectors, _, _)| profile.is_none_or(|p| p == selectors.profile)).collect();
+    if pages.is_empty() {
+        let known: Vec<&str> = canary_pages().iter().map(|(selectors, _, _)| selectors.profile).collect();
+        return Err(format!("Unknown selector profile '{}'. Known profiles: {}", profile.unwrap_or_default(), known.join(", ")));
+    }
+
+    let mut compared = 0;
+    for (selectors, base_url, url) in pages {
+        let pick = |profiles: &HashMap<String, SelectorProfile>| {
+            profiles.get(selectors.profile).cloned().unwrap_or_else(|| SelectorProfile::from_cards(selectors))
+        };
+        let (old_profile, new_profile) = (pick(&old_profiles), pick(&new_profiles));
+        if old_profile == new_profile && profile.is_none() {
+            continue;
+        }
+
+        compared += 1;
+        println!("\n🐤 Canary: {} ({})", selectors.profile, url);
+        let Some(html) = fetch_html(client, &url).await else {
+            println!("     ⚠️  Page failed to load");
+            continue;
+        };
+        let old_products = super::parse_with_profile(&html, base_url, selectors.profile, &old_profile);
+        let new_products = super::parse_with_profile(&html, base_url, selectors.profile, &new_profile);
+        println!("     old: {} products, new: {} products", old_products.len(), new_products.len());
+        print_diff(&old_products, &new_products);
+    }
+    if compared == 0 {
+        println!("✅ No selector profile differs between old and new");
+    }
+    Ok(())
+}
@@ -0,0 +1,183 @@
+// Walmart Restored (refurbished) phones. Search pages embed their results as
+// JSON in the __NEXT_DATA__ script, which is far steadier than the card markup,
+// so we read that and page through results until a page comes back empty.
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{Source, SourceListings, next_data, resolve_url};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::WalmartConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::politeness::Politeness;
+
+const BASE_URL: &str = "https://www.walmart.com";
+
+// One page of search results
+struct SearchPage {
+    products: Vec<Product>,
+    max_page: Option<usize>,
+}
+
+// Condition grade from Walmart's condition field, falling back to the title
+fn parse_condition(item: &Value, name: &str) -> Option<String> {
+    let condition = item
+        .pointer("/conditionV2/name")
+        .or_else(|| item.get("condition"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if condition.is_some() {
+        return condition;
+    }
+
+    let lower = name.to_lowercase();
+    if lower.contains("restored premium") {
+        Some("Restored Premium".to_string())
+    } else if lower.contains("restored") || lower.contains("refurbished") {
+        Some("Restored".to_string())
+    } else {
+        None
+    }
+}
+
+fn item_price(item: &Value) -> Option<Money> {
+    item.get("price")
+        .and_then(|v| v.as_f64())
+        .or_else(|| item.pointer("/priceInfo/currentPrice/price").and_then(|v| v.as_f64()))
+        .map(Money::usd)
+        .or_else(|| {
+            item.pointer("/priceInfo/linePrice")
+                .and_then(|v| v.as_str())
+                .and_then(crate::parse_price)
+        })
+}
+
+fn parse_search_page(html: &str) -> Option<SearchPage> {
+    let data = next_data(html)?;
+    let search = data.pointer("/props/pageProps/initialData/searchResult")?;
+
+    let mut products = Vec::new();
+    let stacks = search.get("itemStacks").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    for stack in &stacks {
+        let Some(items) = stack.get("items").and_then(|v| v.as_array()) else {
+            continue;
+        };
+        for item in items {
+            // Stacks also carry ad tiles and placeholders
+            if item.get("__typename").and_then(|v| v.as_str()).is_some_and(|t| t != "Product") {
+                continue;
+            }
+            let name = item.get("name").and_then(|v| v.as_str()).unwrap_or("").trim();
+            let href = item.get("canonicalUrl").and_then(|v| v.as_str()).unwrap_or("");
+            let Some(price) = item_price(item) else {
+                continue;
+            };
+            if name.len() <= 5 || href.is_empty() {
+                continue;
+            }
+
+            let condition = parse_condition(item, name);
+            let name = match &condition {
+                Some(condition) if !name.to_lowercase().contains(&condition.to_lowercase()) => {
+                    format!("{} ({})", name, condition)
+                }
+                _ => name.to_string(),
+            };
+            products.push(Product {
+                name,
+                price: Some(price),
+                url: resolve_url(href.split('?').next().unwrap_or(href), BASE_URL),
+                source: "Walmart".to_string(),
+                sold_date: None,
+                grade: condition,
+                warranty: None,
+                expires_at: None,
+                region: None,
+                currency: None,
+                attributes: ProductAttributes::default(),
+                listed_at: None,
+                lot_quantity: None,
+            });
+        }
+    }
+
+    let max_page = search
+        .pointer("/paginationV2/maxPage")
+        .and_then(|v| v.as_u64())
+        .map(|p| p as usize);
+
+    Some(SearchPage { products, max_page })
+}
+
+pub struct Walmart {
+    config: WalmartConfig,
+}
+
+impl Walmart {
+    pub fn new(config: WalmartConfig) -> Self {
+        Walmart { config }
+    }
+}
+
+#[async_trait]
+impl Source for Walmart {
+    fn name(&self) -> &'static str {
+        "Walmart"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        let queries = [
+            ("iPhone 15", "iphone 15"),
+            ("iPhone 14", "iphone 14"),
+            ("iPhone 13", "iphone 13"),
+            ("Galaxy S24", "samsung galaxy s24"),
+            ("Pixel 8", "google pixel 8"),
+        ];
+
+        'queries: for (device, query) in queries.iter() {
+            let mut device_count = 0;
+
+            for page in 1..=politeness.page_cap(self.config.max_pages) {
+                let url = format!(
+                    "{}/search?q={}+restored&facet=condition%3ARestored&page={}",
+                    BASE_URL,
+                    query.replace(' ', "+"),
+                    page
+                );
+                println!("  Walmart {} page {}: {}", device, page, url);
+
+                let Some(html) = politeness.fetch(client, &url).await else {
+                    break;
+                };
+                if html.contains("Robot or human") {
+                    eprintln!("    🤖 Walmart served a bot check - skipping the rest of Walmart this run");
+                    error_journal::record(ErrorCategory::Blocked, "Walmart", Some(&url), "bot check served", Some(&html));
+                    listings.device_counts.insert(device.to_string(), device_count);
+                    break 'queries;
+                }
+                let Some(result) = parse_search_page(&html) else {
+                    eprintln!("    ❌ No search data found on Walmart page");
+                    error_journal::record(ErrorCategory::Parse, "Walmart", Some(&url), "no __NEXT_DATA__ search results", Some(&html));
+                    break;
+                };
+
+                println!("    Found {} products", result.products.len());
+                let empty = result.products.is_empty();
+                device_count += result.products.len();
+                listings.products.extend(result.products);
+
+                if empty || result.max_page.is_some_and(|max| page >= max) {
+                    break;
+                }
+            }
+
+            listings.device_counts.insert(device.to_string(), device_count);
+        }
+
+        listings
+    }
+}
@@ -0,0 +1,132 @@
+// Woot deals via the Woot developer API (https://developer.woot.com).
+// Deals run for a limited time, so each product carries the offer's end time
+// in `expires_at` and expired offers are dropped before comparison.
+// Only registered when [sources.woot] api_key is set.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+
+use super::{Source, SourceListings};
+use crate::Product;
+use crate::attributes::ProductAttributes;
+use crate::config::WootConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::politeness::Politeness;
+use crate::retry;
+
+const FEED_URL: &str = "https://developer.woot.com/feed";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Feed {
+    #[serde(default)]
+    items: Vec<Offer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Offer {
+    title: String,
+    url: String,
+    #[serde(default)]
+    condition: Option<String>,
+    #[serde(default)]
+    end_date: Option<DateTime<chrono::Utc>>,
+    #[serde(default)]
+    is_sold_out: bool,
+    sale_price: Option<PriceRange>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PriceRange {
+    minimum: f64,
+}
+
+// Refurbished phones that are still for sale
+fn offer_to_product(offer: Offer) -> Option<Product> {
+    let condition = offer.condition.unwrap_or_default();
+    let refurbished = condition.to_lowercase().contains("refurb") || offer.title.to_lowercase().contains("refurb");
+    if offer.is_sold_out || !refurbished || ProductAttributes::parse(&offer.title).model.is_none() {
+        return None;
+    }
+    let price = offer.sale_price?.minimum;
+
+    Some(Product {
+        name: offer.title,
+        price: Some(Money::usd(price)),
+        url: offer.url,
+        source: "Woot".to_string(),
+        sold_date: None,
+        grade: (!condition.is_empty()).then_some(condition),
+        warranty: None,
+        expires_at: offer.end_date.map(|end| end.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string()),
+        region: None,
+        currency: None,
+        attributes: ProductAttributes::default(),
+        listed_at: None,
+        lot_quantity: None,
+    })
+}
+
+pub struct Woot {
+    config: WootConfig,
+}
+
+impl Woot {
+    pub fn new(config: WootConfig) -> Self {
+        Woot { config }
+    }
+
+    async fn fetch_feed(&self, client: &reqwest::Client, feed: &str) -> Option<Feed> {
+        let url = format!("{}/{}", FEED_URL, feed);
+        let response = retry::send(&url, || client.get(&url).header("x-api-key", &self.config.api_key)).await;
+        let body = match response {
+            Ok(response) if response.status().is_success() => response.text().await.ok()?,
+            Ok(response) => {
+                eprintln!("    ❌ Woot API returned {} for feed {}", response.status(), feed);
+                error_journal::record(ErrorCategory::Http, "Woot", Some(&url), response.status(), None);
+                return None;
+            }
+            Err(e) => {
+                eprintln!("    ❌ Failed to fetch Woot feed {}: {}", feed, e);
+                error_journal::record(ErrorCategory::Fetch, "Woot", Some(&url), &e, None);
+                return None;
+            }
+        };
+        match serde_json::from_str(&body) {
+            Ok(feed) => Some(feed),
+            Err(e) => {
+                eprintln!("    ❌ Unexpected Woot API response: {}", e);
+                error_journal::record(ErrorCategory::Parse, "Woot", Some(&url), &e, Some(&body));
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Source for Woot {
+    fn name(&self) -> &'static str {
+        "Woot"
+    }
+
+    async fn scrape(&self, client: &reqwest::Client, _politeness: &Politeness) -> SourceListings {
+        let mut listings = SourceListings::default();
+
+        for feed in &self.config.feeds {
+            println!("  Woot feed {}", feed);
+            let Some(response) = self.fetch_feed(client, feed).await else {
+                continue;
+            };
+            let products: Vec<Product> = response.items.into_iter().filter_map(offer_to_product).collect();
+            println!("    Found {} refurbished phone deals", products.len());
+            listings.device_counts.insert(feed.clone(), products.len());
+            listings.products.extend(products);
+        }
+
+        listings
+    }
+}
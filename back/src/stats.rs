@@ -0,0 +1,46 @@
+// Order statistics over sold prices. Means are easily dragged by lot sales
+// and parts listings, so comp prices are summarized by quantiles.
+
+// Quantile `q` (0.0-1.0) with linear interpolation between ranks. `sorted`
+// must be ascending; empty input gives 0.0.
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return 0.0;
+    };
+    let rank = q.clamp(0.0, 1.0) * last as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+pub fn sorted(prices: &[f64]) -> Vec<f64> {
+    let mut sorted = prices.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted
+}
+
+pub fn median(prices: &[f64]) -> f64 {
+    quantile(&sorted(prices), 0.5)
+}
+
+// Prices outside Tukey's fences: more than `factor` interquartile ranges
+// below Q1 or above Q3
+pub fn iqr_bounds(sorted: &[f64], factor: f64) -> (f64, f64) {
+    let (q1, q3) = (quantile(sorted, 0.25), quantile(sorted, 0.75));
+    let iqr = q3 - q1;
+    (q1 - factor * iqr, q3 + factor * iqr)
+}
+
+// Prices whose modified z-score (0.6745 * |x - median| / MAD) exceeds
+// `threshold`. More robust than IQR on small comp pools. When over half the
+// prices are identical MAD is 0 and any other price would be an outlier, so
+// nothing is rejected.
+pub fn mad_bounds(sorted: &[f64], threshold: f64) -> (f64, f64) {
+    let median = quantile(sorted, 0.5);
+    let deviations: Vec<f64> = sorted.iter().map(|p| (p - median).abs()).collect();
+    let mad = self::median(&deviations);
+    if mad == 0.0 {
+        return (f64::NEG_INFINITY, f64::INFINITY);
+    }
+    let spread = threshold * mad / 0.6745;
+    (median - spread, median + spread)
+}
@@ -0,0 +1,214 @@
+// Run history on disk: one JSON file per run under runs/, plus a small index
+// of per-run summaries so listing history never loads the product arrays.
+// With `--profile <name>`, run history, seen-state, exports and the user's
+// notes, tracked listings, inventory, feedback and baselines live under
+// profiles/<name>/ instead, so profiles sharing a machine stay separate.
+
+use chrono::{Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::{ArbitrageOpportunity, ProductDetails, RunSnapshot};
+use crate::error_journal::{self, ErrorCategory};
+
+const PROFILES_DIR: &str = "profiles";
+pub const RUNS_DIR: &str = "runs";
+const RUN_INDEX_FILE: &str = "runs/index.json";
+// Listing URL -> photos from the detail pages, for eBay drafts (ebay_sell.rs)
+const DETAIL_IMAGES_FILE: &str = "runs/detail_images.json";
+const MAX_DETAIL_IMAGES: usize = 5000;
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Summary of one run, enough for history lists and trend charts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunIndexEntry {
+    pub run_id: u32,
+    pub timestamp: String,
+    pub total_swappa: usize,
+    pub total_newegg: usize,
+    pub total_ebay_sold: usize,
+    pub total_mercari: usize,
+    pub total_storefront: usize,
+    pub opportunity_count: usize,
+    pub best_opportunity: Option<ArbitrageOpportunity>,
+    // Swappa products with a sold comparison, and how many of those are profitable
+    pub swappa_analyzed: usize,
+    pub swappa_profitable: usize,
+    pub avg_swappa_margin: Option<f64>,
+}
+
+impl RunIndexEntry {
+    pub fn from_snapshot(snapshot: &RunSnapshot) -> RunIndexEntry {
+        let margins: Vec<f64> = snapshot.swappa_products.iter().filter_map(|p| p.margin_percent).collect();
+        RunIndexEntry {
+            run_id: snapshot.run_id,
+            timestamp: snapshot.timestamp.clone(),
+            total_swappa: snapshot.total_swappa,
+            total_newegg: snapshot.total_newegg,
+            total_ebay_sold: snapshot.total_ebay_sold,
+            total_mercari: snapshot.mercari_products.len(),
+            total_storefront: snapshot.storefront_products.len(),
+            opportunity_count: snapshot.arbitrage_opportunities.len(),
+            best_opportunity: snapshot.best_opportunity.clone(),
+            swappa_analyzed: snapshot.swappa_products.len(),
+            swappa_profitable: margins.iter().filter(|m| **m > 0.0).count(),
+            avg_swappa_margin: if margins.is_empty() { None } else { Some(margins.iter().sum::<f64>() / margins.len() as f64) },
+        }
+    }
+}
+
+// Set once at startup, before any profile-scoped file is read
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+pub fn set_profile(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid profile name '{}': use letters, digits, - and _", name));
+    }
+    let dir = format!("{}/{}", PROFILES_DIR, name);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+    PROFILE.set(name.to_string()).map_err(|_| "Profile already set".to_string())
+}
+
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+// "runs/index.json" -> "profiles/<name>/runs/index.json" under a profile
+pub fn profile_path(path: &str) -> String {
+    match profile() {
+        Some(name) => format!("{}/{}/{}", PROFILES_DIR, name, path),
+        None => path.to_string(),
+    }
+}
+
+fn run_file(run_id: u32) -> String {
+    profile_path(&format!("{}/run_{}.json", RUNS_DIR, run_id))
+}
+
+// Write via a temp file so the API never reads a half-written file
+pub fn write_json<T: Serialize>(path: &str, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    let tmp = format!("{}.tmp", path);
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp, e))?;
+    fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path, e))
+}
+
+pub fn load_detail_images() -> BTreeMap<String, Vec<String>> {
+    match fs::read_to_string(profile_path(DETAIL_IMAGES_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+// Keep the photos of fetched listing details; the oldest URLs go first once
+// the store is full (keys are URLs, so "oldest" is approximate)
+pub fn record_detail_images(details: &[ProductDetails]) {
+    let details: Vec<&ProductDetails> = details.iter().filter(|d| !d.images.is_empty()).collect();
+    if details.is_empty() {
+        return;
+    }
+    let mut images = load_detail_images();
+    for detail in details {
+        images.insert(detail.url.clone(), detail.images.clone());
+    }
+    while images.len() > MAX_DETAIL_IMAGES {
+        images.pop_first();
+    }
+    if let Err(e) = fs::create_dir_all(profile_path(RUNS_DIR)).map_err(|e| e.to_string()).and_then(|_| write_json(&profile_path(DETAIL_IMAGES_FILE), &images)) {
+        eprintln!("❌ {}", e);
+        error_journal::record(ErrorCategory::Storage, "Storage", None, e, None);
+    }
+}
+
+// Run summaries, oldest first
+pub fn load_run_index() -> Vec<RunIndexEntry> {
+    match fs::read_to_string(profile_path(RUN_INDEX_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn load_run(run_id: u32) -> Option<RunSnapshot> {
+    let content = fs::read_to_string(run_file(run_id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// Rewrite a stored run in place, e.g. with verified buy listings
+pub fn update_run(snapshot: &RunSnapshot) -> Result<(), String> {
+    write_json(&run_file(snapshot.run_id), snapshot)
+}
+
+// Runs stamped before this are past [maintenance] run_history_days. Run
+// timestamps are local "%Y-%m-%d %H:%M:%S", so they compare as strings.
+fn retention_cutoff(retention_days: u64) -> String {
+    (Local::now().naive_local() - TimeDelta::days(retention_days as i64)).format(TIMESTAMP_FORMAT).to_string()
+}
+
+// Store a run and drop the runs older than `retention_days`
+pub fn save_run(snapshot: &RunSnapshot, retention_days: u64) -> Result<(), String> {
+    let runs_dir = profile_path(RUNS_DIR);
+    fs::create_dir_all(&runs_dir).map_err(|e| format!("Failed to create {}: {}", runs_dir, e))?;
+    write_json(&run_file(snapshot.run_id), snapshot)?;
+
+    let mut index = load_run_index();
+    index.retain(|entry| entry.run_id != snapshot.run_id);
+    index.push(RunIndexEntry::from_snapshot(snapshot));
+    let cutoff = retention_cutoff(retention_days);
+    index.retain(|entry| {
+        let keep = entry.run_id == snapshot.run_id || entry.timestamp >= cutoff;
+        if !keep {
+            let _ = fs::remove_file(run_file(entry.run_id));
+        }
+        keep
+    });
+    write_json(&profile_path(RUN_INDEX_FILE), &index)
+}
+
+// Rebuild the index: drop entries whose run file is gone or that are older
+// than `retention_days`, and delete run files the index no longer
+// references. Returns the number of runs removed.
+pub fn compact_runs(retention_days: u64) -> Result<usize, String> {
+    let mut index = load_run_index();
+    let before = index.len();
+    let cutoff = retention_cutoff(retention_days);
+    index.retain(|entry| entry.timestamp >= cutoff && Path::new(&run_file(entry.run_id)).exists());
+    let mut removed = before - index.len();
+
+    if let Ok(entries) = fs::read_dir(profile_path(RUNS_DIR)) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(run_id) = name.strip_prefix("run_").and_then(|n| n.strip_suffix(".json")).and_then(|n| n.parse::<u32>().ok()) else {
+                continue;
+            };
+            if !index.iter().any(|e| e.run_id == run_id) && fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    let index_file = profile_path(RUN_INDEX_FILE);
+    if Path::new(&index_file).exists() {
+        write_json(&index_file, &index)?;
+    }
+    Ok(removed)
+}
+
+// Move the run_history array from an older scraper_data.json into runs/.
+// Run ids restarted at 1 on every launch back then, so they are renumbered.
+pub fn migrate_run_history(legacy: Vec<RunSnapshot>, retention_days: u64) {
+    if legacy.is_empty() || Path::new(&profile_path(RUN_INDEX_FILE)).exists() {
+        return;
+    }
+    let count = legacy.len();
+    for (i, mut snapshot) in legacy.into_iter().enumerate() {
+        snapshot.run_id = i as u32 + 1;
+        if let Err(e) = save_run(&snapshot, retention_days) {
+            eprintln!("❌ Failed to migrate run history: {}", e);
+            error_journal::record(ErrorCategory::Storage, "Run history", None, &e, None);
+            return;
+        }
+    }
+    println!("📦 Migrated {} runs from {} into {}/", count, profile_path(crate::FRONTEND_DATA_FILE), profile_path(RUNS_DIR));
+}
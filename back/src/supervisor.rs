@@ -0,0 +1,569 @@
+// Per-source actors under a coordinating supervisor. Each source runs as its
+// own long-lived task that owns its schedule ([supervisor] every_runs), its
+// retries, its browser settings and its state: the last good output and the
+// empty-device streaks. The supervisor ticks the actors once per run and
+// assembles the run from their reports. A scrape runs in a task of its own,
+// so a panicking source only loses its slot; its last good output stands in
+// for up to max_stale_runs runs, and an actor that dies outright is restarted.
+// Up to [supervisor] concurrency sources scrape at once, so a run takes about
+// as long as its slowest sources rather than all of them end to end; each is
+// checkpointed (checkpoint.rs) as it finishes, and prints its listings then.
+
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Semaphore, mpsc, oneshot};
+use tokio::task::JoinSet;
+
+use crate::checkpoint;
+use crate::config::Config;
+use crate::ebay_auctions::{self, Auction};
+use crate::error_journal::{self, ErrorCategory};
+use crate::politeness::Politeness;
+use crate::sources::decluttr::DecluttrPrice;
+use crate::sources::{self, Source};
+use crate::{Product, ProductDetails, mercari, progress, storage};
+
+// Keys of listings seen in earlier runs, shared by every actor
+pub type SeenProducts = Arc<Mutex<HashSet<String>>>;
+
+// Where a source's output goes when the run is assembled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Newegg,
+    Swappa,
+    Ebay,
+    Mercari,
+    Storefront,
+    Decluttr,
+    Auctions,
+}
+
+impl Role {
+    // Auctions end within the hour, so an old list is worse than none
+    fn reusable(self) -> bool {
+        self != Role::Auctions
+    }
+}
+
+// What one source produced
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceOutput {
+    // Every listing, deduplicated
+    pub products: Vec<Product>,
+    // Mercari sold listings (comps)
+    pub sold: Vec<Product>,
+    // Newegg [categories] pages, buy side in either [newegg] mode
+    pub targeted: Vec<Product>,
+    pub decluttr_prices: Vec<DecluttrPrice>,
+    // Never checkpointed: they'd be stale by the time a run is resumed
+    #[serde(skip)]
+    pub auctions: Vec<Auction>,
+    // Listings not seen in an earlier run
+    pub new_count: usize,
+}
+
+impl SourceOutput {
+    fn is_empty(&self) -> bool {
+        self.products.is_empty() && self.sold.is_empty() && self.decluttr_prices.is_empty() && self.auctions.is_empty()
+    }
+}
+
+pub struct SourceReport {
+    pub name: String,
+    pub role: Role,
+    pub output: SourceOutput,
+    // Run the output was scraped in; an earlier run when the source wasn't
+    // due or failed and its last output stands in
+    pub scraped_in: Option<u32>,
+    pub failure: Option<String>,
+}
+
+// The reports of one run, in scrape order
+pub struct RunReports {
+    pub run_id: u32,
+    pub reports: Vec<SourceReport>,
+}
+
+impl RunReports {
+    // The output of the (single) source with this role
+    pub fn take(&mut self, role: Role) -> SourceOutput {
+        self.reports.iter_mut().find(|r| r.role == role).map(|r| std::mem::take(&mut r.output)).unwrap_or_default()
+    }
+
+    // Listings of every storefront, plus Decluttr's in-stock variants, and how many are new
+    pub fn take_storefronts(&mut self) -> (Vec<Product>, usize) {
+        let mut products = Vec::new();
+        let mut new_count = 0;
+        for report in self.reports.iter_mut().filter(|r| matches!(r.role, Role::Storefront | Role::Decluttr)) {
+            products.append(&mut report.output.products);
+            new_count += report.output.new_count;
+        }
+        (products, new_count)
+    }
+
+    // Whether the source with this role was scraped this run, not failed or reused
+    pub fn scraped_now(&self, role: Role) -> bool {
+        self.reports.iter().any(|r| r.role == role && r.scraped_in == Some(self.run_id) && r.failure.is_none())
+    }
+
+    // Sources that failed or were reused this run
+    pub fn display_health(&self) {
+        for report in &self.reports {
+            match (&report.failure, report.scraped_in) {
+                (Some(failure), Some(run)) => println!("⚠️  {}: {} - using output from run #{}", report.name, failure, run),
+                (Some(failure), None) => println!("❌ {}: {}", report.name, failure),
+                (None, Some(run)) if run != self.run_id => println!("⏭️  {}: not due, using output from run #{}", report.name, run),
+                _ => {}
+            }
+        }
+    }
+}
+
+// The scraping one actor does; cloned into a fresh task per attempt
+#[derive(Clone)]
+enum Job {
+    Newegg,
+    Swappa,
+    Ebay,
+    Mercari,
+    Storefront(Arc<dyn Source>),
+    Decluttr,
+    Auctions,
+}
+
+struct Scrape {
+    output: SourceOutput,
+    device_counts: HashMap<String, usize>,
+    log: Log,
+}
+
+// A scrape's listing summaries, printed in one piece when it finishes so
+// sources scraping at once don't interleave their output
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Log {
+    fn line(&mut self, line: impl Into<String>) {
+        self.0.push(line.into());
+    }
+
+    fn print(&self) {
+        if !self.0.is_empty() {
+            progress::println(&self.0.join("\n"));
+        }
+    }
+}
+
+fn filter_new(products: &[Product], seen: &SeenProducts) -> Vec<Product> {
+    let mut seen = seen.lock().unwrap_or_else(|e| e.into_inner());
+    crate::filter_new_products(products.to_vec(), &mut seen)
+}
+
+fn print_listings(log: &mut Log, label: &str, all: &[Product], new: &[Product]) {
+    log.line(format!("\n{}", "-".repeat(60)));
+    log.line(format!("{}: {} total, {} NEW", label.to_uppercase(), all.len(), new.len()));
+    log.line("-".repeat(60));
+
+    // Always show all scraped items with links
+    if !all.is_empty() {
+        log.line(format!("\n📋 ALL SCRAPED {} ITEMS ({}):", label.to_uppercase(), all.len()));
+        for (i, product) in all.iter().enumerate() {
+            log.line(format!("\n{}. {}", i + 1, product.name));
+            log.line(format!("   💰 Price: {}", product.price_label()));
+            log.line(format!("   🔗 {}", product.url));
+        }
+    }
+
+    if new.is_empty() {
+        log.line(format!("\n  ℹ️  No new {} products found this run", label));
+    } else {
+        log.line(format!("\n🆕 NEW {} PRODUCTS:", label.to_uppercase()));
+        for (i, product) in new.iter().take(15).enumerate() {
+            log.line(format!("\n{}. {}", i + 1, product.name));
+            log.line(format!("   💰 Price: {}", product.price_label()));
+            log.line(format!("   🔗 {}", product.url));
+        }
+    }
+}
+
+fn print_details(log: &mut Log, title: &str, details: &[ProductDetails]) {
+    storage::record_detail_images(details);
+    if details.is_empty() {
+        return;
+    }
+    log.line(format!("\n{}", "=".repeat(60)));
+    log.line(format!("{} ({})", title, details.len()));
+    log.line("=".repeat(60));
+
+    for (i, detail) in details.iter().enumerate() {
+        log.line(format!("\n{}. {}", i + 1, detail.name));
+        log.line(format!("   💰 Price: {}", detail.price.map_or_else(|| "Price not found".to_string(), |price| price.to_string())));
+        log.line(format!("   📝 Description: {}", crate::truncate_string(&detail.description, 100)));
+        log.line(format!("   🏷️  Condition: {}", detail.condition));
+        log.line(format!("   👤 Seller: {}", detail.seller));
+        if !detail.specs.is_empty() {
+            log.line(format!("   📋 Specs ({}):", detail.specs.len()));
+            for spec in detail.specs.iter().take(3) {
+                log.line(format!("      - {}", crate::truncate_string(spec, 60)));
+            }
+        }
+        if !detail.images.is_empty() {
+            log.line(format!("   🖼️  Images: {}", detail.images.len()));
+        }
+        log.line(format!("   🔗 {}", detail.url));
+    }
+}
+
+impl Job {
+    fn banner(&self, name: &str) -> String {
+        match self {
+            Job::Newegg => "📦 Scraping Newegg...".to_string(),
+            Job::Swappa => "📱 Scraping Swappa...".to_string(),
+            Job::Ebay => "🛍️ Scraping eBay...".to_string(),
+            Job::Mercari => "🏷️ Scraping Mercari...".to_string(),
+            Job::Storefront(_) => format!("🏬 Scraping {}...", name),
+            Job::Decluttr => "🏬 Scraping Decluttr price table...".to_string(),
+            Job::Auctions => "⏱️ Scraping eBay auctions ending soon...".to_string(),
+        }
+    }
+
+    async fn scrape(self, client: reqwest::Client, config: Arc<Config>, seen: SeenProducts) -> Scrape {
+        let client = &client;
+        let mut device_counts = HashMap::new();
+        let mut log = Log::default();
+        let output = match self {
+            Job::Newegg => {
+                let politeness = config.politeness.for_source("Newegg");
+                let targeted = crate::deduplicate_products(
+                    crate::scrape_newegg_pages(client, &config.category_urls("newegg"), &politeness).await,
+                );
+                let mut products = crate::scrape_newegg(client, &config.newegg, &politeness).await;
+                products.extend(targeted.iter().cloned());
+                let products = crate::deduplicate_products(products);
+                let new = filter_new(&products, &seen);
+                print_listings(&mut log, "Newegg", &products, &new);
+
+                // Fetch detailed info for new Newegg products
+                let details = if new.is_empty() { Vec::new() } else { crate::fetch_product_details(client, &new, 5).await };
+                print_details(&mut log, "📦 NEW NEWEGG DETAILED PRODUCTS", &details);
+                SourceOutput { new_count: new.len(), products, targeted, ..Default::default() }
+            }
+            Job::Swappa => {
+                let (scraped, counts) = crate::scrape_swappa(
+                    client,
+                    &config.capture,
+                    &config.swappa,
+                    &config.browser,
+                    &config.politeness.for_source("Swappa"),
+                    &config.category_urls("swappa"),
+                    None,
+                )
+                .await;
+                device_counts = counts;
+                let products = crate::deduplicate_products(scraped);
+                let new = filter_new(&products, &seen);
+                print_listings(&mut log, "Swappa", &products, &new);
+
+                // Fetch detailed info for new Swappa products using Selenium
+                let details = if new.is_empty() { Vec::new() } else { crate::fetch_swappa_details_selenium(&new, 5, &config.browser).await };
+                print_details(&mut log, "📱 NEW SWAPPA DETAILED PRODUCTS", &details);
+                SourceOutput { new_count: new.len(), products, ..Default::default() }
+            }
+            Job::Ebay => {
+                let products = crate::deduplicate_products(
+                    crate::scrape_ebay(
+                        &config.capture,
+                        &config.comps,
+                        &config.ebay,
+                        &config.browser,
+                        &config.politeness.for_source("eBay"),
+                        &config.category_urls("ebay"),
+                        None,
+                    )
+                    .await,
+                );
+                let new = filter_new(&products, &seen);
+                print_listings(&mut log, "eBay", &products, &new);
+                SourceOutput { new_count: new.len(), products, ..Default::default() }
+            }
+            Job::Mercari => {
+                // Active listings are buy-side, sold listings are comps
+                let listings = mercari::scrape_mercari(&config.browser, &config.politeness.for_source("Mercari"), None).await;
+                device_counts = listings.device_counts;
+                let products = crate::deduplicate_products(listings.active);
+                let sold = crate::deduplicate_products(listings.sold);
+                let new = filter_new(&products, &seen);
+
+                log.line(format!("\n{}", "-".repeat(60)));
+                log.line(format!("MERCARI: {} active ({} NEW), {} sold", products.len(), new.len(), sold.len()));
+                log.line("-".repeat(60));
+                if new.is_empty() {
+                    log.line("\n  ℹ️  No new Mercari listings found this run");
+                } else {
+                    log.line("\n🆕 NEW MERCARI LISTINGS:");
+                    for (i, product) in new.iter().take(15).enumerate() {
+                        log.line(format!("\n{}. {}", i + 1, product.name));
+                        log.line(format!("   💰 Price: {}", product.price_label()));
+                        log.line(format!("   🔗 {}", product.url));
+                    }
+                }
+                SourceOutput { new_count: new.len(), products, sold, ..Default::default() }
+            }
+            Job::Storefront(source) => {
+                let listings = source.scrape(client, &config.politeness.for_source(source.name())).await;
+                device_counts = listings.device_counts;
+                let products = crate::deduplicate_products(listings.products);
+                let new = filter_new(&products, &seen);
+
+                log.line(format!("\n{}", "-".repeat(60)));
+                log.line(format!("{}: {} total, {} NEW", source.name().to_uppercase(), products.len(), new.len()));
+                log.line("-".repeat(60));
+                for (i, product) in new.iter().take(15).enumerate() {
+                    log.line(format!("\n{}. {}", i + 1, product.name));
+                    log.line(format!("   💰 Price: {}", product.price_label()));
+                    log.line(format!("   🔗 {}", product.url));
+                }
+                SourceOutput { new_count: new.len(), products, ..Default::default() }
+            }
+            Job::Decluttr => {
+                // Decluttr publishes a fixed price table rather than listings; in-stock
+                // variants join the storefront buy side, the whole table is a reference
+                let (prices, counts) = sources::decluttr::scrape_price_table(client).await;
+                device_counts = counts;
+                let products =
+                    crate::deduplicate_products(prices.iter().filter(|p| p.available).map(|p| p.to_product()).collect());
+                let new = filter_new(&products, &seen);
+                log.line(format!("\n{}", "-".repeat(60)));
+                log.line(format!("DECLUTTR: {} price rows, {} in stock, {} NEW", prices.len(), products.len(), new.len()));
+                log.line("-".repeat(60));
+                SourceOutput { new_count: new.len(), products, decluttr_prices: prices, ..Default::default() }
+            }
+            Job::Auctions => {
+                progress::println(&format!("  Auctions ending within {} minutes", config.auctions.window_minutes));
+                let auctions = ebay_auctions::scrape_ending_auctions(&config.auctions, &config.browser, &config.politeness.for_source("eBay Auction")).await;
+                SourceOutput { auctions, ..Default::default() }
+            }
+        };
+        Scrape { output, device_counts, log }
+    }
+}
+
+struct Tick {
+    run_id: u32,
+    reply: oneshot::Sender<SourceReport>,
+}
+
+struct Actor {
+    name: String,
+    role: Role,
+    job: Job,
+    config: Arc<Config>,
+    client: reqwest::Client,
+    seen: SeenProducts,
+    // Run of the last scrape that produced something, and its output
+    last: Option<(u32, SourceOutput)>,
+    last_attempt: Option<u32>,
+    // Output checkpointed in the interrupted run being resumed, used for it instead of scraping
+    resumed: Option<SourceOutput>,
+}
+
+impl Actor {
+    fn report(&self, output: SourceOutput, scraped_in: Option<u32>, failure: Option<String>) -> SourceReport {
+        SourceReport { name: self.name.clone(), role: self.role, output, scraped_in, failure }
+    }
+
+    // The last good output, minus its new listings, while it may still stand in
+    fn reuse(&self, run_id: u32, max_age: u32) -> Option<(u32, SourceOutput)> {
+        let (scraped_in, output) = self.last.as_ref().filter(|_| self.role.reusable())?;
+        (run_id - scraped_in <= max_age).then(|| (*scraped_in, SourceOutput { new_count: 0, ..output.clone() }))
+    }
+
+    async fn tick(&mut self, run_id: u32) -> SourceReport {
+        if let Some(output) = self.resumed.take() {
+            progress::println(&format!("\n\n⏩ {}: {} listings from the checkpoint", self.name, output.products.len()));
+            // Its listings count as seen, as they would have if the run had finished
+            filter_new(&output.products, &self.seen);
+            self.last_attempt = Some(run_id);
+            self.last = Some((run_id, output.clone()));
+            return self.report(output, Some(run_id), None);
+        }
+        let settings = &self.config.supervisor;
+        let every = settings.every_runs_for(&self.name);
+        if let Some(last) = self.last_attempt
+            && run_id < last + every
+        {
+            return match self.reuse(run_id, every) {
+                Some((scraped_in, output)) => self.report(output, Some(scraped_in), None),
+                None => self.report(SourceOutput::default(), None, None),
+            };
+        }
+        self.last_attempt = Some(run_id);
+        let (retries, max_stale_runs) = (settings.retries, settings.max_stale_runs);
+
+        progress::println(&format!("\n\n{}\n", self.job.banner(&self.name)));
+        let mut failure = String::new();
+        for attempt in 0..=retries {
+            if attempt > 0 {
+                progress::println(&format!("  🔁 Retrying {} ({}/{})", self.name, attempt, retries));
+            }
+            let task = tokio::spawn(self.job.clone().scrape(self.client.clone(), self.config.clone(), self.seen.clone()));
+            match task.await {
+                Ok(scrape) => {
+                    scrape.log.print();
+                    crate::check_empty_devices(&self.name, &scrape.device_counts, settings.empty_device_alert_runs);
+                    if !scrape.output.is_empty() || !self.role.reusable() {
+                        self.last = Some((run_id, scrape.output.clone()));
+                        return self.report(scrape.output, Some(run_id), None);
+                    }
+                    failure = "no listings".to_string();
+                }
+                Err(e) => {
+                    failure = format!("scrape task failed: {}", e);
+                    eprintln!("  ❌ {}: {}", self.name, failure);
+                    error_journal::record(ErrorCategory::Crash, &self.name, None, &failure, None);
+                }
+            }
+        }
+        match self.reuse(run_id, max_stale_runs) {
+            Some((scraped_in, output)) => self.report(output, Some(scraped_in), Some(failure)),
+            None => self.report(SourceOutput::default(), None, Some(failure)),
+        }
+    }
+
+    async fn run(mut self, mut ticks: mpsc::Receiver<Tick>) {
+        while let Some(tick) = ticks.recv().await {
+            let report = self.tick(tick.run_id).await;
+            let _ = tick.reply.send(report);
+        }
+    }
+}
+
+// Sources named after the same site share it: "eBay", "eBay BIN", "eBay auctions"
+pub fn site_of(name: &str) -> String {
+    name.split_whitespace().next().unwrap_or(name).to_lowercase()
+}
+
+struct ActorHandle {
+    name: String,
+    role: Role,
+    job: Job,
+    ticks: mpsc::Sender<Tick>,
+    // Answers its next tick from the checkpoint, without scraping
+    resuming: bool,
+}
+
+pub struct Supervisor {
+    config: Arc<Config>,
+    client: reqwest::Client,
+    seen: SeenProducts,
+    actors: Vec<ActorHandle>,
+}
+
+impl Supervisor {
+    // One actor per source, in the order they are scraped each run; `resumed`
+    // holds checkpointed outputs, by source name, for the first run
+    pub fn start(config: Arc<Config>, client: reqwest::Client, seen: SeenProducts, mut resumed: HashMap<String, SourceOutput>) -> Supervisor {
+        let mut jobs = vec![
+            ("Newegg".to_string(), Role::Newegg, Job::Newegg),
+            ("Swappa".to_string(), Role::Swappa, Job::Swappa),
+            ("eBay".to_string(), Role::Ebay, Job::Ebay),
+            ("Mercari".to_string(), Role::Mercari, Job::Mercari),
+        ];
+        for source in sources::buy_sources(&config.sources, &config.browser) {
+            jobs.push((source.name().to_string(), Role::Storefront, Job::Storefront(Arc::from(source))));
+        }
+        jobs.push(("Decluttr".to_string(), Role::Decluttr, Job::Decluttr));
+        if config.auctions.enabled {
+            jobs.push(("eBay auctions".to_string(), Role::Auctions, Job::Auctions));
+        }
+
+        let mut supervisor = Supervisor { config, client, seen, actors: Vec::new() };
+        supervisor.actors = jobs
+            .into_iter()
+            .map(|(name, role, job)| {
+                let output = resumed.remove(&name);
+                supervisor.spawn(name, role, job, output)
+            })
+            .collect();
+        supervisor
+    }
+
+    fn spawn(&self, name: String, role: Role, job: Job, resumed: Option<SourceOutput>) -> ActorHandle {
+        let (ticks, receiver) = mpsc::channel(1);
+        let resuming = resumed.is_some();
+        let actor = Actor {
+            name: name.clone(),
+            role,
+            job: job.clone(),
+            config: self.config.clone(),
+            client: self.client.clone(),
+            seen: self.seen.clone(),
+            last: None,
+            last_attempt: None,
+            resumed,
+        };
+        tokio::spawn(actor.run(receiver));
+        ActorHandle { name, role, job, ticks, resuming }
+    }
+
+    // Tick the actors [supervisor] concurrency at a time, in order. Sources on
+    // the same site (eBay, eBay BIN, eBay auctions) never overlap, and after a
+    // fresh scrape its slot pauses per the global preset before the next source.
+    pub async fn run(&mut self, run_id: u32) -> RunReports {
+        let between_sources = Arc::new(Politeness::new(self.config.politeness.preset));
+        let slots = Arc::new(Semaphore::new(self.config.supervisor.concurrency.max(1)));
+        let started = Arc::new(AtomicUsize::new(0));
+        let total = self.actors.len();
+        let mut sites: HashMap<String, Arc<tokio::sync::Mutex<()>>> = HashMap::new();
+        let mut ticks = JoinSet::new();
+        for (i, actor) in self.actors.iter_mut().enumerate() {
+            let resuming = std::mem::take(&mut actor.resuming);
+            let site = sites.entry(site_of(&actor.name)).or_default().clone();
+            let (sender, slots, started, between_sources) = (actor.ticks.clone(), slots.clone(), started.clone(), between_sources.clone());
+            ticks.spawn(async move {
+                let _site = site.lock_owned().await;
+                let _slot = slots.acquire_owned().await;
+                started.fetch_add(1, Ordering::SeqCst);
+                let (reply, response) = oneshot::channel();
+                let report = match sender.send(Tick { run_id, reply }).await {
+                    Ok(()) => response.await.ok(),
+                    Err(_) => None,
+                };
+                let fresh = report.as_ref().is_some_and(|r| r.scraped_in == Some(run_id));
+                if fresh && !resuming && started.load(Ordering::SeqCst) < total {
+                    between_sources.pause(2000).await;
+                }
+                (i, report)
+            });
+        }
+
+        // Completion order; checkpointed as each comes in
+        let mut order = Vec::new();
+        let mut reports = Vec::new();
+        while let Some(joined) = ticks.join_next().await {
+            // Only the tick plumbing runs in these tasks; scrapes panic inside the actor
+            let Ok((i, report)) = joined else {
+                continue;
+            };
+            let report = report.unwrap_or_else(|| {
+                // The actor itself died; start a fresh one for the next run
+                let ActorHandle { name, role, job, .. } = &self.actors[i];
+                let failure = format!("{} actor stopped, restarting it", name);
+                eprintln!("  ❌ {}", failure);
+                error_journal::record(ErrorCategory::Crash, name, None, &failure, None);
+                let report = SourceReport { name: name.clone(), role: *role, output: SourceOutput::default(), scraped_in: None, failure: Some(failure) };
+                self.actors[i] = self.spawn(name.clone(), *role, job.clone(), None);
+                report
+            });
+            order.push(i);
+            reports.push(report);
+            checkpoint::record(run_id, &reports, &self.config.export);
+        }
+
+        let mut ordered: Vec<(usize, SourceReport)> = order.into_iter().zip(reports).collect();
+        ordered.sort_by_key(|(i, _)| *i);
+        RunReports { run_id, reports: ordered.into_iter().map(|(_, report)| report).collect() }
+    }
+}
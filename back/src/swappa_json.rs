@@ -0,0 +1,152 @@
+// Swappa listings from JSON instead of page text: XHR payloads picked up by
+// the capture hook plus JSON embedded in the page (JSON-LD offers and
+// application/json state blobs). scrape_swappa tries this first and only
+// falls back to the innerText/price-regex scan when no listings turn up.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::Product;
+use crate::attributes::{self, ProductAttributes};
+use crate::money::Money;
+use crate::page_driver::PageDriver;
+
+const BASE_URL: &str = "https://swappa.com";
+
+const EMBEDDED_SCRIPT: &str = r#"
+    var blobs = [];
+    var scripts = document.querySelectorAll('script[type="application/ld+json"], script[type="application/json"]');
+    for (var i = 0; i < scripts.length; i++) {
+        try { blobs.push(JSON.parse(scripts[i].textContent)); } catch (e) {}
+    }
+    return blobs;
+"#;
+
+pub async fn embedded_json(driver: &dyn PageDriver) -> Vec<Value> {
+    match driver.run_script(EMBEDDED_SCRIPT).await {
+        Ok(result) => result.as_array().cloned().unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn text(object: &serde_json::Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match object.get(*key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        // JSON-LD nests e.g. itemCondition / seller as objects with a name
+        Value::Object(inner) => inner.get("name").and_then(|v| v.as_str()).map(String::from),
+        _ => None,
+    })
+}
+
+fn price(object: &serde_json::Map<String, Value>) -> Option<f64> {
+    ["price", "price_usd", "listing_price", "amount"].iter().find_map(|key| match object.get(*key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim_start_matches('$').replace(',', "").parse().ok(),
+        _ => None,
+    })
+}
+
+// When the listing was posted, as local "%Y-%m-%d %H:%M:%S"; API dates are
+// RFC 3339, state blobs sometimes carry a plain local timestamp
+fn listed_at(object: &serde_json::Map<String, Value>) -> Option<String> {
+    let text = text(object, &["created", "created_at", "date_created", "datePosted", "listed_at"])?;
+    let at = match DateTime::parse_from_rfc3339(&text) {
+        Ok(at) => at.with_timezone(&Local).naive_local(),
+        Err(_) => NaiveDateTime::parse_from_str(&text, "%Y-%m-%d %H:%M:%S").ok()?,
+    };
+    Some(at.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+// Attributes the listing states as fields rather than in its title
+fn stated_attributes(object: &serde_json::Map<String, Value>, condition: Option<&str>) -> ProductAttributes {
+    ProductAttributes {
+        storage: text(object, &["storage", "capacity"]).as_deref().and_then(attributes::parse_storage),
+        carrier: text(object, &["carrier"]).as_deref().and_then(attributes::parse_carrier),
+        condition: condition.and_then(|condition| attributes::parse_condition("", Some(condition))),
+        color: text(object, &["color", "colour"]).map(|color| color.to_lowercase()),
+        ..ProductAttributes::default()
+    }
+}
+
+// Listing URL from an explicit link or a listing code (Swappa codes look like "LABC12345")
+fn listing_url(object: &serde_json::Map<String, Value>) -> Option<String> {
+    if let Some(url) = text(object, &["url", "absolute_url", "link"])
+        && url.contains("/listing/")
+    {
+        return Some(if url.starts_with('/') { format!("{}{}", BASE_URL, url) } else { url });
+    }
+    let code = text(object, &["listing_code", "code"])?;
+    let looks_like_code = code.len() >= 6 && code.len() <= 12 && code.chars().all(|c| c.is_ascii_alphanumeric());
+    looks_like_code.then(|| format!("{}/listing/view/{}", BASE_URL, code))
+}
+
+fn walk(value: &Value, device: &str, seen: &mut HashSet<String>, products: &mut Vec<Product>) {
+    match value {
+        Value::Object(object) => {
+            if let (Some(price), Some(url)) = (price(object), listing_url(object)) {
+                if price > 0.0 && seen.insert(url.clone()) {
+                    let condition = text(object, &["condition", "condition_name", "itemCondition"]);
+                    let mut name = text(object, &["title", "name", "product_name"]).unwrap_or_else(|| device.to_string());
+                    for part in [text(object, &["storage", "capacity"]), text(object, &["color", "colour"]), text(object, &["carrier"])]
+                        .into_iter()
+                        .flatten()
+                    {
+                        if !name.to_lowercase().contains(&part.to_lowercase()) {
+                            name = format!("{} {}", name, part);
+                        }
+                    }
+                    if let Some(condition) = &condition {
+                        name = format!("{} ({})", name, condition.trim_start_matches("https://schema.org/"));
+                    }
+                    let attributes = stated_attributes(object, condition.as_deref());
+                    products.push(Product {
+                        name,
+                        price: Some(Money::usd(price)),
+                        url,
+                        source: "Swappa".to_string(),
+                        sold_date: None,
+                        grade: condition,
+                        warranty: None,
+                        expires_at: None,
+                        region: None,
+                        currency: None,
+                        attributes,
+                        listed_at: listed_at(object),
+                        lot_quantity: None,
+                    });
+                }
+                return;
+            }
+            for child in object.values() {
+                walk(child, device, seen, products);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                walk(item, device, seen, products);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Listings found in captured responses ([{ url, status, body }]) and embedded JSON
+pub fn parse_listings(captured: &[Value], embedded: &[Value], device: &str) -> Vec<Product> {
+    let mut seen = HashSet::new();
+    let mut products = Vec::new();
+
+    for response in captured {
+        if let Some(body) = response.get("body").and_then(|v| v.as_str())
+            && let Ok(payload) = serde_json::from_str::<Value>(body)
+        {
+            walk(&payload, device, &mut seen, &mut products);
+        }
+    }
+    for blob in embedded {
+        walk(blob, device, &mut seen, &mut products);
+    }
+
+    products
+}
@@ -0,0 +1,221 @@
+// Individual listings being watched outside the normal flow, e.g. ones under
+// negotiation. Added with `back track <url>`; every run re-fetches each
+// tracked listing, records its price and status, and alerts when the price
+// drops or the listing sells or ends.
+
+use chrono::{DateTime, Local};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::cli::TrackArgs;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::storage::{profile_path, write_json};
+use crate::{fetch_html, parse_price};
+
+pub const TRACKED_FILE: &str = "tracked.json";
+// Price/status checks kept per listing
+const MAX_CHECKS: usize = 500;
+
+// Phrases listing pages show once an item is gone
+const SOLD_MARKERS: [&str; 4] = ["this item sold", "this listing sold", "listing has sold", "has been sold"];
+const ENDED_MARKERS: [&str; 5] = [
+    "this listing was ended",
+    "this listing has ended",
+    "bidding has ended",
+    "no longer available",
+    "listing is inactive",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListingStatus {
+    #[default]
+    Unknown,
+    Active,
+    Sold,
+    Ended,
+    // 404/410: the listing page itself was taken down (`back verify`)
+    Gone,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedCheck {
+    pub checked_at: DateTime<Local>,
+    pub price: Option<Money>,
+    pub status: ListingStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedItem {
+    pub url: String,
+    pub source: String,
+    pub added_at: DateTime<Local>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub price: Option<Money>,
+    #[serde(default)]
+    pub status: ListingStatus,
+    #[serde(default)]
+    pub checks: Vec<TrackedCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackingAlert {
+    pub url: String,
+    pub title: Option<String>,
+    pub message: String,
+}
+
+pub fn load_tracked() -> Vec<TrackedItem> {
+    match fs::read_to_string(profile_path(TRACKED_FILE)) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn first_match(document: &Html, selectors: &[(&str, Option<&str>)]) -> Option<String> {
+    selectors.iter().find_map(|(selector, attr)| {
+        let element = document.select(&Selector::parse(selector).ok()?).next()?;
+        let value = match attr {
+            Some(attr) => element.value().attr(attr)?.to_string(),
+            None => element.text().collect::<String>(),
+        };
+        let value = value.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+// Title, price and status from an eBay item page or Swappa listing page
+pub fn parse_listing(html: &str) -> (Option<String>, Option<Money>, ListingStatus) {
+    let document = Html::parse_document(html);
+    let title = first_match(&document, &[("meta[property=\"og:title\"]", Some("content")), ("h1", None)]);
+    let price = first_match(
+        &document,
+        &[
+            ("meta[itemprop=\"price\"]", Some("content")),
+            ("[itemprop=\"price\"]", Some("content")),
+            ("meta[property=\"product:price:amount\"]", Some("content")),
+            (".x-price-primary", None),
+            ("[itemprop=\"price\"]", None),
+        ],
+    )
+    .and_then(|p| parse_price(&p));
+
+    let text = document.root_element().text().collect::<String>().to_lowercase();
+    let status = if SOLD_MARKERS.iter().any(|m| text.contains(m)) {
+        ListingStatus::Sold
+    } else if ENDED_MARKERS.iter().any(|m| text.contains(m)) {
+        ListingStatus::Ended
+    } else if price.is_some() {
+        ListingStatus::Active
+    } else {
+        ListingStatus::Unknown
+    };
+    (title, price, status)
+}
+
+// Re-check every tracked listing, save the results and return the alerts
+pub async fn check_all(client: &reqwest::Client) -> (Vec<TrackedItem>, Vec<TrackingAlert>) {
+    let mut items = load_tracked();
+    let mut alerts = Vec::new();
+    if items.is_empty() {
+        return (items, alerts);
+    }
+
+    println!("\n🔭 Checking {} tracked listings...", items.len());
+    for item in items.iter_mut() {
+        // Nothing left to watch once it's gone
+        if matches!(item.status, ListingStatus::Sold | ListingStatus::Ended) {
+            continue;
+        }
+        let Some(html) = fetch_html(client, &item.url).await else {
+            continue;
+        };
+        let (title, price, status) = parse_listing(&html);
+        if status == ListingStatus::Unknown {
+            error_journal::record(ErrorCategory::Parse, &item.source, Some(&item.url), "No price or status found on tracked listing", Some(&html));
+        }
+        if item.title.is_none() {
+            item.title = title;
+        }
+
+        let mut alert = |message: String| {
+            println!("  🔔 {} - {}", item.title.as_deref().unwrap_or(&item.url), message);
+            alerts.push(TrackingAlert { url: item.url.clone(), title: item.title.clone(), message });
+        };
+        if let (Some(old), Some(new)) = (item.price, price)
+            && new < old
+        {
+            alert(format!("price dropped {} → {}", old, new));
+        }
+        match status {
+            ListingStatus::Sold => alert("sold".to_string()),
+            ListingStatus::Ended => alert("listing ended".to_string()),
+            _ => {}
+        }
+
+        item.price = price.or(item.price);
+        item.status = status;
+        item.checks.push(TrackedCheck { checked_at: Local::now(), price, status });
+        if item.checks.len() > MAX_CHECKS {
+            item.checks.drain(..item.checks.len() - MAX_CHECKS);
+        }
+    }
+
+    if let Err(e) = write_json(&profile_path(TRACKED_FILE), &items) {
+        eprintln!("❌ {}", e);
+        error_journal::record(ErrorCategory::Storage, "Tracking", None, e, None);
+    }
+    (items, alerts)
+}
+
+fn print_item(item: &TrackedItem) {
+    let price = item.price.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string());
+    println!("🔭 {} {} ({:?})", item.source, price, item.status);
+    if let Some(title) = &item.title {
+        println!("   {}", title);
+    }
+    println!("   🔗 {}", item.url);
+}
+
+// `back track [<url>] [--remove]`; with no URL, list tracked listings
+pub fn track(args: &TrackArgs) -> Result<(), String> {
+    let mut items = load_tracked();
+    let Some(url) = args.url.as_deref().map(str::trim) else {
+        if items.is_empty() {
+            println!("No tracked listings in {}", profile_path(TRACKED_FILE));
+        }
+        items.iter().for_each(print_item);
+        return Ok(());
+    };
+    if url.is_empty() {
+        return Err("A listing URL is required".to_string());
+    }
+
+    if args.remove {
+        let before = items.len();
+        items.retain(|item| item.url != url);
+        if items.len() == before {
+            return Err(format!("{} is not tracked", url));
+        }
+        println!("🗑️  Stopped tracking {}", url);
+    } else if items.iter().any(|item| item.url == url) {
+        println!("Already tracking {}", url);
+        return Ok(());
+    } else {
+        items.push(TrackedItem {
+            url: url.to_string(),
+            source: error_journal::source_for_url(url),
+            added_at: Local::now(),
+            title: None,
+            price: None,
+            status: ListingStatus::Unknown,
+            checks: Vec::new(),
+        });
+        println!("🔭 Tracking {} from the next run", url);
+    }
+    write_json(&profile_path(TRACKED_FILE), &items)
+}
@@ -0,0 +1,162 @@
+// Trade-in quotes as a guaranteed price floor. Apple, Gazelle and
+// ItsWorthMore publish trade-in values per model and condition grade; the
+// lowest quote that applies to an opportunity's device and condition is
+// attached as its worst-case exit price. Quotes change rarely, so they are
+// cached for [tradein] cache_hours rather than fetched every run.
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::attributes::{self, Condition, ProductAttributes};
+use crate::config::TradeInConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::money::Money;
+use crate::storage;
+use crate::{fetch_html, parse_price};
+
+const CACHE_FILE: &str = "tradein_quotes.json";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Elements small enough to hold one model and its quote
+const ROW_SELECTORS: &str = "tr, li, [class*='device'], [class*='row'], [class*='card']";
+const MAX_ROW_TEXT: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeInQuote {
+    pub provider: String,
+    // Model, or model + storage when the provider quotes per storage size
+    pub device: String,
+    // Grade the quote is for; None when the page gives one price per model
+    #[serde(default)]
+    pub condition: Option<Condition>,
+    pub price: Money,
+    pub url: String,
+}
+
+// Worst case exit for an opportunity: the lowest trade-in quote for its
+// device at its condition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeInFloor {
+    pub provider: String,
+    pub price: Money,
+    pub url: String,
+}
+
+pub fn parse_quotes(html: &str, provider: &str, url: &str) -> Vec<TradeInQuote> {
+    let document = Html::parse_document(html);
+    let Ok(selector) = Selector::parse(ROW_SELECTORS) else {
+        return Vec::new();
+    };
+
+    // Lowest quote per device and grade; pages repeat models in nested elements
+    let mut lowest: BTreeMap<(String, Option<Condition>), Money> = BTreeMap::new();
+    for row in document.select(&selector) {
+        let text = row.text().collect::<Vec<_>>().join(" ");
+        if text.len() > MAX_ROW_TEXT || !text.contains('$') {
+            continue;
+        }
+        let Some(device) = ProductAttributes::parse(&text).device_key() else {
+            continue;
+        };
+        for (condition, price) in graded_prices(&text) {
+            let entry = lowest.entry((device.clone(), condition)).or_insert(price);
+            *entry = (*entry).min(price);
+        }
+    }
+
+    lowest
+        .into_iter()
+        .map(|((device, condition), price)| TradeInQuote {
+            provider: provider.to_string(),
+            device,
+            condition,
+            price,
+            url: url.to_string(),
+        })
+        .collect()
+}
+
+// Every "$X" in a row with the grade named just before it, e.g.
+// "iPhone 13 128GB Flawless $300 Good $250 Fair $120"
+fn graded_prices(text: &str) -> Vec<(Option<Condition>, Money)> {
+    let starts: Vec<usize> = text.match_indices('$').map(|(i, _)| i).collect();
+    let mut label_start = 0;
+    let mut prices = Vec::new();
+    for (i, &start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(text.len());
+        let Some(price) = parse_price(&text[start..end]) else {
+            continue;
+        };
+        prices.push((attributes::parse_condition(&text[label_start..start], None), price));
+        label_start = start;
+    }
+    prices
+}
+
+#[derive(Serialize, Deserialize)]
+struct QuoteCache {
+    fetched_at: String,
+    quotes: Vec<TradeInQuote>,
+}
+
+// Quotes saved within the last cache_hours
+fn cached_quotes(cache_hours: i64) -> Option<Vec<TradeInQuote>> {
+    let content = fs::read_to_string(storage::profile_path(CACHE_FILE)).ok()?;
+    let cache: QuoteCache = serde_json::from_str(&content).ok()?;
+    let fetched_at = NaiveDateTime::parse_from_str(&cache.fetched_at, TIMESTAMP_FORMAT).ok()?;
+    (Local::now().naive_local() - fetched_at < TimeDelta::hours(cache_hours)).then_some(cache.quotes)
+}
+
+pub async fn fetch_quotes(client: &reqwest::Client, config: &TradeInConfig) -> Vec<TradeInQuote> {
+    let mut quotes = Vec::new();
+    if !config.enabled {
+        return quotes;
+    }
+    if let Some(cached) = cached_quotes(config.cache_hours) {
+        println!("  Using {} cached trade-in quotes", cached.len());
+        return cached;
+    }
+
+    for (provider, url) in &config.providers {
+        println!("  Trade-in quotes from {}: {}", provider, url);
+        let Some(html) = fetch_html(client, url).await else {
+            continue;
+        };
+        let found = parse_quotes(&html, provider, url);
+        println!("    Found {} quotes", found.len());
+        if found.is_empty() {
+            error_journal::record(ErrorCategory::Parse, provider, Some(url), "No trade-in quotes found", Some(&html));
+        }
+        quotes.extend(found);
+    }
+
+    if !quotes.is_empty() {
+        let cache = QuoteCache { fetched_at: Local::now().format(TIMESTAMP_FORMAT).to_string(), quotes: quotes.clone() };
+        if let Err(e) = storage::write_json(&storage::profile_path(CACHE_FILE), &cache) {
+            eprintln!("❌ {}", e);
+            error_journal::record(ErrorCategory::Storage, "Trade-in", None, &e, None);
+        }
+    }
+    quotes
+}
+
+// Floor for a listing: exact model + storage first, then the model alone.
+// Quotes for the listing's grade apply when there are any, else every quote
+// for the device does; the lowest applicable one is the floor.
+pub fn floor_for(name: &str, condition: Option<Condition>, quotes: &[TradeInQuote]) -> Option<TradeInFloor> {
+    let attributes = ProductAttributes::parse(name);
+    let condition = condition.or(attributes.condition);
+    let keys = [attributes.device_key(), attributes.model.clone()];
+    keys.iter().flatten().find_map(|key| {
+        let device: Vec<&TradeInQuote> = quotes.iter().filter(|q| &q.device == key).collect();
+        let graded: Vec<&TradeInQuote> = device.iter().copied().filter(|q| condition.is_some() && q.condition == condition).collect();
+        let applicable = if graded.is_empty() { device } else { graded };
+        applicable
+            .into_iter()
+            .min_by_key(|q| q.price)
+            .map(|q| TradeInFloor { provider: q.provider.clone(), price: q.price, url: q.url.clone() })
+    })
+}
@@ -0,0 +1,155 @@
+// Buy-link health check (`back verify`). Every opportunity in the latest
+// export has its buy listing re-fetched; one that's gone (404/410) or whose
+// page says it sold or ended is marked as such in scraper_data.json and the
+// stored run, and the listings still up are printed as the cleaned list.
+// Hosts are checked in parallel, each at its source's politeness preset:
+// `concurrency` pages at a time with the usual pause between batches.
+
+use chrono::Local;
+use reqwest::StatusCode;
+use reqwest::header::USER_AGENT;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tokio::task::JoinSet;
+
+use crate::config::PolitenessConfig;
+use crate::error_journal::{self, ErrorCategory};
+use crate::tracking::{self, ListingStatus};
+use crate::{FRONTEND_DATA_FILE, ScraperData, retry, storage};
+
+// Base pause between batches to one host, scaled by the preset
+const HOST_PAUSE_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verification {
+    pub status: ListingStatus,
+    pub checked_at: String,
+}
+
+impl Verification {
+    pub fn is_dead(&self) -> bool {
+        matches!(self.status, ListingStatus::Sold | ListingStatus::Ended | ListingStatus::Gone)
+    }
+}
+
+fn host(url: &str) -> String {
+    url.split("://").nth(1).and_then(|rest| rest.split('/').next()).unwrap_or(url).to_string()
+}
+
+async fn check(client: &reqwest::Client, url: &str) -> ListingStatus {
+    let response = retry::send(url, || {
+        client
+            .get(url)
+            .header(USER_AGENT, "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+    })
+    .await;
+    let source = error_journal::source_for_url(url);
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            error_journal::record(ErrorCategory::Fetch, &source, Some(url), &e, None);
+            return ListingStatus::Unknown;
+        }
+    };
+    let status = response.status();
+    if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+        return ListingStatus::Gone;
+    }
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        // Blocked or erroring: no verdict, the listing stays
+        error_journal::record(ErrorCategory::Http, &source, Some(url), format!("HTTP {}", status), Some(&body));
+        return ListingStatus::Unknown;
+    }
+    tracking::parse_listing(&body).2
+}
+
+// Status per URL, each host at its own pace
+pub async fn check_all(client: &reqwest::Client, politeness: &PolitenessConfig, urls: Vec<String>) -> BTreeMap<String, ListingStatus> {
+    let mut by_host: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for url in urls {
+        by_host.entry(host(&url)).or_default().push(url);
+    }
+
+    let mut hosts = JoinSet::new();
+    for urls in by_host.into_values() {
+        let client = client.clone();
+        let politeness = politeness.for_source(&error_journal::source_for_url(&urls[0]));
+        hosts.spawn(async move {
+            let mut statuses = Vec::new();
+            for (i, batch) in urls.chunks(politeness.concurrency.max(1)).enumerate() {
+                if i > 0 {
+                    politeness.pause(HOST_PAUSE_MS).await;
+                }
+                let mut checks = JoinSet::new();
+                for url in batch {
+                    let (client, url) = (client.clone(), url.clone());
+                    checks.spawn(async move {
+                        let status = check(&client, &url).await;
+                        (url, status)
+                    });
+                }
+                while let Some(Ok(result)) = checks.join_next().await {
+                    statuses.push(result);
+                }
+            }
+            statuses
+        });
+    }
+
+    let mut statuses = BTreeMap::new();
+    while let Some(Ok(results)) = hosts.join_next().await {
+        statuses.extend(results);
+    }
+    statuses
+}
+
+// `back verify`
+pub async fn verify(client: &reqwest::Client, politeness: &PolitenessConfig) -> Result<(), String> {
+    let path = storage::profile_path(FRONTEND_DATA_FILE);
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut data: ScraperData = serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+
+    let mut urls: Vec<String> = data.arbitrage_opportunities.iter().map(|opp| opp.buy_url.clone()).collect();
+    urls.sort();
+    urls.dedup();
+    if urls.is_empty() {
+        println!("No opportunities in {}", path);
+        return Ok(());
+    }
+    println!("🩺 Checking {} buy listings from run {} ({})...", urls.len(), data.run_count, data.last_updated);
+    let statuses = check_all(client, politeness, urls).await;
+
+    let checked_at = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let mark = |opps: &mut Vec<crate::ArbitrageOpportunity>| {
+        for opp in opps.iter_mut() {
+            if let Some(status) = statuses.get(&opp.buy_url) {
+                opp.verification = Some(Verification { status: *status, checked_at: checked_at.clone() });
+            }
+        }
+    };
+    mark(&mut data.arbitrage_opportunities);
+    storage::write_json(&path, &data)?;
+    // The stored run shares the export's id
+    if let Some(mut run) = storage::load_run(data.run_count) {
+        mark(&mut run.arbitrage_opportunities);
+        storage::update_run(&run)?;
+    }
+
+    let (dead, live): (Vec<_>, Vec<_>) =
+        data.arbitrage_opportunities.iter().partition(|opp| opp.verification.as_ref().is_some_and(Verification::is_dead));
+    for opp in &dead {
+        let status = opp.verification.as_ref().map_or(ListingStatus::Unknown, |v| v.status);
+        println!("  💀 {:?}: {} ({})", status, opp.buy_product_name, opp.buy_url);
+    }
+    println!("\n✅ {} still listed, {} gone:", live.len(), dead.len());
+    for opp in &live {
+        let unconfirmed = if opp.verification.as_ref().is_some_and(|v| v.status == ListingStatus::Active) { "" } else { " (unconfirmed)" };
+        println!(
+            "  💰 {} profit ({:.1}%) - {} on {} at {}{}",
+            opp.potential_profit, opp.margin_percent, opp.buy_product_name, opp.buy_source, opp.buy_price, unconfirmed
+        );
+        println!("     🔗 {}", opp.buy_url);
+    }
+    Ok(())
+}
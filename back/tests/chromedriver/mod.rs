@@ -0,0 +1,32 @@
+// ChromeDriver setup without a browser: reading versions out of `--version`
+// output, matching Chrome and ChromeDriver by major version, and the CRC-32C
+// a download is checked against.
+
+use crate::chromedriver::{crc32c, major, version_in};
+
+#[test]
+fn versions_from_version_output() {
+    assert_eq!(version_in("Google Chrome 120.0.6099.109").as_deref(), Some("120.0.6099.109"));
+    assert_eq!(version_in("Chromium 119.0.6045.199 built on Debian 12.2").as_deref(), Some("119.0.6045.199"));
+    assert_eq!(
+        version_in("ChromeDriver 120.0.6099.109 (3419140ab665596f21b385ce136419fde0924272-refs/branch-heads/6099@{#1483})").as_deref(),
+        Some("120.0.6099.109")
+    );
+    assert_eq!(version_in("chromedriver: command not found"), None);
+    assert_eq!(version_in(""), None);
+}
+
+#[test]
+fn major_versions() {
+    assert_eq!(major("120.0.6099.109"), "120");
+    assert_eq!(major("120"), "120");
+    assert_eq!(major(""), "");
+    assert_ne!(major("119.0.6045.199"), major("120.0.6099.109"));
+}
+
+#[test]
+fn crc32c_check_values() {
+    assert_eq!(crc32c(b""), 0);
+    assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+    assert_ne!(crc32c(b"123456780"), 0xe306_9283);
+}
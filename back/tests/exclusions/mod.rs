@@ -0,0 +1,98 @@
+// Accessory and damage exclusion: keywords per vertical, extras bundled with
+// a phone, negated damage words, and lots dropped or priced per unit.
+
+use serde_json::json;
+
+use crate::Product;
+use crate::config::{ExclusionConfig, LotConfig, LotMode};
+use crate::exclusions::{ExclusionReport, matched_damage, matched_keyword, vertical};
+
+fn listing(name: &str, price: &str) -> Product {
+    serde_json::from_value(json!({ "name": name, "price": price, "url": "https://example.com/1", "source": "eBay" })).unwrap()
+}
+
+#[test]
+fn verticals_from_titles() {
+    assert_eq!(vertical("Apple iPad Air 5th Gen 64GB"), "tablets");
+    assert_eq!(vertical("Samsung Galaxy Tab S8"), "tablets");
+    assert_eq!(vertical("Apple Watch Series 8 45mm"), "watches");
+    assert_eq!(vertical("Apple iPhone 13 128GB"), "phones");
+}
+
+#[test]
+fn accessories_are_matched() {
+    let config = ExclusionConfig::default();
+    assert_eq!(matched_keyword("OtterBox Defender Case for iPhone 13", &config), Some("case"));
+    assert_eq!(matched_keyword("iPhone 14 Pro Tempered Glass Screen Protector 3 Pack", &config), Some("screen protector"));
+    assert_eq!(matched_keyword("Apple iPhone 12 Box Only", &config), Some("box only"));
+    assert_eq!(matched_keyword("Apple iPhone 13 128GB Unlocked", &config), None);
+}
+
+#[test]
+fn extras_bundled_with_a_phone_are_kept() {
+    let config = ExclusionConfig::default();
+    assert_eq!(matched_keyword("Apple iPhone 13 128GB with charger", &config), None);
+    assert_eq!(matched_keyword("Pixel 7 128GB + case", &config), None);
+    assert_eq!(matched_keyword("iPhone 12 64GB includes case", &config), None);
+}
+
+#[test]
+fn keywords_match_whole_words_only() {
+    let config = ExclusionConfig::default();
+    // "showcase" isn't a case, "cases" is
+    assert_eq!(matched_keyword("iPhone 13 showcase condition", &config), None);
+    assert_eq!(matched_keyword("Lot of iPhone 13 cases", &config), Some("case"));
+}
+
+#[test]
+fn watch_cases_are_not_accessories() {
+    let config = ExclusionConfig::default();
+    assert_eq!(matched_keyword("Apple Watch Series 8 45mm Midnight Aluminum Case", &config), None);
+    assert_eq!(matched_keyword("Protective Case for Apple Watch Series 8", &config), Some("protective case"));
+}
+
+#[test]
+fn damage_in_title_or_grade() {
+    let config = ExclusionConfig::default();
+    assert_eq!(matched_damage("Apple iPhone 12 64GB Cracked Screen", None, &config), Some("cracked"));
+    assert_eq!(matched_damage("Apple iPhone 12 64GB", Some("For parts or not working"), &config), Some("for parts"));
+    assert_eq!(matched_damage("Apple iPhone 12 64GB", Some("Good"), &config), None);
+}
+
+#[test]
+fn negated_damage_is_kept() {
+    let config = ExclusionConfig::default();
+    assert_eq!(matched_damage("iPhone 12 64GB not broken, works great", None, &config), None);
+    assert_eq!(matched_damage("iPhone 12 64GB never cracked", None, &config), None);
+}
+
+#[test]
+fn exclusions_are_counted() {
+    let config = ExclusionConfig::default();
+    let mut report = ExclusionReport::default();
+    assert!(report.exclude_buy(&listing("iPhone 13 Case", "$15"), &config));
+    assert!(report.exclude_comp(&listing("iPhone 13 128GB", "$90"), &ExclusionConfig { damaged: vec!["128gb".to_string()], ..config.clone() }));
+    assert!(!report.exclude_comp(&listing("iPhone 13 128GB Unlocked", "$420"), &config));
+    assert_eq!((report.buy_side, report.comps, report.damaged_comps), (1, 1, 1));
+    assert_eq!(report.by_keyword.get("case"), Some(&1));
+}
+
+#[test]
+fn lots_are_priced_per_unit() {
+    let mut report = ExclusionReport::default();
+    let products = report.apply_lots(
+        vec![listing("Lot of 5 iPhone 11 64GB", "$1000.00"), listing("iPhone 11 64GB", "$220.00"), listing("Wholesale lot iPhone 11", "$900.00")],
+        &LotConfig { mode: LotMode::PerUnit },
+    );
+    let summary: Vec<(String, Option<u32>)> = products.iter().map(|p| (p.price_label(), p.lot_quantity)).collect();
+    assert_eq!(summary, [("$200.00".to_string(), Some(5)), ("$220.00".to_string(), None)]);
+    assert_eq!((report.lots_per_unit, report.lots_excluded), (1, 1));
+}
+
+#[test]
+fn lots_can_be_excluded() {
+    let mut report = ExclusionReport::default();
+    let products = report.apply_lots(vec![listing("3x iPhone 11 64GB", "$600.00")], &LotConfig { mode: LotMode::Exclude });
+    assert!(products.is_empty());
+    assert_eq!(report.lots_excluded, 1);
+}
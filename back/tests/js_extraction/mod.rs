@@ -0,0 +1,50 @@
+// Golden tests for the extraction scripts scrape_swappa and scrape_ebay run
+// inside Chrome. Each saved page (tests/fixtures/<name>.html, with a <base>
+// so links resolve to the live site) is opened in headless Chrome and the
+// script's return value is compared with <name>.json, so a selector or regex
+// change shows up as a diff instead of an empty run. They need Chrome, so
+// they're ignored by default; the ChromeDriver is found or downloaded as for
+// a run (chromedriver.rs):
+//
+//     cargo test -- --ignored
+//
+// `UPDATE_GOLDEN=1` rewrites the goldens, as for the parser tests.
+
+use serde_json::Value;
+use thirtyfour::prelude::*;
+
+use crate::chromedriver;
+use crate::page_driver::PageDriver;
+use crate::parser_tests::assert_golden;
+use crate::{ebay_extract_script, swappa_text_scan_script};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+// `script`'s result on the saved page `name`
+async fn run_on_fixture(name: &str, script: &str) -> Value {
+    let mut caps = DesiredCapabilities::chrome();
+    caps.add_arg("--headless=new").ok();
+    caps.add_arg("--window-size=1920,1200").ok();
+    let driver = chromedriver::connect(caps).await.unwrap_or_else(|e| panic!("Failed to connect to ChromeDriver: {}", e));
+
+    let page = format!("file://{}/{}.html", FIXTURES_DIR, name);
+    let result = match driver.open(&page).await {
+        Ok(()) => driver.run_script(script).await,
+        Err(e) => Err(e),
+    };
+    driver.quit().await.ok();
+    result.unwrap_or_else(|e| panic!("Failed to run the script on {}: {}", page, e))
+}
+
+#[tokio::test]
+#[ignore = "needs Chrome"]
+async fn swappa_text_scan() {
+    let script = swappa_text_scan_script("iPhone 13", "https://swappa.com/buy/apple-iphone-13", 30);
+    assert_golden("swappa_device_page", run_on_fixture("swappa_device_page", &script).await);
+}
+
+#[tokio::test]
+#[ignore = "needs Chrome"]
+async fn ebay_sold_search() {
+    assert_golden("ebay_sold_search", run_on_fixture("ebay_sold_search", &ebay_extract_script()).await);
+}
@@ -0,0 +1,42 @@
+// Lot detection: counted lots in their usual spellings, camera zoom that
+// isn't a count, lots with no readable quantity, and "bundle" titles that
+// are one phone with extras.
+
+use crate::attributes::{Lot, parse_lot};
+
+#[test]
+fn counted_lots() {
+    assert_eq!(parse_lot("Lot of 5 Apple iPhone 11 64GB"), Some(Lot::Quantity(5)));
+    assert_eq!(parse_lot("Bundle of 3 Galaxy S21"), Some(Lot::Quantity(3)));
+    assert_eq!(parse_lot("3x iPhone 12 128GB"), Some(Lot::Quantity(3)));
+    assert_eq!(parse_lot("iPhone 12 128GB x3"), Some(Lot::Quantity(3)));
+    assert_eq!(parse_lot("10 pcs iPhone XR"), Some(Lot::Quantity(10)));
+    assert_eq!(parse_lot("iPhone XR (10pcs)"), Some(Lot::Quantity(10)));
+}
+
+#[test]
+fn single_units() {
+    assert_eq!(parse_lot("Apple iPhone 13 128GB Unlocked"), None);
+    assert_eq!(parse_lot("Galaxy S23 Ultra 10x Optical Zoom"), None);
+    assert_eq!(parse_lot("Pixel 7 Pro 5x zoom 128GB"), None);
+    // One unit, or a count out of range
+    assert_eq!(parse_lot("1x iPhone 12"), None);
+    assert_eq!(parse_lot("Lot of 500 iPhone 12"), Some(Lot::Unknown));
+}
+
+#[test]
+fn lots_without_a_count() {
+    assert_eq!(parse_lot("Wholesale iPhone 11 mixed grades"), Some(Lot::Unknown));
+    assert_eq!(parse_lot("iPhone 8 lot"), Some(Lot::Unknown));
+    assert_eq!(parse_lot("Mixed phones bundle, untested"), Some(Lot::Unknown));
+    assert_eq!(parse_lot("Bundle of iPhones, mixed models"), Some(Lot::Unknown));
+}
+
+#[test]
+fn bundled_extras_are_one_unit() {
+    assert_eq!(parse_lot("iPhone 12 64GB Bundle with Case and Charger"), None);
+    assert_eq!(parse_lot("iPhone 12 64GB bundle w/ charger"), None);
+    assert_eq!(parse_lot("iPhone 12 64GB bundle + case"), None);
+    assert_eq!(parse_lot("iPhone 13 Pro 128GB Bundle"), None);
+    assert_eq!(parse_lot("Bundle: iPhone 12 + AirPods"), None);
+}
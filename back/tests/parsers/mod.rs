@@ -0,0 +1,174 @@
+// Golden-output tests for the site parsers, run against pages saved under
+// tests/fixtures/. Each page has a .json next to it holding what the parser
+// extracted when the fixture was last reviewed; a selector change that loses
+// listings or fields shows up as a diff here instead of as a night of empty
+// runs. After an intended change, `UPDATE_GOLDEN=1 cargo test` rewrites the
+// goldens for review. eBay isn't covered: its listings are extracted by
+// JavaScript inside Chrome, not by these parsers (see tests/js_extraction).
+
+use serde_json::{Value, json};
+
+use crate::attributes::{Brand, Carrier, Condition, Storage};
+use crate::money::Money;
+use crate::tradein::{floor_for, parse_quotes};
+use crate::{Product, ProductDetails, deduplicate_products, truncate_string, parse_newegg_product_page, parse_swappa_product_page, scrape_newegg_products, scrape_swappa_products};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn fixture(name: &str) -> String {
+    let path = format!("{}/{}.html", FIXTURES_DIR, name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e))
+}
+
+pub(crate) fn assert_golden(name: &str, actual: Value) {
+    let path = format!("{}/{}.json", FIXTURES_DIR, name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+        return;
+    }
+    let content = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {} (UPDATE_GOLDEN=1 creates it)", path, e));
+    let expected: Value = serde_json::from_str(&content).unwrap_or_else(|e| panic!("Failed to parse {}: {}", path, e));
+    assert_eq!(
+        actual, expected,
+        "{} output no longer matches {}; rerun with UPDATE_GOLDEN=1 if the change is intended",
+        name, path
+    );
+}
+
+fn details_value(details: &ProductDetails) -> Value {
+    json!({
+        "name": details.name,
+        "price": details.price,
+        "url": details.url,
+        "source": details.source,
+        "description": details.description,
+        "specs": details.specs,
+        "images": details.images,
+        "condition": details.condition,
+        "seller": details.seller,
+    })
+}
+
+#[test]
+fn newegg_grid() {
+    let products = scrape_newegg_products(&fixture("newegg_grid"), "https://www.newegg.com");
+    assert!(!products.is_empty(), "no products extracted from the Newegg grid");
+    assert_golden("newegg_grid", serde_json::to_value(&products).unwrap());
+}
+
+#[test]
+fn swappa_listings() {
+    let products = scrape_swappa_products(&fixture("swappa_listings"), "https://swappa.com");
+    assert!(!products.is_empty(), "no listings extracted from the Swappa page");
+    assert_golden("swappa_listings", serde_json::to_value(&products).unwrap());
+}
+
+#[test]
+fn newegg_product_page() {
+    let details = parse_newegg_product_page(&fixture("newegg_product"), "https://www.newegg.com/p/75U-0016-00123");
+    assert_golden("newegg_product", details_value(&details));
+}
+
+#[test]
+fn swappa_product_page() {
+    let details = parse_swappa_product_page(&fixture("swappa_product"), "https://swappa.com/listing/view/LAAA11111");
+    assert_golden("swappa_product", details_value(&details));
+}
+
+#[test]
+fn collected_listings_carry_typed_attributes() {
+    let products = deduplicate_products(scrape_swappa_products(&fixture("swappa_listings"), "https://swappa.com"));
+    let verizon = products.iter().find(|p| p.url.ends_with("LBBB22222")).expect("LBBB22222 listing");
+    let attributes = &verizon.attributes;
+    assert_eq!(attributes.brand, Some(Brand::Apple));
+    assert_eq!(attributes.model.as_deref(), Some("iphone 15 pro"));
+    assert_eq!(attributes.storage, Some(Storage::Gb128));
+    assert_eq!(attributes.carrier, Some(Carrier::Verizon));
+    assert_eq!(attributes.color.as_deref(), Some("natural titanium"));
+
+    // Exported flat, storage in GB under the name stored runs already use
+    let exported = serde_json::to_value(verizon).unwrap();
+    assert_eq!(exported["storage_gb"], json!(128));
+    assert_eq!(exported["carrier"], json!("Verizon"));
+    let stored: Product = serde_json::from_value(json!({
+        "name": "iPhone 14 Unlocked", "price": "$400", "url": "u", "source": "eBay", "storage_gb": 256, "condition": "Good"
+    }))
+    .unwrap();
+    assert_eq!(stored.attributes.storage, Some(Storage::Gb256));
+    assert_eq!(stored.attributes.model, None);
+}
+
+// Distinct listings often share a title; only repeats of the same listing go
+#[test]
+fn same_named_swappa_listings_survive() {
+    let products = scrape_swappa_products(&fixture("swappa_same_titles"), "https://swappa.com");
+    let mut codes: Vec<&str> = products.iter().filter_map(|p| p.url.rsplit('/').next()).map(|c| c.split('?').next().unwrap()).collect();
+    codes.sort();
+    assert_eq!(codes, ["LDDD44444", "LEEE55555", "LFFF66666"]);
+    assert!(products.iter().all(|p| p.name == "Apple iPhone 13 128GB Unlocked"));
+}
+
+#[test]
+fn listings_dedup_by_identity_not_title() {
+    let listing = |source: &str, name: &str, price: &str, url: &str| -> Product {
+        serde_json::from_value(json!({ "name": name, "price": price, "url": url, "source": source })).unwrap()
+    };
+    let products = deduplicate_products(vec![
+        // Two sales with the same title
+        listing("eBay", "Apple iPhone 13 128GB Unlocked", "$380.00", "https://www.ebay.com/itm/111111111111"),
+        listing("eBay", "Apple iPhone 13 128GB Unlocked", "$380.00", "https://www.ebay.com/itm/222222222222?hash=abc"),
+        // The first again, under another search's slug and tracking
+        listing("eBay", "Apple iPhone 13 128GB Unlocked", "$380.00", "https://www.ebay.com/itm/apple-iphone-13/111111111111?_trkparms=x"),
+        // One Newegg item linked two ways
+        listing("Newegg", "Refurbished: Apple iPhone 13", "$399.99", "https://www.newegg.com/apple-iphone-13/p/75U-0016-00123"),
+        listing("Newegg", "Refurbished: Apple iPhone 13", "$399.99", "https://www.newegg.com/Product/Product.aspx?Item=75U-0016-00123"),
+        // Text-scanned listings without links, all pointing at the device page
+        listing("Swappa", "Pixel 8 (Good)", "$329", "https://swappa.com/buy/google-pixel-8"),
+        listing("Swappa", "Pixel 8 (Good)", "$341", "https://swappa.com/buy/google-pixel-8"),
+        listing("Swappa", "Pixel 8 (Good)", "$329", "https://swappa.com/buy/google-pixel-8/"),
+    ]);
+    let summary: Vec<(&str, String, &str)> = products.iter().map(|p| (p.source.as_str(), p.price_label(), p.url.as_str())).collect();
+    assert_eq!(
+        summary,
+        [
+            ("eBay", "$380.00".to_string(), "https://www.ebay.com/itm/111111111111"),
+            ("eBay", "$380.00".to_string(), "https://www.ebay.com/itm/222222222222?hash=abc"),
+            ("Newegg", "$399.99".to_string(), "https://www.newegg.com/apple-iphone-13/p/75U-0016-00123"),
+            ("Swappa", "$329.00".to_string(), "https://swappa.com/buy/google-pixel-8"),
+            ("Swappa", "$341.00".to_string(), "https://swappa.com/buy/google-pixel-8"),
+        ]
+    );
+}
+
+#[test]
+fn trade_in_floor_is_the_lowest_quote_for_the_grade() {
+    let html = r#"<table>
+        <tr><td>iPhone 13 128GB</td><td>Flawless $300</td><td>Good $250</td><td>Fair $120</td></tr>
+        <tr><td>iPhone 12 64GB</td><td>Up to $180</td></tr>
+    </table>"#;
+    let quotes = parse_quotes(html, "Gazelle", "https://www.gazelle.com/iphone");
+    let graded: Vec<(&str, Option<Condition>, Money)> = quotes.iter().map(|q| (q.device.as_str(), q.condition, q.price)).collect();
+    assert_eq!(
+        graded,
+        [
+            ("iphone 12 64gb", None, Money::usd(180.0)),
+            ("iphone 13 128gb", Some(Condition::Excellent), Money::usd(300.0)),
+            ("iphone 13 128gb", Some(Condition::Good), Money::usd(250.0)),
+            ("iphone 13 128gb", Some(Condition::Fair), Money::usd(120.0)),
+        ]
+    );
+
+    let floor = |name: &str, condition| floor_for(name, condition, &quotes).map(|floor| floor.price);
+    assert_eq!(floor("Apple iPhone 13 128GB Unlocked", Some(Condition::Good)), Some(Money::usd(250.0)));
+    // No quote for the grade, or no grade: the worst one
+    assert_eq!(floor("Apple iPhone 13 128GB Unlocked", Some(Condition::New)), Some(Money::usd(120.0)));
+    assert_eq!(floor("Apple iPhone 13 128GB Unlocked", None), Some(Money::usd(120.0)));
+    assert_eq!(floor("Apple iPhone 12 64GB", Some(Condition::Good)), Some(Money::usd(180.0)));
+}
+
+#[test]
+fn truncation_counts_characters() {
+    assert_eq!(truncate_string("Pixel 8 – 128GB", 9), "Pixel 8 –...");
+    assert_eq!(truncate_string("Galaxy™ S24", 7), "Galaxy™...");
+    assert_eq!(truncate_string("iPhone 13", 9), "iPhone 13");
+}
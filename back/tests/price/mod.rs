@@ -0,0 +1,245 @@
+// Property tests for the price parser: random amounts written the ways the
+// sites write them must parse back to the same amount and currency, and no
+// input may panic it. Money amounts must add up and serialize exactly.
+// Cases come from a fixed-seed generator (no proptest in the offline
+// build), so a failure reproduces on every run; the failing text is in the
+// assertion message.
+
+use crate::money::{Currency, Money};
+use crate::parse_price;
+use crate::price::{Price, parse};
+use crate::price_drops::{PriceDrop, display_price_drops};
+
+const CASES: usize = 2000;
+
+// xorshift64*: small, deterministic, good enough to spread cases around
+struct Cases(u64);
+
+impl Cases {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545f4914f6cdd1d)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+// "1299" with `separator` every three digits from the right: "1,299"
+fn grouped(whole: u64, separator: &str) -> String {
+    let digits = whole.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push_str(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+// An amount in cents written in one of the formats listings use
+fn written(cents: u64, style: u64) -> (String, Option<&'static str>) {
+    let (whole, frac) = (cents / 100, cents % 100);
+    match style {
+        0 => (format!("${}.{:02}", grouped(whole, ","), frac), Some("USD")),
+        1 => (format!("US ${}.{:02}", grouped(whole, ","), frac), Some("USD")),
+        2 => (format!("{},{:02} €", grouped(whole, "."), frac), Some("EUR")),
+        3 => (format!("€{},{:02}", grouped(whole, "."), frac), Some("EUR")),
+        4 => (format!("£{}.{:02}", grouped(whole, ","), frac), Some("GBP")),
+        5 => (format!("C${}.{:02}", grouped(whole, ","), frac), Some("CAD")),
+        6 => (format!("{}.{:02} EUR", whole, frac), Some("EUR")),
+        7 => (format!("{},{:02}", grouped(whole, "\u{a0}"), frac), None),
+        8 => (format!("{}.{:02}", grouped(whole, "'"), frac), None),
+        _ => (format!("{}.{:02}", whole, frac), None),
+    }
+}
+
+fn assert_amount(text: &str, parsed: Option<Price>, amount: f64, currency: Option<&str>) {
+    let price = parsed.unwrap_or_else(|| panic!("{:?} didn't parse", text));
+    assert!((price.amount - amount).abs() < 1e-6, "{:?} parsed as {}, expected {}", text, price.amount, amount);
+    assert_eq!(price.currency, currency, "{:?} currency", text);
+}
+
+#[test]
+fn written_amounts_round_trip() {
+    let mut cases = Cases(0x5eed_0001);
+    for _ in 0..CASES {
+        let cents = cases.below(100_000_000);
+        let (text, currency) = written(cents, cases.below(10));
+        assert_amount(&text, parse(&text), cents as f64 / 100.0, currency);
+    }
+}
+
+#[test]
+fn whole_dollar_and_trailing_period_prices() {
+    let mut cases = Cases(0x5eed_0002);
+    for _ in 0..CASES {
+        let whole = cases.below(1_000_000);
+        for text in [format!("${}", grouped(whole, ",")), format!("${}.", grouped(whole, ",")), format!("{} €", grouped(whole, "."))] {
+            let currency = if text.contains('€') { "EUR" } else { "USD" };
+            assert_amount(&text, parse(&text), whole as f64, Some(currency));
+        }
+    }
+}
+
+#[test]
+fn ranges_price_at_the_midpoint() {
+    let mut cases = Cases(0x5eed_0003);
+    for _ in 0..CASES {
+        let low = cases.below(500_000);
+        let high = low + cases.below(500_000);
+        let joiner = [" to ", " - ", "–", " ~ "][cases.below(4) as usize];
+        let text = format!("${}.{:02}{}${}.{:02}", grouped(low / 100, ","), low % 100, joiner, grouped(high / 100, ","), high % 100);
+        assert_amount(&text, parse(&text), (low + high) as f64 / 200.0, Some("USD"));
+    }
+}
+
+#[test]
+fn arbitrary_text_never_panics() {
+    const ALPHABET: [char; 24] = ['$', '€', '£', '¥', '¢', '.', ',', '\'', ' ', '\u{a0}', '-', '–', '0', '1', '5', '9', 'U', 'S', 'D', 't', 'o', 'C', 'A', 'x'];
+    let mut cases = Cases(0x5eed_0004);
+    for _ in 0..CASES * 5 {
+        let len = cases.below(16) as usize;
+        let text: String = (0..len).map(|_| ALPHABET[cases.below(ALPHABET.len() as u64) as usize]).collect();
+        if let Some(price) = parse(&text) {
+            assert!(price.amount.is_finite() && price.amount >= 0.0, "{:?} parsed as {}", text, price.amount);
+        }
+    }
+}
+
+#[test]
+fn listing_examples() {
+    let examples: [(&str, Option<f64>); 15] = [
+        ("$499.99", Some(499.99)),
+        ("US $499.99", Some(499.99)),
+        ("$450 to $500", Some(475.0)),
+        ("$450.00 to $500.00", Some(475.0)),
+        ("1.299,00 €", Some(1299.0)),
+        ("$499.", Some(499.0)),
+        ("$1,299", Some(1299.0)),
+        ("C$1,050", Some(1050.0)),
+        ("99¢", Some(0.99)),
+        ("2 for $300", Some(300.0)),
+        ("$1,299.99 - 20% off", Some(1299.99)),
+        ("Open Box: $279.99", Some(279.99)),
+        ("$ 689 .99", Some(689.99)),
+        ("300", Some(300.0)),
+        ("See price", None),
+    ];
+    for (text, expected) in examples {
+        assert_eq!(parse(text).map(|price| price.amount), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn listing_prices_are_usd_only() {
+    // A euro comp must not count as that many dollars in sold stats
+    assert_eq!(parse_price("1.299,00 €"), None);
+    assert_eq!(parse_price("C$1,050"), None);
+    assert_eq!(parse_price("£450"), None);
+    assert_eq!(parse_price("US $499.99"), Some(Money::from_minor(49999, Currency::Usd)));
+    assert_eq!(parse_price("$450 to $500"), Some(Money::from_minor(47500, Currency::Usd)));
+    assert_eq!(parse_price("300"), Some(Money::from_minor(30000, Currency::Usd)));
+}
+
+#[test]
+fn money_sums_are_exact() {
+    let mut cases = Cases(0x5eed_0005);
+    for _ in 0..CASES {
+        let cents: Vec<i64> = (0..1 + cases.below(20)).map(|_| cases.below(200_000) as i64).collect();
+        let total = cents.iter().fold(Money::default(), |sum, &c| sum + Money::from_minor(c, Currency::Usd));
+        let expected: i64 = cents.iter().sum();
+        assert_eq!(total, Money::from_minor(expected, Currency::Usd));
+        // Written out and read back, as the export and stored runs do
+        let json = serde_json::to_string(&total).unwrap();
+        let amount = format!("{}.{:02}", expected / 100, expected % 100);
+        assert_eq!(json, format!(r#"{{"amount":"{}","currency":"USD"}}"#, amount));
+        assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), total, "{}", json);
+    }
+}
+
+#[test]
+fn money_keeps_its_currency() {
+    for money in [Money::from_minor(45000, Currency::Eur), Money::from_minor(-5, Currency::Gbp), Money::from_minor(150_000, Currency::Jpy)] {
+        let json = serde_json::to_string(&money).unwrap();
+        assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), money, "{}", json);
+    }
+    assert_eq!(serde_json::to_string(&Money::from_minor(-5, Currency::Gbp)).unwrap(), r#"{"amount":"-0.05","currency":"GBP"}"#);
+    assert_eq!(serde_json::to_string(&Money::from_minor(150_000, Currency::Jpy)).unwrap(), r#"{"amount":"150000","currency":"JPY"}"#);
+    // A number amount reads too, but not more decimals than the currency has
+    let eur: Money = serde_json::from_str(r#"{"amount":450,"currency":"EUR"}"#).unwrap();
+    assert_eq!(eur, Money::from_minor(45000, Currency::Eur));
+    assert!(serde_json::from_str::<Money>(r#"{"amount":"1.5","currency":"JPY"}"#).is_err());
+}
+
+#[test]
+fn money_reads_stored_floats_and_strings() {
+    for (json, cents) in [("0.1", 10), ("45.499999999999986", 4550), ("189.99", 18999), ("300", 30000), ("\"1299.5\"", 129950), ("-12.5", -1250)] {
+        assert_eq!(serde_json::from_str::<Money>(json).unwrap(), Money::from_minor(cents, Currency::Usd), "{}", json);
+    }
+    assert!(serde_json::from_str::<Money>("\"see price\"").is_err());
+}
+
+#[test]
+fn listing_prices_read_from_scraped_text() {
+    #[derive(serde::Deserialize)]
+    struct Listing {
+        #[serde(default, deserialize_with = "crate::price::deserialize_listing")]
+        price: Option<Money>,
+    }
+    let price = |json: &str| serde_json::from_str::<Listing>(json).unwrap().price;
+    assert_eq!(price(r#"{"price": "$1,299.99"}"#), Some(Money::from_minor(129999, Currency::Usd)));
+    assert_eq!(price(r#"{"price": "1.299,00 €"}"#), Some(Money::from_minor(129900, Currency::Eur)));
+    assert_eq!(price(r#"{"price": "Price not found"}"#), None);
+    assert_eq!(price(r#"{"price": {"amount": "12.50", "currency": "GBP"}}"#), Some(Money::from_minor(1250, Currency::Gbp)));
+    assert_eq!(price("{}"), None);
+}
+
+#[test]
+fn quantiles_interpolate_in_cents() {
+    let usd = |cents| Money::from_minor(cents, Currency::Usd);
+    let sorted = [usd(10001), usd(10002), usd(10004), usd(20000)];
+    assert_eq!(Money::quantile(&sorted, 0.0), Some(usd(10001)));
+    assert_eq!(Money::quantile(&sorted, 0.5), Some(usd(10003)));
+    assert_eq!(Money::quantile(&sorted, 0.75), Some(usd(12503)));
+    assert_eq!(Money::quantile(&sorted, 1.0), Some(usd(20000)));
+    assert_eq!(Money::quantile(&[], 0.5), None);
+    assert_eq!(Money::median(&[usd(300), usd(100), usd(200)]), Some(usd(200)));
+}
+
+#[test]
+fn money_arithmetic_and_display() {
+    let usd = |cents| Money::from_minor(cents, Currency::Usd);
+    assert_eq!(usd(10) + usd(20), usd(30));
+    assert_eq!(Money::mean(&[usd(100), usd(101)]), Some(usd(101)));
+    assert_eq!(Money::mean(&[usd(-100), usd(-101)]), Some(usd(-101)));
+    assert_eq!(Money::mean(&[]), None);
+    assert_eq!(usd(39999).scale(0.1325), usd(5300));
+    assert_eq!(usd(5000).percent_of(usd(20000)), 25.0);
+    assert_eq!(usd(0).percent_of(usd(0)), 0.0);
+    assert!(usd(3000).cmp_ratio(usd(10000), usd(2999), usd(9999)).is_gt());
+    assert_eq!(format!("{} {} {:+} {:>9}|", usd(129999), usd(-500), usd(500), usd(4550)), "$1299.99 -$5.00 +$5.00    $45.50|");
+    assert_eq!(Money::from_major(1500.4, Currency::Jpy).to_string(), "¥1500");
+}
+
+#[test]
+fn price_drops_print_names_with_multibyte_characters() {
+    let drop = PriceDrop {
+        url: "https://swappa.com/listing/view/LAAA00001".to_string(),
+        source: "Swappa".to_string(),
+        // "–" straddles the 40th byte
+        name: "Apple iPhone 15 Pro Max 256GB Blue Tit – Unlocked".to_string(),
+        previous_price: Money::usd(899.0),
+        price: Money::usd(849.0),
+        drop: Money::usd(50.0),
+        drop_percent: 5.6,
+        previous_run_id: 41,
+        opportunity: false,
+    };
+    assert_eq!(drop.label(), "price dropped by $50.00 (5.6%) since run 41");
+    display_price_drops(&[drop]);
+}
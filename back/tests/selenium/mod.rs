@@ -0,0 +1,217 @@
+// The Selenium scrapers' page handling run against saved script results
+// instead of ChromeDriver. A fixture (tests/fixtures/<name>_driver.json) maps
+// each page URL to the results its scripts returned, as
+// [{ "script": <text the script contains>, "result": <JSON it returned> }];
+// the first entry whose text appears in a script answers it, and scripts with
+// no entry (scrolling) return null. Pages missing from the fixture fail to
+// open, like a navigation error. Wheel scrolls are recorded rather than
+// performed. Capture them from a live page with the
+// script in the devtools console: copy(JSON.stringify(<script body>)).
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::attributes::{Condition, Storage};
+use crate::behavior::{Behavior, BehaviorProfile};
+use crate::config::{CaptureConfig, CompShipping, CompsConfig, EbayConfig, SwappaConfig};
+use crate::page_driver::PageDriver;
+use crate::politeness::{Politeness, Preset};
+use crate::{Product, scrape_ebay_pages, scrape_swappa_pages};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+struct FixtureDriver {
+    pages: HashMap<String, Vec<(String, Value)>>,
+    current: Mutex<Option<String>>,
+    wheel_deltas: Mutex<Vec<f64>>,
+}
+
+impl FixtureDriver {
+    fn load(name: &str) -> FixtureDriver {
+        let path = format!("{}/{}_driver.json", FIXTURES_DIR, name);
+        let content = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e));
+        let fixture: HashMap<String, Vec<Value>> =
+            serde_json::from_str(&content).unwrap_or_else(|e| panic!("Failed to parse {}: {}", path, e));
+        let pages = fixture
+            .into_iter()
+            .map(|(url, scripts)| {
+                let scripts = scripts
+                    .into_iter()
+                    .map(|entry| (entry["script"].as_str().unwrap_or_default().to_string(), entry["result"].clone()))
+                    .collect();
+                (url, scripts)
+            })
+            .collect();
+        FixtureDriver { pages, current: Mutex::new(None), wheel_deltas: Mutex::new(Vec::new()) }
+    }
+}
+
+#[async_trait]
+impl PageDriver for FixtureDriver {
+    async fn open(&self, url: &str) -> Result<(), String> {
+        if !self.pages.contains_key(url) {
+            return Err(format!("{} is not in the fixture", url));
+        }
+        *self.current.lock().unwrap() = Some(url.to_string());
+        Ok(())
+    }
+
+    async fn run_script(&self, script: &str) -> Result<Value, String> {
+        let current = self.current.lock().unwrap().clone().ok_or("no page open")?;
+        let result = self.pages[&current].iter().find(|(text, _)| script.contains(text.as_str())).map(|(_, result)| result.clone());
+        Ok(result.unwrap_or(Value::Null))
+    }
+
+    async fn screenshot_png(&self) -> Result<Vec<u8>, String> {
+        Err("fixtures have no screenshots".to_string())
+    }
+
+    async fn add_init_script(&self, _source: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn move_mouse(&self, _x: f64, _y: f64) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn wheel(&self, _x: f64, _y: f64, delta_y: f64) -> Result<(), String> {
+        self.wheel_deltas.lock().unwrap().push(delta_y);
+        Ok(())
+    }
+
+    async fn settle(&self, _duration: Duration) {}
+}
+
+fn no_delay() -> Politeness {
+    let mut politeness = Politeness::new(Preset::Normal);
+    politeness.delay_factor = 0.0;
+    politeness
+}
+
+fn listed(products: &[Product]) -> Vec<(&str, String, &str)> {
+    products.iter().map(|p| (p.name.as_str(), p.price_label(), p.url.as_str())).collect()
+}
+
+#[tokio::test]
+async fn swappa_prefers_listing_json() {
+    let driver = FixtureDriver::load("swappa");
+    let urls = [("iPhone 13", "https://swappa.com/buy/apple-iphone-13")];
+    let (products, counts) = scrape_swappa_pages(&driver, &CaptureConfig::default(), &SwappaConfig::default(), &no_delay(), &urls).await;
+
+    // Captured XHR first, then embedded JSON-LD; LAAA11111 appears in both
+    assert_eq!(
+        listed(&products),
+        vec![
+            ("Apple iPhone 13 128GB (Mint)", "$389.00".to_string(), "https://swappa.com/listing/view/LAAA11111"),
+            ("Apple iPhone 13 128GB Blue (Good)", "$352.00".to_string(), "https://swappa.com/listing/view/LBBB22222"),
+            ("Apple iPhone 13 256GB (Fair)", "$410.00".to_string(), "https://swappa.com/listing/view/LCCC33333"),
+        ]
+    );
+    assert_eq!(products[0].grade.as_deref(), Some("Mint"));
+    // Fields the JSON states are typed straight away; the rest waits for the title parse
+    assert_eq!(products[0].attributes.storage, Some(Storage::Gb128));
+    assert_eq!(products[0].attributes.condition, Some(Condition::Excellent));
+    assert_eq!(products[0].listed_at.as_deref(), Some("2026-10-01 14:05:00"));
+    assert_eq!(products[0].attributes.model, None);
+    assert_eq!(products[1].attributes.color.as_deref(), Some("blue"));
+    assert_eq!(counts.get("iPhone 13"), Some(&3));
+}
+
+#[tokio::test]
+async fn swappa_falls_back_to_text_scan() {
+    let driver = FixtureDriver::load("swappa");
+    let urls = [("Pixel 8", "https://swappa.com/buy/google-pixel-8")];
+    let (products, counts) = scrape_swappa_pages(&driver, &CaptureConfig::default(), &SwappaConfig::default(), &no_delay(), &urls).await;
+
+    // Nameless rows are dropped; rows without a link point at the device page
+    assert_eq!(
+        listed(&products),
+        vec![
+            ("Pixel 8 128GB Unlocked (Good)", "$329.00".to_string(), "https://swappa.com/prices/google-pixel-8"),
+            ("Pixel 8 (Mint)", "$355.00".to_string(), "https://swappa.com/buy/google-pixel-8"),
+        ]
+    );
+    assert!(products.iter().all(|p| p.source == "Swappa"));
+    assert_eq!(counts.get("Pixel 8"), Some(&2));
+}
+
+#[tokio::test]
+async fn swappa_clicks_load_more_up_to_the_limit() {
+    let driver = FixtureDriver::load("swappa");
+    let urls = [("Pixel 8", "https://swappa.com/buy/google-pixel-8")];
+    let swappa = SwappaConfig { scroll_depth_px: 1000, max_load_more: 2, ..SwappaConfig::default() };
+    scrape_swappa_pages(&driver, &CaptureConfig::default(), &swappa, &no_delay(), &urls).await;
+
+    // The fixture's button never goes away: the first pass plus one per click
+    let deltas = driver.wheel_deltas.lock().unwrap().clone();
+    assert_eq!(deltas.iter().sum::<f64>(), 3000.0);
+
+    let driver = FixtureDriver::load("swappa");
+    let swappa = SwappaConfig { scroll_depth_px: 1000, load_more: false, ..SwappaConfig::default() };
+    scrape_swappa_pages(&driver, &CaptureConfig::default(), &swappa, &no_delay(), &urls).await;
+    assert_eq!(driver.wheel_deltas.lock().unwrap().iter().sum::<f64>(), 1000.0);
+}
+
+#[tokio::test]
+async fn ebay_sold_listings() {
+    let driver = FixtureDriver::load("ebay");
+    let urls = [(
+        "iPhone 14",
+        "https://www.ebay.com/sch/i.html?_nkw=iphone+14+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13",
+    )];
+    let comps = CompsConfig { shipping: CompShipping::Domestic, ..CompsConfig::default() };
+    let products = scrape_ebay_pages(&driver, &CaptureConfig::default(), &comps, &EbayConfig::default(), &no_delay(), &urls).await;
+
+    // Domestic basis adds US shipping but not GSP charges; the priceless card is skipped
+    assert_eq!(
+        listed(&products),
+        vec![
+            ("Apple iPhone 14 128GB Unlocked Midnight", "$424.50".to_string(), "https://www.ebay.com/itm/111111111111"),
+            ("Apple iPhone 14 256GB Unlocked Purple", "$455.00".to_string(), "https://www.ebay.com/itm/222222222222"),
+        ]
+    );
+    assert_eq!(products[0].sold_date.as_deref(), Some("Oct 3, 2026"));
+    assert_eq!(products[1].sold_date, None);
+}
+
+#[tokio::test]
+async fn ebay_follows_result_pages_until_nothing_new() {
+    let driver = FixtureDriver::load("ebay");
+    let urls = [(
+        "iPhone 13",
+        "https://www.ebay.com/sch/i.html?_nkw=iphone+13+unlocked&_sacat=9355&LH_Sold=1&LH_Complete=1&_sop=13",
+    )];
+    let comps = CompsConfig::default();
+
+    // Page 3 only repeats page 2, so page 4 (not in the fixture) is never opened
+    let ebay = EbayConfig { max_pages: 5 };
+    let products = scrape_ebay_pages(&driver, &CaptureConfig::default(), &comps, &ebay, &no_delay(), &urls).await;
+    let urls_read: Vec<&str> = products.iter().map(|p| p.url.as_str()).collect();
+    assert_eq!(
+        urls_read,
+        vec!["https://www.ebay.com/itm/444444444444", "https://www.ebay.com/itm/555555555555", "https://www.ebay.com/itm/666666666666"]
+    );
+
+    let ebay = EbayConfig { max_pages: 1 };
+    let products = scrape_ebay_pages(&driver, &CaptureConfig::default(), &comps, &ebay, &no_delay(), &urls).await;
+    assert_eq!(products.len(), 2);
+}
+
+#[tokio::test]
+async fn behavior_scrolls_the_whole_depth_in_profile_steps() {
+    let profile = BehaviorProfile::builtin("cautious").unwrap();
+    let [_, largest] = profile.scroll_step_px;
+    for seed in 1..50 {
+        let driver = FixtureDriver::load("swappa");
+        Behavior::seeded(profile, 1.0, seed).scroll(&driver, 4000).await;
+        let deltas = driver.wheel_deltas.lock().unwrap().clone();
+        // Scroll-backs are undone, so the steps add up to the depth
+        assert_eq!(deltas.iter().sum::<f64>(), 4000.0, "seed {}: {:?}", seed, deltas);
+        assert!(deltas.iter().all(|d| d.abs() <= largest as f64 && *d != 0.0), "seed {}: {:?}", seed, deltas);
+        // Not the evenly spaced steps a fixed loop makes
+        assert!(deltas.windows(2).any(|w| w[0] != w[1]), "seed {}: {:?}", seed, deltas);
+    }
+}
@@ -0,0 +1,66 @@
+// Title similarity: numbers that aren't model numbers (battery health, screen
+// sizes, shipping times) must not split a match, while different models
+// still score 0.
+
+use crate::similarity::{TokenKind, classify, compare};
+
+fn score(first: &str, second: &str) -> f64 {
+    compare(first, second, 0.3).score
+}
+
+fn kind_of<'a>(classified: &'a [(String, TokenKind)], token: &str) -> Option<&'a TokenKind> {
+    classified.iter().find(|(t, _)| t == token).map(|(_, kind)| kind)
+}
+
+#[test]
+fn battery_health_is_not_a_model_number() {
+    let second = "Apple iPhone 13 Pro 128GB Unlocked - 89% Battery Health";
+    assert_eq!(kind_of(&classify(second), "89"), Some(&TokenKind::Other));
+    assert!(score("iPhone 13 Pro 128GB Unlocked", second) > 80.0);
+}
+
+#[test]
+fn screen_sizes_are_not_model_numbers() {
+    assert!(score("iPhone 14 128GB Unlocked", "Apple iPhone 14 128GB 6.1\" Unlocked") > 80.0);
+    assert!(score("iPhone 14 128GB Unlocked", "Apple iPhone 14 128GB 6.1 inch Unlocked") > 80.0);
+}
+
+#[test]
+fn tablet_screen_sizes_name_the_model() {
+    assert_eq!(score("iPad Air 13-inch M2 128GB", "iPad Air 11-inch M2 128GB"), 0.0);
+    assert_eq!(score("iPad Pro 11 M2 128GB", "iPad Pro 12.9\" M2 128GB"), 0.0);
+    assert!(score("iPad Pro 12.9 M2 128GB", "Apple iPad Pro 12.9-inch M2 128GB") > 90.0);
+    // After the model number it's just the size again
+    assert!(score("iPad Air 5th gen 64GB", "iPad Air 5 10.9-inch 64GB") > 80.0);
+    assert!(score("iPhone 15 Pro Max 256GB", "Apple iPhone 15 Pro Max 6.7 inch 256GB") > 80.0);
+}
+
+#[test]
+fn shipping_times_are_not_model_numbers() {
+    assert!(score("Galaxy S23 256GB Unlocked", "Samsung Galaxy S23 256GB Unlocked 2 Day Shipping") > 80.0);
+    assert!(score("Pixel 8 128GB", "Google Pixel 8 128GB - Ships in 24 hours") > 60.0);
+}
+
+#[test]
+fn numbers_away_from_model_words_are_other() {
+    let classified = classify("iPhone 13 128GB 2 pack");
+    assert_eq!(kind_of(&classified, "13"), Some(&TokenKind::ModelNumber));
+    assert_eq!(kind_of(&classified, "2"), Some(&TokenKind::Other));
+    // Before a model word counts too, and so does a run of them
+    assert_eq!(kind_of(&classify("Samsung S24 Ultra"), "s24"), Some(&TokenKind::ModelNumber));
+    assert_eq!(kind_of(&classify("iPad Pro 11 M2"), "m2"), Some(&TokenKind::ModelNumber));
+}
+
+#[test]
+fn different_models_still_score_zero() {
+    assert_eq!(score("iPhone 13 128GB", "iPhone 14 128GB"), 0.0);
+    assert_eq!(score("iPhone 13 Pro", "iPhone 13 Pro Max"), 0.0);
+    // A decimal with no unit is part of the model
+    assert_eq!(score("iPad Pro 11 M2 128GB", "iPad Pro 12.9 M2 128GB"), 0.0);
+}
+
+#[test]
+fn glued_and_spaced_titles_match() {
+    assert!(score("Apple iPhone 13 ProMax 256GB", "iPhone 13 Pro Max 256 GB") > 90.0);
+    assert!(score("Galaxy Watch6 40mm", "Samsung Galaxy Watch 6 40 mm") > 90.0);
+}
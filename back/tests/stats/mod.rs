@@ -0,0 +1,58 @@
+// Comp price statistics: interpolated quantiles and the IQR and MAD outlier
+// fences CompStats rejects sold prices with.
+
+use crate::stats::{iqr_bounds, mad_bounds, median, quantile, sorted};
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() < 1e-9
+}
+
+#[test]
+fn quantiles_interpolate_between_ranks() {
+    let prices = [100.0, 200.0, 300.0, 400.0, 500.0];
+    assert!(close(quantile(&prices, 0.0), 100.0));
+    assert!(close(quantile(&prices, 0.25), 200.0));
+    assert!(close(quantile(&prices, 0.5), 300.0));
+    assert!(close(quantile(&prices, 1.0), 500.0));
+    assert!(close(quantile(&[100.0, 200.0], 0.5), 150.0));
+    assert!(close(quantile(&[100.0, 200.0, 300.0, 400.0], 0.25), 175.0));
+}
+
+#[test]
+fn quantile_edge_cases() {
+    assert_eq!(quantile(&[], 0.5), 0.0);
+    assert!(close(quantile(&[250.0], 0.75), 250.0));
+    // Out-of-range q is clamped
+    assert!(close(quantile(&[100.0, 200.0], 1.5), 200.0));
+    assert!(close(quantile(&[100.0, 200.0], -1.0), 100.0));
+}
+
+#[test]
+fn median_sorts_its_input() {
+    assert_eq!(sorted(&[300.0, 100.0, 200.0]), vec![100.0, 200.0, 300.0]);
+    assert!(close(median(&[400.0, 100.0, 300.0, 200.0]), 250.0));
+}
+
+#[test]
+fn iqr_fences_reject_a_parts_listing() {
+    let prices = sorted(&[40.0, 480.0, 500.0, 510.0, 520.0, 540.0]);
+    let (low, high) = iqr_bounds(&prices, 1.5);
+    assert!(low > 40.0 && low < 480.0);
+    assert!(high > 540.0);
+}
+
+#[test]
+fn mad_fences_reject_a_lot_sale() {
+    let prices = sorted(&[480.0, 500.0, 505.0, 510.0, 520.0, 1900.0]);
+    let (low, high) = mad_bounds(&prices, 3.5);
+    assert!(low < 480.0);
+    assert!(high > 520.0 && high < 1900.0);
+}
+
+#[test]
+fn mad_of_zero_rejects_nothing() {
+    // Most comps sold at the same price: every other price would fall outside
+    // a zero-width fence
+    let prices = sorted(&[450.0, 500.0, 500.0, 500.0, 520.0]);
+    assert_eq!(mad_bounds(&prices, 3.5), (f64::NEG_INFINITY, f64::INFINITY));
+}